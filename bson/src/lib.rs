@@ -0,0 +1,27 @@
+/*!
+BSON support for `sval`.
+
+BSON documents must be maps or records at the top level, with text keys that don't
+contain NUL characters. [`BsonShape`] checks these rules while forwarding a value to
+another stream, and rewrites values tagged with [`tags::BSON_OBJECT_ID`] or
+[`tags::BSON_DATE`] into the extended JSON form BSON expects.
+
+Some functionality requires the `alloc` Cargo feature to be enabled.
+*/
+
+#![no_std]
+#![deny(missing_docs)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+pub mod tags;
+
+#[cfg(feature = "alloc")]
+mod shape;
+
+#[cfg(feature = "alloc")]
+pub use self::shape::*;
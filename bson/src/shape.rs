@@ -0,0 +1,658 @@
+use crate::tags;
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use sval::{Error, Index, Label, Number, Result, Stream, Tag};
+
+/**
+A [`Stream`] that enforces the shape of a BSON document.
+
+The following rules are checked:
+
+- The top-level value must be a document: a map, record, or record tuple. Tagged values
+  wrapping a document are forwarded as-is.
+- Map keys must be text.
+- Map keys and record labels must not contain NUL characters.
+
+Values tagged with [`tags::BSON_OBJECT_ID`] or [`tags::BSON_DATE`] are rewritten into the
+nested `{"$oid": value}` and `{"$date": value}` records BSON expects.
+
+If a rule is broken then streaming fails and the reason is available from [`BsonShape::error`].
+*/
+#[derive(Debug)]
+pub struct BsonShape<S> {
+    stream: S,
+    depth: usize,
+    key: Option<bool>,
+    tagged: Vec<bool>,
+    err: Option<BsonShapeError>,
+}
+
+/**
+The reason a value failed to stream through a [`BsonShape`].
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BsonShapeError {
+    /**
+    The top-level value isn't a document.
+    */
+    NotADocument,
+    /**
+    A map key isn't text.
+    */
+    NonTextKey,
+    /**
+    A map key or record label contains a NUL character.
+    */
+    NulInKey,
+}
+
+impl fmt::Display for BsonShapeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BsonShapeError::NotADocument => f.write_str("the top-level value must be a document"),
+            BsonShapeError::NonTextKey => f.write_str("document keys must be text"),
+            BsonShapeError::NulInKey => f.write_str("document keys must not contain NUL"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+mod std_support {
+    use super::*;
+
+    use std::error;
+
+    impl error::Error for BsonShapeError {}
+}
+
+impl<S> BsonShape<S> {
+    /**
+    Wrap a stream, enforcing the shape of a BSON document on values streamed through it.
+    */
+    pub fn new(stream: S) -> Self {
+        BsonShape {
+            stream,
+            depth: 0,
+            key: None,
+            tagged: Vec::new(),
+            err: None,
+        }
+    }
+
+    /**
+    Get the reason streaming failed, if it was because a BSON rule was broken.
+    */
+    pub fn error(&self) -> Option<BsonShapeError> {
+        self.err
+    }
+
+    /**
+    Get the wrapped stream.
+    */
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+
+    fn fail(&mut self, err: BsonShapeError) -> Result {
+        self.err = Some(err);
+        Err(Error::unsupported())
+    }
+
+    fn check_value(&mut self) -> Result {
+        if self.depth == 0 {
+            return self.fail(BsonShapeError::NotADocument);
+        }
+
+        if self.key.is_some() {
+            return self.fail(BsonShapeError::NonTextKey);
+        }
+
+        Ok(())
+    }
+
+    fn value(&mut self, f: impl FnOnce(&mut S) -> Result) -> Result {
+        self.check_value()?;
+        f(&mut self.stream)
+    }
+
+    fn begin(&mut self, f: impl FnOnce(&mut S) -> Result) -> Result {
+        self.check_value()?;
+
+        self.depth += 1;
+        f(&mut self.stream)
+    }
+
+    fn begin_document(&mut self, f: impl FnOnce(&mut S) -> Result) -> Result {
+        if self.key.is_some() {
+            return self.fail(BsonShapeError::NonTextKey);
+        }
+
+        self.depth += 1;
+        f(&mut self.stream)
+    }
+
+    fn end(&mut self, f: impl FnOnce(&mut S) -> Result) -> Result {
+        self.depth -= 1;
+        f(&mut self.stream)
+    }
+
+    fn check_key(&mut self, key: &str) -> Result {
+        if key.contains('\0') {
+            return self.fail(BsonShapeError::NulInKey);
+        }
+
+        Ok(())
+    }
+
+    fn text_fragment_key(&mut self, fragment: &str) -> Result {
+        if self.key.is_some() {
+            self.check_key(fragment)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn rewrite_label(tag: Option<&Tag>) -> Option<Label<'static>> {
+    match tag {
+        Some(&tags::BSON_OBJECT_ID) => Some(Label::new("$oid")),
+        Some(&tags::BSON_DATE) => Some(Label::new("$date")),
+        _ => None,
+    }
+}
+
+impl<'sval, S: Stream<'sval>> Stream<'sval> for BsonShape<S> {
+    fn null(&mut self) -> Result {
+        self.value(|stream| stream.null())
+    }
+
+    fn bool(&mut self, value: bool) -> Result {
+        self.value(|stream| stream.bool(value))
+    }
+
+    fn text_begin(&mut self, num_bytes: Option<usize>) -> Result {
+        if self.depth == 0 {
+            return self.fail(BsonShapeError::NotADocument);
+        }
+
+        if let Some(ref mut is_text) = self.key {
+            *is_text = true;
+        }
+
+        self.stream.text_begin(num_bytes)
+    }
+
+    fn text_fragment(&mut self, fragment: &'sval str) -> Result {
+        self.text_fragment_key(fragment)?;
+        self.stream.text_fragment(fragment)
+    }
+
+    fn text_fragment_computed(&mut self, fragment: &str) -> Result {
+        self.text_fragment_key(fragment)?;
+        self.stream.text_fragment_computed(fragment)
+    }
+
+    fn text_end(&mut self) -> Result {
+        self.stream.text_end()
+    }
+
+    fn binary_begin(&mut self, num_bytes: Option<usize>) -> Result {
+        self.value(|stream| stream.binary_begin(num_bytes))
+    }
+
+    fn binary_fragment(&mut self, fragment: &'sval [u8]) -> Result {
+        self.stream.binary_fragment(fragment)
+    }
+
+    fn binary_fragment_computed(&mut self, fragment: &[u8]) -> Result {
+        self.stream.binary_fragment_computed(fragment)
+    }
+
+    fn binary_end(&mut self) -> Result {
+        self.stream.binary_end()
+    }
+
+    fn u8(&mut self, value: u8) -> Result {
+        self.value(|stream| stream.u8(value))
+    }
+
+    fn u16(&mut self, value: u16) -> Result {
+        self.value(|stream| stream.u16(value))
+    }
+
+    fn u32(&mut self, value: u32) -> Result {
+        self.value(|stream| stream.u32(value))
+    }
+
+    fn u64(&mut self, value: u64) -> Result {
+        self.value(|stream| stream.u64(value))
+    }
+
+    fn u128(&mut self, value: u128) -> Result {
+        self.value(|stream| stream.u128(value))
+    }
+
+    fn i8(&mut self, value: i8) -> Result {
+        self.value(|stream| stream.i8(value))
+    }
+
+    fn i16(&mut self, value: i16) -> Result {
+        self.value(|stream| stream.i16(value))
+    }
+
+    fn i32(&mut self, value: i32) -> Result {
+        self.value(|stream| stream.i32(value))
+    }
+
+    fn i64(&mut self, value: i64) -> Result {
+        self.value(|stream| stream.i64(value))
+    }
+
+    fn i128(&mut self, value: i128) -> Result {
+        self.value(|stream| stream.i128(value))
+    }
+
+    fn f32(&mut self, value: f32) -> Result {
+        self.value(|stream| stream.f32(value))
+    }
+
+    fn f64(&mut self, value: f64) -> Result {
+        self.value(|stream| stream.f64(value))
+    }
+
+    fn num(&mut self, num: &Number) -> Result {
+        self.value(|stream| stream.num(num))
+    }
+
+    fn map_begin(&mut self, num_entries: Option<usize>) -> Result {
+        self.begin_document(|stream| stream.map_begin(num_entries))
+    }
+
+    fn map_key_begin(&mut self) -> Result {
+        self.key = Some(false);
+        self.stream.map_key_begin()
+    }
+
+    fn map_key_end(&mut self) -> Result {
+        if self.key.take() != Some(true) {
+            return self.fail(BsonShapeError::NonTextKey);
+        }
+
+        self.stream.map_key_end()
+    }
+
+    fn map_value_begin(&mut self) -> Result {
+        self.stream.map_value_begin()
+    }
+
+    fn map_value_end(&mut self) -> Result {
+        self.stream.map_value_end()
+    }
+
+    fn map_end(&mut self) -> Result {
+        self.end(|stream| stream.map_end())
+    }
+
+    fn seq_begin(&mut self, num_entries: Option<usize>) -> Result {
+        self.begin(|stream| stream.seq_begin(num_entries))
+    }
+
+    fn seq_value_begin(&mut self) -> Result {
+        self.stream.seq_value_begin()
+    }
+
+    fn seq_value_end(&mut self) -> Result {
+        self.stream.seq_value_end()
+    }
+
+    fn seq_end(&mut self) -> Result {
+        self.end(|stream| stream.seq_end())
+    }
+
+    fn enum_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.begin(|stream| stream.enum_begin(tag, label, index))
+    }
+
+    fn enum_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.end(|stream| stream.enum_end(tag, label, index))
+    }
+
+    fn tagged_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        match rewrite_label(tag) {
+            Some(field) => {
+                self.tagged.push(true);
+                self.begin(|stream| {
+                    stream.record_begin(None, None, None, Some(1))?;
+                    stream.record_value_begin(None, &field)
+                })
+            }
+            None => {
+                self.tagged.push(false);
+                self.stream.tagged_begin(tag, label, index)
+            }
+        }
+    }
+
+    fn tagged_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        if self.tagged.pop().unwrap_or(false) {
+            let field = rewrite_label(tag).ok_or_else(Error::new)?;
+
+            self.end(|stream| {
+                stream.record_value_end(None, &field)?;
+                stream.record_end(None, None, None)
+            })
+        } else {
+            self.stream.tagged_end(tag, label, index)
+        }
+    }
+
+    fn tag(&mut self, tag: Option<&Tag>, label: Option<&Label>, index: Option<&Index>) -> Result {
+        self.value(|stream| stream.tag(tag, label, index))
+    }
+
+    fn tag_hint(&mut self, tag: &Tag) -> Result {
+        self.stream.tag_hint(tag)
+    }
+
+    fn record_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+        num_entries: Option<usize>,
+    ) -> Result {
+        self.begin_document(|stream| stream.record_begin(tag, label, index, num_entries))
+    }
+
+    fn record_value_begin(&mut self, tag: Option<&Tag>, label: &Label) -> Result {
+        self.check_key(label.as_str())?;
+        self.stream.record_value_begin(tag, label)
+    }
+
+    fn record_value_end(&mut self, tag: Option<&Tag>, label: &Label) -> Result {
+        self.stream.record_value_end(tag, label)
+    }
+
+    fn record_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.end(|stream| stream.record_end(tag, label, index))
+    }
+
+    fn tuple_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+        num_entries: Option<usize>,
+    ) -> Result {
+        self.begin(|stream| stream.tuple_begin(tag, label, index, num_entries))
+    }
+
+    fn tuple_value_begin(&mut self, tag: Option<&Tag>, index: &Index) -> Result {
+        self.stream.tuple_value_begin(tag, index)
+    }
+
+    fn tuple_value_end(&mut self, tag: Option<&Tag>, index: &Index) -> Result {
+        self.stream.tuple_value_end(tag, index)
+    }
+
+    fn tuple_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.end(|stream| stream.tuple_end(tag, label, index))
+    }
+
+    fn record_tuple_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+        num_entries: Option<usize>,
+    ) -> Result {
+        self.begin_document(|stream| stream.record_tuple_begin(tag, label, index, num_entries))
+    }
+
+    fn record_tuple_value_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: &Label,
+        index: &Index,
+    ) -> Result {
+        self.check_key(label.as_str())?;
+        self.stream.record_tuple_value_begin(tag, label, index)
+    }
+
+    fn record_tuple_value_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: &Label,
+        index: &Index,
+    ) -> Result {
+        self.stream.record_tuple_value_end(tag, label, index)
+    }
+
+    fn record_tuple_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.end(|stream| stream.record_tuple_end(tag, label, index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec;
+
+    use sval_test::{assert_forwards_num, Token, TokenBuf};
+
+    fn bson_shape<'a>(
+        f: impl FnOnce(&mut BsonShape<TokenBuf<'a>>) -> Result,
+    ) -> core::result::Result<TokenBuf<'a>, BsonShapeError> {
+        let mut stream = BsonShape::new(TokenBuf::new());
+
+        match f(&mut stream) {
+            Ok(()) => Ok(stream.into_inner()),
+            Err(_) => Err(stream.error().unwrap()),
+        }
+    }
+
+    #[test]
+    fn rewrite_tagged() {
+        // ObjectIds and dates are rewritten into nested records
+        assert_eq!(
+            {
+                use Token::*;
+
+                vec![
+                    RecordBegin(None, None, None, Some(2)),
+                    RecordValueBegin(None, Label::new("_id")),
+                    RecordBegin(None, None, None, Some(1)),
+                    RecordValueBegin(None, Label::new("$oid")),
+                    TextBegin(Some(3)),
+                    TextFragmentComputed("abc".into()),
+                    TextEnd,
+                    RecordValueEnd(None, Label::new("$oid")),
+                    RecordEnd(None, None, None),
+                    RecordValueEnd(None, Label::new("_id")),
+                    RecordValueBegin(None, Label::new("at")),
+                    RecordBegin(None, None, None, Some(1)),
+                    RecordValueBegin(None, Label::new("$date")),
+                    I64(42),
+                    RecordValueEnd(None, Label::new("$date")),
+                    RecordEnd(None, None, None),
+                    RecordValueEnd(None, Label::new("at")),
+                    RecordEnd(None, None, None),
+                ]
+            },
+            bson_shape(|stream| {
+                stream.record_begin(None, None, None, Some(2))?;
+
+                stream.record_value_begin(None, &Label::new("_id"))?;
+                stream.tagged_begin(Some(&tags::BSON_OBJECT_ID), None, None)?;
+                stream.value_computed("abc")?;
+                stream.tagged_end(Some(&tags::BSON_OBJECT_ID), None, None)?;
+                stream.record_value_end(None, &Label::new("_id"))?;
+
+                stream.record_value_begin(None, &Label::new("at"))?;
+                stream.tagged_begin(Some(&tags::BSON_DATE), None, None)?;
+                stream.i64(42)?;
+                stream.tagged_end(Some(&tags::BSON_DATE), None, None)?;
+                stream.record_value_end(None, &Label::new("at"))?;
+
+                stream.record_end(None, None, None)
+            })
+            .unwrap()
+            .as_tokens()
+        );
+    }
+
+    #[test]
+    fn forward_documents() {
+        // Maps with text keys are forwarded unchanged
+        assert_eq!(
+            {
+                use Token::*;
+
+                vec![
+                    MapBegin(Some(1)),
+                    MapKeyBegin,
+                    TextBegin(Some(1)),
+                    TextFragmentComputed("a".into()),
+                    TextEnd,
+                    MapKeyEnd,
+                    MapValueBegin,
+                    SeqBegin(Some(1)),
+                    SeqValueBegin,
+                    I32(1),
+                    SeqValueEnd,
+                    SeqEnd,
+                    MapValueEnd,
+                    MapEnd,
+                ]
+            },
+            bson_shape(|stream| {
+                stream.map_begin(Some(1))?;
+
+                stream.map_key_begin()?;
+                stream.value_computed("a")?;
+                stream.map_key_end()?;
+
+                stream.map_value_begin()?;
+                stream.seq_begin(Some(1))?;
+                stream.seq_value_begin()?;
+                stream.i32(1)?;
+                stream.seq_value_end()?;
+                stream.seq_end()?;
+                stream.map_value_end()?;
+
+                stream.map_end()
+            })
+            .unwrap()
+            .as_tokens()
+        );
+
+        assert_forwards_num(BsonShape::new);
+    }
+
+    #[test]
+    fn invalid_documents() {
+        // The top-level value must be a document
+        assert_eq!(
+            BsonShapeError::NotADocument,
+            bson_shape(|stream| stream.i32(1)).unwrap_err()
+        );
+        assert_eq!(
+            BsonShapeError::NotADocument,
+            bson_shape(|stream| {
+                stream.seq_begin(None)?;
+                stream.seq_end()
+            })
+            .unwrap_err()
+        );
+
+        // Map keys must be text
+        assert_eq!(
+            BsonShapeError::NonTextKey,
+            bson_shape(|stream| {
+                stream.map_begin(Some(1))?;
+
+                stream.map_key_begin()?;
+                stream.i32(1)?;
+                stream.map_key_end()?;
+
+                stream.map_value_begin()?;
+                stream.i32(1)?;
+                stream.map_value_end()?;
+
+                stream.map_end()
+            })
+            .unwrap_err()
+        );
+
+        // Keys must not contain NUL
+        assert_eq!(
+            BsonShapeError::NulInKey,
+            bson_shape(|stream| {
+                stream.map_begin(Some(1))?;
+
+                stream.map_key_begin()?;
+                stream.value_computed("a\0b")?;
+                stream.map_key_end()?;
+
+                stream.map_value_begin()?;
+                stream.i32(1)?;
+                stream.map_value_end()?;
+
+                stream.map_end()
+            })
+            .unwrap_err()
+        );
+        assert_eq!(
+            BsonShapeError::NulInKey,
+            bson_shape(|stream| {
+                stream.record_begin(None, None, None, Some(1))?;
+
+                stream.record_value_begin(None, &Label::new("a\0b"))?;
+                stream.i32(1)?;
+                stream.record_value_end(None, &Label::new("a\0b"))?;
+
+                stream.record_end(None, None, None)
+            })
+            .unwrap_err()
+        );
+    }
+}
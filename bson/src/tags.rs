@@ -0,0 +1,27 @@
+/*!
+Tags for BSON-specific types.
+*/
+
+/**
+A tag for BSON ObjectIds.
+
+The [`BsonShape`](crate::BsonShape) stream rewrites tagged values with this tag into `{"$oid": value}`.
+
+# Valid datatypes
+
+- `text`
+- `binary`
+*/
+pub const BSON_OBJECT_ID: sval::Tag = sval::Tag::new("BSON_OBJECT_ID");
+
+/**
+A tag for BSON dates.
+
+The [`BsonShape`](crate::BsonShape) stream rewrites tagged values with this tag into `{"$date": value}`.
+
+# Valid datatypes
+
+- `text`
+- `i64`
+*/
+pub const BSON_DATE: sval::Tag = sval::Tag::new("BSON_DATE");
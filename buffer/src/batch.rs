@@ -0,0 +1,567 @@
+use crate::{
+    std::{mem, vec::Vec},
+    Error, ValueBuf,
+};
+
+const ELEMENTS: usize = 1;
+const ELEMENT_VALUE: usize = 2;
+
+/**
+Buffer the elements of a sequence into batches, passing each batch to a callback.
+
+Elements are buffered into [`ValueBuf`]s. Once `batch_size` elements have been buffered,
+they're passed to the callback and the batch is cleared. Any remaining elements are passed
+to the callback as a final partial batch when the sequence ends. This means only a single
+batch of elements needs to be held in memory at a time.
+
+```text
+[1, 2, 3, 4, 5]
+```
+
+with a batch size of `2` is passed to the callback as:
+
+```text
+[1, 2]
+[3, 4]
+[5]
+```
+
+This type requires the `alloc` feature.
+*/
+#[derive(Debug)]
+pub struct BatchSeq<'sval, F> {
+    batch: Vec<ValueBuf<'sval>>,
+    batch_size: usize,
+    depth: usize,
+    wrappers: usize,
+    current: ValueBuf<'sval>,
+    is_complete: bool,
+    err: Option<Error>,
+    f: F,
+}
+
+impl<'sval, F: FnMut(&[ValueBuf<'sval>])> BatchSeq<'sval, F> {
+    /**
+    Create a new batcher that passes batches of `batch_size` elements to `f`.
+
+    A `batch_size` of `0` is treated as `1`.
+    */
+    pub fn new(batch_size: usize, f: F) -> Self {
+        let batch_size = batch_size.max(1);
+
+        BatchSeq {
+            batch: Vec::with_capacity(batch_size),
+            batch_size,
+            depth: 0,
+            wrappers: 0,
+            current: ValueBuf::new(),
+            is_complete: false,
+            err: None,
+            f,
+        }
+    }
+
+    /**
+    Stream a sequence through a new batcher, passing batches of `batch_size` elements to `f`.
+    */
+    pub fn batch(
+        v: &'sval (impl sval::Value + ?Sized),
+        batch_size: usize,
+        f: F,
+    ) -> Result<(), Error> {
+        let mut batch = BatchSeq::new(batch_size, f);
+
+        match v.stream(&mut batch) {
+            Ok(()) if batch.is_complete() => Ok(()),
+            Ok(()) => Err(Error::invalid_value("the value is not a sequence")),
+            Err(_) => Err(batch
+                .into_err()
+                .unwrap_or_else(|| Error::invalid_value("the value itself failed to stream"))),
+        }
+    }
+
+    /**
+    Whether or not a complete sequence has been batched.
+    */
+    pub fn is_complete(&self) -> bool {
+        self.is_complete && self.wrappers == 0
+    }
+
+    /**
+    Take an error produced while attempting to batch a value.
+
+    This method may return `None` even if streaming failed if a value failed
+    without ever calling into the batcher.
+    */
+    pub fn into_err(self) -> Option<Error> {
+        self.err
+    }
+
+    fn fail(&mut self, err: Error) -> sval::Result {
+        let sval_err = err.to_sval();
+        self.err = Some(err);
+
+        Err(sval_err)
+    }
+
+    fn buffer(&mut self, f: impl FnOnce(&mut ValueBuf<'sval>) -> sval::Result) -> sval::Result {
+        if self.depth < ELEMENT_VALUE {
+            return self.fail(Error::unsupported("a sequence", "a value"));
+        }
+
+        match f(&mut self.current) {
+            Ok(()) => Ok(()),
+            Err(_) => {
+                let err = mem::take(&mut self.current)
+                    .into_err()
+                    .unwrap_or_else(|| Error::invalid_value("failed to buffer an element"));
+
+                self.fail(err)
+            }
+        }
+    }
+
+    fn buffer_begin(
+        &mut self,
+        f: impl FnOnce(&mut ValueBuf<'sval>) -> sval::Result,
+    ) -> sval::Result {
+        self.buffer(f)?;
+        self.depth += 1;
+
+        Ok(())
+    }
+
+    fn buffer_end(&mut self, f: impl FnOnce(&mut ValueBuf<'sval>) -> sval::Result) -> sval::Result {
+        self.buffer(f)?;
+        self.depth -= 1;
+
+        Ok(())
+    }
+
+    fn element_begin(&mut self) -> sval::Result {
+        self.depth = ELEMENT_VALUE;
+
+        Ok(())
+    }
+
+    fn element_end(&mut self) -> sval::Result {
+        self.batch.push(mem::take(&mut self.current));
+        self.depth = ELEMENTS;
+
+        if self.batch.len() == self.batch_size {
+            self.flush();
+        }
+
+        Ok(())
+    }
+
+    fn flush(&mut self) {
+        if !self.batch.is_empty() {
+            (self.f)(&self.batch);
+            self.batch.clear();
+        }
+    }
+}
+
+impl<'sval, F: FnMut(&[ValueBuf<'sval>])> sval::Stream<'sval> for BatchSeq<'sval, F> {
+    fn null(&mut self) -> sval::Result {
+        self.buffer(|buf| buf.null())
+    }
+
+    fn bool(&mut self, value: bool) -> sval::Result {
+        self.buffer(|buf| buf.bool(value))
+    }
+
+    fn text_begin(&mut self, num_bytes: Option<usize>) -> sval::Result {
+        self.buffer(|buf| buf.text_begin(num_bytes))
+    }
+
+    fn text_fragment(&mut self, fragment: &'sval str) -> sval::Result {
+        self.buffer(|buf| buf.text_fragment(fragment))
+    }
+
+    fn text_fragment_computed(&mut self, fragment: &str) -> sval::Result {
+        self.buffer(|buf| buf.text_fragment_computed(fragment))
+    }
+
+    fn text_end(&mut self) -> sval::Result {
+        self.buffer(|buf| buf.text_end())
+    }
+
+    fn binary_begin(&mut self, num_bytes: Option<usize>) -> sval::Result {
+        self.buffer(|buf| buf.binary_begin(num_bytes))
+    }
+
+    fn binary_fragment(&mut self, fragment: &'sval [u8]) -> sval::Result {
+        self.buffer(|buf| buf.binary_fragment(fragment))
+    }
+
+    fn binary_fragment_computed(&mut self, fragment: &[u8]) -> sval::Result {
+        self.buffer(|buf| buf.binary_fragment_computed(fragment))
+    }
+
+    fn binary_end(&mut self) -> sval::Result {
+        self.buffer(|buf| buf.binary_end())
+    }
+
+    fn u8(&mut self, value: u8) -> sval::Result {
+        self.buffer(|buf| buf.u8(value))
+    }
+
+    fn u16(&mut self, value: u16) -> sval::Result {
+        self.buffer(|buf| buf.u16(value))
+    }
+
+    fn u32(&mut self, value: u32) -> sval::Result {
+        self.buffer(|buf| buf.u32(value))
+    }
+
+    fn u64(&mut self, value: u64) -> sval::Result {
+        self.buffer(|buf| buf.u64(value))
+    }
+
+    fn u128(&mut self, value: u128) -> sval::Result {
+        self.buffer(|buf| buf.u128(value))
+    }
+
+    fn i8(&mut self, value: i8) -> sval::Result {
+        self.buffer(|buf| buf.i8(value))
+    }
+
+    fn i16(&mut self, value: i16) -> sval::Result {
+        self.buffer(|buf| buf.i16(value))
+    }
+
+    fn i32(&mut self, value: i32) -> sval::Result {
+        self.buffer(|buf| buf.i32(value))
+    }
+
+    fn i64(&mut self, value: i64) -> sval::Result {
+        self.buffer(|buf| buf.i64(value))
+    }
+
+    fn i128(&mut self, value: i128) -> sval::Result {
+        self.buffer(|buf| buf.i128(value))
+    }
+
+    fn f32(&mut self, value: f32) -> sval::Result {
+        self.buffer(|buf| buf.f32(value))
+    }
+
+    fn f64(&mut self, value: f64) -> sval::Result {
+        self.buffer(|buf| buf.f64(value))
+    }
+
+    fn map_begin(&mut self, num_entries: Option<usize>) -> sval::Result {
+        self.buffer_begin(|buf| buf.map_begin(num_entries))
+    }
+
+    fn map_key_begin(&mut self) -> sval::Result {
+        self.buffer(|buf| buf.map_key_begin())
+    }
+
+    fn map_key_end(&mut self) -> sval::Result {
+        self.buffer(|buf| buf.map_key_end())
+    }
+
+    fn map_value_begin(&mut self) -> sval::Result {
+        self.buffer(|buf| buf.map_value_begin())
+    }
+
+    fn map_value_end(&mut self) -> sval::Result {
+        self.buffer(|buf| buf.map_value_end())
+    }
+
+    fn map_end(&mut self) -> sval::Result {
+        self.buffer_end(|buf| buf.map_end())
+    }
+
+    fn seq_begin(&mut self, num_entries: Option<usize>) -> sval::Result {
+        if self.depth == 0 {
+            if self.is_complete {
+                return self.fail(Error::invalid_value("a sequence has already been batched"));
+            }
+
+            self.depth = ELEMENTS;
+
+            return Ok(());
+        }
+
+        self.buffer_begin(|buf| buf.seq_begin(num_entries))
+    }
+
+    fn seq_value_begin(&mut self) -> sval::Result {
+        if self.depth == ELEMENTS {
+            return self.element_begin();
+        }
+
+        self.buffer(|buf| buf.seq_value_begin())
+    }
+
+    fn seq_value_end(&mut self) -> sval::Result {
+        if self.depth == ELEMENT_VALUE {
+            return self.element_end();
+        }
+
+        self.buffer(|buf| buf.seq_value_end())
+    }
+
+    fn seq_end(&mut self) -> sval::Result {
+        if self.depth == ELEMENTS {
+            self.flush();
+
+            self.depth = 0;
+            self.is_complete = true;
+
+            return Ok(());
+        }
+
+        self.buffer_end(|buf| buf.seq_end())
+    }
+
+    fn enum_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.buffer_begin(|buf| buf.enum_begin(tag, label, index))
+    }
+
+    fn enum_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.buffer_end(|buf| buf.enum_end(tag, label, index))
+    }
+
+    fn tagged_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        // Tagged values around the sequence, like arrays with a constant size, are ignored
+        if self.depth == 0 && !self.is_complete {
+            self.wrappers += 1;
+
+            return Ok(());
+        }
+
+        self.buffer_begin(|buf| buf.tagged_begin(tag, label, index))
+    }
+
+    fn tagged_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        if self.depth == 0 && self.wrappers > 0 {
+            self.wrappers -= 1;
+
+            return Ok(());
+        }
+
+        self.buffer_end(|buf| buf.tagged_end(tag, label, index))
+    }
+
+    fn tag(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.buffer(|buf| buf.tag(tag, label, index))
+    }
+
+    fn tag_hint(&mut self, tag: &sval::Tag) -> sval::Result {
+        if self.depth < ELEMENT_VALUE {
+            return Ok(());
+        }
+
+        self.buffer(|buf| buf.tag_hint(tag))
+    }
+
+    fn record_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+        num_entries: Option<usize>,
+    ) -> sval::Result {
+        self.buffer_begin(|buf| buf.record_begin(tag, label, index, num_entries))
+    }
+
+    fn record_value_begin(&mut self, tag: Option<&sval::Tag>, label: &sval::Label) -> sval::Result {
+        self.buffer(|buf| buf.record_value_begin(tag, label))
+    }
+
+    fn record_value_end(&mut self, tag: Option<&sval::Tag>, label: &sval::Label) -> sval::Result {
+        self.buffer(|buf| buf.record_value_end(tag, label))
+    }
+
+    fn record_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.buffer_end(|buf| buf.record_end(tag, label, index))
+    }
+
+    fn tuple_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+        num_entries: Option<usize>,
+    ) -> sval::Result {
+        self.buffer_begin(|buf| buf.tuple_begin(tag, label, index, num_entries))
+    }
+
+    fn tuple_value_begin(&mut self, tag: Option<&sval::Tag>, index: &sval::Index) -> sval::Result {
+        self.buffer(|buf| buf.tuple_value_begin(tag, index))
+    }
+
+    fn tuple_value_end(&mut self, tag: Option<&sval::Tag>, index: &sval::Index) -> sval::Result {
+        self.buffer(|buf| buf.tuple_value_end(tag, index))
+    }
+
+    fn tuple_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.buffer_end(|buf| buf.tuple_end(tag, label, index))
+    }
+
+    fn record_tuple_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+        num_entries: Option<usize>,
+    ) -> sval::Result {
+        self.buffer_begin(|buf| buf.record_tuple_begin(tag, label, index, num_entries))
+    }
+
+    fn record_tuple_value_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: &sval::Label,
+        index: &sval::Index,
+    ) -> sval::Result {
+        self.buffer(|buf| buf.record_tuple_value_begin(tag, label, index))
+    }
+
+    fn record_tuple_value_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: &sval::Label,
+        index: &sval::Index,
+    ) -> sval::Result {
+        self.buffer(|buf| buf.record_tuple_value_end(tag, label, index))
+    }
+
+    fn record_tuple_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.buffer_end(|buf| buf.record_tuple_end(tag, label, index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::DynValue;
+
+    use sval_derive_macros::*;
+
+    #[derive(Value)]
+    struct Row {
+        id: i32,
+        name: &'static str,
+    }
+
+    fn batches(v: &(impl sval::Value + ?Sized), batch_size: usize) -> Vec<Vec<DynValue>> {
+        let mut batches = Vec::new();
+
+        BatchSeq::batch(v, batch_size, |batch: &[ValueBuf]| {
+            batches.push(
+                batch
+                    .iter()
+                    .map(|value| DynValue::collect(value).unwrap())
+                    .collect(),
+            );
+        })
+        .unwrap();
+
+        batches
+    }
+
+    fn dyn_values<V: sval::Value>(values: &[V]) -> Vec<DynValue> {
+        values
+            .iter()
+            .map(|value| DynValue::collect(value).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn batch_seq_batches_elements() {
+        assert_eq!(
+            vec![dyn_values(&[1, 2]), dyn_values(&[3, 4]), dyn_values(&[5])],
+            batches(&[1, 2, 3, 4, 5], 2)
+        );
+
+        assert_eq!(
+            vec![dyn_values(&[1, 2]), dyn_values(&[3, 4])],
+            batches(&[1, 2, 3, 4], 2)
+        );
+    }
+
+    #[test]
+    fn batch_seq_nested_elements() {
+        let rows = [
+            Row { id: 1, name: "a" },
+            Row { id: 2, name: "b" },
+            Row { id: 3, name: "c" },
+        ];
+
+        assert_eq!(
+            vec![dyn_values(&rows[..2]), dyn_values(&rows[2..])],
+            batches(&rows, 2)
+        );
+
+        assert_eq!(
+            vec![dyn_values(&[vec![1, 2]]), dyn_values(&[vec![3]])],
+            batches(&[vec![1, 2], vec![3]], 1)
+        );
+    }
+
+    #[test]
+    fn batch_seq_empty() {
+        assert!(batches(&[] as &[i32], 2).is_empty());
+    }
+
+    #[test]
+    fn batch_seq_zero_batch_size() {
+        assert_eq!(
+            vec![dyn_values(&[1]), dyn_values(&[2])],
+            batches(&[1, 2], 0)
+        );
+    }
+
+    #[test]
+    fn batch_seq_non_seq() {
+        assert!(BatchSeq::batch(&42, 2, |_: &[ValueBuf]| {}).is_err());
+        assert!(BatchSeq::batch(&Row { id: 1, name: "a" }, 2, |_: &[ValueBuf]| {}).is_err());
+    }
+}
@@ -0,0 +1,718 @@
+use crate::{std::mem, Error, ValueBuf};
+
+/**
+A stream that buffers any map, sequence, record, or tuple without a length hint,
+then replays it into an inner stream with its actual length.
+
+Streams for formats that write lengths before entries, like MessagePack or CBOR,
+can't write a collection until they know how many entries it has. Wrapping them in a
+`BufferedStream` means they'll only ever see `Some` length hints. Collections that
+already have a hint are passed through directly without being buffered.
+
+When a collection without a hint begins, it's buffered into a [`ValueBuf`] until it
+completes, including any nested values. Before being replayed, the hints on the
+collection and any nested collections without their own are filled in.
+
+```text
+seq_begin(None)
+  seq_value(1)
+  seq_value(2)
+seq_end()
+```
+
+is replayed into the inner stream as:
+
+```text
+seq_begin(Some(2))
+  seq_value(1)
+  seq_value(2)
+seq_end()
+```
+
+This type requires the `alloc` feature.
+*/
+#[derive(Debug)]
+pub struct BufferedStream<'sval, S> {
+    stream: S,
+    buf: ValueBuf<'sval>,
+    depth: usize,
+    err: Option<Error>,
+}
+
+impl<'sval, S: sval::Stream<'sval>> BufferedStream<'sval, S> {
+    /**
+    Wrap a stream, buffering any collections without a length hint before passing them to it.
+    */
+    pub fn new(stream: S) -> Self {
+        BufferedStream {
+            stream,
+            buf: ValueBuf::new(),
+            depth: 0,
+            err: None,
+        }
+    }
+
+    /**
+    Stream a value through a new buffered stream.
+    */
+    pub fn stream(stream: S, v: &'sval (impl sval::Value + ?Sized)) -> Result<S, Error> {
+        let mut buffered = BufferedStream::new(stream);
+
+        match v.stream(&mut buffered) {
+            Ok(()) if buffered.is_complete() => Ok(buffered.stream),
+            Ok(()) => Err(Error::invalid_value("the value is incomplete")),
+            Err(_) => Err(buffered
+                .into_err()
+                .unwrap_or_else(|| Error::invalid_value("the value itself failed to stream"))),
+        }
+    }
+
+    /**
+    Whether or not every buffered collection has been passed to the inner stream.
+    */
+    pub fn is_complete(&self) -> bool {
+        self.depth == 0
+    }
+
+    /**
+    Get the inner stream.
+
+    If [`BufferedStream::is_complete`] is `false` then the inner stream will be
+    missing the collection that's still being buffered.
+    */
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+
+    /**
+    Take an error produced while attempting to buffer a value.
+
+    This method may return `None` even if streaming failed if the inner stream
+    itself failed, or if a value failed without ever calling into the buffer.
+    */
+    pub fn into_err(self) -> Option<Error> {
+        self.err
+    }
+
+    fn fail(&mut self, err: Error) -> sval::Result {
+        let sval_err = err.to_sval();
+        self.err = Some(err);
+
+        Err(sval_err)
+    }
+
+    fn buffer(&mut self, f: impl FnOnce(&mut ValueBuf<'sval>) -> sval::Result) -> sval::Result {
+        match f(&mut self.buf) {
+            Ok(()) => Ok(()),
+            Err(_) => {
+                let err = mem::take(&mut self.buf)
+                    .into_err()
+                    .unwrap_or_else(|| Error::invalid_value("failed to buffer a value"));
+
+                self.depth = 0;
+                self.fail(err)
+            }
+        }
+    }
+
+    fn forward(
+        &mut self,
+        stream: impl FnOnce(&mut S) -> sval::Result,
+        buffer: impl FnOnce(&mut ValueBuf<'sval>) -> sval::Result,
+    ) -> sval::Result {
+        if self.depth == 0 {
+            stream(&mut self.stream)
+        } else {
+            self.buffer(buffer)
+        }
+    }
+
+    fn forward_begin(
+        &mut self,
+        stream: impl FnOnce(&mut S) -> sval::Result,
+        buffer: impl FnOnce(&mut ValueBuf<'sval>) -> sval::Result,
+    ) -> sval::Result {
+        if self.depth == 0 {
+            stream(&mut self.stream)
+        } else {
+            self.buffer(buffer)?;
+            self.depth += 1;
+
+            Ok(())
+        }
+    }
+
+    fn forward_collection_begin(
+        &mut self,
+        num_entries: Option<usize>,
+        stream: impl FnOnce(&mut S) -> sval::Result,
+        buffer: impl FnOnce(&mut ValueBuf<'sval>) -> sval::Result,
+    ) -> sval::Result {
+        if self.depth == 0 && num_entries.is_some() {
+            stream(&mut self.stream)
+        } else {
+            self.buffer(buffer)?;
+            self.depth += 1;
+
+            Ok(())
+        }
+    }
+
+    fn forward_end(
+        &mut self,
+        stream: impl FnOnce(&mut S) -> sval::Result,
+        buffer: impl FnOnce(&mut ValueBuf<'sval>) -> sval::Result,
+    ) -> sval::Result {
+        if self.depth == 0 {
+            return stream(&mut self.stream);
+        }
+
+        self.buffer(buffer)?;
+        self.depth -= 1;
+
+        if self.depth == 0 {
+            self.replay()?;
+        }
+
+        Ok(())
+    }
+
+    fn replay(&mut self) -> sval::Result {
+        self.buf.fill_num_entries();
+
+        let r = sval_ref::stream_ref(&mut self.stream, &self.buf);
+        self.buf.clear();
+
+        r
+    }
+}
+
+impl<'sval, S: sval::Stream<'sval>> sval::Stream<'sval> for BufferedStream<'sval, S> {
+    fn null(&mut self) -> sval::Result {
+        self.forward(|stream| stream.null(), |buf| buf.null())
+    }
+
+    fn bool(&mut self, value: bool) -> sval::Result {
+        self.forward(|stream| stream.bool(value), |buf| buf.bool(value))
+    }
+
+    fn text_begin(&mut self, num_bytes: Option<usize>) -> sval::Result {
+        self.forward(
+            |stream| stream.text_begin(num_bytes),
+            |buf| buf.text_begin(num_bytes),
+        )
+    }
+
+    fn text_fragment(&mut self, fragment: &'sval str) -> sval::Result {
+        self.forward(
+            |stream| stream.text_fragment(fragment),
+            |buf| buf.text_fragment(fragment),
+        )
+    }
+
+    fn text_fragment_computed(&mut self, fragment: &str) -> sval::Result {
+        self.forward(
+            |stream| stream.text_fragment_computed(fragment),
+            |buf| buf.text_fragment_computed(fragment),
+        )
+    }
+
+    fn text_end(&mut self) -> sval::Result {
+        self.forward(|stream| stream.text_end(), |buf| buf.text_end())
+    }
+
+    fn binary_begin(&mut self, num_bytes: Option<usize>) -> sval::Result {
+        self.forward(
+            |stream| stream.binary_begin(num_bytes),
+            |buf| buf.binary_begin(num_bytes),
+        )
+    }
+
+    fn binary_fragment(&mut self, fragment: &'sval [u8]) -> sval::Result {
+        self.forward(
+            |stream| stream.binary_fragment(fragment),
+            |buf| buf.binary_fragment(fragment),
+        )
+    }
+
+    fn binary_fragment_computed(&mut self, fragment: &[u8]) -> sval::Result {
+        self.forward(
+            |stream| stream.binary_fragment_computed(fragment),
+            |buf| buf.binary_fragment_computed(fragment),
+        )
+    }
+
+    fn binary_end(&mut self) -> sval::Result {
+        self.forward(|stream| stream.binary_end(), |buf| buf.binary_end())
+    }
+
+    fn u8(&mut self, value: u8) -> sval::Result {
+        self.forward(|stream| stream.u8(value), |buf| buf.u8(value))
+    }
+
+    fn u16(&mut self, value: u16) -> sval::Result {
+        self.forward(|stream| stream.u16(value), |buf| buf.u16(value))
+    }
+
+    fn u32(&mut self, value: u32) -> sval::Result {
+        self.forward(|stream| stream.u32(value), |buf| buf.u32(value))
+    }
+
+    fn u64(&mut self, value: u64) -> sval::Result {
+        self.forward(|stream| stream.u64(value), |buf| buf.u64(value))
+    }
+
+    fn u128(&mut self, value: u128) -> sval::Result {
+        self.forward(|stream| stream.u128(value), |buf| buf.u128(value))
+    }
+
+    fn i8(&mut self, value: i8) -> sval::Result {
+        self.forward(|stream| stream.i8(value), |buf| buf.i8(value))
+    }
+
+    fn i16(&mut self, value: i16) -> sval::Result {
+        self.forward(|stream| stream.i16(value), |buf| buf.i16(value))
+    }
+
+    fn i32(&mut self, value: i32) -> sval::Result {
+        self.forward(|stream| stream.i32(value), |buf| buf.i32(value))
+    }
+
+    fn i64(&mut self, value: i64) -> sval::Result {
+        self.forward(|stream| stream.i64(value), |buf| buf.i64(value))
+    }
+
+    fn i128(&mut self, value: i128) -> sval::Result {
+        self.forward(|stream| stream.i128(value), |buf| buf.i128(value))
+    }
+
+    fn f32(&mut self, value: f32) -> sval::Result {
+        self.forward(|stream| stream.f32(value), |buf| buf.f32(value))
+    }
+
+    fn f64(&mut self, value: f64) -> sval::Result {
+        self.forward(|stream| stream.f64(value), |buf| buf.f64(value))
+    }
+
+    fn num(&mut self, num: &sval::Number) -> sval::Result {
+        self.forward(|stream| stream.num(num), |buf| buf.num(num))
+    }
+
+    fn map_begin(&mut self, num_entries: Option<usize>) -> sval::Result {
+        self.forward_collection_begin(
+            num_entries,
+            |stream| stream.map_begin(num_entries),
+            |buf| buf.map_begin(num_entries),
+        )
+    }
+
+    fn map_key_begin(&mut self) -> sval::Result {
+        self.forward(|stream| stream.map_key_begin(), |buf| buf.map_key_begin())
+    }
+
+    fn map_key_end(&mut self) -> sval::Result {
+        self.forward(|stream| stream.map_key_end(), |buf| buf.map_key_end())
+    }
+
+    fn map_value_begin(&mut self) -> sval::Result {
+        self.forward(
+            |stream| stream.map_value_begin(),
+            |buf| buf.map_value_begin(),
+        )
+    }
+
+    fn map_value_end(&mut self) -> sval::Result {
+        self.forward(|stream| stream.map_value_end(), |buf| buf.map_value_end())
+    }
+
+    fn map_end(&mut self) -> sval::Result {
+        self.forward_end(|stream| stream.map_end(), |buf| buf.map_end())
+    }
+
+    fn seq_begin(&mut self, num_entries: Option<usize>) -> sval::Result {
+        self.forward_collection_begin(
+            num_entries,
+            |stream| stream.seq_begin(num_entries),
+            |buf| buf.seq_begin(num_entries),
+        )
+    }
+
+    fn seq_value_begin(&mut self) -> sval::Result {
+        self.forward(
+            |stream| stream.seq_value_begin(),
+            |buf| buf.seq_value_begin(),
+        )
+    }
+
+    fn seq_value_end(&mut self) -> sval::Result {
+        self.forward(|stream| stream.seq_value_end(), |buf| buf.seq_value_end())
+    }
+
+    fn seq_end(&mut self) -> sval::Result {
+        self.forward_end(|stream| stream.seq_end(), |buf| buf.seq_end())
+    }
+
+    fn enum_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.forward_begin(
+            |stream| stream.enum_begin(tag, label, index),
+            |buf| buf.enum_begin(tag, label, index),
+        )
+    }
+
+    fn enum_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.forward_end(
+            |stream| stream.enum_end(tag, label, index),
+            |buf| buf.enum_end(tag, label, index),
+        )
+    }
+
+    fn tagged_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.forward_begin(
+            |stream| stream.tagged_begin(tag, label, index),
+            |buf| buf.tagged_begin(tag, label, index),
+        )
+    }
+
+    fn tagged_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.forward_end(
+            |stream| stream.tagged_end(tag, label, index),
+            |buf| buf.tagged_end(tag, label, index),
+        )
+    }
+
+    fn tag(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.forward(
+            |stream| stream.tag(tag, label, index),
+            |buf| buf.tag(tag, label, index),
+        )
+    }
+
+    fn tag_hint(&mut self, tag: &sval::Tag) -> sval::Result {
+        self.forward(|stream| stream.tag_hint(tag), |buf| buf.tag_hint(tag))
+    }
+
+    fn record_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+        num_entries: Option<usize>,
+    ) -> sval::Result {
+        self.forward_collection_begin(
+            num_entries,
+            |stream| stream.record_begin(tag, label, index, num_entries),
+            |buf| buf.record_begin(tag, label, index, num_entries),
+        )
+    }
+
+    fn record_value_begin(&mut self, tag: Option<&sval::Tag>, label: &sval::Label) -> sval::Result {
+        self.forward(
+            |stream| stream.record_value_begin(tag, label),
+            |buf| buf.record_value_begin(tag, label),
+        )
+    }
+
+    fn record_value_end(&mut self, tag: Option<&sval::Tag>, label: &sval::Label) -> sval::Result {
+        self.forward(
+            |stream| stream.record_value_end(tag, label),
+            |buf| buf.record_value_end(tag, label),
+        )
+    }
+
+    fn record_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.forward_end(
+            |stream| stream.record_end(tag, label, index),
+            |buf| buf.record_end(tag, label, index),
+        )
+    }
+
+    fn tuple_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+        num_entries: Option<usize>,
+    ) -> sval::Result {
+        self.forward_collection_begin(
+            num_entries,
+            |stream| stream.tuple_begin(tag, label, index, num_entries),
+            |buf| buf.tuple_begin(tag, label, index, num_entries),
+        )
+    }
+
+    fn tuple_value_begin(&mut self, tag: Option<&sval::Tag>, index: &sval::Index) -> sval::Result {
+        self.forward(
+            |stream| stream.tuple_value_begin(tag, index),
+            |buf| buf.tuple_value_begin(tag, index),
+        )
+    }
+
+    fn tuple_value_end(&mut self, tag: Option<&sval::Tag>, index: &sval::Index) -> sval::Result {
+        self.forward(
+            |stream| stream.tuple_value_end(tag, index),
+            |buf| buf.tuple_value_end(tag, index),
+        )
+    }
+
+    fn tuple_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.forward_end(
+            |stream| stream.tuple_end(tag, label, index),
+            |buf| buf.tuple_end(tag, label, index),
+        )
+    }
+
+    fn record_tuple_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+        num_entries: Option<usize>,
+    ) -> sval::Result {
+        self.forward_collection_begin(
+            num_entries,
+            |stream| stream.record_tuple_begin(tag, label, index, num_entries),
+            |buf| buf.record_tuple_begin(tag, label, index, num_entries),
+        )
+    }
+
+    fn record_tuple_value_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: &sval::Label,
+        index: &sval::Index,
+    ) -> sval::Result {
+        self.forward(
+            |stream| stream.record_tuple_value_begin(tag, label, index),
+            |buf| buf.record_tuple_value_begin(tag, label, index),
+        )
+    }
+
+    fn record_tuple_value_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: &sval::Label,
+        index: &sval::Index,
+    ) -> sval::Result {
+        self.forward(
+            |stream| stream.record_tuple_value_end(tag, label, index),
+            |buf| buf.record_tuple_value_end(tag, label, index),
+        )
+    }
+
+    fn record_tuple_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.forward_end(
+            |stream| stream.record_tuple_end(tag, label, index),
+            |buf| buf.record_tuple_end(tag, label, index),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use sval::{Stream as _, StreamExt as _};
+    use sval_test::{Token, TokenBuf};
+
+    struct UnsizedSeq<'a>(&'a [i32]);
+
+    impl<'a> sval::Value for UnsizedSeq<'a> {
+        fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(
+            &'sval self,
+            stream: &mut S,
+        ) -> sval::Result {
+            stream.seq_begin(None)?;
+
+            for v in self.0 {
+                stream.seq_value(v)?;
+            }
+
+            stream.seq_end()
+        }
+    }
+
+    struct UnsizedRecord<'a> {
+        id: i32,
+        tags: UnsizedSeq<'a>,
+    }
+
+    impl<'a> sval::Value for UnsizedRecord<'a> {
+        fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(
+            &'sval self,
+            stream: &mut S,
+        ) -> sval::Result {
+            let id = sval::Label::new("id");
+            let tags = sval::Label::new("tags");
+
+            stream.record_begin(None, None, None, None)?;
+
+            stream.record_value_begin(None, &id)?;
+            stream.value(&self.id)?;
+            stream.record_value_end(None, &id)?;
+
+            stream.record_value_begin(None, &tags)?;
+            stream.value(&self.tags)?;
+            stream.record_value_end(None, &tags)?;
+
+            stream.record_end(None, None, None)
+        }
+    }
+
+    fn tokens<'sval>(v: &'sval (impl sval::Value + ?Sized)) -> TokenBuf<'sval> {
+        BufferedStream::stream(TokenBuf::new(), v).unwrap()
+    }
+
+    #[test]
+    fn buffered_stream_replays_with_hint() {
+        use Token::*;
+
+        assert_eq!(
+            vec![
+                SeqBegin(Some(3)),
+                SeqValueBegin,
+                I32(1),
+                SeqValueEnd,
+                SeqValueBegin,
+                I32(2),
+                SeqValueEnd,
+                SeqValueBegin,
+                I32(3),
+                SeqValueEnd,
+                SeqEnd,
+            ],
+            tokens(&UnsizedSeq(&[1, 2, 3])).as_tokens()
+        );
+
+        assert_eq!(
+            vec![SeqBegin(Some(0)), SeqEnd],
+            tokens(&UnsizedSeq(&[])).as_tokens()
+        );
+    }
+
+    #[test]
+    fn buffered_stream_replays_nested() {
+        use Token::*;
+
+        assert_eq!(
+            vec![
+                RecordBegin(None, None, None, Some(2)),
+                RecordValueBegin(None, sval::Label::new("id")),
+                I32(1),
+                RecordValueEnd(None, sval::Label::new("id")),
+                RecordValueBegin(None, sval::Label::new("tags")),
+                SeqBegin(Some(2)),
+                SeqValueBegin,
+                I32(1),
+                SeqValueEnd,
+                SeqValueBegin,
+                I32(2),
+                SeqValueEnd,
+                SeqEnd,
+                RecordValueEnd(None, sval::Label::new("tags")),
+                RecordEnd(None, None, None),
+            ],
+            tokens(&UnsizedRecord {
+                id: 1,
+                tags: UnsizedSeq(&[1, 2]),
+            })
+            .as_tokens()
+        );
+    }
+
+    #[test]
+    fn buffered_stream_passes_through_sized() {
+        let values = [UnsizedSeq(&[1]), UnsizedSeq(&[2, 3])];
+
+        let mut stream = BufferedStream::new(TokenBuf::new());
+
+        // The outer sequence has a hint, so isn't buffered
+        stream.seq_begin(Some(2)).unwrap();
+        assert!(stream.is_complete());
+
+        stream.seq_value_begin().unwrap();
+        stream.value(&values[0]).unwrap();
+        stream.seq_value_end().unwrap();
+
+        stream.seq_value_begin().unwrap();
+        stream.seq_begin(None).unwrap();
+        assert!(!stream.is_complete());
+        stream.seq_value(&2).unwrap();
+        stream.seq_value(&3).unwrap();
+        stream.seq_end().unwrap();
+        assert!(stream.is_complete());
+        stream.seq_value_end().unwrap();
+
+        stream.seq_end().unwrap();
+
+        assert_eq!(
+            {
+                use Token::*;
+
+                &[
+                    SeqBegin(Some(2)),
+                    SeqValueBegin,
+                    SeqBegin(Some(1)),
+                    SeqValueBegin,
+                    I32(1),
+                    SeqValueEnd,
+                    SeqEnd,
+                    SeqValueEnd,
+                    SeqValueBegin,
+                    SeqBegin(Some(2)),
+                    SeqValueBegin,
+                    I32(2),
+                    SeqValueEnd,
+                    SeqValueBegin,
+                    I32(3),
+                    SeqValueEnd,
+                    SeqEnd,
+                    SeqValueEnd,
+                    SeqEnd,
+                ] as &[_]
+            },
+            stream.into_inner().as_tokens()
+        );
+
+        sval_test::assert_forwards_num(BufferedStream::new);
+    }
+}
@@ -0,0 +1,731 @@
+use crate::{
+    std::{mem, vec::Vec},
+    Error, ValueBuf,
+};
+
+const ROWS: usize = 1;
+const FIELDS: usize = 2;
+const FIELD_VALUE: usize = 3;
+
+/**
+Buffer a sequence of records into a record of sequences.
+
+This is a struct-of-arrays transform: each field of the streamed records becomes
+a field of the same name holding a sequence of that field's value from every record.
+The first record determines the fields and their order. All other records must have
+the same fields in the same order.
+
+```text
+[{ a: 1, b: true }, { a: 2, b: false }]
+```
+
+is streamed as:
+
+```text
+{ a: [1, 2], b: [true, false] }
+```
+
+All records need to be buffered before any columns can be streamed.
+
+This type requires the `alloc` feature.
+*/
+#[derive(Debug)]
+pub struct Columnar<'sval> {
+    columns: Vec<(sval::Label<'static>, Vec<ValueBuf<'sval>>)>,
+    rows: usize,
+    depth: usize,
+    wrappers: usize,
+    field: usize,
+    current: ValueBuf<'sval>,
+    is_complete: bool,
+    err: Option<Error>,
+}
+
+impl<'sval> Default for Columnar<'sval> {
+    fn default() -> Self {
+        Columnar::new()
+    }
+}
+
+impl<'sval> Columnar<'sval> {
+    /**
+    Create a new empty columnar buffer.
+    */
+    pub fn new() -> Self {
+        Columnar {
+            columns: Vec::new(),
+            rows: 0,
+            depth: 0,
+            wrappers: 0,
+            field: 0,
+            current: ValueBuf::new(),
+            is_complete: false,
+            err: None,
+        }
+    }
+
+    /**
+    Buffer a sequence of records into columns.
+    */
+    pub fn collect(v: &'sval (impl sval::Value + ?Sized)) -> Result<Self, Error> {
+        let mut columnar = Columnar::new();
+
+        match v.stream(&mut columnar) {
+            Ok(()) if columnar.is_complete() => Ok(columnar),
+            Ok(()) => Err(Error::invalid_value(
+                "the value is not a sequence of records",
+            )),
+            Err(_) => Err(columnar
+                .into_err()
+                .unwrap_or_else(|| Error::invalid_value("the value itself failed to stream"))),
+        }
+    }
+
+    /**
+    Whether or not a complete sequence of records has been buffered.
+    */
+    pub fn is_complete(&self) -> bool {
+        self.is_complete && self.wrappers == 0
+    }
+
+    /**
+    The number of records that have been buffered.
+    */
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /**
+    Take an error produced while attempting to buffer a value.
+
+    This method may return `None` even if streaming failed if a value failed
+    without ever calling into the buffer.
+    */
+    pub fn into_err(self) -> Option<Error> {
+        self.err
+    }
+
+    fn fail(&mut self, err: Error) -> sval::Result {
+        let sval_err = err.to_sval();
+        self.err = Some(err);
+
+        Err(sval_err)
+    }
+
+    fn buffer(&mut self, f: impl FnOnce(&mut ValueBuf<'sval>) -> sval::Result) -> sval::Result {
+        if self.depth < FIELD_VALUE {
+            return self.fail(Error::unsupported("a sequence of records", "a value"));
+        }
+
+        match f(&mut self.current) {
+            Ok(()) => Ok(()),
+            Err(_) => {
+                let err = mem::take(&mut self.current)
+                    .into_err()
+                    .unwrap_or_else(|| Error::invalid_value("failed to buffer a field value"));
+
+                self.fail(err)
+            }
+        }
+    }
+
+    fn buffer_begin(
+        &mut self,
+        f: impl FnOnce(&mut ValueBuf<'sval>) -> sval::Result,
+    ) -> sval::Result {
+        self.buffer(f)?;
+        self.depth += 1;
+
+        Ok(())
+    }
+
+    fn buffer_end(&mut self, f: impl FnOnce(&mut ValueBuf<'sval>) -> sval::Result) -> sval::Result {
+        self.buffer(f)?;
+        self.depth -= 1;
+
+        Ok(())
+    }
+
+    fn row_begin(&mut self) -> sval::Result {
+        self.depth = FIELDS;
+        self.field = 0;
+
+        Ok(())
+    }
+
+    fn row_end(&mut self) -> sval::Result {
+        if self.rows > 0 && self.field != self.columns.len() {
+            return self.fail(Error::invalid_value(
+                "records have a different number of fields",
+            ));
+        }
+
+        self.depth = ROWS;
+        self.rows += 1;
+
+        Ok(())
+    }
+
+    fn field_begin(&mut self, label: &sval::Label) -> sval::Result {
+        if self.rows == 0 {
+            self.columns.push((label.to_owned(), Vec::new()));
+        } else {
+            match self.columns.get(self.field) {
+                Some((column, _)) if column == label => (),
+                _ => {
+                    return self.fail(Error::invalid_value(
+                        "records have different fields or field order",
+                    ))
+                }
+            }
+        }
+
+        self.depth = FIELD_VALUE;
+
+        Ok(())
+    }
+
+    fn field_end(&mut self) -> sval::Result {
+        let value = mem::take(&mut self.current);
+        self.columns[self.field].1.push(value);
+
+        self.depth = FIELDS;
+        self.field += 1;
+
+        Ok(())
+    }
+}
+
+impl<'a> sval::Value for Columnar<'a> {
+    fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> sval::Result {
+        if !self.is_complete() {
+            return sval::error();
+        }
+
+        stream.record_begin(None, None, None, Some(self.columns.len()))?;
+
+        for (label, values) in &self.columns {
+            stream.record_value_begin(None, label)?;
+            stream.seq_begin(Some(values.len()))?;
+
+            for value in values {
+                stream.seq_value_begin()?;
+                stream.value_computed(value)?;
+                stream.seq_value_end()?;
+            }
+
+            stream.seq_end()?;
+            stream.record_value_end(None, label)?;
+        }
+
+        stream.record_end(None, None, None)
+    }
+}
+
+impl<'sval> sval::Stream<'sval> for Columnar<'sval> {
+    fn null(&mut self) -> sval::Result {
+        self.buffer(|buf| buf.null())
+    }
+
+    fn bool(&mut self, value: bool) -> sval::Result {
+        self.buffer(|buf| buf.bool(value))
+    }
+
+    fn text_begin(&mut self, num_bytes: Option<usize>) -> sval::Result {
+        self.buffer(|buf| buf.text_begin(num_bytes))
+    }
+
+    fn text_fragment(&mut self, fragment: &'sval str) -> sval::Result {
+        self.buffer(|buf| buf.text_fragment(fragment))
+    }
+
+    fn text_fragment_computed(&mut self, fragment: &str) -> sval::Result {
+        self.buffer(|buf| buf.text_fragment_computed(fragment))
+    }
+
+    fn text_end(&mut self) -> sval::Result {
+        self.buffer(|buf| buf.text_end())
+    }
+
+    fn binary_begin(&mut self, num_bytes: Option<usize>) -> sval::Result {
+        self.buffer(|buf| buf.binary_begin(num_bytes))
+    }
+
+    fn binary_fragment(&mut self, fragment: &'sval [u8]) -> sval::Result {
+        self.buffer(|buf| buf.binary_fragment(fragment))
+    }
+
+    fn binary_fragment_computed(&mut self, fragment: &[u8]) -> sval::Result {
+        self.buffer(|buf| buf.binary_fragment_computed(fragment))
+    }
+
+    fn binary_end(&mut self) -> sval::Result {
+        self.buffer(|buf| buf.binary_end())
+    }
+
+    fn u8(&mut self, value: u8) -> sval::Result {
+        self.buffer(|buf| buf.u8(value))
+    }
+
+    fn u16(&mut self, value: u16) -> sval::Result {
+        self.buffer(|buf| buf.u16(value))
+    }
+
+    fn u32(&mut self, value: u32) -> sval::Result {
+        self.buffer(|buf| buf.u32(value))
+    }
+
+    fn u64(&mut self, value: u64) -> sval::Result {
+        self.buffer(|buf| buf.u64(value))
+    }
+
+    fn u128(&mut self, value: u128) -> sval::Result {
+        self.buffer(|buf| buf.u128(value))
+    }
+
+    fn i8(&mut self, value: i8) -> sval::Result {
+        self.buffer(|buf| buf.i8(value))
+    }
+
+    fn i16(&mut self, value: i16) -> sval::Result {
+        self.buffer(|buf| buf.i16(value))
+    }
+
+    fn i32(&mut self, value: i32) -> sval::Result {
+        self.buffer(|buf| buf.i32(value))
+    }
+
+    fn i64(&mut self, value: i64) -> sval::Result {
+        self.buffer(|buf| buf.i64(value))
+    }
+
+    fn i128(&mut self, value: i128) -> sval::Result {
+        self.buffer(|buf| buf.i128(value))
+    }
+
+    fn f32(&mut self, value: f32) -> sval::Result {
+        self.buffer(|buf| buf.f32(value))
+    }
+
+    fn f64(&mut self, value: f64) -> sval::Result {
+        self.buffer(|buf| buf.f64(value))
+    }
+
+    fn map_begin(&mut self, num_entries: Option<usize>) -> sval::Result {
+        self.buffer_begin(|buf| buf.map_begin(num_entries))
+    }
+
+    fn map_key_begin(&mut self) -> sval::Result {
+        self.buffer(|buf| buf.map_key_begin())
+    }
+
+    fn map_key_end(&mut self) -> sval::Result {
+        self.buffer(|buf| buf.map_key_end())
+    }
+
+    fn map_value_begin(&mut self) -> sval::Result {
+        self.buffer(|buf| buf.map_value_begin())
+    }
+
+    fn map_value_end(&mut self) -> sval::Result {
+        self.buffer(|buf| buf.map_value_end())
+    }
+
+    fn map_end(&mut self) -> sval::Result {
+        self.buffer_end(|buf| buf.map_end())
+    }
+
+    fn seq_begin(&mut self, num_entries: Option<usize>) -> sval::Result {
+        if self.depth == 0 {
+            if self.is_complete {
+                return self.fail(Error::invalid_value("a sequence has already been buffered"));
+            }
+
+            self.depth = ROWS;
+
+            return Ok(());
+        }
+
+        self.buffer_begin(|buf| buf.seq_begin(num_entries))
+    }
+
+    fn seq_value_begin(&mut self) -> sval::Result {
+        if self.depth == ROWS {
+            return Ok(());
+        }
+
+        self.buffer(|buf| buf.seq_value_begin())
+    }
+
+    fn seq_value_end(&mut self) -> sval::Result {
+        if self.depth == ROWS {
+            return Ok(());
+        }
+
+        self.buffer(|buf| buf.seq_value_end())
+    }
+
+    fn seq_end(&mut self) -> sval::Result {
+        if self.depth == ROWS {
+            self.depth = 0;
+            self.is_complete = true;
+
+            return Ok(());
+        }
+
+        self.buffer_end(|buf| buf.seq_end())
+    }
+
+    fn enum_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.buffer_begin(|buf| buf.enum_begin(tag, label, index))
+    }
+
+    fn enum_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.buffer_end(|buf| buf.enum_end(tag, label, index))
+    }
+
+    fn tagged_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        // Tagged values around the sequence, like arrays with a constant size, are ignored
+        if self.depth == 0 && !self.is_complete {
+            self.wrappers += 1;
+
+            return Ok(());
+        }
+
+        self.buffer_begin(|buf| buf.tagged_begin(tag, label, index))
+    }
+
+    fn tagged_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        if self.depth == 0 && self.wrappers > 0 {
+            self.wrappers -= 1;
+
+            return Ok(());
+        }
+
+        self.buffer_end(|buf| buf.tagged_end(tag, label, index))
+    }
+
+    fn tag(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.buffer(|buf| buf.tag(tag, label, index))
+    }
+
+    fn tag_hint(&mut self, tag: &sval::Tag) -> sval::Result {
+        if self.depth < FIELD_VALUE {
+            return Ok(());
+        }
+
+        self.buffer(|buf| buf.tag_hint(tag))
+    }
+
+    fn record_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+        num_entries: Option<usize>,
+    ) -> sval::Result {
+        if self.depth == ROWS {
+            return self.row_begin();
+        }
+
+        self.buffer_begin(|buf| buf.record_begin(tag, label, index, num_entries))
+    }
+
+    fn record_value_begin(&mut self, tag: Option<&sval::Tag>, label: &sval::Label) -> sval::Result {
+        if self.depth == FIELDS {
+            return self.field_begin(label);
+        }
+
+        self.buffer(|buf| buf.record_value_begin(tag, label))
+    }
+
+    fn record_value_end(&mut self, tag: Option<&sval::Tag>, label: &sval::Label) -> sval::Result {
+        if self.depth == FIELD_VALUE {
+            return self.field_end();
+        }
+
+        self.buffer(|buf| buf.record_value_end(tag, label))
+    }
+
+    fn record_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        if self.depth == FIELDS {
+            return self.row_end();
+        }
+
+        self.buffer_end(|buf| buf.record_end(tag, label, index))
+    }
+
+    fn tuple_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+        num_entries: Option<usize>,
+    ) -> sval::Result {
+        self.buffer_begin(|buf| buf.tuple_begin(tag, label, index, num_entries))
+    }
+
+    fn tuple_value_begin(&mut self, tag: Option<&sval::Tag>, index: &sval::Index) -> sval::Result {
+        self.buffer(|buf| buf.tuple_value_begin(tag, index))
+    }
+
+    fn tuple_value_end(&mut self, tag: Option<&sval::Tag>, index: &sval::Index) -> sval::Result {
+        self.buffer(|buf| buf.tuple_value_end(tag, index))
+    }
+
+    fn tuple_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.buffer_end(|buf| buf.tuple_end(tag, label, index))
+    }
+
+    fn record_tuple_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+        num_entries: Option<usize>,
+    ) -> sval::Result {
+        if self.depth == ROWS {
+            return self.row_begin();
+        }
+
+        self.buffer_begin(|buf| buf.record_tuple_begin(tag, label, index, num_entries))
+    }
+
+    fn record_tuple_value_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: &sval::Label,
+        index: &sval::Index,
+    ) -> sval::Result {
+        if self.depth == FIELDS {
+            return self.field_begin(label);
+        }
+
+        self.buffer(|buf| buf.record_tuple_value_begin(tag, label, index))
+    }
+
+    fn record_tuple_value_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: &sval::Label,
+        index: &sval::Index,
+    ) -> sval::Result {
+        if self.depth == FIELD_VALUE {
+            return self.field_end();
+        }
+
+        self.buffer(|buf| buf.record_tuple_value_end(tag, label, index))
+    }
+
+    fn record_tuple_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        if self.depth == FIELDS {
+            return self.row_end();
+        }
+
+        self.buffer_end(|buf| buf.record_tuple_end(tag, label, index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::std::borrow::ToOwned;
+
+    use sval::Stream as _;
+    use sval_derive_macros::*;
+    use sval_test::{assert_tokens, Token};
+
+    #[derive(Value)]
+    struct Row {
+        id: i32,
+        name: &'static str,
+        tags: &'static [&'static str],
+    }
+
+    #[test]
+    fn columnar_transposes_records() {
+        let rows = [
+            Row {
+                id: 1,
+                name: "a",
+                tags: &["x"],
+            },
+            Row {
+                id: 2,
+                name: "b",
+                tags: &[],
+            },
+        ];
+
+        let columnar = Columnar::collect(&rows).unwrap();
+
+        assert_eq!(2, columnar.rows());
+
+        assert_tokens(&columnar, {
+            use Token::*;
+
+            &[
+                RecordBegin(None, None, None, Some(3)),
+                RecordValueBegin(None, sval::Label::new("id")),
+                SeqBegin(Some(2)),
+                SeqValueBegin,
+                I32(1),
+                SeqValueEnd,
+                SeqValueBegin,
+                I32(2),
+                SeqValueEnd,
+                SeqEnd,
+                RecordValueEnd(None, sval::Label::new("id")),
+                RecordValueBegin(None, sval::Label::new("name")),
+                SeqBegin(Some(2)),
+                SeqValueBegin,
+                TextBegin(Some(1)),
+                TextFragmentComputed("a".to_owned()),
+                TextEnd,
+                SeqValueEnd,
+                SeqValueBegin,
+                TextBegin(Some(1)),
+                TextFragmentComputed("b".to_owned()),
+                TextEnd,
+                SeqValueEnd,
+                SeqEnd,
+                RecordValueEnd(None, sval::Label::new("name")),
+                RecordValueBegin(None, sval::Label::new("tags")),
+                SeqBegin(Some(2)),
+                SeqValueBegin,
+                SeqBegin(Some(1)),
+                SeqValueBegin,
+                TextBegin(Some(1)),
+                TextFragmentComputed("x".to_owned()),
+                TextEnd,
+                SeqValueEnd,
+                SeqEnd,
+                SeqValueEnd,
+                SeqValueBegin,
+                SeqBegin(Some(0)),
+                SeqEnd,
+                SeqValueEnd,
+                SeqEnd,
+                RecordValueEnd(None, sval::Label::new("tags")),
+                RecordEnd(None, None, None),
+            ]
+        });
+    }
+
+    #[test]
+    fn columnar_empty_seq() {
+        let columnar = Columnar::collect(&[] as &[Row]).unwrap();
+
+        assert_eq!(0, columnar.rows());
+
+        assert_tokens(&columnar, {
+            use Token::*;
+
+            &[
+                RecordBegin(None, None, None, Some(0)),
+                RecordEnd(None, None, None),
+            ]
+        });
+    }
+
+    #[test]
+    fn columnar_heterogeneous_records() {
+        #[derive(Value)]
+        struct Short {
+            id: i32,
+        }
+
+        #[derive(Value)]
+        struct Long {
+            id: i32,
+            name: &'static str,
+        }
+
+        #[derive(Value)]
+        struct Renamed {
+            key: i32,
+        }
+
+        assert!(Columnar::collect(&[Short { id: 1 }]).is_ok());
+
+        let mut columnar = Columnar::new();
+
+        columnar.seq_begin(None).unwrap();
+        columnar.seq_value_begin().unwrap();
+        columnar.value_computed(&Short { id: 1 }).unwrap();
+        columnar.seq_value_end().unwrap();
+        columnar.seq_value_begin().unwrap();
+        assert!(columnar.value_computed(&Long { id: 2, name: "b" }).is_err());
+
+        let mut columnar = Columnar::new();
+
+        columnar.seq_begin(None).unwrap();
+        columnar.seq_value_begin().unwrap();
+        columnar.value_computed(&Long { id: 1, name: "a" }).unwrap();
+        columnar.seq_value_end().unwrap();
+        columnar.seq_value_begin().unwrap();
+        assert!(columnar.value_computed(&Short { id: 2 }).is_err());
+
+        let mut columnar = Columnar::new();
+
+        columnar.seq_begin(None).unwrap();
+        columnar.seq_value_begin().unwrap();
+        columnar.value_computed(&Short { id: 1 }).unwrap();
+        columnar.seq_value_end().unwrap();
+        columnar.seq_value_begin().unwrap();
+        assert!(columnar.value_computed(&Renamed { key: 2 }).is_err());
+    }
+
+    #[test]
+    fn columnar_non_records() {
+        assert!(Columnar::collect(&42).is_err());
+        assert!(Columnar::collect(&[1, 2, 3]).is_err());
+        assert!(Columnar::collect(&[Some(Row {
+            id: 1,
+            name: "a",
+            tags: &[],
+        })])
+        .is_err());
+    }
+}
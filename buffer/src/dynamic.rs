@@ -0,0 +1,433 @@
+use crate::{
+    std::{string::String, vec::Vec},
+    Error,
+};
+
+/**
+An owned, dynamically typed value.
+
+Unlike [`ValueBuf`](crate::ValueBuf), which buffers the stream of a value so it can be replayed,
+this type collects a value into a simple tree that's easy to inspect with pattern matching.
+Only the core data model is retained, so tags, labels, and indexes are discarded. Records
+are collected as maps with text keys, and tuples as sequences.
+
+This type requires the `alloc` feature.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub enum DynValue {
+    /**
+    An empty value.
+    */
+    Null,
+    /**
+    A boolean.
+    */
+    Bool(bool),
+    /**
+    A signed integer.
+    */
+    I64(i64),
+    /**
+    An unsigned integer.
+    */
+    U64(u64),
+    /**
+    A floating point number.
+    */
+    F64(f64),
+    /**
+    A string of text.
+    */
+    Text(String),
+    /**
+    A bitstring.
+    */
+    Binary(Vec<u8>),
+    /**
+    A sequence of values.
+    */
+    Seq(Vec<DynValue>),
+    /**
+    A mapping of keys to values.
+
+    Entries are kept in the order they were streamed.
+    */
+    Map(Vec<(DynValue, DynValue)>),
+}
+
+impl DynValue {
+    /**
+    Collect a value into a dynamic value.
+
+    Integers that don't fit in 64 bits are collected as text.
+    */
+    pub fn collect(v: &(impl sval::Value + ?Sized)) -> Result<Self, Error> {
+        let mut collector = DynValueCollector {
+            stack: Vec::new(),
+            value: None,
+            err: None,
+        };
+
+        match v.stream(&mut collector) {
+            Ok(()) => collector.finish(),
+            Err(_) => Err(collector
+                .err
+                .unwrap_or_else(|| Error::invalid_value("the value itself failed to stream"))),
+        }
+    }
+}
+
+impl sval::Value for DynValue {
+    fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> sval::Result {
+        match self {
+            DynValue::Null => stream.null(),
+            DynValue::Bool(v) => stream.bool(*v),
+            DynValue::I64(v) => stream.i64(*v),
+            DynValue::U64(v) => stream.u64(*v),
+            DynValue::F64(v) => stream.f64(*v),
+            DynValue::Text(v) => stream.value(&**v),
+            DynValue::Binary(v) => stream.value(sval::BinarySlice::new(v)),
+            DynValue::Seq(v) => {
+                stream.seq_begin(Some(v.len()))?;
+
+                for value in v {
+                    stream.seq_value_begin()?;
+                    stream.value(value)?;
+                    stream.seq_value_end()?;
+                }
+
+                stream.seq_end()
+            }
+            DynValue::Map(v) => {
+                stream.map_begin(Some(v.len()))?;
+
+                for (key, value) in v {
+                    stream.map_key_begin()?;
+                    stream.value(key)?;
+                    stream.map_key_end()?;
+
+                    stream.map_value_begin()?;
+                    stream.value(value)?;
+                    stream.map_value_end()?;
+                }
+
+                stream.map_end()
+            }
+        }
+    }
+}
+
+struct DynValueCollector {
+    stack: Vec<Frame>,
+    value: Option<DynValue>,
+    err: Option<Error>,
+}
+
+enum Frame {
+    Text(String),
+    Binary(Vec<u8>),
+    Seq(Vec<DynValue>),
+    Map {
+        entries: Vec<(DynValue, DynValue)>,
+        key: Option<DynValue>,
+    },
+}
+
+impl DynValueCollector {
+    fn try_catch(&mut self, f: impl FnOnce(&mut Self) -> Result<(), Error>) -> sval::Result {
+        match f(self) {
+            Ok(()) => Ok(()),
+            Err(e) => self.fail(e),
+        }
+    }
+
+    fn fail(&mut self, err: Error) -> sval::Result {
+        let sval_err = err.to_sval();
+        self.err = Some(err);
+
+        Err(sval_err)
+    }
+
+    fn push_value(&mut self, value: DynValue) -> Result<(), Error> {
+        match self.stack.last_mut() {
+            None if self.value.is_none() => {
+                self.value = Some(value);
+                Ok(())
+            }
+            None => Err(Error::invalid_value("multiple values were streamed")),
+            Some(Frame::Seq(values)) => {
+                values.push(value);
+                Ok(())
+            }
+            Some(Frame::Map { entries, key }) => {
+                match key.take() {
+                    Some(key) => entries.push((key, value)),
+                    None => *key = Some(value),
+                }
+
+                Ok(())
+            }
+            Some(Frame::Text(_)) => Err(Error::unsupported("text fragment", "value")),
+            Some(Frame::Binary(_)) => Err(Error::unsupported("binary fragment", "value")),
+        }
+    }
+
+    fn finish(self) -> Result<DynValue, Error> {
+        match self.value {
+            Some(value) if self.stack.is_empty() => Ok(value),
+            _ => Err(Error::invalid_value("the value is incomplete")),
+        }
+    }
+}
+
+impl<'sval> sval::Stream<'sval> for DynValueCollector {
+    fn null(&mut self) -> sval::Result {
+        self.try_catch(|buf| buf.push_value(DynValue::Null))
+    }
+
+    fn bool(&mut self, value: bool) -> sval::Result {
+        self.try_catch(|buf| buf.push_value(DynValue::Bool(value)))
+    }
+
+    fn text_begin(&mut self, num_bytes: Option<usize>) -> sval::Result {
+        self.stack
+            .push(Frame::Text(String::with_capacity(num_bytes.unwrap_or(0))));
+
+        Ok(())
+    }
+
+    fn text_fragment_computed(&mut self, fragment: &str) -> sval::Result {
+        self.try_catch(|buf| match buf.stack.last_mut() {
+            Some(Frame::Text(text)) => {
+                text.push_str(fragment);
+                Ok(())
+            }
+            _ => Err(Error::outside_container("text")),
+        })
+    }
+
+    fn text_end(&mut self) -> sval::Result {
+        self.try_catch(|buf| match buf.stack.pop() {
+            Some(Frame::Text(text)) => buf.push_value(DynValue::Text(text)),
+            _ => Err(Error::outside_container("text")),
+        })
+    }
+
+    fn binary_begin(&mut self, num_bytes: Option<usize>) -> sval::Result {
+        self.stack
+            .push(Frame::Binary(Vec::with_capacity(num_bytes.unwrap_or(0))));
+
+        Ok(())
+    }
+
+    fn binary_fragment_computed(&mut self, fragment: &[u8]) -> sval::Result {
+        self.try_catch(|buf| match buf.stack.last_mut() {
+            Some(Frame::Binary(binary)) => {
+                binary.extend_from_slice(fragment);
+                Ok(())
+            }
+            _ => Err(Error::outside_container("binary")),
+        })
+    }
+
+    fn binary_end(&mut self) -> sval::Result {
+        self.try_catch(|buf| match buf.stack.pop() {
+            Some(Frame::Binary(binary)) => buf.push_value(DynValue::Binary(binary)),
+            _ => Err(Error::outside_container("binary")),
+        })
+    }
+
+    fn u64(&mut self, value: u64) -> sval::Result {
+        self.try_catch(|buf| buf.push_value(DynValue::U64(value)))
+    }
+
+    fn i64(&mut self, value: i64) -> sval::Result {
+        self.try_catch(|buf| buf.push_value(DynValue::I64(value)))
+    }
+
+    fn f64(&mut self, value: f64) -> sval::Result {
+        self.try_catch(|buf| buf.push_value(DynValue::F64(value)))
+    }
+
+    fn map_begin(&mut self, num_entries: Option<usize>) -> sval::Result {
+        self.stack.push(Frame::Map {
+            entries: Vec::with_capacity(num_entries.unwrap_or(0)),
+            key: None,
+        });
+
+        Ok(())
+    }
+
+    fn map_key_begin(&mut self) -> sval::Result {
+        Ok(())
+    }
+
+    fn map_key_end(&mut self) -> sval::Result {
+        Ok(())
+    }
+
+    fn map_value_begin(&mut self) -> sval::Result {
+        Ok(())
+    }
+
+    fn map_value_end(&mut self) -> sval::Result {
+        Ok(())
+    }
+
+    fn map_end(&mut self) -> sval::Result {
+        self.try_catch(|buf| match buf.stack.pop() {
+            Some(Frame::Map { entries, key: None }) => buf.push_value(DynValue::Map(entries)),
+            Some(Frame::Map { .. }) => Err(Error::invalid_value("a map key is missing its value")),
+            _ => Err(Error::invalid_value("a map ended without being started")),
+        })
+    }
+
+    fn seq_begin(&mut self, num_entries: Option<usize>) -> sval::Result {
+        self.stack
+            .push(Frame::Seq(Vec::with_capacity(num_entries.unwrap_or(0))));
+
+        Ok(())
+    }
+
+    fn seq_value_begin(&mut self) -> sval::Result {
+        Ok(())
+    }
+
+    fn seq_value_end(&mut self) -> sval::Result {
+        Ok(())
+    }
+
+    fn seq_end(&mut self) -> sval::Result {
+        self.try_catch(|buf| match buf.stack.pop() {
+            Some(Frame::Seq(values)) => buf.push_value(DynValue::Seq(values)),
+            _ => Err(Error::invalid_value(
+                "a sequence ended without being started",
+            )),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::std::{string::ToString, vec};
+
+    use sval_derive_macros::*;
+
+    #[test]
+    fn collect_primitive() {
+        assert_eq!(DynValue::Null, DynValue::collect(&()).unwrap());
+        assert_eq!(DynValue::Bool(true), DynValue::collect(&true).unwrap());
+        assert_eq!(DynValue::I64(-1), DynValue::collect(&-1i8).unwrap());
+        assert_eq!(DynValue::U64(1), DynValue::collect(&1u8).unwrap());
+        assert_eq!(DynValue::F64(1.5), DynValue::collect(&1.5f32).unwrap());
+        assert_eq!(
+            DynValue::Text("abc".to_string()),
+            DynValue::collect("abc").unwrap()
+        );
+        assert_eq!(
+            DynValue::Binary(vec![1, 2, 3]),
+            DynValue::collect(sval::BinarySlice::new(&[1, 2, 3])).unwrap()
+        );
+        assert_eq!(
+            DynValue::Text(u128::MAX.to_string()),
+            DynValue::collect(&u128::MAX).unwrap()
+        );
+    }
+
+    #[test]
+    fn collect_option() {
+        assert_eq!(DynValue::Null, DynValue::collect(&None::<i32>).unwrap());
+        assert_eq!(DynValue::I64(1), DynValue::collect(&Some(1i32)).unwrap());
+    }
+
+    #[test]
+    fn collect_seq() {
+        assert_eq!(
+            DynValue::Seq(vec![DynValue::I64(1), DynValue::I64(2)]),
+            DynValue::collect(&[1i32, 2] as &[_]).unwrap()
+        );
+    }
+
+    #[test]
+    fn collect_record() {
+        #[derive(Value)]
+        struct Record<'a> {
+            a: i32,
+            b: &'a str,
+            c: Inner,
+        }
+
+        #[derive(Value)]
+        struct Inner(bool, Option<u8>);
+
+        assert_eq!(
+            DynValue::Map(vec![
+                (DynValue::Text("a".to_string()), DynValue::I64(1)),
+                (
+                    DynValue::Text("b".to_string()),
+                    DynValue::Text("text".to_string())
+                ),
+                (
+                    DynValue::Text("c".to_string()),
+                    DynValue::Seq(vec![DynValue::Bool(true), DynValue::Null])
+                ),
+            ]),
+            DynValue::collect(&Record {
+                a: 1,
+                b: "text",
+                c: Inner(true, None),
+            })
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn collect_enum() {
+        #[derive(Value)]
+        enum Enum {
+            Unit,
+            Newtype(i32),
+        }
+
+        assert_eq!(
+            DynValue::Text("Unit".to_string()),
+            DynValue::collect(&Enum::Unit).unwrap()
+        );
+        assert_eq!(
+            DynValue::I64(1),
+            DynValue::collect(&Enum::Newtype(1)).unwrap()
+        );
+    }
+
+    #[test]
+    fn stream_roundtrip() {
+        let value = DynValue::Map(vec![
+            (
+                DynValue::Text("a".to_string()),
+                DynValue::Seq(vec![DynValue::U64(1), DynValue::Binary(vec![1])]),
+            ),
+            (DynValue::I64(-1), DynValue::F64(1.5)),
+        ]);
+
+        assert_eq!(value, DynValue::collect(&value).unwrap());
+    }
+
+    #[test]
+    fn collect_invalid() {
+        struct Invalid;
+
+        impl sval::Value for Invalid {
+            fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(
+                &'sval self,
+                stream: &mut S,
+            ) -> sval::Result {
+                stream.seq_begin(None)?;
+                stream.map_end()
+            }
+        }
+
+        assert!(DynValue::collect(&Invalid).is_err());
+    }
+}
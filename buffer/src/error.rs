@@ -57,6 +57,16 @@ impl Error {
         Error(ErrorKind::InvalidValue { reason })
     }
 
+    /**
+    Get the kind of `sval` error to return from a stream that failed with this error.
+    */
+    pub(crate) fn to_sval(&self) -> sval::Error {
+        match self.0 {
+            ErrorKind::Unsupported { .. } => sval::Error::unsupported(),
+            _ => sval::Error::buffer(),
+        }
+    }
+
     #[track_caller]
     pub(crate) fn no_alloc(method: &'static str) -> Self {
         /*
@@ -161,8 +161,10 @@ impl<'a> TextCollector<'a> {
     }
 
     fn fail(&mut self, err: Error) -> sval::Result {
+        let sval_err = err.to_sval();
         self.err = Some(err);
-        sval::error()
+
+        Err(sval_err)
     }
 }
 
@@ -375,8 +377,10 @@ impl<'a> BinaryCollector<'a> {
     }
 
     fn fail(&mut self, err: Error) -> sval::Result {
+        let sval_err = err.to_sval();
         self.err = Some(err);
-        sval::error()
+
+        Err(sval_err)
     }
 }
 
@@ -2,7 +2,16 @@
 Buffering support for `sval`.
 
 This crate provides the [`ValueBuf`] type, which can buffer a flat
-stream of data into a tree of borrowed values.
+stream of data into a tree of borrowed values. It also provides the
+[`DynValue`] type, which collects data into a simple owned tree that's
+easy to inspect, the [`Columnar`] type, which transposes a sequence
+of records into a record of sequences, the [`BatchSeq`] type, which
+buffers the elements of a sequence into batches, and the [`BufferedStream`]
+type, which fills in missing length hints before passing collections to
+another stream. The [`ValueReader`] type buffers a value so its stream
+can be pulled one [`Token`] at a time. The [`SkipNone`] type drops
+entries of maps and records with a value of `None` before passing
+them to another stream.
 
 Some functionality requires the `alloc` Cargo feature to be enabled.
 Rather than conditionally compile these methods, this library stubs
@@ -44,7 +53,23 @@ mod std {
 mod fragments;
 mod value;
 
+#[cfg(feature = "alloc")]
+mod batch;
+#[cfg(feature = "alloc")]
+mod buffered;
+#[cfg(feature = "alloc")]
+mod columnar;
+#[cfg(feature = "alloc")]
+mod dynamic;
+#[cfg(feature = "alloc")]
+mod reader;
+#[cfg(feature = "alloc")]
+mod skip_none;
+
 #[cfg(feature = "alloc")]
 fn assert_static<T: 'static>(_: &mut T) {}
 
 pub use self::{error::*, fragments::*, value::*};
+
+#[cfg(feature = "alloc")]
+pub use self::{batch::*, buffered::*, columnar::*, dynamic::*, reader::*, skip_none::*};
@@ -0,0 +1,930 @@
+use crate::{
+    std::{
+        mem,
+        string::String,
+        vec::{self, Vec},
+    },
+    Error,
+};
+
+/**
+A pull-based reader over the tokens of a buffered value.
+
+`sval` values are streamed by pushing data into a [`sval::Stream`]. This type inverts that
+by first buffering the entire value, and then yielding its calls one at a time as [`Token`]s
+through [`ValueReader::next_token`], or as an [`Iterator`]. This makes it possible to drive
+a pull-based consumer from any value.
+
+Values collected through [`ValueReader::collect`] keep any text or binary they borrow from,
+so their fragments are yielded without copying. Values collected through
+[`ValueReader::collect_owned`] are streamed as computed, so all of their fragments are owned.
+
+This type requires the `alloc` feature.
+*/
+#[derive(Debug, Clone)]
+pub struct ValueReader<'sval> {
+    tokens: vec::IntoIter<Token<'sval>>,
+}
+
+impl<'sval> ValueReader<'sval> {
+    /**
+    Buffer a value so its tokens can be read.
+    */
+    pub fn collect(v: &'sval (impl sval::Value + ?Sized)) -> Result<Self, Error> {
+        let mut collector = TokenCollector::new();
+
+        match v.stream(&mut collector) {
+            Ok(()) => Ok(collector.into_reader()),
+            Err(_) => Err(Error::invalid_value("the value itself failed to stream")),
+        }
+    }
+
+    /**
+    Get the next token, or `None` if all tokens have been read.
+    */
+    pub fn next_token(&mut self) -> Option<Token<'sval>> {
+        self.tokens.next()
+    }
+}
+
+impl ValueReader<'static> {
+    /**
+    Buffer a value so its tokens can be read, including any internal borrowed data.
+    */
+    pub fn collect_owned(v: impl sval::Value) -> Result<Self, Error> {
+        let mut collector = TokenCollector::new();
+
+        // Buffering the value as computed means any borrowed data will
+        // have to be converted into owned anyways
+        match sval::stream_computed(&mut collector, v) {
+            Ok(()) => Ok(collector.into_reader()),
+            Err(_) => Err(Error::invalid_value("the value itself failed to stream")),
+        }
+    }
+}
+
+impl<'sval> Iterator for ValueReader<'sval> {
+    type Item = Token<'sval>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_token()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.tokens.size_hint()
+    }
+}
+
+/**
+A single call to an [`sval::Stream`] method, yielded by a [`ValueReader`].
+
+Text and binary fragments that were borrowed from the value are yielded as
+[`Token::TextFragment`] and [`Token::BinaryFragment`]. Fragments that were computed
+while streaming are owned, and are yielded as [`Token::TextFragmentComputed`] and
+[`Token::BinaryFragmentComputed`].
+*/
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum Token<'sval> {
+    /**
+    [`sval::Stream::u8`].
+    */
+    U8(u8),
+    /**
+    [`sval::Stream::u16`].
+    */
+    U16(u16),
+    /**
+    [`sval::Stream::u32`].
+    */
+    U32(u32),
+    /**
+    [`sval::Stream::u64`].
+    */
+    U64(u64),
+    /**
+    [`sval::Stream::u128`].
+    */
+    U128(u128),
+    /**
+    [`sval::Stream::i8`].
+    */
+    I8(i8),
+    /**
+    [`sval::Stream::i16`].
+    */
+    I16(i16),
+    /**
+    [`sval::Stream::i32`].
+    */
+    I32(i32),
+    /**
+    [`sval::Stream::i64`].
+    */
+    I64(i64),
+    /**
+    [`sval::Stream::i128`].
+    */
+    I128(i128),
+    /**
+    [`sval::Stream::f32`].
+    */
+    F32(f32),
+    /**
+    [`sval::Stream::f64`].
+    */
+    F64(f64),
+    /**
+    [`sval::Stream::bool`].
+    */
+    Bool(bool),
+    /**
+    [`sval::Stream::null`].
+    */
+    Null,
+    /**
+    [`sval::Stream::tag`].
+    */
+    Tag(
+        Option<sval::Tag>,
+        Option<sval::Label<'static>>,
+        Option<sval::Index>,
+    ),
+    /**
+    [`sval::Stream::tag_hint`].
+    */
+    TagHint(sval::Tag),
+    /**
+    [`sval::Stream::text_begin`].
+    */
+    TextBegin(Option<usize>),
+    /**
+    [`sval::Stream::text_fragment`].
+    */
+    TextFragment(&'sval str),
+    /**
+    [`sval::Stream::text_fragment_computed`].
+    */
+    TextFragmentComputed(String),
+    /**
+    [`sval::Stream::text_end`].
+    */
+    TextEnd,
+    /**
+    [`sval::Stream::binary_begin`].
+    */
+    BinaryBegin(Option<usize>),
+    /**
+    [`sval::Stream::binary_fragment`].
+    */
+    BinaryFragment(&'sval [u8]),
+    /**
+    [`sval::Stream::binary_fragment_computed`].
+    */
+    BinaryFragmentComputed(Vec<u8>),
+    /**
+    [`sval::Stream::binary_end`].
+    */
+    BinaryEnd,
+    /**
+    [`sval::Stream::map_begin`].
+    */
+    MapBegin(Option<usize>),
+    /**
+    [`sval::Stream::map_key_begin`].
+    */
+    MapKeyBegin,
+    /**
+    [`sval::Stream::map_key_end`].
+    */
+    MapKeyEnd,
+    /**
+    [`sval::Stream::map_value_begin`].
+    */
+    MapValueBegin,
+    /**
+    [`sval::Stream::map_value_end`].
+    */
+    MapValueEnd,
+    /**
+    [`sval::Stream::map_end`].
+    */
+    MapEnd,
+    /**
+    [`sval::Stream::seq_begin`].
+    */
+    SeqBegin(Option<usize>),
+    /**
+    [`sval::Stream::seq_value_begin`].
+    */
+    SeqValueBegin,
+    /**
+    [`sval::Stream::seq_value_end`].
+    */
+    SeqValueEnd,
+    /**
+    [`sval::Stream::seq_end`].
+    */
+    SeqEnd,
+    /**
+    [`sval::Stream::enum_begin`].
+    */
+    EnumBegin(
+        Option<sval::Tag>,
+        Option<sval::Label<'static>>,
+        Option<sval::Index>,
+    ),
+    /**
+    [`sval::Stream::enum_end`].
+    */
+    EnumEnd(
+        Option<sval::Tag>,
+        Option<sval::Label<'static>>,
+        Option<sval::Index>,
+    ),
+    /**
+    [`sval::Stream::tagged_begin`].
+    */
+    TaggedBegin(
+        Option<sval::Tag>,
+        Option<sval::Label<'static>>,
+        Option<sval::Index>,
+    ),
+    /**
+    [`sval::Stream::tagged_end`].
+    */
+    TaggedEnd(
+        Option<sval::Tag>,
+        Option<sval::Label<'static>>,
+        Option<sval::Index>,
+    ),
+    /**
+    [`sval::Stream::record_begin`].
+    */
+    RecordBegin(
+        Option<sval::Tag>,
+        Option<sval::Label<'static>>,
+        Option<sval::Index>,
+        Option<usize>,
+    ),
+    /**
+    [`sval::Stream::record_value_begin`].
+    */
+    RecordValueBegin(Option<sval::Tag>, sval::Label<'static>),
+    /**
+    [`sval::Stream::record_value_end`].
+    */
+    RecordValueEnd(Option<sval::Tag>, sval::Label<'static>),
+    /**
+    [`sval::Stream::record_end`].
+    */
+    RecordEnd(
+        Option<sval::Tag>,
+        Option<sval::Label<'static>>,
+        Option<sval::Index>,
+    ),
+    /**
+    [`sval::Stream::tuple_begin`].
+    */
+    TupleBegin(
+        Option<sval::Tag>,
+        Option<sval::Label<'static>>,
+        Option<sval::Index>,
+        Option<usize>,
+    ),
+    /**
+    [`sval::Stream::tuple_value_begin`].
+    */
+    TupleValueBegin(Option<sval::Tag>, sval::Index),
+    /**
+    [`sval::Stream::tuple_value_end`].
+    */
+    TupleValueEnd(Option<sval::Tag>, sval::Index),
+    /**
+    [`sval::Stream::tuple_end`].
+    */
+    TupleEnd(
+        Option<sval::Tag>,
+        Option<sval::Label<'static>>,
+        Option<sval::Index>,
+    ),
+    /**
+    [`sval::Stream::record_tuple_begin`].
+    */
+    RecordTupleBegin(
+        Option<sval::Tag>,
+        Option<sval::Label<'static>>,
+        Option<sval::Index>,
+        Option<usize>,
+    ),
+    /**
+    [`sval::Stream::record_tuple_value_begin`].
+    */
+    RecordTupleValueBegin(Option<sval::Tag>, sval::Label<'static>, sval::Index),
+    /**
+    [`sval::Stream::record_tuple_value_end`].
+    */
+    RecordTupleValueEnd(Option<sval::Tag>, sval::Label<'static>, sval::Index),
+    /**
+    [`sval::Stream::record_tuple_end`].
+    */
+    RecordTupleEnd(
+        Option<sval::Tag>,
+        Option<sval::Label<'static>>,
+        Option<sval::Index>,
+    ),
+}
+
+impl<'sval> Token<'sval> {
+    /**
+    Stream this token into a [`sval::Stream`].
+
+    Text and binary fragments are always streamed as computed, so tokens can be
+    streamed into a stream with any lifetime.
+    */
+    pub(crate) fn stream_computed<'a, S: sval::Stream<'a> + ?Sized>(
+        &self,
+        stream: &mut S,
+    ) -> sval::Result {
+        match self {
+            Token::U8(v) => stream.u8(*v),
+            Token::U16(v) => stream.u16(*v),
+            Token::U32(v) => stream.u32(*v),
+            Token::U64(v) => stream.u64(*v),
+            Token::U128(v) => stream.u128(*v),
+            Token::I8(v) => stream.i8(*v),
+            Token::I16(v) => stream.i16(*v),
+            Token::I32(v) => stream.i32(*v),
+            Token::I64(v) => stream.i64(*v),
+            Token::I128(v) => stream.i128(*v),
+            Token::F32(v) => stream.f32(*v),
+            Token::F64(v) => stream.f64(*v),
+            Token::Bool(v) => stream.bool(*v),
+            Token::Null => stream.null(),
+            Token::Tag(tag, label, index) => {
+                stream.tag(tag.as_ref(), label.as_ref(), index.as_ref())
+            }
+            Token::TagHint(tag) => stream.tag_hint(tag),
+            Token::TextBegin(num_bytes) => stream.text_begin(*num_bytes),
+            Token::TextFragment(v) => stream.text_fragment_computed(v),
+            Token::TextFragmentComputed(v) => stream.text_fragment_computed(v),
+            Token::TextEnd => stream.text_end(),
+            Token::BinaryBegin(num_bytes) => stream.binary_begin(*num_bytes),
+            Token::BinaryFragment(v) => stream.binary_fragment_computed(v),
+            Token::BinaryFragmentComputed(v) => stream.binary_fragment_computed(v),
+            Token::BinaryEnd => stream.binary_end(),
+            Token::MapBegin(num_entries) => stream.map_begin(*num_entries),
+            Token::MapKeyBegin => stream.map_key_begin(),
+            Token::MapKeyEnd => stream.map_key_end(),
+            Token::MapValueBegin => stream.map_value_begin(),
+            Token::MapValueEnd => stream.map_value_end(),
+            Token::MapEnd => stream.map_end(),
+            Token::SeqBegin(num_entries) => stream.seq_begin(*num_entries),
+            Token::SeqValueBegin => stream.seq_value_begin(),
+            Token::SeqValueEnd => stream.seq_value_end(),
+            Token::SeqEnd => stream.seq_end(),
+            Token::EnumBegin(tag, label, index) => {
+                stream.enum_begin(tag.as_ref(), label.as_ref(), index.as_ref())
+            }
+            Token::EnumEnd(tag, label, index) => {
+                stream.enum_end(tag.as_ref(), label.as_ref(), index.as_ref())
+            }
+            Token::TaggedBegin(tag, label, index) => {
+                stream.tagged_begin(tag.as_ref(), label.as_ref(), index.as_ref())
+            }
+            Token::TaggedEnd(tag, label, index) => {
+                stream.tagged_end(tag.as_ref(), label.as_ref(), index.as_ref())
+            }
+            Token::RecordBegin(tag, label, index, num_entries) => {
+                stream.record_begin(tag.as_ref(), label.as_ref(), index.as_ref(), *num_entries)
+            }
+            Token::RecordValueBegin(tag, label) => stream.record_value_begin(tag.as_ref(), label),
+            Token::RecordValueEnd(tag, label) => stream.record_value_end(tag.as_ref(), label),
+            Token::RecordEnd(tag, label, index) => {
+                stream.record_end(tag.as_ref(), label.as_ref(), index.as_ref())
+            }
+            Token::TupleBegin(tag, label, index, num_entries) => {
+                stream.tuple_begin(tag.as_ref(), label.as_ref(), index.as_ref(), *num_entries)
+            }
+            Token::TupleValueBegin(tag, index) => stream.tuple_value_begin(tag.as_ref(), index),
+            Token::TupleValueEnd(tag, index) => stream.tuple_value_end(tag.as_ref(), index),
+            Token::TupleEnd(tag, label, index) => {
+                stream.tuple_end(tag.as_ref(), label.as_ref(), index.as_ref())
+            }
+            Token::RecordTupleBegin(tag, label, index, num_entries) => stream.record_tuple_begin(
+                tag.as_ref(),
+                label.as_ref(),
+                index.as_ref(),
+                *num_entries,
+            ),
+            Token::RecordTupleValueBegin(tag, label, index) => {
+                stream.record_tuple_value_begin(tag.as_ref(), label, index)
+            }
+            Token::RecordTupleValueEnd(tag, label, index) => {
+                stream.record_tuple_value_end(tag.as_ref(), label, index)
+            }
+            Token::RecordTupleEnd(tag, label, index) => {
+                stream.record_tuple_end(tag.as_ref(), label.as_ref(), index.as_ref())
+            }
+        }
+    }
+}
+
+/**
+A [`sval::Stream`] that buffers the calls made on it as [`Token`]s.
+*/
+#[derive(Debug, Default)]
+pub(crate) struct TokenCollector<'sval> {
+    tokens: Vec<Token<'sval>>,
+}
+
+impl<'sval> TokenCollector<'sval> {
+    pub(crate) fn new() -> Self {
+        TokenCollector { tokens: Vec::new() }
+    }
+
+    /**
+    Take the tokens that have been buffered so far, leaving the collector empty.
+    */
+    pub(crate) fn take(&mut self) -> Vec<Token<'sval>> {
+        mem::take(&mut self.tokens)
+    }
+
+    fn push(&mut self, token: Token<'sval>) {
+        self.tokens.push(token);
+    }
+
+    fn into_reader(self) -> ValueReader<'sval> {
+        ValueReader {
+            tokens: self.tokens.into_iter(),
+        }
+    }
+}
+
+impl<'sval> sval::Stream<'sval> for TokenCollector<'sval> {
+    fn null(&mut self) -> sval::Result {
+        self.push(Token::Null);
+        Ok(())
+    }
+
+    fn bool(&mut self, value: bool) -> sval::Result {
+        self.push(Token::Bool(value));
+        Ok(())
+    }
+
+    fn text_begin(&mut self, num_bytes: Option<usize>) -> sval::Result {
+        self.push(Token::TextBegin(num_bytes));
+        Ok(())
+    }
+
+    fn text_fragment_computed(&mut self, fragment: &str) -> sval::Result {
+        self.push(Token::TextFragmentComputed(String::from(fragment)));
+        Ok(())
+    }
+
+    fn text_end(&mut self) -> sval::Result {
+        self.push(Token::TextEnd);
+        Ok(())
+    }
+
+    fn i64(&mut self, value: i64) -> sval::Result {
+        self.push(Token::I64(value));
+        Ok(())
+    }
+
+    fn f64(&mut self, value: f64) -> sval::Result {
+        self.push(Token::F64(value));
+        Ok(())
+    }
+
+    fn seq_begin(&mut self, num_entries_hint: Option<usize>) -> sval::Result {
+        self.push(Token::SeqBegin(num_entries_hint));
+        Ok(())
+    }
+
+    fn seq_value_begin(&mut self) -> sval::Result {
+        self.push(Token::SeqValueBegin);
+        Ok(())
+    }
+
+    fn seq_value_end(&mut self) -> sval::Result {
+        self.push(Token::SeqValueEnd);
+        Ok(())
+    }
+
+    fn seq_end(&mut self) -> sval::Result {
+        self.push(Token::SeqEnd);
+        Ok(())
+    }
+
+    fn text_fragment(&mut self, fragment: &'sval str) -> sval::Result {
+        self.push(Token::TextFragment(fragment));
+        Ok(())
+    }
+
+    fn binary_begin(&mut self, num_bytes: Option<usize>) -> sval::Result {
+        self.push(Token::BinaryBegin(num_bytes));
+        Ok(())
+    }
+
+    fn binary_fragment(&mut self, fragment: &'sval [u8]) -> sval::Result {
+        self.push(Token::BinaryFragment(fragment));
+        Ok(())
+    }
+
+    fn binary_fragment_computed(&mut self, fragment: &[u8]) -> sval::Result {
+        self.push(Token::BinaryFragmentComputed(fragment.to_vec()));
+        Ok(())
+    }
+
+    fn binary_end(&mut self) -> sval::Result {
+        self.push(Token::BinaryEnd);
+        Ok(())
+    }
+
+    fn u8(&mut self, value: u8) -> sval::Result {
+        self.push(Token::U8(value));
+        Ok(())
+    }
+
+    fn u16(&mut self, value: u16) -> sval::Result {
+        self.push(Token::U16(value));
+        Ok(())
+    }
+
+    fn u32(&mut self, value: u32) -> sval::Result {
+        self.push(Token::U32(value));
+        Ok(())
+    }
+
+    fn u64(&mut self, value: u64) -> sval::Result {
+        self.push(Token::U64(value));
+        Ok(())
+    }
+
+    fn u128(&mut self, value: u128) -> sval::Result {
+        self.push(Token::U128(value));
+        Ok(())
+    }
+
+    fn i8(&mut self, value: i8) -> sval::Result {
+        self.push(Token::I8(value));
+        Ok(())
+    }
+
+    fn i16(&mut self, value: i16) -> sval::Result {
+        self.push(Token::I16(value));
+        Ok(())
+    }
+
+    fn i32(&mut self, value: i32) -> sval::Result {
+        self.push(Token::I32(value));
+        Ok(())
+    }
+
+    fn i128(&mut self, value: i128) -> sval::Result {
+        self.push(Token::I128(value));
+        Ok(())
+    }
+
+    fn f32(&mut self, value: f32) -> sval::Result {
+        self.push(Token::F32(value));
+        Ok(())
+    }
+
+    fn map_begin(&mut self, num_entries_hint: Option<usize>) -> sval::Result {
+        self.push(Token::MapBegin(num_entries_hint));
+        Ok(())
+    }
+
+    fn map_key_begin(&mut self) -> sval::Result {
+        self.push(Token::MapKeyBegin);
+        Ok(())
+    }
+
+    fn map_key_end(&mut self) -> sval::Result {
+        self.push(Token::MapKeyEnd);
+        Ok(())
+    }
+
+    fn map_value_begin(&mut self) -> sval::Result {
+        self.push(Token::MapValueBegin);
+        Ok(())
+    }
+
+    fn map_value_end(&mut self) -> sval::Result {
+        self.push(Token::MapValueEnd);
+        Ok(())
+    }
+
+    fn map_end(&mut self) -> sval::Result {
+        self.push(Token::MapEnd);
+        Ok(())
+    }
+
+    fn enum_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.push(Token::EnumBegin(
+            tag.cloned(),
+            label.map(|label| label.to_owned()),
+            index.cloned(),
+        ));
+        Ok(())
+    }
+
+    fn enum_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.push(Token::EnumEnd(
+            tag.cloned(),
+            label.map(|label| label.to_owned()),
+            index.cloned(),
+        ));
+        Ok(())
+    }
+
+    fn tagged_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.push(Token::TaggedBegin(
+            tag.cloned(),
+            label.map(|label| label.to_owned()),
+            index.cloned(),
+        ));
+        Ok(())
+    }
+
+    fn tagged_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.push(Token::TaggedEnd(
+            tag.cloned(),
+            label.map(|label| label.to_owned()),
+            index.cloned(),
+        ));
+        Ok(())
+    }
+
+    fn tag(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.push(Token::Tag(
+            tag.cloned(),
+            label.map(|label| label.to_owned()),
+            index.cloned(),
+        ));
+        Ok(())
+    }
+
+    fn tag_hint(&mut self, tag: &sval::Tag) -> sval::Result {
+        self.push(Token::TagHint(tag.clone()));
+
+        Ok(())
+    }
+
+    fn record_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+        num_entries: Option<usize>,
+    ) -> sval::Result {
+        self.push(Token::RecordBegin(
+            tag.cloned(),
+            label.map(|label| label.to_owned()),
+            index.cloned(),
+            num_entries,
+        ));
+        Ok(())
+    }
+
+    fn record_value_begin(&mut self, tag: Option<&sval::Tag>, label: &sval::Label) -> sval::Result {
+        self.push(Token::RecordValueBegin(tag.cloned(), label.to_owned()));
+        Ok(())
+    }
+
+    fn record_value_end(&mut self, tag: Option<&sval::Tag>, label: &sval::Label) -> sval::Result {
+        self.push(Token::RecordValueEnd(tag.cloned(), label.to_owned()));
+        Ok(())
+    }
+
+    fn record_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.push(Token::RecordEnd(
+            tag.cloned(),
+            label.map(|label| label.to_owned()),
+            index.cloned(),
+        ));
+        Ok(())
+    }
+
+    fn tuple_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+        num_entries: Option<usize>,
+    ) -> sval::Result {
+        self.push(Token::TupleBegin(
+            tag.cloned(),
+            label.map(|label| label.to_owned()),
+            index.cloned(),
+            num_entries,
+        ));
+        Ok(())
+    }
+
+    fn tuple_value_begin(&mut self, tag: Option<&sval::Tag>, index: &sval::Index) -> sval::Result {
+        self.push(Token::TupleValueBegin(tag.cloned(), index.clone()));
+        Ok(())
+    }
+
+    fn tuple_value_end(&mut self, tag: Option<&sval::Tag>, index: &sval::Index) -> sval::Result {
+        self.push(Token::TupleValueEnd(tag.cloned(), index.clone()));
+        Ok(())
+    }
+
+    fn tuple_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.push(Token::TupleEnd(
+            tag.cloned(),
+            label.map(|label| label.to_owned()),
+            index.cloned(),
+        ));
+        Ok(())
+    }
+
+    fn record_tuple_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+        num_entries: Option<usize>,
+    ) -> sval::Result {
+        self.push(Token::RecordTupleBegin(
+            tag.cloned(),
+            label.map(|label| label.to_owned()),
+            index.cloned(),
+            num_entries,
+        ));
+        Ok(())
+    }
+
+    fn record_tuple_value_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: &sval::Label,
+        index: &sval::Index,
+    ) -> sval::Result {
+        self.push(Token::RecordTupleValueBegin(
+            tag.cloned(),
+            label.to_owned(),
+            index.clone(),
+        ));
+        Ok(())
+    }
+
+    fn record_tuple_value_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: &sval::Label,
+        index: &sval::Index,
+    ) -> sval::Result {
+        self.push(Token::RecordTupleValueEnd(
+            tag.cloned(),
+            label.to_owned(),
+            index.clone(),
+        ));
+        Ok(())
+    }
+
+    fn record_tuple_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.push(Token::RecordTupleEnd(
+            tag.cloned(),
+            label.map(|label| label.to_owned()),
+            index.cloned(),
+        ));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::std::collections::BTreeMap;
+
+    use sval_test::TokenBuf;
+
+    #[test]
+    fn read_map() {
+        let map = BTreeMap::from([("a", 1), ("b", 2)]);
+
+        let pulled = ValueReader::collect(&map).unwrap().collect::<Vec<_>>();
+
+        // The tokens are the same as the calls pushed to a stream
+        let mut pushed = TokenBuf::new();
+        sval::stream(&mut pushed, &map).unwrap();
+
+        assert_eq!(format!("{:?}", pushed.as_tokens()), format!("{:?}", pulled));
+
+        assert_eq!(
+            vec![
+                Token::MapBegin(Some(2)),
+                Token::MapKeyBegin,
+                Token::TextBegin(Some(1)),
+                Token::TextFragment("a"),
+                Token::TextEnd,
+                Token::MapKeyEnd,
+                Token::MapValueBegin,
+                Token::I32(1),
+                Token::MapValueEnd,
+                Token::MapKeyBegin,
+                Token::TextBegin(Some(1)),
+                Token::TextFragment("b"),
+                Token::TextEnd,
+                Token::MapKeyEnd,
+                Token::MapValueBegin,
+                Token::I32(2),
+                Token::MapValueEnd,
+                Token::MapEnd,
+            ],
+            pulled
+        );
+    }
+
+    #[test]
+    fn read_next_token() {
+        let mut reader = ValueReader::collect("a").unwrap();
+
+        assert_eq!(Some(Token::TextBegin(Some(1))), reader.next_token());
+        assert_eq!(Some(Token::TextFragment("a")), reader.next_token());
+        assert_eq!(Some(Token::TextEnd), reader.next_token());
+        assert_eq!(None, reader.next_token());
+        assert_eq!(None, reader.next_token());
+    }
+
+    #[test]
+    fn read_owned() {
+        let reader = {
+            let value = String::from("a");
+
+            ValueReader::collect_owned(&value).unwrap()
+        };
+
+        // Borrowed fragments are converted into owned ones
+        assert_eq!(
+            vec![
+                Token::TextBegin(Some(1)),
+                Token::TextFragmentComputed(String::from("a")),
+                Token::TextEnd,
+            ],
+            reader.collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn read_failing_value() {
+        struct Kaboom;
+
+        impl sval::Value for Kaboom {
+            fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(
+                &'sval self,
+                _: &mut S,
+            ) -> sval::Result {
+                sval::error()
+            }
+        }
+
+        assert!(ValueReader::collect(&Kaboom).is_err());
+    }
+}
@@ -0,0 +1,773 @@
+use crate::reader::TokenCollector;
+use sval::{tags, Index, Label, Stream, Tag};
+
+/**
+Wrap a stream in a [`SkipNone`] that drops entries of maps and records with a value of `None`.
+*/
+pub fn skip_none<'sval, S: Stream<'sval>>(stream: S) -> SkipNone<S> {
+    SkipNone::new(stream)
+}
+
+/**
+A [`Stream`] that drops entries of maps and records with a value of `None`.
+
+Some formats prefer to leave out optional fields instead of writing them as `null`.
+When the value of a map entry, record field, or record tuple field is a single
+[`tags::RUST_OPTION_NONE`] tag, the whole entry is dropped, including its key.
+Values of `None` anywhere else, like in sequences or at the root, are forwarded unchanged.
+
+The key of an entry is streamed before its value, so the start of each entry is held on to
+until the first part of its value arrives. Map keys are buffered in full, so any text or
+binary within them is forwarded as computed. The entries of maps and records nested within
+map keys aren't dropped.
+
+Since the number of entries in maps and records isn't known upfront, they're forwarded without
+a length hint. All other data is forwarded to the wrapped stream unchanged.
+
+This type requires the `alloc` feature.
+*/
+#[derive(Debug)]
+pub struct SkipNone<S> {
+    stream: S,
+    entry: Option<Entry>,
+    key: TokenCollector<'static>,
+    key_depth: usize,
+    is_skipping: bool,
+}
+
+/**
+The start of an entry that hasn't been forwarded yet.
+*/
+#[derive(Debug)]
+enum Entry {
+    // The key is held in `SkipNone::key`
+    Map,
+    Record(Option<Tag>, Label<'static>),
+    RecordTuple(Option<Tag>, Label<'static>, Index),
+}
+
+impl<S> SkipNone<S> {
+    /**
+    Wrap a stream, dropping entries of maps and records with a value of `None`.
+    */
+    pub fn new(stream: S) -> Self {
+        SkipNone {
+            stream,
+            entry: None,
+            key: TokenCollector::new(),
+            key_depth: 0,
+            is_skipping: false,
+        }
+    }
+
+    /**
+    Get the wrapped stream.
+    */
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+
+    /**
+    Get the buffer for the map key that's being streamed, if there is one.
+    */
+    fn key(&mut self) -> Option<&mut TokenCollector<'static>> {
+        if self.key_depth > 0 {
+            Some(&mut self.key)
+        } else {
+            None
+        }
+    }
+
+    /**
+    Check whether the value of an entry that's being dropped has ended.
+    */
+    fn skip_end(&mut self) -> bool {
+        if self.is_skipping {
+            self.is_skipping = false;
+
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl<'sval, S: Stream<'sval>> SkipNone<S> {
+    /**
+    Forward the start of an entry now that its value is known not to be `None`.
+    */
+    fn value_begin(&mut self) -> sval::Result {
+        match self.entry.take() {
+            None => Ok(()),
+            Some(Entry::Map) => {
+                self.stream.map_key_begin()?;
+
+                for token in self.key.take() {
+                    token.stream_computed(&mut self.stream)?;
+                }
+
+                self.stream.map_key_end()?;
+                self.stream.map_value_begin()
+            }
+            Some(Entry::Record(tag, label)) => self.stream.record_value_begin(tag.as_ref(), &label),
+            Some(Entry::RecordTuple(tag, label, index)) => {
+                self.stream
+                    .record_tuple_value_begin(tag.as_ref(), &label, &index)
+            }
+        }
+    }
+}
+
+impl<'sval, S: Stream<'sval>> Stream<'sval> for SkipNone<S> {
+    fn null(&mut self) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.null();
+        }
+
+        self.value_begin()?;
+        self.stream.null()
+    }
+
+    fn bool(&mut self, value: bool) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.bool(value);
+        }
+
+        self.value_begin()?;
+        self.stream.bool(value)
+    }
+
+    fn text_begin(&mut self, num_bytes: Option<usize>) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.text_begin(num_bytes);
+        }
+
+        self.value_begin()?;
+        self.stream.text_begin(num_bytes)
+    }
+
+    fn text_fragment(&mut self, fragment: &'sval str) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.text_fragment_computed(fragment);
+        }
+
+        self.value_begin()?;
+        self.stream.text_fragment(fragment)
+    }
+
+    fn text_fragment_computed(&mut self, fragment: &str) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.text_fragment_computed(fragment);
+        }
+
+        self.value_begin()?;
+        self.stream.text_fragment_computed(fragment)
+    }
+
+    fn text_end(&mut self) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.text_end();
+        }
+
+        self.value_begin()?;
+        self.stream.text_end()
+    }
+
+    fn binary_begin(&mut self, num_bytes: Option<usize>) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.binary_begin(num_bytes);
+        }
+
+        self.value_begin()?;
+        self.stream.binary_begin(num_bytes)
+    }
+
+    fn binary_fragment(&mut self, fragment: &'sval [u8]) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.binary_fragment_computed(fragment);
+        }
+
+        self.value_begin()?;
+        self.stream.binary_fragment(fragment)
+    }
+
+    fn binary_fragment_computed(&mut self, fragment: &[u8]) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.binary_fragment_computed(fragment);
+        }
+
+        self.value_begin()?;
+        self.stream.binary_fragment_computed(fragment)
+    }
+
+    fn binary_end(&mut self) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.binary_end();
+        }
+
+        self.value_begin()?;
+        self.stream.binary_end()
+    }
+
+    fn u8(&mut self, value: u8) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.u8(value);
+        }
+
+        self.value_begin()?;
+        self.stream.u8(value)
+    }
+
+    fn u16(&mut self, value: u16) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.u16(value);
+        }
+
+        self.value_begin()?;
+        self.stream.u16(value)
+    }
+
+    fn u32(&mut self, value: u32) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.u32(value);
+        }
+
+        self.value_begin()?;
+        self.stream.u32(value)
+    }
+
+    fn u64(&mut self, value: u64) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.u64(value);
+        }
+
+        self.value_begin()?;
+        self.stream.u64(value)
+    }
+
+    fn u128(&mut self, value: u128) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.u128(value);
+        }
+
+        self.value_begin()?;
+        self.stream.u128(value)
+    }
+
+    fn i8(&mut self, value: i8) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.i8(value);
+        }
+
+        self.value_begin()?;
+        self.stream.i8(value)
+    }
+
+    fn i16(&mut self, value: i16) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.i16(value);
+        }
+
+        self.value_begin()?;
+        self.stream.i16(value)
+    }
+
+    fn i32(&mut self, value: i32) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.i32(value);
+        }
+
+        self.value_begin()?;
+        self.stream.i32(value)
+    }
+
+    fn i64(&mut self, value: i64) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.i64(value);
+        }
+
+        self.value_begin()?;
+        self.stream.i64(value)
+    }
+
+    fn i128(&mut self, value: i128) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.i128(value);
+        }
+
+        self.value_begin()?;
+        self.stream.i128(value)
+    }
+
+    fn f32(&mut self, value: f32) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.f32(value);
+        }
+
+        self.value_begin()?;
+        self.stream.f32(value)
+    }
+
+    fn f64(&mut self, value: f64) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.f64(value);
+        }
+
+        self.value_begin()?;
+        self.stream.f64(value)
+    }
+
+    fn num(&mut self, num: &sval::Number) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.num(num);
+        }
+
+        self.value_begin()?;
+        self.stream.num(num)
+    }
+
+    fn map_begin(&mut self, num_entries: Option<usize>) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.map_begin(num_entries);
+        }
+
+        self.value_begin()?;
+        self.stream.map_begin(None)
+    }
+
+    fn map_key_begin(&mut self) -> sval::Result {
+        if let Some(key) = self.key() {
+            key.map_key_begin()?;
+            self.key_depth += 1;
+
+            return Ok(());
+        }
+
+        self.value_begin()?;
+
+        self.key = TokenCollector::new();
+        self.key_depth = 1;
+
+        Ok(())
+    }
+
+    fn map_key_end(&mut self) -> sval::Result {
+        self.key_depth = self.key_depth.checked_sub(1).ok_or_else(sval::Error::new)?;
+
+        // The end of the outermost key is forwarded along with the rest of its entry
+        if let Some(key) = self.key() {
+            key.map_key_end()?;
+        }
+
+        Ok(())
+    }
+
+    fn map_value_begin(&mut self) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.map_value_begin();
+        }
+
+        self.value_begin()?;
+        self.entry = Some(Entry::Map);
+
+        Ok(())
+    }
+
+    fn map_value_end(&mut self) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.map_value_end();
+        }
+
+        if self.skip_end() {
+            return Ok(());
+        }
+
+        self.value_begin()?;
+        self.stream.map_value_end()
+    }
+
+    fn map_end(&mut self) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.map_end();
+        }
+
+        self.value_begin()?;
+        self.stream.map_end()
+    }
+
+    fn seq_begin(&mut self, num_entries: Option<usize>) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.seq_begin(num_entries);
+        }
+
+        self.value_begin()?;
+        self.stream.seq_begin(num_entries)
+    }
+
+    fn seq_value_begin(&mut self) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.seq_value_begin();
+        }
+
+        self.value_begin()?;
+        self.stream.seq_value_begin()
+    }
+
+    fn seq_value_end(&mut self) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.seq_value_end();
+        }
+
+        self.value_begin()?;
+        self.stream.seq_value_end()
+    }
+
+    fn seq_end(&mut self) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.seq_end();
+        }
+
+        self.value_begin()?;
+        self.stream.seq_end()
+    }
+
+    fn enum_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.enum_begin(tag, label, index);
+        }
+
+        self.value_begin()?;
+        self.stream.enum_begin(tag, label, index)
+    }
+
+    fn enum_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.enum_end(tag, label, index);
+        }
+
+        self.value_begin()?;
+        self.stream.enum_end(tag, label, index)
+    }
+
+    fn tagged_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.tagged_begin(tag, label, index);
+        }
+
+        self.value_begin()?;
+        self.stream.tagged_begin(tag, label, index)
+    }
+
+    fn tagged_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.tagged_end(tag, label, index);
+        }
+
+        self.value_begin()?;
+        self.stream.tagged_end(tag, label, index)
+    }
+
+    fn tag(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.tag(tag, label, index);
+        }
+
+        // If the value of an entry is `None` then drop the entry
+        if self.entry.is_some() && tag == Some(&tags::RUST_OPTION_NONE) {
+            self.entry = None;
+            self.is_skipping = true;
+
+            return Ok(());
+        }
+
+        self.value_begin()?;
+        self.stream.tag(tag, label, index)
+    }
+
+    fn tag_hint(&mut self, tag: &Tag) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.tag_hint(tag);
+        }
+
+        self.value_begin()?;
+        self.stream.tag_hint(tag)
+    }
+
+    fn record_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+        num_entries: Option<usize>,
+    ) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.record_begin(tag, label, index, num_entries);
+        }
+
+        self.value_begin()?;
+        self.stream.record_begin(tag, label, index, None)
+    }
+
+    fn record_value_begin(&mut self, tag: Option<&Tag>, label: &Label) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.record_value_begin(tag, label);
+        }
+
+        self.value_begin()?;
+        self.entry = Some(Entry::Record(tag.cloned(), label.to_owned()));
+
+        Ok(())
+    }
+
+    fn record_value_end(&mut self, tag: Option<&Tag>, label: &Label) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.record_value_end(tag, label);
+        }
+
+        if self.skip_end() {
+            return Ok(());
+        }
+
+        self.value_begin()?;
+        self.stream.record_value_end(tag, label)
+    }
+
+    fn record_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.record_end(tag, label, index);
+        }
+
+        self.value_begin()?;
+        self.stream.record_end(tag, label, index)
+    }
+
+    fn tuple_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+        num_entries: Option<usize>,
+    ) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.tuple_begin(tag, label, index, num_entries);
+        }
+
+        self.value_begin()?;
+        self.stream.tuple_begin(tag, label, index, num_entries)
+    }
+
+    fn tuple_value_begin(&mut self, tag: Option<&Tag>, index: &Index) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.tuple_value_begin(tag, index);
+        }
+
+        self.value_begin()?;
+        self.stream.tuple_value_begin(tag, index)
+    }
+
+    fn tuple_value_end(&mut self, tag: Option<&Tag>, index: &Index) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.tuple_value_end(tag, index);
+        }
+
+        self.value_begin()?;
+        self.stream.tuple_value_end(tag, index)
+    }
+
+    fn tuple_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.tuple_end(tag, label, index);
+        }
+
+        self.value_begin()?;
+        self.stream.tuple_end(tag, label, index)
+    }
+
+    fn record_tuple_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+        num_entries: Option<usize>,
+    ) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.record_tuple_begin(tag, label, index, num_entries);
+        }
+
+        self.value_begin()?;
+        self.stream.record_tuple_begin(tag, label, index, None)
+    }
+
+    fn record_tuple_value_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: &Label,
+        index: &Index,
+    ) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.record_tuple_value_begin(tag, label, index);
+        }
+
+        self.value_begin()?;
+        self.entry = Some(Entry::RecordTuple(
+            tag.cloned(),
+            label.to_owned(),
+            index.clone(),
+        ));
+
+        Ok(())
+    }
+
+    fn record_tuple_value_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: &Label,
+        index: &Index,
+    ) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.record_tuple_value_end(tag, label, index);
+        }
+
+        if self.skip_end() {
+            return Ok(());
+        }
+
+        self.value_begin()?;
+        self.stream.record_tuple_value_end(tag, label, index)
+    }
+
+    fn record_tuple_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> sval::Result {
+        if let Some(key) = self.key() {
+            return key.record_tuple_end(tag, label, index);
+        }
+
+        self.value_begin()?;
+        self.stream.record_tuple_end(tag, label, index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sval_test::TokenBuf;
+
+    #[test]
+    fn skip_none() {
+        struct Entries<'a> {
+            is_map: bool,
+            num_entries: Option<usize>,
+            entries: &'a [(&'static str, Option<i32>)],
+        }
+
+        impl<'a> sval::Value for Entries<'a> {
+            fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(
+                &'sval self,
+                stream: &mut S,
+            ) -> sval::Result {
+                if self.is_map {
+                    stream.map_begin(self.num_entries)?;
+
+                    for (key, value) in self.entries {
+                        // Map keys are buffered, so they're forwarded as computed
+                        stream.map_key_begin()?;
+                        stream.value_computed(key)?;
+                        stream.map_key_end()?;
+
+                        stream.map_value_begin()?;
+                        stream.value(value)?;
+                        stream.map_value_end()?;
+                    }
+
+                    stream.map_end()
+                } else {
+                    stream.record_begin(None, None, None, self.num_entries)?;
+
+                    for (label, value) in self.entries {
+                        let label = sval::Label::new(label);
+
+                        stream.record_value_begin(None, &label)?;
+                        stream.value(value)?;
+                        stream.record_value_end(None, &label)?;
+                    }
+
+                    stream.record_end(None, None, None)
+                }
+            }
+        }
+
+        for is_map in [false, true] {
+            let value = Entries {
+                is_map,
+                num_entries: Some(4),
+                entries: &[("a", Some(1)), ("b", None), ("c", Some(2)), ("d", None)],
+            };
+
+            let mut stream = crate::skip_none(TokenBuf::new());
+            sval::stream(&mut stream, &value).unwrap();
+
+            // Entries with a value of `None` are dropped, including their keys
+            let expected = Entries {
+                is_map,
+                num_entries: None,
+                entries: &[("a", Some(1)), ("c", Some(2))],
+            };
+
+            let mut expected_stream = TokenBuf::new();
+            sval::stream(&mut expected_stream, &expected).unwrap();
+
+            assert_eq!(expected_stream.as_tokens(), stream.into_inner().as_tokens());
+        }
+
+        // `None` anywhere else is forwarded unchanged
+        let value = vec![Some(1), None];
+
+        let mut stream = crate::skip_none(TokenBuf::new());
+        sval::stream(&mut stream, &value).unwrap();
+
+        let mut expected = TokenBuf::new();
+        sval::stream(&mut expected, &value).unwrap();
+
+        assert_eq!(expected.as_tokens(), stream.into_inner().as_tokens());
+
+        sval_test::assert_forwards_num(crate::skip_none);
+    }
+}
@@ -263,8 +263,10 @@ impl<'sval> ValueBuf<'sval> {
     }
 
     fn fail(&mut self, err: Error) -> sval::Result {
+        let sval_err = err.to_sval();
         self.err = Some(err);
-        sval::error()
+
+        Err(sval_err)
     }
 
     /**
@@ -814,6 +816,12 @@ pub fn stream_to_value<'sval>(
 
 /**
 Buffer an owned value.
+
+The returned buffer doesn't borrow anything, so it can be stored and streamed later.
+Text and binary fragments are copied into the buffer, while tags, labels, and indexes
+are kept as-is. Labels based on static strings are not copied.
+
+This method will fail if the `alloc` feature is not enabled.
 */
 pub fn stream_to_value_owned(v: impl sval::Value) -> Result<ValueBuf<'static>, Error> {
     ValueBuf::collect_owned(v)
@@ -979,6 +987,92 @@ impl<'sval> ValueBuf<'sval> {
     fn current_mut(&mut self) -> &mut ValuePart<'sval> {
         self.parts.last_mut().expect("missing current")
     }
+
+    /**
+    Replace any missing entry hints on buffered maps, sequences, records, and tuples
+    with the number of entries they actually contain.
+    */
+    #[cfg(feature = "alloc")]
+    pub(crate) fn fill_num_entries(&mut self) {
+        for i in 0..self.parts.len() {
+            let (len, is_entry): (usize, fn(&ValueKind) -> bool) = match self.parts[i].kind {
+                ValueKind::Map {
+                    len,
+                    num_entries_hint: None,
+                } => (len, |kind| matches!(kind, ValueKind::MapKey { .. })),
+                ValueKind::Seq {
+                    len,
+                    num_entries_hint: None,
+                } => (len, |kind| matches!(kind, ValueKind::SeqValue { .. })),
+                ValueKind::Record {
+                    len,
+                    num_entries: None,
+                    ..
+                } => (len, |kind| matches!(kind, ValueKind::RecordValue { .. })),
+                ValueKind::Tuple {
+                    len,
+                    num_entries: None,
+                    ..
+                } => (len, |kind| matches!(kind, ValueKind::TupleValue { .. })),
+                ValueKind::RecordTuple {
+                    len,
+                    num_entries: None,
+                    ..
+                } => (len, |kind| {
+                    matches!(kind, ValueKind::RecordTupleValue { .. })
+                }),
+                _ => continue,
+            };
+
+            // Only count direct children, skipping over the bodies of any nested values
+            let mut count = 0;
+            let mut child = i + 1;
+            while child <= i + len {
+                let kind = &self.parts[child].kind;
+
+                if is_entry(kind) {
+                    count += 1;
+                }
+
+                child += 1 + kind.len();
+            }
+
+            match &mut self.parts[i].kind {
+                ValueKind::Map {
+                    num_entries_hint, ..
+                }
+                | ValueKind::Seq {
+                    num_entries_hint, ..
+                } => *num_entries_hint = Some(count),
+                ValueKind::Record { num_entries, .. }
+                | ValueKind::Tuple { num_entries, .. }
+                | ValueKind::RecordTuple { num_entries, .. } => *num_entries = Some(count),
+                _ => unreachable!(),
+            }
+        }
+    }
+}
+
+impl<'sval> ValueKind<'sval> {
+    #[cfg(feature = "alloc")]
+    fn len(&self) -> usize {
+        match self {
+            ValueKind::Map { len, .. }
+            | ValueKind::MapKey { len }
+            | ValueKind::MapValue { len }
+            | ValueKind::Seq { len, .. }
+            | ValueKind::SeqValue { len }
+            | ValueKind::Enum { len, .. }
+            | ValueKind::Tagged { len, .. }
+            | ValueKind::Record { len, .. }
+            | ValueKind::RecordValue { len, .. }
+            | ValueKind::Tuple { len, .. }
+            | ValueKind::TupleValue { len, .. }
+            | ValueKind::RecordTuple { len, .. }
+            | ValueKind::RecordTupleValue { len, .. } => *len,
+            _ => 0,
+        }
+    }
 }
 
 impl<'sval> ValueSlice<'sval> {
@@ -1804,6 +1898,16 @@ mod tests {
         let _ = Value::collect(&Kaboom);
         let _ = Value::collect_owned(&Kaboom);
     }
+
+    #[test]
+    fn buffer_error_kind() {
+        let mut buf = ValueBuf::new();
+
+        buf.map_begin(None).unwrap();
+        let err = buf.text_fragment("text").unwrap_err();
+
+        assert_eq!(sval::ErrorKind::Buffer, err.kind());
+    }
 }
 
 #[cfg(test)]
@@ -1811,7 +1915,7 @@ mod tests {
 mod alloc_tests {
     use super::*;
 
-    use crate::std::string::String;
+    use crate::std::{string::String, vec::Vec};
 
     use sval::Stream as _;
     use sval_derive_macros::*;
@@ -1854,6 +1958,77 @@ mod alloc_tests {
         }
     }
 
+    #[test]
+    fn stream_to_value_owned_roundtrip() {
+        struct Record {
+            field: String,
+            text: String,
+            binary: Vec<u8>,
+        }
+
+        impl sval::Value for Record {
+            fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(
+                &'sval self,
+                stream: &mut S,
+            ) -> sval::Result {
+                stream.record_begin(
+                    Some(&sval::tags::RUST_OPTION_SOME),
+                    Some(&sval::Label::new("Record")),
+                    Some(&sval::Index::new(1)),
+                    Some(2),
+                )?;
+
+                stream.record_value_begin(None, &sval::Label::new("text"))?;
+                stream.value(&*self.text)?;
+                stream.record_value_end(None, &sval::Label::new("text"))?;
+
+                stream.record_value_begin(None, &sval::Label::new_computed(&self.field))?;
+                stream.value(sval::BinarySlice::new(&self.binary))?;
+                stream.record_value_end(None, &sval::Label::new_computed(&self.field))?;
+
+                stream.record_end(
+                    Some(&sval::tags::RUST_OPTION_SOME),
+                    Some(&sval::Label::new("Record")),
+                    Some(&sval::Index::new(1)),
+                )
+            }
+        }
+
+        let record = Record {
+            field: String::from("binary"),
+            text: String::from("abc"),
+            binary: vec![1, 2, 3],
+        };
+
+        // The owned value doesn't borrow from the original,
+        // so its text and binary are always streamed as computed
+        let mut expected = sval_test::TokenBuf::new();
+        sval::stream_computed(&mut expected, &record).unwrap();
+
+        let owned = stream_to_value_owned(&record).unwrap();
+
+        let mut actual = sval_test::TokenBuf::new();
+        sval::stream(&mut actual, &owned).unwrap();
+
+        assert_eq!(expected.as_tokens(), actual.as_tokens());
+
+        // Labels that were static strings are kept as static strings
+        match owned.parts[0].kind {
+            ValueKind::Record {
+                label: Some(ref label),
+                ..
+            } => assert_eq!(Some("Record"), label.as_static_str()),
+            _ => unreachable!(),
+        }
+
+        match owned.parts[1].kind {
+            ValueKind::RecordValue { ref label, .. } => {
+                assert_eq!(Some("text"), label.as_static_str())
+            }
+            _ => unreachable!(),
+        }
+    }
+
     #[test]
     fn buffer_option() {
         let expected = vec![ValuePart {
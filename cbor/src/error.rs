@@ -0,0 +1,59 @@
+use core::fmt;
+
+/**
+An error encountered streaming CBOR.
+*/
+#[derive(Debug)]
+pub struct Error {
+    pub(crate) kind: ErrorKind,
+}
+
+#[derive(Debug)]
+pub(crate) enum ErrorKind {
+    #[cfg(feature = "alloc")]
+    Generic,
+    #[cfg(feature = "alloc")]
+    InvalidLength { expected: u64, actual: u64 },
+}
+
+impl fmt::Display for Error {
+    // Errors are only produced when encoding, which requires `alloc`
+    #[cfg_attr(not(feature = "alloc"), allow(unused_variables))]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            #[cfg(feature = "alloc")]
+            ErrorKind::Generic => write!(f, "an error occurred serializing a value to CBOR"),
+            #[cfg(feature = "alloc")]
+            ErrorKind::InvalidLength { expected, actual } => write!(
+                f,
+                "a length hint of {} didn't match the actual length of {}",
+                expected, actual
+            ),
+        }
+    }
+}
+
+impl Error {
+    #[cfg(feature = "alloc")]
+    pub(crate) fn generic() -> Self {
+        Error {
+            kind: ErrorKind::Generic,
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub(crate) fn invalid_length(expected: u64, actual: u64) -> Self {
+        Error {
+            kind: ErrorKind::InvalidLength { expected, actual },
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+mod std_support {
+    use super::*;
+
+    use std::error;
+
+    impl error::Error for Error {}
+}
@@ -0,0 +1,30 @@
+/*!
+CBOR support for `sval`.
+
+Values are encoded in a `serde`-compatible way. Maps, sequences, text, and binary
+use definite lengths when their `*_begin` method is given a length hint, and
+indefinite lengths otherwise.
+
+Some functionality requires the `alloc` Cargo feature to be enabled.
+*/
+
+#![no_std]
+#![deny(missing_docs)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+mod error;
+
+pub mod tags;
+
+pub use self::error::*;
+
+#[cfg(feature = "alloc")]
+mod to_vec;
+
+#[cfg(feature = "alloc")]
+pub use self::to_vec::*;
@@ -0,0 +1,14 @@
+/*!
+Tags for CBOR-specific types.
+*/
+
+/**
+A tag for timestamps in the RFC3339 format, like `2024-02-29T12:00:00Z`.
+
+Text with this tag is encoded with CBOR's standard date/time tag.
+
+# Valid datatypes
+
+- `text`
+*/
+pub const RFC3339_DATE_TIME: sval::Tag = sval::Tag::new("RFC3339_DATE_TIME");
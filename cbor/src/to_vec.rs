@@ -0,0 +1,513 @@
+use crate::Error;
+
+use alloc::vec::Vec;
+
+const MAJOR_UNSIGNED: u8 = 0;
+const MAJOR_NEGATIVE: u8 = 1;
+const MAJOR_BINARY: u8 = 2;
+const MAJOR_TEXT: u8 = 3;
+const MAJOR_ARRAY: u8 = 4;
+const MAJOR_MAP: u8 = 5;
+const MAJOR_TAG: u8 = 6;
+
+const FALSE: u8 = 0xf4;
+const TRUE: u8 = 0xf5;
+const NULL: u8 = 0xf6;
+const F32: u8 = 0xfa;
+const F64: u8 = 0xfb;
+const BREAK: u8 = 0xff;
+
+const INDEFINITE: u8 = 31;
+
+const TAG_DATE_TIME: u64 = 0;
+const TAG_POSITIVE_BIGNUM: u64 = 2;
+const TAG_NEGATIVE_BIGNUM: u64 = 3;
+
+/**
+Stream a value as CBOR into a byte buffer.
+
+Integers that don't fit in 64 bits are encoded as bignums. Text tagged with
+[`tags::RFC3339_DATE_TIME`](crate::tags::RFC3339_DATE_TIME) is encoded with the standard date/time tag.
+This method will fail if a length hint doesn't match the actual length of its value.
+*/
+pub fn stream_to_vec(v: impl sval::Value) -> Result<Vec<u8>, Error> {
+    let mut stream = Encoder::new();
+
+    match v.stream(&mut stream) {
+        Ok(()) => Ok(stream.out),
+        Err(_) => Err(stream.err.unwrap_or_else(Error::generic)),
+    }
+}
+
+struct Encoder {
+    out: Vec<u8>,
+    containers: Vec<Length>,
+    fragments: Length,
+    is_variant: bool,
+    err: Option<Error>,
+}
+
+/**
+The expected and actual length of a map, sequence, text, or binary value.
+
+Values without an expected length are encoded with an indefinite length.
+*/
+#[derive(Clone, Copy)]
+struct Length {
+    expected: Option<u64>,
+    actual: u64,
+}
+
+impl Length {
+    fn new(expected: Option<usize>) -> Self {
+        Length {
+            expected: expected.map(|len| len as u64),
+            actual: 0,
+        }
+    }
+}
+
+impl Encoder {
+    fn new() -> Self {
+        Encoder {
+            out: Vec::new(),
+            containers: Vec::new(),
+            fragments: Length::new(None),
+            is_variant: false,
+            err: None,
+        }
+    }
+
+    fn fail(&mut self, err: Error) -> sval::Result {
+        self.err = Some(err);
+        sval::error()
+    }
+
+    fn head(&mut self, major: u8, v: u64) {
+        let major = major << 5;
+
+        if v < 24 {
+            self.out.push(major | v as u8);
+        } else if v <= u8::MAX as u64 {
+            self.out.push(major | 24);
+            self.out.push(v as u8);
+        } else if v <= u16::MAX as u64 {
+            self.out.push(major | 25);
+            self.out.extend_from_slice(&(v as u16).to_be_bytes());
+        } else if v <= u32::MAX as u64 {
+            self.out.push(major | 26);
+            self.out.extend_from_slice(&(v as u32).to_be_bytes());
+        } else {
+            self.out.push(major | 27);
+            self.out.extend_from_slice(&v.to_be_bytes());
+        }
+    }
+
+    fn unsigned(&mut self, v: u64) -> sval::Result {
+        self.head(MAJOR_UNSIGNED, v);
+
+        Ok(())
+    }
+
+    fn signed(&mut self, v: i64) -> sval::Result {
+        if v < 0 {
+            // Negative integers are encoded as `-1 - v`
+            self.head(MAJOR_NEGATIVE, !v as u64);
+        } else {
+            self.head(MAJOR_UNSIGNED, v as u64);
+        }
+
+        Ok(())
+    }
+
+    fn bignum(&mut self, tag: u64, v: u128) -> sval::Result {
+        let bytes = v.to_be_bytes();
+        let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+
+        self.head(MAJOR_TAG, tag);
+        self.head(MAJOR_BINARY, (bytes.len() - start) as u64);
+        self.out.extend_from_slice(&bytes[start..]);
+
+        Ok(())
+    }
+
+    fn fragments_begin(&mut self, major: u8, num_bytes: Option<usize>) -> sval::Result {
+        self.fragments = Length::new(num_bytes);
+
+        match self.fragments.expected {
+            Some(len) => self.head(major, len),
+            None => self.out.push((major << 5) | INDEFINITE),
+        }
+
+        Ok(())
+    }
+
+    fn fragment(&mut self, major: u8, fragment: &[u8]) -> sval::Result {
+        self.fragments.actual += fragment.len() as u64;
+
+        // Each fragment of an indefinite-length value is its own definite-length chunk
+        if self.fragments.expected.is_none() {
+            self.head(major, fragment.len() as u64);
+        }
+
+        self.out.extend_from_slice(fragment);
+
+        Ok(())
+    }
+
+    fn fragments_end(&mut self) -> sval::Result {
+        let fragments = self.fragments;
+
+        self.end(fragments)
+    }
+
+    fn container_begin(&mut self, major: u8, num_entries: Option<usize>) -> sval::Result {
+        // Values within an enum that isn't a variant aren't wrapped
+        self.is_variant = false;
+
+        let container = Length::new(num_entries);
+
+        match container.expected {
+            Some(len) => self.head(major, len),
+            None => self.out.push((major << 5) | INDEFINITE),
+        }
+
+        self.containers.push(container);
+
+        Ok(())
+    }
+
+    fn container_next(&mut self) -> sval::Result {
+        match self.containers.last_mut() {
+            Some(container) => {
+                container.actual += 1;
+
+                Ok(())
+            }
+            None => self.fail(Error::generic()),
+        }
+    }
+
+    fn container_end(&mut self) -> sval::Result {
+        match self.containers.pop() {
+            Some(container) => self.end(container),
+            None => self.fail(Error::generic()),
+        }
+    }
+
+    fn end(&mut self, length: Length) -> sval::Result {
+        match length.expected {
+            Some(expected) if expected != length.actual => {
+                self.fail(Error::invalid_length(expected, length.actual))
+            }
+            Some(_) => Ok(()),
+            None => {
+                self.out.push(BREAK);
+
+                Ok(())
+            }
+        }
+    }
+
+    fn label(&mut self, label: &sval::Label) -> sval::Result {
+        self.head(MAJOR_TEXT, label.as_str().len() as u64);
+        self.out.extend_from_slice(label.as_str().as_bytes());
+
+        Ok(())
+    }
+
+    /**
+    Begin a variant of an enum.
+
+    Variants with a label or index are encoded as a single-entry map from that label
+    or index to their value, like `serde`'s externally tagged enums.
+    */
+    fn variant_begin(
+        &mut self,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        if !self.is_variant {
+            return Ok(());
+        }
+
+        self.is_variant = false;
+
+        if let Some(label) = label {
+            self.head(MAJOR_MAP, 1);
+            self.label(label)
+        } else if let Some(index) = index.and_then(|index| index.to_i64()) {
+            self.head(MAJOR_MAP, 1);
+            self.signed(index)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<'sval> sval::Stream<'sval> for Encoder {
+    fn null(&mut self) -> sval::Result {
+        self.out.push(NULL);
+
+        Ok(())
+    }
+
+    fn bool(&mut self, value: bool) -> sval::Result {
+        self.out.push(if value { TRUE } else { FALSE });
+
+        Ok(())
+    }
+
+    fn text_begin(&mut self, num_bytes: Option<usize>) -> sval::Result {
+        self.fragments_begin(MAJOR_TEXT, num_bytes)
+    }
+
+    fn text_fragment_computed(&mut self, fragment: &str) -> sval::Result {
+        self.fragment(MAJOR_TEXT, fragment.as_bytes())
+    }
+
+    fn text_end(&mut self) -> sval::Result {
+        self.fragments_end()
+    }
+
+    fn binary_begin(&mut self, num_bytes: Option<usize>) -> sval::Result {
+        self.fragments_begin(MAJOR_BINARY, num_bytes)
+    }
+
+    fn binary_fragment_computed(&mut self, fragment: &[u8]) -> sval::Result {
+        self.fragment(MAJOR_BINARY, fragment)
+    }
+
+    fn binary_end(&mut self) -> sval::Result {
+        self.fragments_end()
+    }
+
+    fn u8(&mut self, value: u8) -> sval::Result {
+        self.unsigned(value as u64)
+    }
+
+    fn u16(&mut self, value: u16) -> sval::Result {
+        self.unsigned(value as u64)
+    }
+
+    fn u32(&mut self, value: u32) -> sval::Result {
+        self.unsigned(value as u64)
+    }
+
+    fn u64(&mut self, value: u64) -> sval::Result {
+        self.unsigned(value)
+    }
+
+    fn u128(&mut self, value: u128) -> sval::Result {
+        if let Ok(value) = u64::try_from(value) {
+            self.unsigned(value)
+        } else {
+            self.bignum(TAG_POSITIVE_BIGNUM, value)
+        }
+    }
+
+    fn i8(&mut self, value: i8) -> sval::Result {
+        self.signed(value as i64)
+    }
+
+    fn i16(&mut self, value: i16) -> sval::Result {
+        self.signed(value as i64)
+    }
+
+    fn i32(&mut self, value: i32) -> sval::Result {
+        self.signed(value as i64)
+    }
+
+    fn i64(&mut self, value: i64) -> sval::Result {
+        self.signed(value)
+    }
+
+    fn i128(&mut self, value: i128) -> sval::Result {
+        if value >= 0 {
+            self.u128(value as u128)
+        } else if let Ok(value) = u64::try_from(!value) {
+            self.head(MAJOR_NEGATIVE, value);
+
+            Ok(())
+        } else {
+            self.bignum(TAG_NEGATIVE_BIGNUM, !value as u128)
+        }
+    }
+
+    fn f32(&mut self, value: f32) -> sval::Result {
+        self.out.push(F32);
+        self.out.extend_from_slice(&value.to_be_bytes());
+
+        Ok(())
+    }
+
+    fn f64(&mut self, value: f64) -> sval::Result {
+        self.out.push(F64);
+        self.out.extend_from_slice(&value.to_be_bytes());
+
+        Ok(())
+    }
+
+    fn map_begin(&mut self, num_entries: Option<usize>) -> sval::Result {
+        self.container_begin(MAJOR_MAP, num_entries)
+    }
+
+    fn map_key_begin(&mut self) -> sval::Result {
+        self.container_next()
+    }
+
+    fn map_key_end(&mut self) -> sval::Result {
+        Ok(())
+    }
+
+    fn map_value_begin(&mut self) -> sval::Result {
+        Ok(())
+    }
+
+    fn map_value_end(&mut self) -> sval::Result {
+        Ok(())
+    }
+
+    fn map_end(&mut self) -> sval::Result {
+        self.container_end()
+    }
+
+    fn seq_begin(&mut self, num_entries: Option<usize>) -> sval::Result {
+        self.container_begin(MAJOR_ARRAY, num_entries)
+    }
+
+    fn seq_value_begin(&mut self) -> sval::Result {
+        self.container_next()
+    }
+
+    fn seq_value_end(&mut self) -> sval::Result {
+        Ok(())
+    }
+
+    fn seq_end(&mut self) -> sval::Result {
+        self.container_end()
+    }
+
+    fn enum_begin(
+        &mut self,
+        _: Option<&sval::Tag>,
+        _: Option<&sval::Label>,
+        _: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.is_variant = true;
+
+        Ok(())
+    }
+
+    fn enum_end(
+        &mut self,
+        _: Option<&sval::Tag>,
+        _: Option<&sval::Label>,
+        _: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.is_variant = false;
+
+        Ok(())
+    }
+
+    fn tagged_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.variant_begin(label, index)?;
+
+        if let Some(&crate::tags::RFC3339_DATE_TIME) = tag {
+            self.head(MAJOR_TAG, TAG_DATE_TIME);
+        }
+
+        Ok(())
+    }
+
+    fn tagged_end(
+        &mut self,
+        _: Option<&sval::Tag>,
+        _: Option<&sval::Label>,
+        _: Option<&sval::Index>,
+    ) -> sval::Result {
+        Ok(())
+    }
+
+    fn tag(
+        &mut self,
+        _: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        // Unit variants are encoded as just their label or index
+        if self.is_variant {
+            self.is_variant = false;
+
+            if let Some(label) = label {
+                return self.label(label);
+            } else if let Some(index) = index.and_then(|index| index.to_i64()) {
+                return self.signed(index);
+            }
+        }
+
+        self.null()
+    }
+
+    fn record_begin(
+        &mut self,
+        _: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+        num_entries: Option<usize>,
+    ) -> sval::Result {
+        self.variant_begin(label, index)?;
+        self.container_begin(MAJOR_MAP, num_entries)
+    }
+
+    fn record_value_begin(&mut self, _: Option<&sval::Tag>, label: &sval::Label) -> sval::Result {
+        self.container_next()?;
+        self.label(label)
+    }
+
+    fn record_value_end(&mut self, _: Option<&sval::Tag>, _: &sval::Label) -> sval::Result {
+        Ok(())
+    }
+
+    fn record_end(
+        &mut self,
+        _: Option<&sval::Tag>,
+        _: Option<&sval::Label>,
+        _: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.container_end()
+    }
+
+    fn tuple_begin(
+        &mut self,
+        _: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+        num_entries: Option<usize>,
+    ) -> sval::Result {
+        self.variant_begin(label, index)?;
+        self.container_begin(MAJOR_ARRAY, num_entries)
+    }
+
+    fn tuple_value_begin(&mut self, _: Option<&sval::Tag>, _: &sval::Index) -> sval::Result {
+        self.container_next()
+    }
+
+    fn tuple_value_end(&mut self, _: Option<&sval::Tag>, _: &sval::Index) -> sval::Result {
+        Ok(())
+    }
+
+    fn tuple_end(
+        &mut self,
+        _: Option<&sval::Tag>,
+        _: Option<&sval::Label>,
+        _: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.container_end()
+    }
+}
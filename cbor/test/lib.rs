@@ -0,0 +1,257 @@
+#![cfg(test)]
+
+#[macro_use]
+extern crate sval_derive;
+
+#[macro_use]
+extern crate serde_derive;
+
+#[path = "../../serde/test/twitter.rs"]
+mod twitter;
+
+fn assert_cbor(v: impl sval::Value + serde::Serialize) {
+    let expected = serde_cbor::to_vec(&v).unwrap();
+    let actual = sval_cbor::stream_to_vec(&v).unwrap();
+
+    assert_eq!(expected, actual);
+}
+
+fn assert_cbor_value(v: impl sval::Value + serde::Serialize) {
+    let expected: serde_cbor::Value =
+        serde_cbor::from_slice(&serde_cbor::to_vec(&v).unwrap()).unwrap();
+    let actual: serde_cbor::Value =
+        serde_cbor::from_slice(&sval_cbor::stream_to_vec(&v).unwrap()).unwrap();
+
+    assert_eq!(expected, actual);
+}
+
+#[derive(Value, Serialize)]
+struct MapStruct {
+    field_0: i32,
+    field_1: bool,
+    field_2: &'static str,
+}
+
+#[derive(Value, Serialize)]
+struct SeqStruct(i32, bool, &'static str);
+
+#[derive(Value, Serialize)]
+struct Tagged(i32);
+
+#[derive(Value, Serialize)]
+enum Enum {
+    Constant,
+    Tagged(i32),
+    MapStruct {
+        field_0: i32,
+        field_1: bool,
+        field_2: &'static str,
+    },
+    SeqStruct(i32, bool, &'static str),
+}
+
+struct Fragments<'a>(&'a [&'a str]);
+
+impl<'a> sval::Value for Fragments<'a> {
+    fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> sval::Result {
+        stream.text_begin(None)?;
+
+        for fragment in self.0 {
+            stream.text_fragment(fragment)?;
+        }
+
+        stream.text_end()
+    }
+}
+
+struct UnsizedSeq<'a>(&'a [i32]);
+
+impl<'a> sval::Value for UnsizedSeq<'a> {
+    fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> sval::Result {
+        stream.seq_begin(None)?;
+
+        for v in self.0 {
+            stream.seq_value_begin()?;
+            stream.value(v)?;
+            stream.seq_value_end()?;
+        }
+
+        stream.seq_end()
+    }
+}
+
+#[test]
+fn stream_primitive() {
+    assert_cbor(0u8);
+    assert_cbor(23u8);
+    assert_cbor(24u8);
+    assert_cbor(u16::MAX);
+    assert_cbor(u32::MAX);
+    assert_cbor(u64::MAX);
+    assert_cbor(-1i8);
+    assert_cbor(-25i32);
+    assert_cbor(i64::MIN);
+    assert_cbor(true);
+    assert_cbor(false);
+    assert_cbor("");
+    assert_cbor("abc");
+    assert_cbor(());
+    assert_cbor(None::<i32>);
+    assert_cbor(Some(42i32));
+}
+
+#[test]
+fn stream_float() {
+    // `serde_cbor` uses the shortest encoding that preserves a float
+    // so these are compared by value instead of by bytes
+    assert_cbor_value(1.5f32);
+    assert_cbor_value(1.1f64);
+    assert_cbor_value(f64::MAX);
+
+    assert_eq!(
+        vec![0xfa, 0x3f, 0xc0, 0x00, 0x00],
+        sval_cbor::stream_to_vec(1.5f32).unwrap()
+    );
+}
+
+#[test]
+fn stream_128bit() {
+    assert_cbor(42u128);
+    assert_cbor(-42i128);
+    assert_cbor(u64::MAX as u128);
+    assert_cbor(-(u64::MAX as i128) - 1);
+
+    let mut expected = vec![0xc2, 0x50];
+    expected.extend_from_slice(&[0xff; 16]);
+    assert_eq!(expected, sval_cbor::stream_to_vec(u128::MAX).unwrap());
+
+    assert_eq!(
+        vec![0xc2, 0x49, 0x01, 0, 0, 0, 0, 0, 0, 0, 0],
+        sval_cbor::stream_to_vec(u64::MAX as u128 + 1).unwrap()
+    );
+
+    // -2^64 - 1 is encoded as the bignum 2^64
+    assert_eq!(
+        vec![0xc3, 0x49, 0x01, 0, 0, 0, 0, 0, 0, 0, 0],
+        sval_cbor::stream_to_vec(-(u64::MAX as i128) - 2).unwrap()
+    );
+}
+
+#[test]
+fn stream_binary() {
+    assert_eq!(
+        vec![0x43, 1, 2, 3],
+        sval_cbor::stream_to_vec(sval::BinarySlice::new(&[1, 2, 3])).unwrap()
+    );
+}
+
+#[test]
+fn stream_indefinite_text() {
+    let actual = sval_cbor::stream_to_vec(Fragments(&["a", "bc"])).unwrap();
+
+    assert_eq!(vec![0x7f, 0x61, b'a', 0x62, b'b', b'c', 0xff], actual);
+    assert_eq!("abc", serde_cbor::from_slice::<String>(&actual).unwrap());
+}
+
+#[test]
+fn stream_indefinite_seq() {
+    let actual = sval_cbor::stream_to_vec(UnsizedSeq(&[1, 2])).unwrap();
+
+    assert_eq!(vec![0x9f, 0x01, 0x02, 0xff], actual);
+    assert_eq!(
+        vec![1, 2],
+        serde_cbor::from_slice::<Vec<i32>>(&actual).unwrap()
+    );
+}
+
+#[test]
+fn stream_invalid_length() {
+    struct WrongLength;
+
+    impl sval::Value for WrongLength {
+        fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(
+            &'sval self,
+            stream: &mut S,
+        ) -> sval::Result {
+            stream.seq_begin(Some(2))?;
+
+            stream.seq_value_begin()?;
+            stream.i32(1)?;
+            stream.seq_value_end()?;
+
+            stream.seq_end()
+        }
+    }
+
+    assert!(sval_cbor::stream_to_vec(WrongLength).is_err());
+}
+
+#[test]
+fn stream_rfc3339() {
+    #[derive(Value)]
+    #[sval(tag = "sval_cbor::tags::RFC3339_DATE_TIME")]
+    struct Timestamp(&'static str);
+
+    let mut expected = vec![0xc0, 0x74];
+    expected.extend_from_slice(b"2013-03-21T20:04:00Z");
+
+    assert_eq!(
+        expected,
+        sval_cbor::stream_to_vec(Timestamp("2013-03-21T20:04:00Z")).unwrap()
+    );
+}
+
+#[test]
+fn stream_collections() {
+    assert_cbor(vec![1, 2, 3]);
+    assert_cbor(Vec::<i32>::new());
+    assert_cbor([[1, 2], [3, 4]]);
+    assert_cbor((1, true, "a"));
+    assert_cbor({
+        let mut map = std::collections::BTreeMap::new();
+        map.insert("a", 1);
+        map.insert("b", 2);
+        map
+    });
+}
+
+#[test]
+fn stream_struct() {
+    assert_cbor(MapStruct {
+        field_0: 1,
+        field_1: true,
+        field_2: "a",
+    });
+    assert_cbor(SeqStruct(1, true, "a"));
+    assert_cbor(Tagged(1));
+}
+
+#[test]
+fn stream_enum() {
+    assert_cbor(Enum::Constant);
+    assert_cbor(Enum::Tagged(1));
+    assert_cbor(Enum::MapStruct {
+        field_0: 1,
+        field_1: true,
+        field_2: "a",
+    });
+    assert_cbor(Enum::SeqStruct(1, true, "a"));
+    assert_cbor(vec![Some(Enum::Tagged(1)), None]);
+}
+
+#[test]
+fn stream_twitter() {
+    let value = twitter::input_struct();
+
+    let actual = sval_cbor::stream_to_vec(&value).unwrap();
+
+    // The encoded value can be deserialized by `serde_cbor`
+    let roundtrip: twitter::Twitter = serde_cbor::from_slice(&actual).unwrap();
+
+    assert_eq!(
+        serde_cbor::to_vec(&value).unwrap(),
+        serde_cbor::to_vec(&roundtrip).unwrap()
+    );
+
+    assert_cbor_value(&value);
+}
@@ -0,0 +1,230 @@
+#![cfg(test)]
+#![feature(test)]
+
+extern crate test;
+
+#[macro_use]
+extern crate sval_derive;
+
+/**
+A wide struct whose derived impl uses labels and indexes cached in statics.
+*/
+#[derive(Value)]
+struct Wide {
+    f0: u8,
+    f1: u8,
+    f2: u8,
+    f3: u8,
+    f4: u8,
+    f5: u8,
+    f6: u8,
+    f7: u8,
+    f8: u8,
+    f9: u8,
+    f10: u8,
+    f11: u8,
+    f12: u8,
+    f13: u8,
+    f14: u8,
+    f15: u8,
+    f16: u8,
+    f17: u8,
+    f18: u8,
+    f19: u8,
+    f20: u8,
+    f21: u8,
+    f22: u8,
+    f23: u8,
+    f24: u8,
+    f25: u8,
+    f26: u8,
+    f27: u8,
+    f28: u8,
+    f29: u8,
+    f30: u8,
+    f31: u8,
+}
+
+/**
+The same struct as `Wide`, but streamed with labels and indexes constructed on each call.
+*/
+struct WideInline([u8; 32]);
+
+impl sval::Value for WideInline {
+    fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> sval::Result {
+        stream.record_tuple_begin(
+            None,
+            Some(&sval::Label::new("Wide").with_tag(&sval::tags::VALUE_IDENT)),
+            None,
+            Some(self.0.len()),
+        )?;
+
+        for (i, value) in self.0.iter().enumerate() {
+            stream.record_tuple_value_begin(
+                None,
+                &sval::Label::new(FIELD_NAMES[i]).with_tag(&sval::tags::VALUE_IDENT),
+                &sval::Index::new(i).with_tag(&sval::tags::VALUE_OFFSET),
+            )?;
+            stream.u8(*value)?;
+            stream.record_tuple_value_end(
+                None,
+                &sval::Label::new(FIELD_NAMES[i]).with_tag(&sval::tags::VALUE_IDENT),
+                &sval::Index::new(i).with_tag(&sval::tags::VALUE_OFFSET),
+            )?;
+        }
+
+        stream.record_tuple_end(
+            None,
+            Some(&sval::Label::new("Wide").with_tag(&sval::tags::VALUE_IDENT)),
+            None,
+        )
+    }
+}
+
+fn wide() -> Wide {
+    Wide {
+        f0: 0,
+        f1: 1,
+        f2: 2,
+        f3: 3,
+        f4: 4,
+        f5: 5,
+        f6: 6,
+        f7: 7,
+        f8: 8,
+        f9: 9,
+        f10: 10,
+        f11: 11,
+        f12: 12,
+        f13: 13,
+        f14: 14,
+        f15: 15,
+        f16: 16,
+        f17: 17,
+        f18: 18,
+        f19: 19,
+        f20: 20,
+        f21: 21,
+        f22: 22,
+        f23: 23,
+        f24: 24,
+        f25: 25,
+        f26: 26,
+        f27: 27,
+        f28: 28,
+        f29: 29,
+        f30: 30,
+        f31: 31,
+    }
+}
+
+fn wide_inline() -> WideInline {
+    let mut fields = [0; 32];
+
+    for (i, field) in fields.iter_mut().enumerate() {
+        *field = i as u8;
+    }
+
+    WideInline(fields)
+}
+
+struct NullStream;
+
+impl<'sval> sval::Stream<'sval> for NullStream {
+    #[inline(never)]
+    fn null(&mut self) -> sval::Result {
+        Ok(())
+    }
+
+    #[inline(never)]
+    fn bool(&mut self, _: bool) -> sval::Result {
+        Ok(())
+    }
+
+    #[inline(never)]
+    fn text_begin(&mut self, _: Option<usize>) -> sval::Result {
+        Ok(())
+    }
+
+    #[inline(never)]
+    fn text_fragment_computed(&mut self, _: &str) -> sval::Result {
+        Ok(())
+    }
+
+    #[inline(never)]
+    fn text_end(&mut self) -> sval::Result {
+        Ok(())
+    }
+
+    #[inline(never)]
+    fn i64(&mut self, _: i64) -> sval::Result {
+        Ok(())
+    }
+
+    #[inline(never)]
+    fn f64(&mut self, _: f64) -> sval::Result {
+        Ok(())
+    }
+
+    #[inline(never)]
+    fn seq_begin(&mut self, _: Option<usize>) -> sval::Result {
+        Ok(())
+    }
+
+    #[inline(never)]
+    fn seq_value_begin(&mut self) -> sval::Result {
+        Ok(())
+    }
+
+    #[inline(never)]
+    fn seq_value_end(&mut self) -> sval::Result {
+        Ok(())
+    }
+
+    #[inline(never)]
+    fn seq_end(&mut self) -> sval::Result {
+        Ok(())
+    }
+}
+
+#[test]
+fn wide_inline_matches_derived() {
+    assert_eq!(
+        sval_json::stream_to_string(wide()).unwrap(),
+        sval_json::stream_to_string(wide_inline()).unwrap(),
+    );
+}
+
+#[bench]
+fn derived_cached_32(b: &mut test::Bencher) {
+    let record = wide();
+
+    b.iter(|| sval::stream(&mut NullStream, test::black_box(&record)))
+}
+
+#[bench]
+fn inline_32(b: &mut test::Bencher) {
+    let record = wide_inline();
+
+    b.iter(|| sval::stream(&mut NullStream, test::black_box(&record)))
+}
+
+#[bench]
+fn json_derived_cached_32(b: &mut test::Bencher) {
+    let record = wide();
+
+    b.iter(|| sval_json::stream_to_string(test::black_box(&record)))
+}
+
+#[bench]
+fn json_inline_32(b: &mut test::Bencher) {
+    let record = wide_inline();
+
+    b.iter(|| sval_json::stream_to_string(test::black_box(&record)))
+}
+
+const FIELD_NAMES: &[&'static str] = &[
+    "f0", "f1", "f2", "f3", "f4", "f5", "f6", "f7", "f8", "f9", "f10", "f11", "f12", "f13", "f14",
+    "f15", "f16", "f17", "f18", "f19", "f20", "f21", "f22", "f23", "f24", "f25", "f26", "f27",
+    "f28", "f29", "f30", "f31",
+];
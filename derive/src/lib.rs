@@ -8,21 +8,35 @@ This library hosts a custom-derive to simplify implementing `sval::Value`.
 Container attributes:
 
 - `#[sval(tag = "path::to::TAG")]`: Set a tag on the struct. No tag is used by default.
+- `#[sval(tag_hint = "path::to::TAG")]`: Pass a tag to `Stream::tag_hint` before the struct is streamed.
+  The hint is emitted before `record_tuple_begin` (or the equivalent for the struct's shape).
+- `#[sval(version = 1)]`: Wrap the struct in a tagged value with the `sval::tags::SCHEMA_VERSION` tag and the version as its index.
+  No version is used by default.
 - `#[sval(label = "text")]`: Set a label on the struct. The identifier of the struct is used by default.
 - `#[sval(index = 1)]`: Set an index on the struct. No index is used by default.
 - `#[sval(unlabeled_fields)]`: Specify that all fields should be unlabeled. This will stream the struct as a tuple.
 If `#[sval(unindexed_fields)]` is also specified then it will stream the struct as a sequence.
 - `#[sval(unindexed_fields]`: Specify that all fields should be unindexed. This will stream the struct as a record.
 If `#[sval(unlabeled_fields)]` is also specified then it will stream the struct as a sequence.
+- `#[sval(as = "record")]`: Stream the struct as a `"record_tuple"`, `"record"`, `"tuple"`, or `"seq"`.
+  This is an explicit alternative to `#[sval(unlabeled_fields)]` and `#[sval(unindexed_fields)]`, and can't be combined with them.
+  Streaming as a `"record_tuple"` or `"record"` requires all fields to have labels.
+- `#[sval(transparent)]`: Stream the struct as its only field without wrapping it in a record.
+  The struct must have exactly one field that isn't skipped, and that field can't have any attributes.
 
 Field attributes:
 
 - `#[sval(tag = "path::to::TAG")]`: Set a tag on the struct field itself. No tag is used by default.
 If you want to use a tag to signal that the field's value has a particular property then use `#[sval(data_tag)]`.
 - `#[sval(data_tag = "path::to::TAG")]`: Set a tag on the struct field's value. No tag is used by default.
+- `#[sval(tag_hint = "path::to::TAG")]`: Pass a tag to `Stream::tag_hint` before the struct field's value is streamed.
+  The hint is emitted after `record_tuple_value_begin` (or the equivalent for the struct's shape), so it applies to the field's value
+  rather than the struct. If the field also has a `data_tag` then the hint is emitted before it.
 - `#[sval(label = "text")]`: Set a label on the struct field. The identifier of the field is used by default.
 - `#[sval(index = 1)]`: Set an index on the struct field. The zero-based offset of the field is used by default.
 - `#[sval(skip)]`: Skip a field.
+- `#[sval(feature = "name")]`: Only stream the field when the `name` Cargo feature is enabled in the crate deriving `Value`.
+  The field stays in the struct, but when the feature is off it's skipped, as if it had `#[sval(skip)]`.
 - `#[sval(flatten)]`: Flatten the field onto the struct. This attribute requires the `flatten` Cargo feature.
 
 # Newtypes
@@ -30,6 +44,9 @@ If you want to use a tag to signal that the field's value has a particular prope
 Container attributes:
 
 - `#[sval(tag = "path::to::TAG")]`: Set a tag on the newtype. No tag is used by default.
+- `#[sval(tag_hint = "path::to::TAG")]`: Pass a tag to `Stream::tag_hint` before the newtype is streamed.
+- `#[sval(version = 1)]`: Wrap the newtype in a tagged value with the `sval::tags::SCHEMA_VERSION` tag and the version as its index.
+  No version is used by default.
 - `#[sval(label = "text")]`: Set a label on the newtype. The identifier of the newtype is used by default.
 - `#[sval(index = 1)]`: Set an index on the newtype. No index is used by default.
 - `#[sval(transparent)]`: Stream the newtype as its underlying field without wrapping it.
@@ -39,15 +56,30 @@ Container attributes:
 Container attributes:
 
 - `#[sval(tag = "path::to::TAG")]`: Set a tag on the enum. No tag is used by default.
+- `#[sval(tag_hint = "path::to::TAG")]`: Pass a tag to `Stream::tag_hint` before the enum is streamed.
+- `#[sval(version = 1)]`: Wrap the enum in a tagged value with the `sval::tags::SCHEMA_VERSION` tag and the version as its index.
+  No version is used by default.
 - `#[sval(label = "text")]`: Set a label on the enum. The identifier of the enum is used by default.
 - `#[sval(index = 1)]`: Set an index on the enum. No index is used by default.
 - `#[sval(dynamic)]`: Stream the variant without wrapping it in an enum.
+- `#[sval(discriminant)]`: Stream each variant as its integer discriminant, using the type from the enum's `#[repr]`.
+  The enum must have an integer `#[repr]`, like `#[repr(i16)]`, and only unit variants.
 
 Variant attributes:
 
 - `#[sval(tag = "path::to::TAG")]`: Set a tag on the enum variant itself. No tag is used by default.
 - `#[sval(label = "text")]`: Set a label on the enum variant. The identifier of the variant is used by default.
 - `#[sval(index = 1)]`: Set an index on the enum variant. The zero-based offset of the variant is used by default.
+- `#[sval(as = "record")]`: Stream the fields of the enum variant as a `"record_tuple"`, `"record"`, `"tuple"`, or `"seq"`.
+- `#[sval(transparent)]`: Stream a newtype variant as its underlying field without wrapping it in the enum.
+  Other variants are still wrapped. Only newtype variants can be transparent.
+
+# Borrowed values
+
+With the `ref` Cargo feature, `#[derive(ValueRef)]` also implements `sval_ref::ValueRef` for a type.
+The type must also derive `Value`, and it can have at most one lifetime parameter, which is used as the `'sval` lifetime.
+Each field is streamed through `sval_ref::stream_ref`, so borrowed data in fields like `&'a str` keeps its lifetime.
+All fields must implement `ValueRef`, and `#[sval(flatten)]` isn't supported.
 */
 
 #[doc(inline)]
@@ -56,4 +88,7 @@ pub use sval_derive_macros::*;
 pub mod extensions {
     #[cfg(feature = "flatten")]
     pub use sval_flatten as flatten;
+
+    #[cfg(feature = "ref")]
+    pub use sval_ref as value_ref;
 }
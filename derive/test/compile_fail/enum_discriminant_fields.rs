@@ -0,0 +1,13 @@
+use sval_derive::*;
+
+#[derive(Value)]
+#[sval(discriminant)]
+#[repr(i16)]
+pub enum Enum {
+    A,
+    B(i32),
+}
+
+fn main() {
+
+}
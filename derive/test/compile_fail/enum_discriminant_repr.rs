@@ -0,0 +1,12 @@
+use sval_derive::*;
+
+#[derive(Value)]
+#[sval(discriminant)]
+pub enum Enum {
+    A,
+    B,
+}
+
+fn main() {
+
+}
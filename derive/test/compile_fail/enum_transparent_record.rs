@@ -0,0 +1,11 @@
+use sval_derive::*;
+
+#[derive(Value)]
+pub enum Enum {
+    #[sval(transparent)]
+    Record { a: i32 },
+}
+
+fn main() {
+
+}
\ No newline at end of file
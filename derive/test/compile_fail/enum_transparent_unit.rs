@@ -0,0 +1,11 @@
+use sval_derive::*;
+
+#[derive(Value)]
+pub enum Enum {
+    #[sval(transparent)]
+    Unit,
+}
+
+fn main() {
+
+}
\ No newline at end of file
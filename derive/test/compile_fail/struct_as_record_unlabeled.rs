@@ -0,0 +1,9 @@
+use sval_derive::*;
+
+#[derive(Value)]
+#[sval(as = "record")]
+pub struct Record(i32, i32);
+
+fn main() {
+
+}
\ No newline at end of file
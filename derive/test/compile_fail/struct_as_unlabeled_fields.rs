@@ -0,0 +1,11 @@
+use sval_derive::*;
+
+#[derive(Value)]
+#[sval(as = "record", unlabeled_fields)]
+pub struct Record {
+    a: i32,
+}
+
+fn main() {
+
+}
\ No newline at end of file
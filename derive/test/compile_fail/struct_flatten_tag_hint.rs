@@ -0,0 +1,16 @@
+use sval_derive::*;
+
+#[derive(Value)]
+pub struct Inner {
+    a: i32,
+}
+
+#[derive(Value)]
+pub struct Record {
+    #[sval(flatten, tag_hint = "sval::tags::NUMBER")]
+    inner: Inner,
+}
+
+fn main() {
+
+}
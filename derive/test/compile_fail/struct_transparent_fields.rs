@@ -0,0 +1,12 @@
+use sval_derive::*;
+
+#[derive(Value)]
+#[sval(transparent)]
+pub struct Record {
+    a: i32,
+    b: i32,
+}
+
+fn main() {
+
+}
\ No newline at end of file
@@ -159,6 +159,148 @@ mod derive_struct {
         })
     }
 
+    #[test]
+    fn as_container() {
+        #[derive(Value)]
+        #[sval(as = "record_tuple")]
+        struct RecordTuple {
+            a: i32,
+        }
+
+        #[derive(Value)]
+        #[sval(as = "record")]
+        struct Record {
+            a: i32,
+        }
+
+        #[derive(Value)]
+        #[sval(as = "tuple")]
+        struct Tuple {
+            a: i32,
+        }
+
+        #[derive(Value)]
+        #[sval(as = "seq")]
+        struct Seq {
+            a: i32,
+        }
+
+        #[derive(Value)]
+        #[sval(as = "record")]
+        struct LabeledTuple(#[sval(label = "a")] i32, #[sval(label = "b")] bool);
+
+        assert_tokens(&RecordTuple { a: 42 }, {
+            use sval_test::Token::*;
+
+            &[
+                RecordTupleBegin(None, Some(sval::Label::new("RecordTuple")), None, Some(1)),
+                RecordTupleValueBegin(None, sval::Label::new("a"), sval::Index::new(0)),
+                I32(42),
+                RecordTupleValueEnd(None, sval::Label::new("a"), sval::Index::new(0)),
+                RecordTupleEnd(None, Some(sval::Label::new("RecordTuple")), None),
+            ]
+        });
+
+        assert_tokens(&Record { a: 42 }, {
+            use sval_test::Token::*;
+
+            &[
+                RecordBegin(None, Some(sval::Label::new("Record")), None, Some(1)),
+                RecordValueBegin(None, sval::Label::new("a")),
+                I32(42),
+                RecordValueEnd(None, sval::Label::new("a")),
+                RecordEnd(None, Some(sval::Label::new("Record")), None),
+            ]
+        });
+
+        assert_tokens(&Tuple { a: 42 }, {
+            use sval_test::Token::*;
+
+            &[
+                TupleBegin(None, Some(sval::Label::new("Tuple")), None, Some(1)),
+                TupleValueBegin(None, sval::Index::new(0)),
+                I32(42),
+                TupleValueEnd(None, sval::Index::new(0)),
+                TupleEnd(None, Some(sval::Label::new("Tuple")), None),
+            ]
+        });
+
+        assert_tokens(&Seq { a: 42 }, {
+            use sval_test::Token::*;
+
+            &[
+                TaggedBegin(None, Some(sval::Label::new("Seq")), None),
+                SeqBegin(Some(1)),
+                SeqValueBegin,
+                I32(42),
+                SeqValueEnd,
+                SeqEnd,
+                TaggedEnd(None, Some(sval::Label::new("Seq")), None),
+            ]
+        });
+
+        assert_tokens(&LabeledTuple(42, true), {
+            use sval_test::Token::*;
+
+            &[
+                RecordBegin(None, Some(sval::Label::new("LabeledTuple")), None, Some(2)),
+                RecordValueBegin(None, sval::Label::new("a")),
+                I32(42),
+                RecordValueEnd(None, sval::Label::new("a")),
+                RecordValueBegin(None, sval::Label::new("b")),
+                Bool(true),
+                RecordValueEnd(None, sval::Label::new("b")),
+                RecordEnd(None, Some(sval::Label::new("LabeledTuple")), None),
+            ]
+        });
+    }
+
+    #[test]
+    fn map_labels() {
+        #[derive(Value)]
+        struct RecordTuple {
+            a: i32,
+            #[sval(flatten)]
+            b: Record,
+        }
+
+        #[derive(Value)]
+        #[sval(unindexed_fields)]
+        struct Record {
+            c: i32,
+        }
+
+        let mut stream = sval::map_labels(sval_test::TokenBuf::new(), |label| {
+            sval::Label::new_owned(label.as_str().to_uppercase())
+        });
+        sval::stream(
+            &mut stream,
+            &RecordTuple {
+                a: 42,
+                b: Record { c: 43 },
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            {
+                use sval_test::Token::*;
+
+                &[
+                    RecordTupleBegin(None, Some(sval::Label::new("RecordTuple")), None, None),
+                    RecordTupleValueBegin(None, sval::Label::new("A"), sval::Index::new(0)),
+                    I32(42),
+                    RecordTupleValueEnd(None, sval::Label::new("A"), sval::Index::new(0)),
+                    RecordTupleValueBegin(None, sval::Label::new("C"), sval::Index::new(1)),
+                    I32(43),
+                    RecordTupleValueEnd(None, sval::Label::new("C"), sval::Index::new(1)),
+                    RecordTupleEnd(None, Some(sval::Label::new("RecordTuple")), None),
+                ] as &[_]
+            },
+            stream.into_inner().as_tokens()
+        );
+    }
+
     #[test]
     fn data_tagged() {
         #[derive(Value)]
@@ -182,6 +324,144 @@ mod derive_struct {
         })
     }
 
+    #[test]
+    fn feature() {
+        #[derive(Value)]
+        struct RecordTuple {
+            a: i32,
+            #[sval(feature = "enabled")]
+            b: i32,
+            #[sval(feature = "disabled")]
+            c: i32,
+            d: i32,
+        }
+
+        // Fields are only streamed when their feature is enabled,
+        // and indexes are assigned to the fields that are streamed
+        let fields = [
+            ("a", 42, true),
+            ("b", 43, cfg!(feature = "enabled")),
+            ("c", 44, cfg!(feature = "disabled")),
+            ("d", 45, true),
+        ];
+        let len = fields.iter().filter(|(_, _, streamed)| *streamed).count();
+
+        let mut expected = {
+            use sval_test::Token::*;
+
+            vec![RecordTupleBegin(
+                None,
+                Some(sval::Label::new("RecordTuple")),
+                None,
+                Some(len),
+            )]
+        };
+
+        for (index, (label, value, _)) in fields
+            .into_iter()
+            .filter(|(_, _, streamed)| *streamed)
+            .enumerate()
+        {
+            use sval_test::Token::*;
+
+            expected.extend([
+                RecordTupleValueBegin(None, sval::Label::new(label), sval::Index::new(index)),
+                I32(value),
+                RecordTupleValueEnd(None, sval::Label::new(label), sval::Index::new(index)),
+            ]);
+        }
+
+        expected.push(sval_test::Token::RecordTupleEnd(
+            None,
+            Some(sval::Label::new("RecordTuple")),
+            None,
+        ));
+
+        assert_tokens(
+            &RecordTuple {
+                a: 42,
+                b: 43,
+                c: 44,
+                d: 45,
+            },
+            &expected,
+        )
+    }
+
+    #[test]
+    fn tag_hinted() {
+        const CONTAINER: sval::Tag = sval::Tag::new("container");
+        const FIELD: sval::Tag = sval::Tag::new("field");
+
+        #[derive(Value)]
+        #[sval(tag_hint = CONTAINER)]
+        struct RecordTuple {
+            #[sval(tag_hint = "FIELD")]
+            a: i32,
+            #[sval(tag_hint = FIELD, data_tag = sval::tags::NUMBER)]
+            b: i32,
+        }
+
+        assert_tokens(&RecordTuple { a: 42, b: 43 }, {
+            use sval_test::Token::*;
+
+            &[
+                TagHint(CONTAINER),
+                RecordTupleBegin(None, Some(sval::Label::new("RecordTuple")), None, Some(2)),
+                RecordTupleValueBegin(None, sval::Label::new("a"), sval::Index::new(0)),
+                TagHint(FIELD),
+                I32(42),
+                RecordTupleValueEnd(None, sval::Label::new("a"), sval::Index::new(0)),
+                RecordTupleValueBegin(None, sval::Label::new("b"), sval::Index::new(1)),
+                TagHint(FIELD),
+                TaggedBegin(Some(sval::tags::NUMBER), None, None),
+                I32(43),
+                TaggedEnd(Some(sval::tags::NUMBER), None, None),
+                RecordTupleValueEnd(None, sval::Label::new("b"), sval::Index::new(1)),
+                RecordTupleEnd(None, Some(sval::Label::new("RecordTuple")), None),
+            ]
+        })
+    }
+
+    #[test]
+    fn versioned() {
+        const CONTAINER: sval::Tag = sval::Tag::new("container");
+
+        #[derive(Value)]
+        #[sval(version = 2, tag_hint = CONTAINER)]
+        struct RecordTuple {
+            a: i32,
+        }
+
+        assert_eq!(
+            Some(sval::tags::SCHEMA_VERSION),
+            sval::Value::tag(&RecordTuple { a: 42 })
+        );
+
+        assert_tokens(&RecordTuple { a: 42 }, {
+            use sval_test::Token::*;
+
+            &[
+                TaggedBegin(
+                    Some(sval::tags::SCHEMA_VERSION),
+                    None,
+                    Some(sval::Index::new(2)),
+                ),
+                TagHint(CONTAINER),
+                RecordTupleBegin(None, Some(sval::Label::new("RecordTuple")), None, Some(1)),
+                RecordTupleValueBegin(None, sval::Label::new("a"), sval::Index::new(0)),
+                I32(42),
+                RecordTupleValueEnd(None, sval::Label::new("a"), sval::Index::new(0)),
+                RecordTupleEnd(None, Some(sval::Label::new("RecordTuple")), None),
+                TaggedEnd(
+                    Some(sval::tags::SCHEMA_VERSION),
+                    None,
+                    Some(sval::Index::new(2)),
+                ),
+            ]
+        })
+    }
+
     #[test]
     fn unlabeled_unindexed_data_tagged() {
         #[derive(Value)]
@@ -253,6 +533,76 @@ mod derive_struct {
         )
     }
 
+    #[test]
+    fn flattened_map() {
+        use std::collections::BTreeMap;
+
+        #[derive(Value)]
+        struct RecordTuple {
+            a: i32,
+            b: i32,
+            #[sval(flatten)]
+            extra: BTreeMap<String, i32>,
+        }
+
+        assert_tokens(
+            &RecordTuple {
+                a: 1,
+                b: 2,
+                extra: BTreeMap::from([("c".to_owned(), 3), ("d".to_owned(), 4)]),
+            },
+            {
+                use sval_test::Token::*;
+
+                &[
+                    RecordTupleBegin(None, Some(sval::Label::new("RecordTuple")), None, None),
+                    RecordTupleValueBegin(None, sval::Label::new("a"), sval::Index::new(0)),
+                    I32(1),
+                    RecordTupleValueEnd(None, sval::Label::new("a"), sval::Index::new(0)),
+                    RecordTupleValueBegin(None, sval::Label::new("b"), sval::Index::new(1)),
+                    I32(2),
+                    RecordTupleValueEnd(None, sval::Label::new("b"), sval::Index::new(1)),
+                    RecordTupleValueBegin(None, sval::Label::new("c"), sval::Index::new(2)),
+                    I32(3),
+                    RecordTupleValueEnd(None, sval::Label::new("c"), sval::Index::new(2)),
+                    RecordTupleValueBegin(None, sval::Label::new("d"), sval::Index::new(3)),
+                    I32(4),
+                    RecordTupleValueEnd(None, sval::Label::new("d"), sval::Index::new(3)),
+                    RecordTupleEnd(None, Some(sval::Label::new("RecordTuple")), None),
+                ]
+            },
+        );
+
+        // Complex keys are stringified into labels
+        #[derive(Value)]
+        struct ComplexKeys {
+            a: i32,
+            #[sval(flatten)]
+            extra: BTreeMap<(i32, bool), i32>,
+        }
+
+        assert_tokens(
+            &ComplexKeys {
+                a: 1,
+                extra: BTreeMap::from([((2, true), 3)]),
+            },
+            {
+                use sval_test::Token::*;
+
+                &[
+                    RecordTupleBegin(None, Some(sval::Label::new("ComplexKeys")), None, None),
+                    RecordTupleValueBegin(None, sval::Label::new("a"), sval::Index::new(0)),
+                    I32(1),
+                    RecordTupleValueEnd(None, sval::Label::new("a"), sval::Index::new(0)),
+                    RecordTupleValueBegin(None, sval::Label::new("2true"), sval::Index::new(1)),
+                    I32(3),
+                    RecordTupleValueEnd(None, sval::Label::new("2true"), sval::Index::new(1)),
+                    RecordTupleEnd(None, Some(sval::Label::new("ComplexKeys")), None),
+                ]
+            },
+        );
+    }
+
     #[test]
     fn tagged() {
         const CONTAINER: sval::Tag = sval::Tag::new("container");
@@ -324,18 +674,86 @@ mod derive_struct {
             ]
         })
     }
-}
-
-mod derive_tuple {
-    use super::*;
 
     #[test]
-    fn basic() {
+    fn transparent() {
         #[derive(Value)]
-        struct Tuple(i32, i32);
+        #[sval(transparent)]
+        struct Wrapper {
+            inner: i32,
+        }
 
-        assert_tokens(&Tuple(42, 43), {
-            use sval_test::Token::*;
+        let mut expected = sval_test::TokenBuf::new();
+        sval::stream(&mut expected, &42).unwrap();
+
+        let mut actual = sval_test::TokenBuf::new();
+        sval::stream(&mut actual, &Wrapper { inner: 42 }).unwrap();
+
+        assert_eq!(expected.as_tokens(), actual.as_tokens());
+
+        // Skipped fields don't count towards the single field
+        #[derive(Value)]
+        #[sval(transparent)]
+        struct Skipped<'a> {
+            #[sval(skip)]
+            #[allow(dead_code)]
+            skipped: i32,
+            inner: &'a str,
+        }
+
+        assert_tokens(
+            &Skipped {
+                skipped: 1,
+                inner: "a",
+            },
+            {
+                use sval_test::Token::*;
+
+                &[TextBegin(Some(1)), TextFragment("a"), TextEnd]
+            },
+        );
+
+        #[allow(dead_code)]
+        #[derive(Value)]
+        #[sval(transparent)]
+        struct Tuple(#[sval(skip)] i32, i32);
+
+        assert_tokens(&Tuple(1, 42), {
+            use sval_test::Token::*;
+
+            &[I32(42)]
+        });
+
+        // The tag of the field is forwarded
+        const CONTAINER: sval::Tag = sval::Tag::new("container");
+
+        #[derive(Value)]
+        #[sval(tag = CONTAINER)]
+        struct Tagged(i32);
+
+        #[derive(Value)]
+        #[sval(transparent)]
+        struct TaggedWrapper {
+            inner: Tagged,
+        }
+
+        assert_eq!(
+            Some(CONTAINER),
+            sval::Value::tag(&TaggedWrapper { inner: Tagged(42) })
+        );
+    }
+}
+
+mod derive_tuple {
+    use super::*;
+
+    #[test]
+    fn basic() {
+        #[derive(Value)]
+        struct Tuple(i32, i32);
+
+        assert_tokens(&Tuple(42, 43), {
+            use sval_test::Token::*;
 
             &[
                 TupleBegin(None, Some(sval::Label::new("Tuple")), None, Some(2)),
@@ -612,6 +1030,21 @@ mod derive_newtype {
             ]
         })
     }
+
+    #[test]
+    fn tag_hinted() {
+        const CONTAINER: sval::Tag = sval::Tag::new("container");
+
+        #[derive(Value)]
+        #[sval(transparent, tag_hint = CONTAINER)]
+        struct Tagged(i32);
+
+        assert_tokens(&Tagged(42), {
+            use sval_test::Token::*;
+
+            &[TagHint(CONTAINER), I32(42)]
+        })
+    }
 }
 
 mod derive_unit_struct {
@@ -1160,6 +1593,58 @@ mod derive_enum {
         });
     }
 
+    #[test]
+    fn versioned() {
+        #[derive(Value)]
+        #[sval(version = 1)]
+        enum Enum {
+            A,
+            B(i32),
+        }
+
+        assert_tokens(&Enum::A, {
+            use sval_test::Token::*;
+
+            &[
+                TaggedBegin(
+                    Some(sval::tags::SCHEMA_VERSION),
+                    None,
+                    Some(sval::Index::new(1)),
+                ),
+                EnumBegin(None, Some(sval::Label::new("Enum")), None),
+                Tag(None, Some(sval::Label::new("A")), Some(sval::Index::new(0))),
+                EnumEnd(None, Some(sval::Label::new("Enum")), None),
+                TaggedEnd(
+                    Some(sval::tags::SCHEMA_VERSION),
+                    None,
+                    Some(sval::Index::new(1)),
+                ),
+            ]
+        });
+
+        assert_tokens(&Enum::B(42), {
+            use sval_test::Token::*;
+
+            &[
+                TaggedBegin(
+                    Some(sval::tags::SCHEMA_VERSION),
+                    None,
+                    Some(sval::Index::new(1)),
+                ),
+                EnumBegin(None, Some(sval::Label::new("Enum")), None),
+                TaggedBegin(None, Some(sval::Label::new("B")), Some(sval::Index::new(1))),
+                I32(42),
+                TaggedEnd(None, Some(sval::Label::new("B")), Some(sval::Index::new(1))),
+                EnumEnd(None, Some(sval::Label::new("Enum")), None),
+                TaggedEnd(
+                    Some(sval::tags::SCHEMA_VERSION),
+                    None,
+                    Some(sval::Index::new(1)),
+                ),
+            ]
+        });
+    }
+
     #[test]
     fn discriminant() {
         #[derive(Value)]
@@ -1286,6 +1771,446 @@ mod derive_enum {
             ]
         });
     }
+
+    #[test]
+    fn as_container_variant() {
+        #[derive(Value)]
+        enum Enum {
+            #[sval(as = "record")]
+            Record { a: i32 },
+        }
+
+        assert_tokens(&Enum::Record { a: 42 }, {
+            use sval_test::Token::*;
+
+            &[
+                EnumBegin(None, Some(sval::Label::new("Enum")), None),
+                RecordBegin(
+                    None,
+                    Some(sval::Label::new("Record")),
+                    Some(sval::Index::new(0)),
+                    Some(1),
+                ),
+                RecordValueBegin(None, sval::Label::new("a")),
+                I32(42),
+                RecordValueEnd(None, sval::Label::new("a")),
+                RecordEnd(
+                    None,
+                    Some(sval::Label::new("Record")),
+                    Some(sval::Index::new(0)),
+                ),
+                EnumEnd(None, Some(sval::Label::new("Enum")), None),
+            ]
+        });
+    }
+
+    #[test]
+    fn transparent_variant() {
+        #[derive(Value)]
+        enum Enum {
+            Tag,
+            I32(i32),
+            #[sval(transparent)]
+            Bool(bool),
+            Record {
+                a: i32,
+            },
+        }
+
+        assert_tokens(&Enum::Bool(true), {
+            use sval_test::Token::*;
+
+            &[Bool(true)]
+        });
+
+        assert_tokens(&Enum::I32(42), {
+            use sval_test::Token::*;
+
+            &[
+                EnumBegin(None, Some(sval::Label::new("Enum")), None),
+                TaggedBegin(
+                    None,
+                    Some(sval::Label::new("I32")),
+                    Some(sval::Index::new(1)),
+                ),
+                I32(42),
+                TaggedEnd(
+                    None,
+                    Some(sval::Label::new("I32")),
+                    Some(sval::Index::new(1)),
+                ),
+                EnumEnd(None, Some(sval::Label::new("Enum")), None),
+            ]
+        });
+
+        assert_tokens(&Enum::Tag, {
+            use sval_test::Token::*;
+
+            &[
+                EnumBegin(None, Some(sval::Label::new("Enum")), None),
+                Tag(
+                    None,
+                    Some(sval::Label::new("Tag")),
+                    Some(sval::Index::new(0)),
+                ),
+                EnumEnd(None, Some(sval::Label::new("Enum")), None),
+            ]
+        });
+
+        // Variants after a transparent one keep their index
+        assert_tokens(&Enum::Record { a: 42 }, {
+            use sval_test::Token::*;
+
+            &[
+                EnumBegin(None, Some(sval::Label::new("Enum")), None),
+                RecordTupleBegin(
+                    None,
+                    Some(sval::Label::new("Record")),
+                    Some(sval::Index::new(3)),
+                    Some(1),
+                ),
+                RecordTupleValueBegin(None, sval::Label::new("a"), sval::Index::new(0)),
+                I32(42),
+                RecordTupleValueEnd(None, sval::Label::new("a"), sval::Index::new(0)),
+                RecordTupleEnd(
+                    None,
+                    Some(sval::Label::new("Record")),
+                    Some(sval::Index::new(3)),
+                ),
+                EnumEnd(None, Some(sval::Label::new("Enum")), None),
+            ]
+        });
+    }
+
+    #[test]
+    fn discriminant_as_value() {
+        #[derive(Value)]
+        #[sval(discriminant)]
+        #[repr(i16)]
+        enum Discriminant {
+            A = -1,
+            B = 7,
+            C,
+        }
+
+        assert_tokens(&Discriminant::A, {
+            use sval_test::Token::*;
+
+            &[I16(-1)]
+        });
+
+        assert_tokens(&Discriminant::B, {
+            use sval_test::Token::*;
+
+            &[I16(7)]
+        });
+
+        assert_tokens(&Discriminant::C, {
+            use sval_test::Token::*;
+
+            &[I16(8)]
+        });
+    }
+
+    #[test]
+    fn discriminant_as_value_usize() {
+        #[derive(Value)]
+        #[sval(discriminant)]
+        #[repr(usize)]
+        enum Discriminant {
+            A,
+            B,
+        }
+
+        assert_tokens(&Discriminant::A, {
+            use sval_test::Token::*;
+
+            &[U64(0)]
+        });
+
+        assert_tokens(&Discriminant::B, {
+            use sval_test::Token::*;
+
+            &[U64(1)]
+        });
+    }
+}
+
+mod derive_value_ref {
+    use sval_derive::ValueRef;
+
+    use super::*;
+
+    #[test]
+    fn borrowed_str() {
+        #[derive(Value, ValueRef)]
+        struct RecordTuple<'a> {
+            a: &'a str,
+            b: i32,
+        }
+
+        let a = String::from("borrowed");
+        let value = RecordTuple { a: &a, b: 42 };
+
+        let mut tokens = sval_test::TokenBuf::new();
+        sval_ref::stream_ref(&mut tokens, &value).unwrap();
+
+        assert_eq!(
+            {
+                use sval_test::Token::*;
+
+                &[
+                    RecordTupleBegin(None, Some(sval::Label::new("RecordTuple")), None, Some(2)),
+                    RecordTupleValueBegin(None, sval::Label::new("a"), sval::Index::new(0)),
+                    TextBegin(Some(8)),
+                    TextFragment("borrowed"),
+                    TextEnd,
+                    RecordTupleValueEnd(None, sval::Label::new("a"), sval::Index::new(0)),
+                    RecordTupleValueBegin(None, sval::Label::new("b"), sval::Index::new(1)),
+                    I32(42),
+                    RecordTupleValueEnd(None, sval::Label::new("b"), sval::Index::new(1)),
+                    RecordTupleEnd(None, Some(sval::Label::new("RecordTuple")), None),
+                ] as &[_]
+            },
+            tokens.as_tokens()
+        );
+    }
+
+    #[test]
+    fn newtype() {
+        #[derive(Value, ValueRef)]
+        struct Newtype<'a>(&'a str);
+
+        let a = String::from("borrowed");
+
+        let mut tokens = sval_test::TokenBuf::new();
+        sval_ref::stream_ref(&mut tokens, Newtype(&a)).unwrap();
+
+        assert_eq!(
+            {
+                use sval_test::Token::*;
+
+                &[
+                    TaggedBegin(None, Some(sval::Label::new("Newtype")), None),
+                    TextBegin(Some(8)),
+                    TextFragment("borrowed"),
+                    TextEnd,
+                    TaggedEnd(None, Some(sval::Label::new("Newtype")), None),
+                ] as &[_]
+            },
+            tokens.as_tokens()
+        );
+    }
+}
+
+mod records_as_maps {
+    use super::*;
+
+    fn tokens(value: &impl sval::Value) -> sval_test::TokenBuf<'_> {
+        let mut tokens = sval_test::TokenBuf::new();
+        sval::stream(&mut sval::records_as_maps(&mut tokens), value).unwrap();
+
+        tokens
+    }
+
+    #[test]
+    fn record() {
+        #[derive(Value)]
+        struct Record {
+            a: i32,
+            b: bool,
+        }
+
+        assert_eq!(
+            {
+                use sval_test::Token::*;
+
+                &[
+                    MapBegin(Some(2)),
+                    MapKeyBegin,
+                    TextBegin(Some(1)),
+                    TextFragmentComputed("a".to_owned()),
+                    TextEnd,
+                    MapKeyEnd,
+                    MapValueBegin,
+                    I32(42),
+                    MapValueEnd,
+                    MapKeyBegin,
+                    TextBegin(Some(1)),
+                    TextFragmentComputed("b".to_owned()),
+                    TextEnd,
+                    MapKeyEnd,
+                    MapValueBegin,
+                    Bool(true),
+                    MapValueEnd,
+                    MapEnd,
+                ] as &[_]
+            },
+            tokens(&Record { a: 42, b: true }).as_tokens()
+        );
+
+        sval_test::assert_forwards_num(sval::records_as_maps);
+    }
+
+    #[test]
+    fn tuple() {
+        #[derive(Value)]
+        struct Tuple(i32, bool);
+
+        assert_eq!(
+            {
+                use sval_test::Token::*;
+
+                &[
+                    MapBegin(Some(2)),
+                    MapKeyBegin,
+                    TextBegin(None),
+                    TextFragmentComputed("0".to_owned()),
+                    TextEnd,
+                    MapKeyEnd,
+                    MapValueBegin,
+                    I32(42),
+                    MapValueEnd,
+                    MapKeyBegin,
+                    TextBegin(None),
+                    TextFragmentComputed("1".to_owned()),
+                    TextEnd,
+                    MapKeyEnd,
+                    MapValueBegin,
+                    Bool(true),
+                    MapValueEnd,
+                    MapEnd,
+                ] as &[_]
+            },
+            tokens(&Tuple(42, true)).as_tokens()
+        );
+    }
+
+    #[test]
+    fn enum_variants() {
+        #[derive(Value)]
+        enum Enum {
+            Unit,
+            Newtype(i32),
+            Record { a: i32 },
+        }
+
+        assert_eq!(
+            {
+                use sval_test::Token::*;
+
+                &[
+                    MapBegin(Some(1)),
+                    MapKeyBegin,
+                    TextBegin(Some(4)),
+                    TextFragmentComputed("Unit".to_owned()),
+                    TextEnd,
+                    MapKeyEnd,
+                    MapValueBegin,
+                    Null,
+                    MapValueEnd,
+                    MapEnd,
+                ] as &[_]
+            },
+            tokens(&Enum::Unit).as_tokens()
+        );
+
+        assert_eq!(
+            {
+                use sval_test::Token::*;
+
+                &[
+                    MapBegin(Some(1)),
+                    MapKeyBegin,
+                    TextBegin(Some(7)),
+                    TextFragmentComputed("Newtype".to_owned()),
+                    TextEnd,
+                    MapKeyEnd,
+                    MapValueBegin,
+                    TaggedBegin(
+                        None,
+                        Some(sval::Label::new("Newtype")),
+                        Some(sval::Index::new(1)),
+                    ),
+                    I32(42),
+                    TaggedEnd(
+                        None,
+                        Some(sval::Label::new("Newtype")),
+                        Some(sval::Index::new(1)),
+                    ),
+                    MapValueEnd,
+                    MapEnd,
+                ] as &[_]
+            },
+            tokens(&Enum::Newtype(42)).as_tokens()
+        );
+
+        assert_eq!(
+            {
+                use sval_test::Token::*;
+
+                &[
+                    MapBegin(Some(1)),
+                    MapKeyBegin,
+                    TextBegin(Some(6)),
+                    TextFragmentComputed("Record".to_owned()),
+                    TextEnd,
+                    MapKeyEnd,
+                    MapValueBegin,
+                    MapBegin(Some(1)),
+                    MapKeyBegin,
+                    TextBegin(Some(1)),
+                    TextFragmentComputed("a".to_owned()),
+                    TextEnd,
+                    MapKeyEnd,
+                    MapValueBegin,
+                    I32(42),
+                    MapValueEnd,
+                    MapEnd,
+                    MapValueEnd,
+                    MapEnd,
+                ] as &[_]
+            },
+            tokens(&Enum::Record { a: 42 }).as_tokens()
+        );
+    }
+
+    #[test]
+    fn unlabeled_variant() {
+        struct Unlabeled;
+
+        impl sval::Value for Unlabeled {
+            fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(
+                &'sval self,
+                stream: &mut S,
+            ) -> sval::Result {
+                stream.enum_begin(None, None, None)?;
+                stream.tag(None, None, Some(&sval::Index::new(3)))?;
+                stream.enum_end(None, None, None)
+            }
+        }
+
+        // Variants without a label are keyed by their index
+        assert_eq!(
+            {
+                use sval_test::Token::*;
+
+                &[
+                    MapBegin(Some(1)),
+                    MapKeyBegin,
+                    TextBegin(None),
+                    TextFragmentComputed("3".to_owned()),
+                    TextEnd,
+                    MapKeyEnd,
+                    MapValueBegin,
+                    Null,
+                    MapValueEnd,
+                    MapEnd,
+                ] as &[_]
+            },
+            tokens(&Unlabeled).as_tokens()
+        );
+    }
 }
 
 #[test]
@@ -2,7 +2,7 @@ use std::collections::HashSet;
 
 use syn::{Attribute, Expr, ExprUnary, Lit, Path, UnOp};
 
-use crate::{index::IndexValue, label::LabelValue};
+use crate::{index::IndexValue, label::LabelValue, stream::RecordTupleTarget};
 
 /**
 The `tag` attribute.
@@ -72,6 +72,66 @@ impl RawAttribute for DataTagAttr {
     }
 }
 
+/**
+The `tag_hint` attribute.
+
+This attribute specifies a path to an `sval::Tag` to pass
+to `Stream::tag_hint` before streaming the annotated item.
+ */
+pub(crate) struct TagHintAttr;
+
+impl SvalAttribute for TagHintAttr {
+    type Result = syn::Path;
+
+    fn try_from_expr(&self, expr: &Expr) -> Option<Self::Result> {
+        match expr {
+            Expr::Lit(lit) => Some(self.from_lit(&lit.lit)),
+            Expr::Path(path) => Some(path.path.clone()),
+            _ => None,
+        }
+    }
+
+    fn from_lit(&self, lit: &Lit) -> Self::Result {
+        if let Lit::Str(ref s) = lit {
+            s.parse().expect("invalid value")
+        } else {
+            panic!("unexpected value")
+        }
+    }
+}
+
+impl RawAttribute for TagHintAttr {
+    fn key(&self) -> &str {
+        "tag_hint"
+    }
+}
+
+/**
+The `version` attribute.
+
+This attribute specifies a schema version number to wrap
+the annotated item in.
+*/
+pub(crate) struct VersionAttr;
+
+impl SvalAttribute for VersionAttr {
+    type Result = u64;
+
+    fn from_lit(&self, lit: &Lit) -> Self::Result {
+        if let Lit::Int(ref n) = lit {
+            n.base10_parse().expect("invalid value")
+        } else {
+            panic!("unexpected value")
+        }
+    }
+}
+
+impl RawAttribute for VersionAttr {
+    fn key(&self) -> &str {
+        "version"
+    }
+}
+
 /**
 The `label` attribute.
 
@@ -184,6 +244,32 @@ impl RawAttribute for SkipAttr {
     }
 }
 
+/**
+The `feature` attribute.
+
+This attribute specifies a Cargo feature that must be enabled
+for the annotated item to be streamed.
+*/
+pub(crate) struct FeatureAttr;
+
+impl SvalAttribute for FeatureAttr {
+    type Result = syn::LitStr;
+
+    fn from_lit(&self, lit: &Lit) -> Self::Result {
+        if let Lit::Str(ref s) = lit {
+            s.clone()
+        } else {
+            panic!("unexpected value")
+        }
+    }
+}
+
+impl RawAttribute for FeatureAttr {
+    fn key(&self) -> &str {
+        "feature"
+    }
+}
+
 /**
 The `unlabeled_fields` attribute.
 
@@ -234,6 +320,41 @@ impl RawAttribute for UnindexedFieldsAttr {
     }
 }
 
+/**
+The `as` attribute.
+
+This attribute selects the kind of value fields are streamed in,
+as an alternative to `unlabeled_fields` and `unindexed_fields`.
+*/
+pub(crate) struct AsAttr;
+
+impl SvalAttribute for AsAttr {
+    type Result = RecordTupleTarget;
+
+    fn from_lit(&self, lit: &Lit) -> Self::Result {
+        if let Lit::Str(ref s) = lit {
+            match &*s.value() {
+                "record_tuple" => RecordTupleTarget::RecordTuple,
+                "record" => RecordTupleTarget::Record,
+                "tuple" => RecordTupleTarget::Tuple,
+                "seq" => RecordTupleTarget::Seq,
+                value => panic!(
+                    "unsupported value `{}` for `as`, expected `record_tuple`, `record`, `tuple`, or `seq`",
+                    value
+                ),
+            }
+        } else {
+            panic!("unexpected value")
+        }
+    }
+}
+
+impl RawAttribute for AsAttr {
+    fn key(&self) -> &str {
+        "as"
+    }
+}
+
 /**
 The `unlabeled_variants` attribute.
 
@@ -309,11 +430,38 @@ impl RawAttribute for DynamicAttr {
     }
 }
 
+/**
+The `discriminant` attribute.
+
+This attribute signals that an enum should stream its variants
+as their integer discriminants.
+*/
+pub(crate) struct DiscriminantAttr;
+
+impl SvalAttribute for DiscriminantAttr {
+    type Result = bool;
+
+    fn from_lit(&self, lit: &Lit) -> Self::Result {
+        if let Lit::Bool(ref b) = lit {
+            b.value
+        } else {
+            panic!("unexpected value")
+        }
+    }
+}
+
+impl RawAttribute for DiscriminantAttr {
+    fn key(&self) -> &str {
+        "discriminant"
+    }
+}
+
 /**
 The `transparent` attribute.
 
 This attribute signals that a newtype should stream its inner field
-without wrapping it in a tag.
+without wrapping it in a tag, or that a struct with a single field should
+stream that field without wrapping it in a record.
 */
 pub(crate) struct TransparentAttr;
 
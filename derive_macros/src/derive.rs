@@ -4,13 +4,52 @@ mod derive_struct;
 mod derive_unit_struct;
 mod derive_void;
 
-use syn::{Data, DataEnum, DataStruct, DeriveInput, Fields};
+use syn::{Data, DataEnum, DataStruct, DeriveInput, Fields, Generics};
+
+use crate::bound;
 
 use self::{
     derive_enum::*, derive_newtype::*, derive_struct::*, derive_unit_struct::*, derive_void::*,
 };
 
+/**
+The trait to derive an implementation of.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ValueTrait {
+    /**
+    `sval::Value`, where the `'sval` lifetime comes from `&'sval self`.
+    */
+    Value,
+    /**
+    `sval_ref::ValueRef<'sval>`, where the `'sval` lifetime comes from the type itself.
+    */
+    #[cfg(feature = "ref")]
+    ValueRef,
+}
+
+impl ValueTrait {
+    pub(crate) fn quote_stream_value(&self, value: &syn::Ident) -> proc_macro2::TokenStream {
+        match self {
+            ValueTrait::Value => quote!(stream.value(#value)),
+            #[cfg(feature = "ref")]
+            ValueTrait::ValueRef => {
+                quote!(sval_derive::extensions::value_ref::stream_ref(&mut *stream, #value))
+            }
+        }
+    }
+}
+
 pub(crate) fn derive(input: DeriveInput) -> proc_macro2::TokenStream {
+    derive_trait(input, ValueTrait::Value)
+}
+
+#[cfg(feature = "ref")]
+pub(crate) fn derive_ref(input: DeriveInput) -> proc_macro2::TokenStream {
+    derive_trait(input, ValueTrait::ValueRef)
+}
+
+fn derive_trait(input: DeriveInput, value_trait: ValueTrait) -> proc_macro2::TokenStream {
     match &input.data {
         Data::Struct(DataStruct {
             fields: Fields::Unit,
@@ -18,7 +57,7 @@ pub(crate) fn derive(input: DeriveInput) -> proc_macro2::TokenStream {
         }) => {
             let attrs = UnitStructAttrs::from_attrs(&input.attrs);
 
-            derive_unit_struct(&input.ident, &input.generics, &attrs)
+            derive_unit_struct(value_trait, &input.ident, &input.generics, &attrs)
         }
         Data::Struct(DataStruct {
             fields: Fields::Unnamed(ref fields),
@@ -26,35 +65,64 @@ pub(crate) fn derive(input: DeriveInput) -> proc_macro2::TokenStream {
         }) if fields.unnamed.len() == 1 => {
             let attrs = NewtypeAttrs::from_attrs(&input.attrs);
 
-            derive_newtype(&input.ident, &input.generics, &fields.unnamed[0], &attrs)
+            derive_newtype(
+                value_trait,
+                &input.ident,
+                &input.generics,
+                &fields.unnamed[0],
+                &attrs,
+            )
         }
         Data::Struct(DataStruct { ref fields, .. }) => {
             let attrs = StructAttrs::from_attrs(&input.attrs);
 
-            derive_struct(&input.ident, &input.generics, fields, &attrs)
+            derive_struct(value_trait, &input.ident, &input.generics, fields, &attrs)
         }
         Data::Enum(DataEnum { ref variants, .. }) if variants.len() == 0 => {
             let attrs = VoidAttrs::from_attrs(&input.attrs);
 
-            derive_void(&input.ident, &input.generics, &attrs)
+            derive_void(value_trait, &input.ident, &input.generics, &attrs)
         }
         Data::Enum(DataEnum { variants, .. }) => {
             let attrs = EnumAttrs::from_attrs(&input.attrs);
 
-            derive_enum(&input.ident, &input.generics, variants.iter(), &attrs)
+            derive_enum(
+                value_trait,
+                &input.ident,
+                &input.generics,
+                variants.iter(),
+                &attrs,
+            )
         }
         _ => panic!("unsupported container type"),
     }
 }
 
 fn impl_tokens(
-    impl_generics: syn::ImplGenerics,
+    value_trait: ValueTrait,
+    ident: &syn::Ident,
+    generics: &Generics,
+    stream_body: proc_macro2::TokenStream,
+    tag_body: Option<proc_macro2::TokenStream>,
+) -> proc_macro2::TokenStream {
+    match value_trait {
+        ValueTrait::Value => impl_value_tokens(ident, generics, stream_body, tag_body),
+        #[cfg(feature = "ref")]
+        ValueTrait::ValueRef => impl_value_ref_tokens(ident, generics, stream_body),
+    }
+}
+
+fn impl_value_tokens(
     ident: &syn::Ident,
-    ty_generics: syn::TypeGenerics,
-    bounded_where_clause: &syn::WhereClause,
+    generics: &Generics,
     stream_body: proc_macro2::TokenStream,
     tag_body: Option<proc_macro2::TokenStream>,
 ) -> proc_macro2::TokenStream {
+    let (impl_generics, ty_generics, _) = generics.split_for_impl();
+
+    let bound = parse_quote!(sval::Value);
+    let bounded_where_clause = bound::where_clause_with_bound(generics, bound);
+
     let stream_fn = quote!(
         fn stream<'sval, __SvalStream: sval::Stream<'sval> + ?Sized>(&'sval self, stream: &mut __SvalStream) -> sval::Result {
             #stream_body
@@ -83,3 +151,45 @@ fn impl_tokens(
         };
     }
 }
+
+#[cfg(feature = "ref")]
+fn impl_value_ref_tokens(
+    ident: &syn::Ident,
+    generics: &Generics,
+    stream_body: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let (_, ty_generics, _) = generics.split_for_impl();
+
+    // The `'sval` lifetime of the impl is the single lifetime parameter
+    // on the type, if there is one. If the type doesn't have any lifetime
+    // parameters then it can implement `ValueRef` for any lifetime
+    let mut lifetimes = generics.lifetimes();
+    let (lifetime, impl_generics): (syn::Lifetime, Generics) =
+        match (lifetimes.next(), lifetimes.next()) {
+            (Some(lifetime), None) => (lifetime.lifetime.clone(), generics.clone()),
+            (None, _) => {
+                let mut impl_generics = generics.clone();
+                impl_generics.params.insert(0, parse_quote!('sval));
+
+                (parse_quote!('sval), impl_generics)
+            }
+            _ => panic!("`ValueRef` can only be derived for types with at most one lifetime"),
+        };
+
+    let bound = quote!(sval_derive::extensions::value_ref::ValueRef<#lifetime>);
+    let bounded_where_clause = bound::where_clause_with_bound(&impl_generics, bound);
+
+    let (impl_generics, _, _) = impl_generics.split_for_impl();
+
+    quote! {
+        const _: () = {
+            extern crate sval;
+
+            impl #impl_generics sval_derive::extensions::value_ref::ValueRef<#lifetime> for #ident #ty_generics #bounded_where_clause {
+                fn stream_ref<__SvalStream: sval::Stream<#lifetime> + ?Sized>(&self, stream: &mut __SvalStream) -> sval::Result {
+                    #stream_body
+                }
+            }
+        };
+    }
+}
@@ -2,24 +2,31 @@ use syn::{Attribute, Fields, Generics, Ident, Path, Variant};
 
 use crate::{
     attr::{self, SvalAttribute},
-    bound,
     derive::{
         derive_newtype::NewtypeAttrs, derive_struct::StructAttrs,
-        derive_unit_struct::UnitStructAttrs, impl_tokens,
+        derive_unit_struct::UnitStructAttrs, impl_tokens, ValueTrait,
     },
     index::{quote_optional_index, Index, IndexAllocator, IndexValue},
     label::{label_or_ident, quote_optional_label, LabelValue},
-    stream::{stream_newtype, stream_record_tuple, stream_tag, RecordTupleTarget},
-    tag::{quote_optional_tag, quote_optional_tag_owned},
+    stream::{
+        stream_newtype, stream_record_tuple, stream_tag, RecordTupleContainer, RecordTupleTarget,
+    },
+    tag::{
+        quote_optional_tag, quote_optional_tag_hint, quote_optional_tag_owned,
+        quote_optional_version, quote_optional_version_tag,
+    },
 };
 
 pub(crate) struct EnumAttrs {
     tag: Option<Path>,
+    tag_hint: Option<Path>,
+    version: Option<u64>,
     label: Option<LabelValue>,
     index: Option<IndexValue>,
     unlabeled_variants: bool,
     unindexed_variants: bool,
     dynamic: bool,
+    discriminant: Option<Ident>,
 }
 
 impl EnumAttrs {
@@ -28,9 +35,12 @@ impl EnumAttrs {
             "enum",
             &[
                 &attr::TagAttr,
+                &attr::TagHintAttr,
+                &attr::VersionAttr,
                 &attr::LabelAttr,
                 &attr::IndexAttr,
                 &attr::DynamicAttr,
+                &attr::DiscriminantAttr,
                 &attr::UnlabeledVariantsAttr,
                 &attr::UnindexedVariantsAttr,
             ],
@@ -38,6 +48,8 @@ impl EnumAttrs {
         );
 
         let tag = attr::get_unchecked("enum", attr::TagAttr, attrs);
+        let tag_hint = attr::get_unchecked("enum", attr::TagHintAttr, attrs);
+        let version = attr::get_unchecked("enum", attr::VersionAttr, attrs);
         let label = attr::get_unchecked("enum", attr::LabelAttr, attrs);
         let index = attr::get_unchecked("enum", attr::IndexAttr, attrs);
         let unlabeled_variants =
@@ -45,6 +57,8 @@ impl EnumAttrs {
         let unindexed_variants =
             attr::get_unchecked("enum", attr::UnindexedVariantsAttr, attrs).unwrap_or(false);
         let dynamic = attr::get_unchecked("enum", attr::DynamicAttr, attrs).unwrap_or(false);
+        let discriminant =
+            attr::get_unchecked("enum", attr::DiscriminantAttr, attrs).unwrap_or(false);
 
         if dynamic {
             assert!(tag.is_none(), "dynamic enums can't have tags");
@@ -55,13 +69,39 @@ impl EnumAttrs {
             assert!(!unindexed_variants, "dynamic enums don't have variants");
         }
 
+        let discriminant = if discriminant {
+            assert!(!dynamic, "discriminant enums can't be dynamic");
+            assert!(tag.is_none(), "discriminant enums can't have tags");
+            assert!(label.is_none(), "discriminant enums can't have labels");
+            assert!(index.is_none(), "discriminant enums can't have indexes");
+
+            assert!(
+                !unlabeled_variants,
+                "discriminant enums don't have labeled variants"
+            );
+            assert!(
+                !unindexed_variants,
+                "discriminant enums don't have indexed variants"
+            );
+
+            Some(
+                repr_int(attrs)
+                    .expect("discriminant enums need an integer `#[repr]`, like `#[repr(i32)]`"),
+            )
+        } else {
+            None
+        };
+
         EnumAttrs {
             tag,
+            tag_hint,
+            version,
             label,
             index,
             unlabeled_variants,
             unindexed_variants,
             dynamic,
+            discriminant,
         }
     }
 
@@ -69,6 +109,14 @@ impl EnumAttrs {
         self.tag.as_ref()
     }
 
+    pub(crate) fn tag_hint(&self) -> Option<&Path> {
+        self.tag_hint.as_ref()
+    }
+
+    pub(crate) fn version(&self) -> Option<u64> {
+        self.version
+    }
+
     pub(crate) fn label(&self) -> Option<LabelValue> {
         self.label.clone()
     }
@@ -78,16 +126,37 @@ impl EnumAttrs {
     }
 }
 
+fn repr_int(attrs: &[Attribute]) -> Option<Ident> {
+    let mut repr = None;
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("repr")) {
+        attr.parse_nested_meta(|meta| {
+            if let Some(ident) = meta.path.get_ident() {
+                match &*ident.to_string() {
+                    "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16" | "u32"
+                    | "u64" | "u128" | "usize" => repr = Some(ident.clone()),
+                    _ => (),
+                }
+            }
+
+            Ok(())
+        })
+        .expect("failed to parse `#[repr]`");
+    }
+
+    repr
+}
+
 pub(crate) fn derive_enum<'a>(
+    value_trait: ValueTrait,
     ident: &Ident,
     generics: &Generics,
     variants: impl Iterator<Item = &'a Variant> + 'a,
     attrs: &EnumAttrs,
 ) -> proc_macro2::TokenStream {
-    let (impl_generics, ty_generics, _) = generics.split_for_impl();
-
-    let bound = parse_quote!(sval::Value);
-    let bounded_where_clause = bound::where_clause_with_bound(&generics, bound);
+    if let Some(ref repr) = attrs.discriminant {
+        return derive_enum_discriminant(value_trait, ident, generics, variants, attrs, repr);
+    }
 
     let mut variant_match_arms = Vec::new();
     let mut index_allocator = IndexAllocator::new();
@@ -119,6 +188,9 @@ pub(crate) fn derive_enum<'a>(
     };
 
     let variant_transparent = attrs.dynamic;
+    let mut transparent_variants = Vec::new();
+
+    let tag_hint = quote_optional_tag_hint(attrs.tag_hint());
 
     for variant in variants {
         // Only allow a subset of attributes on enum variants
@@ -131,12 +203,18 @@ pub(crate) fn derive_enum<'a>(
                 &attr::TagAttr,
                 &attr::LabelAttr,
                 &attr::IndexAttr,
+                &attr::TransparentAttr,
+                &attr::AsAttr,
                 &attr::UnlabeledFieldsAttr,
                 &attr::UnindexedFieldsAttr,
             ],
             &variant.attrs,
         );
 
+        let transparent =
+            attr::get_unchecked("enum variant", attr::TransparentAttr, &variant.attrs)
+                .unwrap_or(false);
+
         let discriminant = variant
             .discriminant
             .as_ref()
@@ -144,17 +222,31 @@ pub(crate) fn derive_enum<'a>(
 
         let variant_ident = &variant.ident;
 
+        if transparent {
+            assert!(
+                !matches!(variant.fields, Fields::Unit),
+                "unit variants can't be transparent because they don't have a value to stream"
+            );
+            assert!(
+                matches!(variant.fields, Fields::Unnamed(ref fields) if fields.unnamed.len() == 1),
+                "only newtype variants can be transparent"
+            );
+
+            transparent_variants.push(variant_ident);
+        }
+
         variant_match_arms.push(match variant.fields {
             Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => {
                 let attrs = NewtypeAttrs::from_attrs(&variant.attrs);
 
                 stream_newtype(
+                    value_trait,
                     quote!(#ident :: #variant_ident),
                     &fields.unnamed[0],
                     attrs.tag(),
                     variant_label(attrs.label(), variant_ident),
                     variant_index(attrs.index(), discriminant),
-                    variant_transparent,
+                    variant_transparent || attrs.transparent(),
                 )
             }
             Fields::Unit => {
@@ -169,30 +261,38 @@ pub(crate) fn derive_enum<'a>(
             }
             Fields::Named(ref fields) => {
                 let attrs = StructAttrs::from_attrs(&variant.attrs);
+                attrs.ensure_labeled_fields(fields.named.iter());
 
                 stream_record_tuple(
+                    value_trait,
                     quote!(#ident :: #variant_ident),
                     fields.named.iter(),
                     RecordTupleTarget::named_fields(),
-                    attrs.tag(),
-                    variant_label(attrs.label(), variant_ident),
-                    variant_index(attrs.index(), discriminant),
-                    attrs.unlabeled_fields(),
-                    attrs.unindexed_fields(),
+                    RecordTupleContainer {
+                        tag: attrs.tag(),
+                        label: variant_label(attrs.label(), variant_ident),
+                        index: variant_index(attrs.index(), discriminant),
+                        unlabeled_fields: attrs.unlabeled_fields(),
+                        unindexed_fields: attrs.unindexed_fields(),
+                    },
                 )
             }
             Fields::Unnamed(ref fields) => {
                 let attrs = StructAttrs::from_attrs(&variant.attrs);
+                attrs.ensure_labeled_fields(fields.unnamed.iter());
 
                 stream_record_tuple(
+                    value_trait,
                     quote!(#ident :: #variant_ident),
                     fields.unnamed.iter(),
                     RecordTupleTarget::unnamed_fields(),
-                    attrs.tag(),
-                    variant_label(attrs.label(), variant_ident),
-                    variant_index(attrs.index(), discriminant),
-                    attrs.unlabeled_fields(),
-                    attrs.unindexed_fields(),
+                    RecordTupleContainer {
+                        tag: attrs.tag(),
+                        label: variant_label(attrs.label(), variant_ident),
+                        index: variant_index(attrs.index(), discriminant),
+                        unlabeled_fields: attrs.unlabeled_fields(),
+                        unindexed_fields: attrs.unindexed_fields(),
+                    },
                 )
             }
         });
@@ -200,18 +300,22 @@ pub(crate) fn derive_enum<'a>(
 
     if attrs.dynamic {
         impl_tokens(
-            impl_generics,
+            value_trait,
             ident,
-            ty_generics,
-            &bounded_where_clause,
-            quote!({
-                match self {
-                    #(#variant_match_arms)*
-                }
+            generics,
+            quote_optional_version(
+                attrs.version(),
+                quote!({
+                    #tag_hint
 
-                Ok(())
-            }),
-            None,
+                    match self {
+                        #(#variant_match_arms)*
+                    }
+
+                    Ok(())
+                }),
+            ),
+            quote_optional_version_tag(attrs.version(), None),
         )
     } else {
         let tag = quote_optional_tag(attrs.tag());
@@ -219,21 +323,106 @@ pub(crate) fn derive_enum<'a>(
         let label = quote_optional_label(Some(label_or_ident(attrs.label(), ident)));
         let index = quote_optional_index(attrs.index());
 
+        // Transparent variants are streamed without the enum wrapping them
+        let (enum_begin, enum_end) = if transparent_variants.is_empty() {
+            (
+                quote!(stream.enum_begin(#tag, #label, #index)?;),
+                quote!(stream.enum_end(#tag, #label, #index)),
+            )
+        } else {
+            (
+                quote!(
+                    let __sval_transparent = matches!(self, #(#ident :: #transparent_variants { .. })|*);
+
+                    if !__sval_transparent {
+                        stream.enum_begin(#tag, #label, #index)?;
+                    }
+                ),
+                quote!(
+                    if !__sval_transparent {
+                        stream.enum_end(#tag, #label, #index)
+                    } else {
+                        Ok(())
+                    }
+                ),
+            )
+        };
+
         impl_tokens(
-            impl_generics,
+            value_trait,
             ident,
-            ty_generics,
-            &bounded_where_clause,
+            generics,
+            quote_optional_version(
+                attrs.version(),
+                quote!({
+                    #tag_hint
+
+                    #enum_begin
+
+                    match self {
+                        #(#variant_match_arms)*
+                    }
+
+                    #enum_end
+                }),
+            ),
+            quote_optional_version_tag(attrs.version(), Some(tag_owned)),
+        )
+    }
+}
+
+fn derive_enum_discriminant<'a>(
+    value_trait: ValueTrait,
+    ident: &Ident,
+    generics: &Generics,
+    variants: impl Iterator<Item = &'a Variant> + 'a,
+    attrs: &EnumAttrs,
+    repr: &Ident,
+) -> proc_macro2::TokenStream {
+    let mut variant_match_arms = Vec::new();
+
+    for variant in variants {
+        // Variants are streamed as plain integers, so there's nothing
+        // for attributes on them to apply to
+        attr::check("discriminant enum variant", &[], &variant.attrs);
+
+        let variant_ident = &variant.ident;
+
+        assert!(
+            matches!(variant.fields, Fields::Unit),
+            "discriminant enums can only have unit variants, but `{}` has fields",
+            variant_ident
+        );
+
+        variant_match_arms
+            .push(quote!(#ident :: #variant_ident => #ident :: #variant_ident as #repr,));
+    }
+
+    // `isize` and `usize` don't have their own methods on `Stream`
+    let (stream_method, cast) = match &*repr.to_string() {
+        "isize" => (quote!(i64), quote!(as i64)),
+        "usize" => (quote!(u64), quote!(as u64)),
+        _ => (quote!(#repr), quote!()),
+    };
+
+    let tag_hint = quote_optional_tag_hint(attrs.tag_hint());
+
+    impl_tokens(
+        value_trait,
+        ident,
+        generics,
+        quote_optional_version(
+            attrs.version(),
             quote!({
-                stream.enum_begin(#tag, #label, #index)?;
+                #tag_hint
 
-                match self {
+                let discriminant = match self {
                     #(#variant_match_arms)*
-                }
+                };
 
-                stream.enum_end(#tag, #label, #index)
+                stream.#stream_method(discriminant #cast)
             }),
-            Some(tag_owned),
-        )
-    }
+        ),
+        quote_optional_version_tag(attrs.version(), None),
+    )
 }
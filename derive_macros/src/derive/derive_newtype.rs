@@ -1,16 +1,21 @@
 use syn::{Attribute, Field, Generics, Ident, Path};
 
 use crate::{
-    attr, bound,
-    derive::impl_tokens,
+    attr,
+    derive::{impl_tokens, ValueTrait},
     index::{Index, IndexAllocator, IndexValue},
     label::{label_or_ident, LabelValue},
     stream::stream_newtype,
-    tag::quote_optional_tag_owned,
+    tag::{
+        quote_optional_tag_hint, quote_optional_tag_owned, quote_optional_version,
+        quote_optional_version_tag,
+    },
 };
 
 pub(crate) struct NewtypeAttrs {
     tag: Option<Path>,
+    tag_hint: Option<Path>,
+    version: Option<u64>,
     label: Option<LabelValue>,
     index: Option<IndexValue>,
     transparent: bool,
@@ -22,6 +27,8 @@ impl NewtypeAttrs {
             "newtype",
             &[
                 &attr::TagAttr,
+                &attr::TagHintAttr,
+                &attr::VersionAttr,
                 &attr::LabelAttr,
                 &attr::IndexAttr,
                 &attr::TransparentAttr,
@@ -30,6 +37,8 @@ impl NewtypeAttrs {
         );
 
         let tag = attr::get_unchecked("newtype", attr::TagAttr, attrs);
+        let tag_hint = attr::get_unchecked("newtype", attr::TagHintAttr, attrs);
+        let version = attr::get_unchecked("newtype", attr::VersionAttr, attrs);
         let label = attr::get_unchecked("newtype", attr::LabelAttr, attrs);
         let index = attr::get_unchecked("newtype", attr::IndexAttr, attrs);
         let transparent =
@@ -43,6 +52,8 @@ impl NewtypeAttrs {
 
         NewtypeAttrs {
             tag,
+            tag_hint,
+            version,
             label,
             index,
             transparent,
@@ -53,6 +64,14 @@ impl NewtypeAttrs {
         self.tag.as_ref()
     }
 
+    pub(crate) fn tag_hint(&self) -> Option<&Path> {
+        self.tag_hint.as_ref()
+    }
+
+    pub(crate) fn version(&self) -> Option<u64> {
+        self.version
+    }
+
     pub(crate) fn label(&self) -> Option<LabelValue> {
         self.label.clone()
     }
@@ -67,17 +86,14 @@ impl NewtypeAttrs {
 }
 
 pub(crate) fn derive_newtype<'a>(
+    value_trait: ValueTrait,
     ident: &Ident,
     generics: &Generics,
     field: &Field,
     attrs: &NewtypeAttrs,
 ) -> proc_macro2::TokenStream {
-    let (impl_generics, ty_generics, _) = generics.split_for_impl();
-
-    let bound = parse_quote!(sval::Value);
-    let bounded_where_clause = bound::where_clause_with_bound(&generics, bound);
-
     let match_arm = stream_newtype(
+        value_trait,
         quote!(#ident),
         field,
         attrs.tag(),
@@ -87,19 +103,24 @@ pub(crate) fn derive_newtype<'a>(
     );
 
     let tag = quote_optional_tag_owned(attrs.tag());
+    let tag_hint = quote_optional_tag_hint(attrs.tag_hint());
 
     impl_tokens(
-        impl_generics,
+        value_trait,
         ident,
-        ty_generics,
-        &bounded_where_clause,
-        quote!({
-            match self {
-                #match_arm
-            }
-
-            Ok(())
-        }),
-        Some(tag),
+        generics,
+        quote_optional_version(
+            attrs.version(),
+            quote!({
+                #tag_hint
+
+                match self {
+                    #match_arm
+                }
+
+                Ok(())
+            }),
+        ),
+        quote_optional_version_tag(attrs.version(), Some(tag)),
     )
 }
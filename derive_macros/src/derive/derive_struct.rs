@@ -1,20 +1,28 @@
-use syn::{Attribute, Fields, Generics, Ident, Path};
+use proc_macro2::Span;
+use syn::{Attribute, Field, Fields, Generics, Ident, Member, Path};
 
 use crate::{
-    attr, bound,
-    derive::impl_tokens,
+    attr,
+    derive::{impl_tokens, ValueTrait},
     index::{Index, IndexAllocator, IndexValue},
     label::{label_or_ident, LabelValue},
-    stream::{stream_record_tuple, RecordTupleTarget},
-    tag::quote_optional_tag_owned,
+    stream::{stream_record_tuple, RecordTupleContainer, RecordTupleTarget},
+    tag::{
+        quote_optional_tag_hint, quote_optional_tag_owned, quote_optional_version,
+        quote_optional_version_tag,
+    },
 };
 
 pub(crate) struct StructAttrs {
     tag: Option<Path>,
+    tag_hint: Option<Path>,
+    version: Option<u64>,
     label: Option<LabelValue>,
     index: Option<IndexValue>,
     unlabeled_fields: bool,
     unindexed_fields: bool,
+    labeled_fields: bool,
+    transparent: bool,
 }
 
 impl StructAttrs {
@@ -23,29 +31,72 @@ impl StructAttrs {
             "struct",
             &[
                 &attr::TagAttr,
+                &attr::TagHintAttr,
+                &attr::VersionAttr,
                 &attr::LabelAttr,
                 &attr::IndexAttr,
+                &attr::AsAttr,
                 &attr::UnlabeledFieldsAttr,
                 &attr::UnindexedFieldsAttr,
+                &attr::TransparentAttr,
             ],
             attrs,
         );
 
         let tag = attr::get_unchecked("struct", attr::TagAttr, attrs);
+        let tag_hint = attr::get_unchecked("struct", attr::TagHintAttr, attrs);
+        let version = attr::get_unchecked("struct", attr::VersionAttr, attrs);
         let label = attr::get_unchecked("struct", attr::LabelAttr, attrs);
         let index = attr::get_unchecked("struct", attr::IndexAttr, attrs);
 
-        let unlabeled_fields =
-            attr::get_unchecked("struct", attr::UnlabeledFieldsAttr, attrs).unwrap_or(false);
-        let unindexed_fields =
-            attr::get_unchecked("struct", attr::UnindexedFieldsAttr, attrs).unwrap_or(false);
+        let unlabeled_fields = attr::get_unchecked("struct", attr::UnlabeledFieldsAttr, attrs);
+        let unindexed_fields = attr::get_unchecked("struct", attr::UnindexedFieldsAttr, attrs);
+
+        let (unlabeled_fields, unindexed_fields, labeled_fields) =
+            match attr::get_unchecked("struct", attr::AsAttr, attrs) {
+                Some(target) => {
+                    assert!(
+                        unlabeled_fields.is_none() && unindexed_fields.is_none(),
+                        "`as` can't be used with `unlabeled_fields` or `unindexed_fields`"
+                    );
+
+                    match target {
+                        RecordTupleTarget::RecordTuple => (false, false, true),
+                        RecordTupleTarget::Record => (false, true, true),
+                        RecordTupleTarget::Tuple => (true, false, false),
+                        RecordTupleTarget::Seq => (true, true, false),
+                    }
+                }
+                None => (
+                    unlabeled_fields.unwrap_or(false),
+                    unindexed_fields.unwrap_or(false),
+                    false,
+                ),
+            };
+
+        let transparent =
+            attr::get_unchecked("struct", attr::TransparentAttr, attrs).unwrap_or(false);
+
+        if transparent {
+            assert!(tag.is_none(), "transparent values cannot have tags");
+            assert!(label.is_none(), "transparent values cannot have labels");
+            assert!(index.is_none(), "transparent values cannot have indexes");
+            assert!(
+                !unlabeled_fields && !unindexed_fields && !labeled_fields,
+                "transparent values don't stream their fields as a record or tuple"
+            );
+        }
 
         StructAttrs {
             tag,
+            tag_hint,
+            version,
             label,
             index,
             unlabeled_fields,
             unindexed_fields,
+            labeled_fields,
+            transparent,
         }
     }
 
@@ -53,6 +104,14 @@ impl StructAttrs {
         self.tag.as_ref()
     }
 
+    pub(crate) fn tag_hint(&self) -> Option<&Path> {
+        self.tag_hint.as_ref()
+    }
+
+    pub(crate) fn version(&self) -> Option<u64> {
+        self.version
+    }
+
     pub(crate) fn label(&self) -> Option<LabelValue> {
         self.label.clone()
     }
@@ -68,50 +127,127 @@ impl StructAttrs {
     pub(crate) fn unindexed_fields(&self) -> bool {
         self.unindexed_fields
     }
+
+    pub(crate) fn transparent(&self) -> bool {
+        self.transparent
+    }
+
+    /**
+    Ensure fields have labels if they're streamed `as` a record.
+
+    Fields without a label would otherwise fall back to a tuple or sequence.
+    */
+    pub(crate) fn ensure_labeled_fields<'a>(&self, fields: impl Iterator<Item = &'a Field>) {
+        if !self.labeled_fields {
+            return;
+        }
+
+        for field in fields {
+            let skip =
+                attr::get_unchecked("struct field", attr::SkipAttr, &field.attrs).unwrap_or(false);
+            let label = attr::get_unchecked("struct field", attr::LabelAttr, &field.attrs);
+
+            assert!(
+                skip || field.ident.is_some() || label.is_some(),
+                "fields of values streamed as records need labels"
+            );
+        }
+    }
 }
 
 pub(crate) fn derive_struct<'a>(
+    value_trait: ValueTrait,
     ident: &Ident,
     generics: &Generics,
     fields: &Fields,
     attrs: &StructAttrs,
 ) -> proc_macro2::TokenStream {
-    let (impl_generics, ty_generics, _) = generics.split_for_impl();
-
-    let bound = parse_quote!(sval::Value);
-    let bounded_where_clause = bound::where_clause_with_bound(&generics, bound);
-
     let (fields, target) = match fields {
         Fields::Named(ref fields) => (&fields.named, RecordTupleTarget::named_fields()),
         Fields::Unnamed(ref fields) => (&fields.unnamed, RecordTupleTarget::unnamed_fields()),
         _ => unreachable!(),
     };
 
-    let match_arm = stream_record_tuple(
-        quote!(#ident),
-        fields.iter(),
-        target,
-        attrs.tag(),
-        Some(label_or_ident(attrs.label(), ident)),
-        attrs.index(),
-        attrs.unlabeled_fields(),
-        attrs.unindexed_fields(),
-    );
+    attrs.ensure_labeled_fields(fields.iter());
 
     let tag = quote_optional_tag_owned(attrs.tag());
+    let tag_hint = quote_optional_tag_hint(attrs.tag_hint());
+
+    let (match_arm, tag) = if attrs.transparent() {
+        stream_transparent_field(value_trait, quote!(#ident), fields.iter())
+    } else {
+        let match_arm = stream_record_tuple(
+            value_trait,
+            quote!(#ident),
+            fields.iter(),
+            target,
+            RecordTupleContainer {
+                tag: attrs.tag(),
+                label: Some(label_or_ident(attrs.label(), ident)),
+                index: attrs.index(),
+                unlabeled_fields: attrs.unlabeled_fields(),
+                unindexed_fields: attrs.unindexed_fields(),
+            },
+        );
+
+        (match_arm, tag)
+    };
 
     impl_tokens(
-        impl_generics,
+        value_trait,
         ident,
-        ty_generics,
-        &bounded_where_clause,
-        quote!({
-            match self {
-                #match_arm
-            }
-
-            Ok(())
+        generics,
+        quote_optional_version(
+            attrs.version(),
+            quote!({
+                #tag_hint
+
+                match self {
+                    #match_arm
+                }
+
+                Ok(())
+            }),
+        ),
+        quote_optional_version_tag(attrs.version(), Some(tag)),
+    )
+}
+
+/**
+Stream the only field of a struct that isn't skipped, without wrapping it in a record.
+
+This also returns the body of `tag`, which forwards to the field's own tag.
+*/
+fn stream_transparent_field<'a>(
+    value_trait: ValueTrait,
+    path: proc_macro2::TokenStream,
+    fields: impl Iterator<Item = &'a Field>,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    let mut streamed = fields.enumerate().filter(|(_, field)| {
+        !attr::get_unchecked("struct field", attr::SkipAttr, &field.attrs).unwrap_or(false)
+    });
+
+    let (index, field) = match (streamed.next(), streamed.next()) {
+        (Some(field), None) => field,
+        _ => panic!("transparent structs must have exactly one field that isn't skipped"),
+    };
+
+    attr::ensure_empty("transparent field", &field.attrs);
+
+    let member = match field.ident {
+        Some(ref ident) => Member::Named(ident.clone()),
+        None => Member::Unnamed(syn::Index::from(index)),
+    };
+
+    let field_ident = Ident::new("field0", Span::call_site());
+    let value = value_trait.quote_stream_value(&field_ident);
+
+    (
+        quote!(#path { #member: ref #field_ident, .. } => {
+            #value?;
+        }),
+        quote!(match self {
+            #path { #member: ref #field_ident, .. } => sval::Value::tag(#field_ident),
         }),
-        Some(tag),
     )
 }
@@ -1,16 +1,21 @@
 use syn::{Attribute, Generics, Ident, Path};
 
 use crate::{
-    attr, bound,
-    derive::impl_tokens,
+    attr,
+    derive::{impl_tokens, ValueTrait},
     index::{Index, IndexAllocator, IndexValue},
     label::{label_or_ident, LabelValue},
     stream::stream_tag,
-    tag::quote_optional_tag_owned,
+    tag::{
+        quote_optional_tag_hint, quote_optional_tag_owned, quote_optional_version,
+        quote_optional_version_tag,
+    },
 };
 
 pub(crate) struct UnitStructAttrs {
     tag: Option<Path>,
+    tag_hint: Option<Path>,
+    version: Option<u64>,
     label: Option<LabelValue>,
     index: Option<IndexValue>,
 }
@@ -19,21 +24,43 @@ impl UnitStructAttrs {
     pub(crate) fn from_attrs(attrs: &[Attribute]) -> Self {
         attr::check(
             "unit struct",
-            &[&attr::TagAttr, &attr::LabelAttr, &attr::IndexAttr],
+            &[
+                &attr::TagAttr,
+                &attr::TagHintAttr,
+                &attr::VersionAttr,
+                &attr::LabelAttr,
+                &attr::IndexAttr,
+            ],
             attrs,
         );
 
         let tag = attr::get_unchecked("unit struct", attr::TagAttr, attrs);
+        let tag_hint = attr::get_unchecked("unit struct", attr::TagHintAttr, attrs);
+        let version = attr::get_unchecked("unit struct", attr::VersionAttr, attrs);
         let label = attr::get_unchecked("unit struct", attr::LabelAttr, attrs);
         let index = attr::get_unchecked("unit struct", attr::IndexAttr, attrs);
 
-        UnitStructAttrs { tag, label, index }
+        UnitStructAttrs {
+            tag,
+            tag_hint,
+            version,
+            label,
+            index,
+        }
     }
 
     pub(crate) fn tag(&self) -> Option<&Path> {
         self.tag.as_ref()
     }
 
+    pub(crate) fn tag_hint(&self) -> Option<&Path> {
+        self.tag_hint.as_ref()
+    }
+
+    pub(crate) fn version(&self) -> Option<u64> {
+        self.version
+    }
+
     pub(crate) fn label(&self) -> Option<LabelValue> {
         self.label.clone()
     }
@@ -44,15 +71,11 @@ impl UnitStructAttrs {
 }
 
 pub(crate) fn derive_unit_struct<'a>(
+    value_trait: ValueTrait,
     ident: &Ident,
     generics: &Generics,
     attrs: &UnitStructAttrs,
 ) -> proc_macro2::TokenStream {
-    let (impl_generics, ty_generics, _) = generics.split_for_impl();
-
-    let bound = parse_quote!(sval::Value);
-    let bounded_where_clause = bound::where_clause_with_bound(&generics, bound);
-
     let match_arm = stream_tag(
         quote!(_),
         attrs.tag(),
@@ -61,19 +84,24 @@ pub(crate) fn derive_unit_struct<'a>(
     );
 
     let tag = quote_optional_tag_owned(attrs.tag());
+    let tag_hint = quote_optional_tag_hint(attrs.tag_hint());
 
     impl_tokens(
-        impl_generics,
+        value_trait,
         ident,
-        ty_generics,
-        &bounded_where_clause,
-        quote!({
-            match self {
-                #match_arm
-            }
+        generics,
+        quote_optional_version(
+            attrs.version(),
+            quote!({
+                #tag_hint
+
+                match self {
+                    #match_arm
+                }
 
-            Ok(())
-        }),
-        Some(tag),
+                Ok(())
+            }),
+        ),
+        quote_optional_version_tag(attrs.version(), Some(tag)),
     )
 }
@@ -1,6 +1,9 @@
 use syn::{Attribute, Generics, Ident};
 
-use crate::{attr, bound, derive::impl_tokens};
+use crate::{
+    attr,
+    derive::{impl_tokens, ValueTrait},
+};
 
 pub(crate) struct VoidAttrs {}
 
@@ -13,22 +16,17 @@ impl VoidAttrs {
 }
 
 pub(crate) fn derive_void<'a>(
+    value_trait: ValueTrait,
     ident: &Ident,
     generics: &Generics,
     attrs: &VoidAttrs,
 ) -> proc_macro2::TokenStream {
     let _ = attrs;
 
-    let (impl_generics, ty_generics, _) = generics.split_for_impl();
-
-    let bound = parse_quote!(sval::Value);
-    let bounded_where_clause = bound::where_clause_with_bound(&generics, bound);
-
     impl_tokens(
-        impl_generics,
+        value_trait,
         ident,
-        ty_generics,
-        &bounded_where_clause,
+        generics,
         quote!({ match *self {} }),
         None,
     )
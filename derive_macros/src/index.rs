@@ -73,14 +73,31 @@ pub(crate) enum Index {
 }
 
 pub(crate) fn quote_index(index: Index) -> proc_macro2::TokenStream {
+    // Indexes that are integer literals are constructed once into a static
+    // Other indexes may be constants defined elsewhere or computed while streaming
     match index {
+        Index::Explicit(index) if is_literal(&index) => quote!({
+            static __SVAL_INDEX: sval::Index = sval::Index::new_isize(#index);
+
+            &__SVAL_INDEX
+        }),
         Index::Explicit(index) => quote!(&sval::Index::from(#index)),
+        Index::Implicit(index) if is_literal(&index) => quote!({
+            static __SVAL_INDEX: sval::Index =
+                sval::Index::new_isize(#index).with_tag(&sval::tags::VALUE_OFFSET);
+
+            &__SVAL_INDEX
+        }),
         Index::Implicit(index) => {
             quote!(&sval::Index::from(#index).with_tag(&sval::tags::VALUE_OFFSET))
         }
     }
 }
 
+fn is_literal(index: &proc_macro2::TokenStream) -> bool {
+    syn::parse2::<syn::LitInt>(index.clone()).is_ok()
+}
+
 pub(crate) fn quote_optional_index(index: Option<Index>) -> proc_macro2::TokenStream {
     match index {
         Some(index) => {
@@ -43,11 +43,20 @@ pub(crate) fn optional_label_or_ident<'a>(
 }
 
 pub(crate) fn quote_label(label: Label) -> proc_macro2::TokenStream {
+    // Labels with text known at compile time are constructed once into a static
+    // rather than on each call to `stream`
     match label {
-        Label::Implicit(implicit) => {
-            quote!(&sval::Label::new(#implicit).with_tag(&sval::tags::VALUE_IDENT))
-        }
-        Label::Const(explicit) => quote!(&sval::Label::new(#explicit)),
+        Label::Implicit(implicit) => quote!({
+            static __SVAL_LABEL: sval::Label<'static> =
+                sval::Label::new(#implicit).with_tag(&sval::tags::VALUE_IDENT);
+
+            &__SVAL_LABEL
+        }),
+        Label::Const(explicit) => quote!({
+            static __SVAL_LABEL: sval::Label<'static> = sval::Label::new(#explicit);
+
+            &__SVAL_LABEL
+        }),
         Label::Ident(explicit) => quote!(&#explicit),
     }
 }
@@ -20,3 +20,9 @@ use syn::DeriveInput;
 pub fn derive_value(input: TokenStream) -> TokenStream {
     TokenStream::from(derive::derive(parse_macro_input!(input as DeriveInput)))
 }
+
+#[cfg(feature = "ref")]
+#[proc_macro_derive(ValueRef, attributes(sval))]
+pub fn derive_value_ref(input: TokenStream) -> TokenStream {
+    TokenStream::from(derive::derive_ref(parse_macro_input!(input as DeriveInput)))
+}
@@ -3,12 +3,14 @@ use syn::{Field, Path};
 use crate::label::Label;
 use crate::{
     attr,
+    derive::ValueTrait,
     index::{quote_optional_index, Index},
     label::quote_optional_label,
     tag::quote_optional_tag,
 };
 
 pub(crate) fn stream_newtype(
+    value_trait: ValueTrait,
     path: proc_macro2::TokenStream,
     field: &Field,
     tag: Option<&Path>,
@@ -18,18 +20,21 @@ pub(crate) fn stream_newtype(
 ) -> proc_macro2::TokenStream {
     attr::ensure_empty("newtype field", &field.attrs);
 
+    let field_ident = syn::Ident::new("field0", proc_macro2::Span::call_site());
+    let value = value_trait.quote_stream_value(&field_ident);
+
     if transparent {
-        quote!(#path(ref field0) => {
-            stream.value(field0)?;
+        quote!(#path(ref #field_ident) => {
+            #value?;
         })
     } else {
         let tag = quote_optional_tag(tag);
         let label = quote_optional_label(label);
         let index = quote_optional_index(index);
 
-        quote!(#path(ref field0) => {
+        quote!(#path(ref #field_ident) => {
             stream.tagged_begin(#tag, #label, #index)?;
-            stream.value(field0)?;
+            #value?;
             stream.tagged_end(#tag, #label, #index)?;
         })
     }
@@ -3,9 +3,10 @@ use syn::{spanned::Spanned, Field, Ident, Path};
 use crate::label::{optional_label_or_ident, Label, LabelValue};
 use crate::{
     attr::{self},
+    derive::ValueTrait,
     index::{quote_index, quote_optional_index, Index, IndexAllocator},
     label::{quote_label, quote_optional_label},
-    tag::quote_optional_tag,
+    tag::{quote_optional_tag, quote_optional_tag_hint},
 };
 
 pub(crate) enum RecordTupleTarget {
@@ -25,16 +26,32 @@ impl RecordTupleTarget {
     }
 }
 
+/**
+The attributes of the struct or variant that contains a set of fields.
+*/
+pub(crate) struct RecordTupleContainer<'a> {
+    pub(crate) tag: Option<&'a Path>,
+    pub(crate) label: Option<Label>,
+    pub(crate) index: Option<Index>,
+    pub(crate) unlabeled_fields: bool,
+    pub(crate) unindexed_fields: bool,
+}
+
 pub(crate) fn stream_record_tuple<'a>(
+    value_trait: ValueTrait,
     path: proc_macro2::TokenStream,
     fields: impl Iterator<Item = &'a Field>,
     mut target: RecordTupleTarget,
-    tag: Option<&Path>,
-    label: Option<Label>,
-    index: Option<Index>,
-    unlabeled_fields: bool,
-    unindexed_fields: bool,
+    container: RecordTupleContainer,
 ) -> proc_macro2::TokenStream {
+    let RecordTupleContainer {
+        tag,
+        label,
+        index,
+        unlabeled_fields,
+        unindexed_fields,
+    } = container;
+
     let tag = quote_optional_tag(tag);
     let label = quote_optional_label(label);
     let index = quote_optional_index(index);
@@ -43,6 +60,7 @@ pub(crate) fn stream_record_tuple<'a>(
     let mut stream_field = Vec::new();
 
     let mut field_count = 0usize;
+    let mut field_features = Vec::new();
     let mut labeled_field_count = 0;
     let mut indexed_field_count = 0;
     let mut const_size = true;
@@ -58,10 +76,12 @@ pub(crate) fn stream_record_tuple<'a>(
             &[
                 &attr::TagAttr,
                 &attr::DataTagAttr,
+                &attr::TagHintAttr,
                 &attr::IndexAttr,
                 &attr::LabelAttr,
                 &attr::SkipAttr,
                 &attr::FlattenAttr,
+                &attr::FeatureAttr,
             ],
             &field.attrs,
         );
@@ -106,18 +126,39 @@ pub(crate) fn stream_record_tuple<'a>(
 
         const_size = const_size && !flatten;
 
+        // Flattened fields are streamed as part of their parent, so there's
+        // no value for a tag hint to apply to
+        if flatten {
+            attr::ensure_missing("flattened struct field", attr::TagHintAttr, &field.attrs);
+        }
+
+        assert!(
+            !flatten || value_trait == ValueTrait::Value,
+            "flattened fields aren't supported when deriving `ValueRef`"
+        );
+
+        let stream_value = value_trait.quote_stream_value(&ident);
+
+        let tag_hint = quote_optional_tag_hint(
+            attr::get_unchecked("struct field", attr::TagHintAttr, &field.attrs).as_ref(),
+        );
+
         let value = if let Some(data_tag) =
             attr::get_unchecked("struct field", attr::DataTagAttr, &field.attrs)
         {
             let data_tag = quote_optional_tag(Some(&data_tag));
 
             quote!({
+                #tag_hint
                 stream.tagged_begin(#data_tag, None, None)?;
-                stream.value(#ident)?;
+                #stream_value?;
                 stream.tagged_end(#data_tag, None, None)?
             })
         } else {
-            quote!(stream.value(#ident)?)
+            quote!({
+                #tag_hint
+                #stream_value?
+            })
         };
 
         match (&label, &index) {
@@ -186,6 +227,18 @@ pub(crate) fn stream_record_tuple<'a>(
             }
         }
 
+        // Fields that depend on a Cargo feature are only streamed when
+        // that feature is enabled in the crate the derive is expanded in
+        if let Some(feature) = attr::get_unchecked("struct field", attr::FeatureAttr, &field.attrs)
+        {
+            let stream_feature_field = stream_field.pop().expect("missing field");
+            stream_field.push(quote!(if cfg!(feature = #feature) {
+                #stream_feature_field
+            }));
+
+            field_features.push(feature);
+        }
+
         field_binding.push(binding);
         field_count += 1;
     }
@@ -200,7 +253,9 @@ pub(crate) fn stream_record_tuple<'a>(
     );
 
     let field_count = if const_size {
-        quote!(Some(#field_count))
+        let const_field_count = field_count - field_features.len();
+
+        quote!(Some(#const_field_count #(+ (cfg!(feature = #field_features) as usize))*))
     } else {
         quote!(None)
     };
@@ -13,3 +13,36 @@ pub(crate) fn quote_optional_tag_owned(tag: Option<&Path>) -> proc_macro2::Token
         None => quote!(None),
     }
 }
+
+pub(crate) fn quote_optional_tag_hint(tag_hint: Option<&Path>) -> proc_macro2::TokenStream {
+    match tag_hint {
+        Some(tag_hint) => quote!(stream.tag_hint(&#tag_hint)?;),
+        None => quote!(),
+    }
+}
+
+pub(crate) fn quote_optional_version(
+    version: Option<u64>,
+    stream_body: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    match version {
+        Some(version) => quote!({
+            stream.tagged_begin(Some(&sval::tags::SCHEMA_VERSION), None, Some(&sval::Index::new_u64(#version)))?;
+
+            (#stream_body)?;
+
+            stream.tagged_end(Some(&sval::tags::SCHEMA_VERSION), None, Some(&sval::Index::new_u64(#version)))
+        }),
+        None => stream_body,
+    }
+}
+
+pub(crate) fn quote_optional_version_tag(
+    version: Option<u64>,
+    tag_body: Option<proc_macro2::TokenStream>,
+) -> Option<proc_macro2::TokenStream> {
+    match version {
+        Some(_) => Some(quote!(Some(sval::tags::SCHEMA_VERSION))),
+        None => tag_body,
+    }
+}
@@ -0,0 +1,59 @@
+use core::fmt;
+
+use crate::stream::Stream;
+
+/**
+Create a value that's streamed by calling a function.
+
+This makes it possible to stream an arbitrary sequence of calls as a value without
+declaring a type for it. The function is called each time the value is streamed.
+It must produce exactly one complete value, such as a single number or a record with
+all of its fields; streams may fail or produce invalid output otherwise.
+
+The function is given a `dyn Stream` so it doesn't need to be generic. Since it can be
+called with a stream of any lifetime, any text or binary it streams that isn't `'static`
+needs to use the `_computed` methods.
+
+```
+use sval::Stream as _;
+
+let value = sval_dynamic::from_fn(|stream| {
+    stream.seq_begin(Some(2))?;
+
+    stream.seq_value_begin()?;
+    stream.i32(1)?;
+    stream.seq_value_end()?;
+
+    stream.seq_value_begin()?;
+    stream.i32(2)?;
+    stream.seq_value_end()?;
+
+    stream.seq_end()
+});
+```
+*/
+pub fn from_fn<F: Fn(&mut dyn Stream) -> sval::Result>(f: F) -> FromFn<F> {
+    FromFn(f)
+}
+
+/**
+A value that's streamed by calling a function.
+
+See [`from_fn`] for details.
+*/
+pub struct FromFn<F>(F);
+
+impl<F> fmt::Debug for FromFn<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FromFn").finish_non_exhaustive()
+    }
+}
+
+impl<F: Fn(&mut dyn Stream) -> sval::Result> sval::Value for FromFn<F> {
+    fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(
+        &'sval self,
+        mut stream: &mut S,
+    ) -> sval::Result {
+        (self.0)(&mut stream)
+    }
+}
@@ -4,11 +4,14 @@ Object-safe wrappers for `sval::Stream` and `sval::Value`.
 This crate makes it possible to erase a concrete `sval::Value` or `sval::Stream`
 as a `dyn Value` or `dyn Stream`. It doesn't require any allocator,
 so it's possible to use in no-std environments.
+
+It also provides [`from_fn`] for streaming a value from a function.
 */
 
 #![no_std]
 #![deny(missing_docs)]
 
+mod from_fn;
 mod stream;
 mod value;
 
@@ -16,6 +19,10 @@ mod private {
     pub struct Erased<T>(pub(crate) T);
 }
 
-pub use self::{stream::Stream, value::Value};
+pub use self::{
+    from_fn::{from_fn, FromFn},
+    stream::Stream,
+    value::Value,
+};
 
 // NOTE: Tests for forwarding through dynamic traits is in `sval_test`
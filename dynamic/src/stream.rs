@@ -28,6 +28,8 @@ mod private {
 
         fn dispatch_f64(&mut self, value: f64) -> sval::Result;
 
+        fn dispatch_num(&mut self, num: &sval::Number) -> sval::Result;
+
         fn dispatch_bool(&mut self, value: bool) -> sval::Result;
 
         fn dispatch_text_begin(&mut self, num_bytes: Option<usize>) -> sval::Result;
@@ -267,6 +269,10 @@ impl<'sval, R: sval::Stream<'sval>> private::DispatchStream<'sval> for R {
         self.f64(value)
     }
 
+    fn dispatch_num(&mut self, num: &sval::Number) -> sval::Result {
+        self.num(num)
+    }
+
     fn dispatch_bool(&mut self, value: bool) -> sval::Result {
         self.bool(value)
     }
@@ -559,6 +565,10 @@ macro_rules! impl_stream {
                 self.erase_stream().0.dispatch_f64(value)
             }
 
+            fn num(&mut self, num: &sval::Number) -> sval::Result {
+                self.erase_stream().0.dispatch_num(num)
+            }
+
             fn bool(&mut self, value: bool) -> sval::Result {
                 self.erase_stream().0.dispatch_bool(value)
             }
@@ -0,0 +1,39 @@
+use core::fmt;
+
+/**
+An error encountered streaming EDN.
+*/
+#[derive(Debug)]
+pub struct Error {
+    pub(crate) kind: ErrorKind,
+}
+
+#[derive(Debug)]
+pub(crate) enum ErrorKind {
+    Generic,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            ErrorKind::Generic => write!(f, "an error occurred serializing a value to EDN"),
+        }
+    }
+}
+
+impl Error {
+    pub(crate) fn generic() -> Self {
+        Error {
+            kind: ErrorKind::Generic,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+mod std_support {
+    use super::*;
+
+    use std::error;
+
+    impl error::Error for Error {}
+}
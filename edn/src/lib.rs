@@ -0,0 +1,37 @@
+/*!
+EDN support for `sval`.
+
+Values are written in the same way Clojure prints its own data:
+
+- Null is written as `nil`.
+- Maps and records are written as maps, like `{:id 1, :name "sval"}`.
+  The labels of record fields are written as keywords.
+- Sequences and tuples are written as vectors, like `[1 2 3]`.
+- Enum variants with values are written as maps with a single keyword key, like `{:Tagged 1}`,
+  and variants without values are written as keywords, like `:Constant`.
+- `Some` is written as its value, and `None` is written as `nil`.
+- Characters are written as character literals, like `\a` or `\newline`.
+- Integers that don't fit in an `i64` are written as bigints, like `18446744073709551615N`.
+
+Some functionality requires the `alloc` Cargo feature to be enabled.
+*/
+
+#![no_std]
+#![deny(missing_docs)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+mod error;
+
+mod to_fmt;
+pub use self::{error::*, to_fmt::*};
+
+#[cfg(feature = "alloc")]
+mod to_string;
+
+#[cfg(feature = "alloc")]
+pub use self::to_string::*;
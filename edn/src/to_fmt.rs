@@ -0,0 +1,571 @@
+use core::fmt::{self, Write};
+
+use sval::Stream as _;
+
+use crate::Error;
+
+macro_rules! _try {
+    ($e:expr) => {
+        match ($e) {
+            Ok(_o) => _o,
+            Err(_) => return Err(sval::Error::new()),
+        }
+    };
+}
+
+macro_rules! _try_no_conv {
+    ($e:expr) => {
+        match ($e) {
+            Ok(()) => (),
+            Err(e) => return Err(e),
+        }
+    };
+}
+
+/**
+Stream a value as EDN to an underlying formatter.
+*/
+pub fn stream_to_fmt_write(fmt: impl Write, v: impl sval::Value) -> Result<(), Error> {
+    let mut stream = Formatter::new(fmt);
+
+    match v.stream(&mut stream) {
+        Ok(()) => Ok(()),
+        Err(_) => Err(Error::generic()),
+    }
+}
+
+pub(crate) struct Formatter<W> {
+    is_internally_tagged: bool,
+    is_current_depth_empty: bool,
+    text: TextKind,
+    out: W,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TextKind {
+    String,
+    Number,
+    Char,
+}
+
+impl<W> Formatter<W> {
+    pub fn new(out: W) -> Self {
+        Formatter {
+            is_internally_tagged: false,
+            is_current_depth_empty: true,
+            text: TextKind::String,
+            out,
+        }
+    }
+}
+
+impl<W> fmt::Debug for Formatter<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Formatter")
+            .field("is_internally_tagged", &self.is_internally_tagged)
+            .field("is_current_depth_empty", &self.is_current_depth_empty)
+            .field("text", &self.text)
+            .finish()
+    }
+}
+
+impl<'sval, W> sval::Stream<'sval> for Formatter<W>
+where
+    W: Write,
+{
+    fn null(&mut self) -> sval::Result {
+        self.is_current_depth_empty = false;
+
+        _try!(self.out.write_str("nil"));
+
+        Ok(())
+    }
+
+    fn bool(&mut self, v: bool) -> sval::Result {
+        self.is_current_depth_empty = false;
+
+        _try!(self.out.write_str(if v { "true" } else { "false" }));
+
+        Ok(())
+    }
+
+    fn text_begin(&mut self, _: Option<usize>) -> sval::Result {
+        self.is_current_depth_empty = false;
+
+        if self.text == TextKind::String {
+            _try!(self.out.write_char('"'));
+        }
+
+        Ok(())
+    }
+
+    fn text_fragment_computed(&mut self, v: &str) -> sval::Result {
+        match self.text {
+            TextKind::String => _try!(escape_str(v, &mut self.out)),
+            TextKind::Number => _try!(self.out.write_str(v)),
+            TextKind::Char => {
+                for c in v.chars() {
+                    _try!(write_char(c, &mut self.out));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn text_end(&mut self) -> sval::Result {
+        if self.text == TextKind::String {
+            _try!(self.out.write_char('"'));
+        }
+
+        Ok(())
+    }
+
+    fn u8(&mut self, v: u8) -> sval::Result {
+        self.integer(v)
+    }
+
+    fn u16(&mut self, v: u16) -> sval::Result {
+        self.integer(v)
+    }
+
+    fn u32(&mut self, v: u32) -> sval::Result {
+        self.integer(v)
+    }
+
+    fn u64(&mut self, v: u64) -> sval::Result {
+        if v > i64::MAX as u64 {
+            self.bigint(v)
+        } else {
+            self.integer(v)
+        }
+    }
+
+    fn u128(&mut self, v: u128) -> sval::Result {
+        if v > i64::MAX as u128 {
+            self.bigint(v)
+        } else {
+            self.integer(v)
+        }
+    }
+
+    fn i8(&mut self, v: i8) -> sval::Result {
+        self.integer(v)
+    }
+
+    fn i16(&mut self, v: i16) -> sval::Result {
+        self.integer(v)
+    }
+
+    fn i32(&mut self, v: i32) -> sval::Result {
+        self.integer(v)
+    }
+
+    fn i64(&mut self, v: i64) -> sval::Result {
+        self.integer(v)
+    }
+
+    fn i128(&mut self, v: i128) -> sval::Result {
+        if v > i64::MAX as i128 || v < i64::MIN as i128 {
+            self.bigint(v)
+        } else {
+            self.integer(v)
+        }
+    }
+
+    fn f32(&mut self, v: f32) -> sval::Result {
+        self.f64(v as f64)
+    }
+
+    fn f64(&mut self, v: f64) -> sval::Result {
+        self.is_current_depth_empty = false;
+
+        if v.is_nan() {
+            _try!(self.out.write_str("##NaN"));
+        } else if v.is_infinite() {
+            _try!(self.out.write_str(if v.is_sign_negative() {
+                "##-Inf"
+            } else {
+                "##Inf"
+            }));
+        } else {
+            _try!(self.out.write_str(ryu::Buffer::new().format_finite(v)));
+        }
+
+        Ok(())
+    }
+
+    fn map_begin(&mut self, _: Option<usize>) -> sval::Result {
+        self.is_current_depth_empty = true;
+
+        _try!(self.out.write_char('{'));
+
+        Ok(())
+    }
+
+    fn map_key_begin(&mut self) -> sval::Result {
+        self.is_internally_tagged = false;
+
+        if !self.is_current_depth_empty {
+            _try!(self.out.write_str(", "));
+        }
+
+        Ok(())
+    }
+
+    fn map_key_end(&mut self) -> sval::Result {
+        _try!(self.out.write_char(' '));
+
+        Ok(())
+    }
+
+    fn map_value_begin(&mut self) -> sval::Result {
+        Ok(())
+    }
+
+    fn map_value_end(&mut self) -> sval::Result {
+        Ok(())
+    }
+
+    fn map_end(&mut self) -> sval::Result {
+        self.is_current_depth_empty = false;
+        _try!(self.out.write_char('}'));
+
+        Ok(())
+    }
+
+    fn seq_begin(&mut self, _: Option<usize>) -> sval::Result {
+        self.is_current_depth_empty = true;
+
+        _try!(self.out.write_char('['));
+
+        Ok(())
+    }
+
+    fn seq_value_begin(&mut self) -> sval::Result {
+        self.is_internally_tagged = false;
+
+        if !self.is_current_depth_empty {
+            _try!(self.out.write_char(' '));
+        }
+
+        Ok(())
+    }
+
+    fn seq_value_end(&mut self) -> sval::Result {
+        Ok(())
+    }
+
+    fn seq_end(&mut self) -> sval::Result {
+        self.is_current_depth_empty = false;
+        _try!(self.out.write_char(']'));
+
+        Ok(())
+    }
+
+    fn enum_begin(
+        &mut self,
+        _: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        _try_no_conv!(self.internally_tagged_begin(label, index));
+
+        self.is_internally_tagged = true;
+        self.is_current_depth_empty = true;
+
+        Ok(())
+    }
+
+    fn enum_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        if self.is_current_depth_empty {
+            _try_no_conv!(self.tag(tag, label, index));
+        }
+
+        if self.is_internally_tagged {
+            self.internally_tagged_map_end()
+        } else {
+            self.internally_tagged_end(label, index)
+        }
+    }
+
+    fn tagged_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        match tag {
+            Some(&sval::tags::NUMBER) => self.text = TextKind::Number,
+            Some(&sval::tags::CHAR) => self.text = TextKind::Char,
+            _ => (),
+        }
+
+        _try_no_conv!(self.internally_tagged_begin(label, index));
+        self.is_current_depth_empty = true;
+
+        Ok(())
+    }
+
+    fn tagged_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        if let Some(&sval::tags::NUMBER) | Some(&sval::tags::CHAR) = tag {
+            self.text = TextKind::String;
+        }
+
+        if self.is_current_depth_empty {
+            _try_no_conv!(self.tag(tag, label, index));
+        }
+
+        self.internally_tagged_end(label, index)
+    }
+
+    fn tag(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.is_internally_tagged = false;
+        self.is_current_depth_empty = false;
+
+        match tag {
+            Some(&sval::tags::RUST_OPTION_NONE) => self.null(),
+            _ => {
+                if let Some(label) = label {
+                    _try!(write_keyword(label.as_str(), &mut self.out));
+
+                    Ok(())
+                } else if let Some(index) = index.and_then(|ix| ix.to_i64()) {
+                    self.i64(index)
+                } else {
+                    self.null()
+                }
+            }
+        }
+    }
+
+    fn record_begin(
+        &mut self,
+        _: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+        num_entries_hint: Option<usize>,
+    ) -> sval::Result {
+        _try_no_conv!(self.internally_tagged_begin(label, index));
+        self.map_begin(num_entries_hint)
+    }
+
+    fn record_value_begin(&mut self, _: Option<&sval::Tag>, label: &sval::Label) -> sval::Result {
+        _try_no_conv!(self.map_key_begin());
+        _try!(write_keyword(label.as_str(), &mut self.out));
+        _try_no_conv!(self.map_key_end());
+
+        self.map_value_begin()
+    }
+
+    fn record_end(
+        &mut self,
+        _: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        _try_no_conv!(self.map_end());
+        self.internally_tagged_end(label, index)
+    }
+
+    fn tuple_begin(
+        &mut self,
+        _: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+        num_entries_hint: Option<usize>,
+    ) -> sval::Result {
+        _try_no_conv!(self.internally_tagged_begin(label, index));
+        self.seq_begin(num_entries_hint)
+    }
+
+    fn tuple_end(
+        &mut self,
+        _: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        _try_no_conv!(self.seq_end());
+        self.internally_tagged_end(label, index)
+    }
+}
+
+impl<W> Formatter<W>
+where
+    W: Write,
+{
+    fn integer(&mut self, v: impl itoa::Integer) -> sval::Result {
+        self.is_current_depth_empty = false;
+
+        _try!(self.out.write_str(itoa::Buffer::new().format(v)));
+
+        Ok(())
+    }
+
+    fn bigint(&mut self, v: impl itoa::Integer) -> sval::Result {
+        _try_no_conv!(self.integer(v));
+
+        // Integers that don't fit in a `long` need the `N` suffix
+        _try!(self.out.write_char('N'));
+
+        Ok(())
+    }
+
+    fn internally_tagged_begin(
+        &mut self,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        // If there's a label then begin a map, using the label as the key
+        if self.is_internally_tagged {
+            self.is_internally_tagged = false;
+
+            if let Some(label) = label {
+                return self.internally_tagged_map_begin_label(label.as_str());
+            } else if let Some(index) = index.and_then(|index| index.to_i64()) {
+                return self.internally_tagged_map_begin_index(index);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn internally_tagged_end(
+        &mut self,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.is_internally_tagged =
+            label.is_some() || index.and_then(|index| index.to_i64()).is_some();
+
+        Ok(())
+    }
+
+    fn internally_tagged_map_begin_label(&mut self, label: &str) -> sval::Result {
+        _try_no_conv!(self.map_begin(Some(1)));
+
+        _try_no_conv!(self.map_key_begin());
+        _try!(write_keyword(label, &mut self.out));
+        _try_no_conv!(self.map_key_end());
+
+        self.map_value_begin()
+    }
+
+    fn internally_tagged_map_begin_index(&mut self, index: i64) -> sval::Result {
+        _try_no_conv!(self.map_begin(Some(1)));
+
+        _try_no_conv!(self.map_key_begin());
+        _try_no_conv!(self.i64(index));
+        _try_no_conv!(self.map_key_end());
+
+        self.map_value_begin()
+    }
+
+    fn internally_tagged_map_end(&mut self) -> sval::Result {
+        _try_no_conv!(self.map_value_end());
+        self.map_end()
+    }
+}
+
+/**
+Write a label as a keyword, like `:label`.
+
+Labels that can't be read back as keywords are written as strings instead.
+*/
+fn write_keyword(label: &str, mut out: impl Write) -> fmt::Result {
+    if is_keyword(label) {
+        _try_no_conv!(out.write_char(':'));
+        out.write_str(label)
+    } else {
+        _try_no_conv!(out.write_char('"'));
+        _try_no_conv!(escape_str(label, &mut out));
+        out.write_char('"')
+    }
+}
+
+fn is_keyword(label: &str) -> bool {
+    const SYMBOL_CHARS: &str = "*+!-_?$%&=<>.";
+
+    let mut chars = label.chars();
+
+    match chars.next() {
+        // A leading sign or `.` can't be followed by a digit, or it would be read as a number
+        Some('+') | Some('-') | Some('.') => {
+            if let Some(c) = chars.clone().next() {
+                if c.is_ascii_digit() {
+                    return false;
+                }
+            }
+        }
+        Some(c) if c.is_ascii_alphabetic() || SYMBOL_CHARS.contains(c) => (),
+        _ => return false,
+    }
+
+    chars.all(|c| c.is_ascii_alphanumeric() || SYMBOL_CHARS.contains(c))
+}
+
+/**
+Write a character literal, like `\a` or `\newline`.
+*/
+fn write_char(c: char, mut out: impl Write) -> fmt::Result {
+    match c {
+        '\n' => out.write_str("\\newline"),
+        '\r' => out.write_str("\\return"),
+        '\t' => out.write_str("\\tab"),
+        ' ' => out.write_str("\\space"),
+        c if c.is_control() => write!(out, "\\u{:04x}", c as u32),
+        c => {
+            _try_no_conv!(out.write_char('\\'));
+            out.write_char(c)
+        }
+    }
+}
+
+fn escape_str(value: &str, mut out: impl Write) -> fmt::Result {
+    let mut start = 0;
+
+    for (i, c) in value.char_indices() {
+        let escape = match c {
+            '"' => Some("\\\""),
+            '\\' => Some("\\\\"),
+            '\n' => Some("\\n"),
+            '\r' => Some("\\r"),
+            '\t' => Some("\\t"),
+            // Other control characters don't have a short escape
+            c if c.is_control() => None,
+            _ => continue,
+        };
+
+        if start < i {
+            _try_no_conv!(out.write_str(&value[start..i]));
+        }
+
+        match escape {
+            Some(escape) => _try_no_conv!(out.write_str(escape)),
+            None => _try_no_conv!(write!(out, "\\u{:04x}", c as u32)),
+        }
+
+        start = i + c.len_utf8();
+    }
+
+    if start != value.len() {
+        _try_no_conv!(out.write_str(&value[start..]));
+    }
+
+    Ok(())
+}
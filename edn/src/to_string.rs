@@ -0,0 +1,13 @@
+use crate::Error;
+
+use alloc::string::String;
+
+/**
+Stream a value as EDN into a string.
+*/
+pub fn stream_to_string(v: impl sval::Value) -> Result<String, Error> {
+    let mut out = String::new();
+    crate::stream_to_fmt_write(&mut out, v)?;
+
+    Ok(out)
+}
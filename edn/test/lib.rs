@@ -0,0 +1,164 @@
+#![cfg(test)]
+
+#[macro_use]
+extern crate sval_derive;
+
+use std::collections::BTreeMap;
+
+fn assert_edn(expected: &str, v: impl sval::Value) {
+    let actual = sval_edn::stream_to_string(v).unwrap();
+
+    assert_eq!(expected, actual);
+}
+
+#[derive(Value)]
+struct MapStruct {
+    field_0: i32,
+    field_1: bool,
+    field_2: &'static str,
+}
+
+#[derive(Value)]
+struct SeqStruct(i32, bool, &'static str);
+
+#[derive(Value)]
+struct Tagged(i32);
+
+#[derive(Value)]
+enum Enum {
+    Constant,
+    Tagged(i32),
+    MapStruct {
+        field_0: i32,
+        field_1: bool,
+        field_2: &'static str,
+    },
+    SeqStruct(i32, bool, &'static str),
+}
+
+#[test]
+fn stream_primitive() {
+    assert_edn("nil", sval::Null);
+    assert_edn("true", true);
+    assert_edn("false", false);
+
+    assert_edn("1", 1u8);
+    assert_edn("-1", -1i8);
+    assert_edn("9223372036854775807", i64::MAX);
+    assert_edn("-9223372036854775808", i64::MIN);
+    assert_edn("9223372036854775808N", i64::MAX as u64 + 1);
+    assert_edn("340282366920938463463374607431768211455N", u128::MAX);
+    assert_edn("-170141183460469231731687303715884105728N", i128::MIN);
+
+    assert_edn("1.5", 1.5f64);
+    assert_edn("##NaN", f64::NAN);
+    assert_edn("##Inf", f64::INFINITY);
+    assert_edn("##-Inf", f64::NEG_INFINITY);
+}
+
+#[test]
+fn stream_text() {
+    assert_edn("\"abc\"", "abc");
+    assert_edn(
+        "\"a \\\"quoted\\\" \\\\ line\\n\"",
+        "a \"quoted\" \\ line\n",
+    );
+    assert_edn("\"\\u0000\"", "\u{0}");
+}
+
+#[test]
+fn stream_char() {
+    assert_edn("\\a", 'a');
+    assert_edn("\\newline", '\n');
+    assert_edn("\\space", ' ');
+    assert_edn("\\tab", '\t');
+    assert_edn("\\u0000", '\u{0}');
+}
+
+#[test]
+fn stream_option() {
+    assert_edn("1", Some(1));
+    assert_edn("nil", None::<i32>);
+}
+
+#[test]
+fn stream_seq() {
+    assert_edn("[]", Vec::<i32>::new());
+    assert_edn("[1 2 3]", vec![1, 2, 3]);
+    assert_edn("[[1] [2 3]]", vec![vec![1], vec![2, 3]]);
+    assert_edn("[1 true \"a\"]", SeqStruct(1, true, "a"));
+}
+
+#[test]
+fn stream_map() {
+    let mut map = BTreeMap::new();
+    map.insert("a", 1);
+    map.insert("b", 2);
+
+    assert_edn("{\"a\" 1, \"b\" 2}", &map);
+    assert_edn("{}", BTreeMap::<i32, i32>::new());
+}
+
+#[test]
+fn stream_record() {
+    assert_edn(
+        "{:field_0 1, :field_1 true, :field_2 \"a\"}",
+        MapStruct {
+            field_0: 1,
+            field_1: true,
+            field_2: "a",
+        },
+    );
+
+    assert_edn("1", Tagged(1));
+}
+
+#[test]
+fn stream_record_non_keyword_label() {
+    struct Record;
+
+    impl sval::Value for Record {
+        fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(
+            &'sval self,
+            stream: &mut S,
+        ) -> sval::Result {
+            stream.record_begin(None, None, None, Some(3))?;
+
+            stream.record_value_begin(None, &sval::Label::new("valid-name?"))?;
+            stream.i32(1)?;
+            stream.record_value_end(None, &sval::Label::new("valid-name?"))?;
+
+            stream.record_value_begin(None, &sval::Label::new("has space"))?;
+            stream.i32(2)?;
+            stream.record_value_end(None, &sval::Label::new("has space"))?;
+
+            stream.record_value_begin(None, &sval::Label::new("1st"))?;
+            stream.i32(3)?;
+            stream.record_value_end(None, &sval::Label::new("1st"))?;
+
+            stream.record_end(None, None, None)
+        }
+    }
+
+    assert_edn("{:valid-name? 1, \"has space\" 2, \"1st\" 3}", Record);
+}
+
+#[test]
+fn stream_enum() {
+    assert_edn(":Constant", Enum::Constant);
+    assert_edn("{:Tagged 1}", Enum::Tagged(1));
+    assert_edn(
+        "{:MapStruct {:field_0 1, :field_1 true, :field_2 \"a\"}}",
+        Enum::MapStruct {
+            field_0: 1,
+            field_1: true,
+            field_2: "a",
+        },
+    );
+    assert_edn("{:SeqStruct [1 true \"a\"]}", Enum::SeqStruct(1, true, "a"));
+
+    assert_edn(
+        "[:Constant {:Tagged 1}]",
+        vec![Enum::Constant, Enum::Tagged(1)],
+    );
+}
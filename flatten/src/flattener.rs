@@ -61,6 +61,11 @@ impl<'sval, S: Flatten<'sval>> Flattener<'sval, S> {
         self.state.index_alloc.current_offset()
     }
 
+    #[inline]
+    pub(crate) fn end_with_stream(self) -> (isize, S) {
+        (self.state.index_alloc.current_offset(), self.stream)
+    }
+
     #[inline]
     fn value(
         &mut self,
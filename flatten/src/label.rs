@@ -13,6 +13,22 @@ impl<'a, 'sval, S: LabelStream<'sval> + ?Sized> LabelStream<'sval> for &'a mut S
     }
 }
 
+#[cfg(feature = "alloc")]
+pub(crate) fn prefix_label(prefix: &str, label: &Label) -> Label<'static> {
+    use alloc::string::String;
+
+    let mut prefixed = String::with_capacity(prefix.len() + label.as_str().len());
+    prefixed.push_str(prefix);
+    prefixed.push_str(label.as_str());
+
+    let prefixed = Label::new_owned(prefixed);
+
+    match label.tag() {
+        Some(tag) => prefixed.with_tag(tag),
+        None => prefixed,
+    }
+}
+
 #[derive(Debug)]
 pub(crate) enum LabelBuf<'sval> {
     Empty,
@@ -41,18 +57,39 @@ impl<'sval> LabelBuf<'sval> {
     }
 
     pub(crate) fn i128(&mut self, v: impl TryInto<i128>) -> sval::Result {
-        *self = LabelBuf::I128(v.try_into().map_err(|_| sval::Error::new())?);
-        Ok(())
+        let v = v.try_into().map_err(|_| sval::Error::new())?;
+
+        match self {
+            LabelBuf::Empty => {
+                *self = LabelBuf::I128(v);
+                Ok(())
+            }
+            _ => self.text_fragment_computed(itoa::Buffer::new().format(v)),
+        }
     }
 
     pub(crate) fn u128(&mut self, v: impl TryInto<u128>) -> sval::Result {
-        *self = LabelBuf::U128(v.try_into().map_err(|_| sval::Error::new())?);
-        Ok(())
+        let v = v.try_into().map_err(|_| sval::Error::new())?;
+
+        match self {
+            LabelBuf::Empty => {
+                *self = LabelBuf::U128(v);
+                Ok(())
+            }
+            _ => self.text_fragment_computed(itoa::Buffer::new().format(v)),
+        }
     }
 
     pub(crate) fn f64(&mut self, v: impl TryInto<f64>) -> sval::Result {
-        *self = LabelBuf::F64(v.try_into().map_err(|_| sval::Error::new())?);
-        Ok(())
+        let v = v.try_into().map_err(|_| sval::Error::new())?;
+
+        match self {
+            LabelBuf::Empty => {
+                *self = LabelBuf::F64(v);
+                Ok(())
+            }
+            _ => self.text_fragment_computed(ryu::Buffer::new().format(v)),
+        }
     }
 
     pub(crate) fn null(&mut self) -> sval::Result {
@@ -98,7 +135,14 @@ impl<'sval> LabelBuf<'sval> {
         match self {
             LabelBuf::Text(buf) => Ok(buf),
             _ => {
-                *self = LabelBuf::Text(TextBuf::new());
+                // Complex values are concatenated, so keep anything already in the label
+                let mut buf = TextBuf::new();
+                self.with_label(|label| {
+                    buf.push_fragment_computed(label.as_str())
+                        .map_err(|_| sval::Error::new())
+                })?;
+
+                *self = LabelBuf::Text(buf);
                 if let LabelBuf::Text(buf) = self {
                     Ok(buf)
                 } else {
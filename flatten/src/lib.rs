@@ -47,6 +47,12 @@ value is stringified and concatenated together.
 - **sequences**: indexes are the offset of sequence values.
 - **records**: indexes are the offset of record values.
 - **tuples**: tuple values are passed through directly.
+
+# Prefixes
+
+When flattening onto a record or tuple with the `alloc` feature, [`flatten_to_record_tuple_with_prefix`]
+can prepend a prefix to each flattened label, so values with the same labels can be flattened onto
+the same parent without colliding.
 */
 
 #![no_std]
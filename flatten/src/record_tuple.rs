@@ -2,6 +2,9 @@ use crate::{
     flattener::{Flatten, Flattener},
     label::LabelBuf,
 };
+
+#[cfg(feature = "alloc")]
+use crate::label::prefix_label;
 use sval::{Index, Label, Stream, Tag};
 
 /**
@@ -30,6 +33,73 @@ pub fn flatten_to_record_tuple<'sval>(
     Ok(stream.end())
 }
 
+/**
+Flatten the fields of a value onto a record or tuple, where the number of fields is known upfront.
+
+This function is like [`flatten_to_record_tuple`], but lets the parent include the `len` fields
+of the flattened value in the length hint it passes to `record_tuple_begin`. If the value doesn't
+flatten to exactly `len` fields then this function will fail, since the hint would be wrong.
+ */
+pub fn flatten_to_record_tuple_sized<'sval>(
+    stream: &mut (impl Stream<'sval> + ?Sized),
+    value: &'sval (impl sval::Value + ?Sized),
+    offset: isize,
+    len: usize,
+) -> sval::Result<isize> {
+    let label_stream = LabelBuf::default();
+
+    let mut stream = Flattener::begin(
+        SizedRecordTupleFlatten {
+            inner: RecordTupleFlatten {
+                stream,
+                label_stream,
+            },
+            count: 0,
+        },
+        offset,
+    );
+
+    value.stream(&mut stream)?;
+
+    let (offset, stream) = stream.end_with_stream();
+
+    if stream.count != len {
+        return sval::error();
+    }
+
+    Ok(offset)
+}
+
+/**
+Flatten the fields of a value onto a record or tuple, prepending a prefix to each label.
+
+This function is like [`flatten_to_record_tuple`], but each flattened label is prefixed with `prefix`,
+so values with overlapping labels can be flattened onto the same record or tuple. Since prefixed labels
+need to be constructed, this function requires the `alloc` feature.
+ */
+#[cfg(feature = "alloc")]
+pub fn flatten_to_record_tuple_with_prefix<'sval>(
+    stream: &mut (impl Stream<'sval> + ?Sized),
+    value: &'sval (impl sval::Value + ?Sized),
+    offset: isize,
+    prefix: &str,
+) -> sval::Result<isize> {
+    let label_stream = LabelBuf::default();
+
+    let mut stream = Flattener::begin(
+        PrefixedRecordTupleFlatten {
+            stream,
+            label_stream,
+            prefix,
+        },
+        offset,
+    );
+
+    value.stream(&mut stream)?;
+
+    Ok(stream.end())
+}
+
 struct RecordTupleFlatten<'sval, S> {
     stream: S,
     label_stream: LabelBuf<'sval>,
@@ -66,6 +136,84 @@ impl<'sval, S: Stream<'sval>> Flatten<'sval> for RecordTupleFlatten<'sval, S> {
     }
 }
 
+struct SizedRecordTupleFlatten<'sval, S> {
+    inner: RecordTupleFlatten<'sval, S>,
+    count: usize,
+}
+
+impl<'sval, S: Stream<'sval>> Flatten<'sval> for SizedRecordTupleFlatten<'sval, S> {
+    type Stream = S;
+    type LabelStream = LabelBuf<'sval>;
+
+    fn stream(&mut self) -> &mut Self::Stream {
+        self.inner.stream()
+    }
+
+    fn label_stream(&mut self) -> &mut Self::LabelStream {
+        self.inner.label_stream()
+    }
+
+    fn flattened_value_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: &Label,
+        index: &Index,
+    ) -> sval::Result {
+        self.count += 1;
+        self.inner.flattened_value_begin(tag, label, index)
+    }
+
+    fn flattened_value_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: &Label,
+        index: &Index,
+    ) -> sval::Result {
+        self.inner.flattened_value_end(tag, label, index)
+    }
+}
+
+#[cfg(feature = "alloc")]
+struct PrefixedRecordTupleFlatten<'sval, 'a, S> {
+    stream: S,
+    label_stream: LabelBuf<'sval>,
+    prefix: &'a str,
+}
+
+#[cfg(feature = "alloc")]
+impl<'sval, 'a, S: Stream<'sval>> Flatten<'sval> for PrefixedRecordTupleFlatten<'sval, 'a, S> {
+    type Stream = S;
+    type LabelStream = LabelBuf<'sval>;
+
+    fn stream(&mut self) -> &mut Self::Stream {
+        &mut self.stream
+    }
+
+    fn label_stream(&mut self) -> &mut Self::LabelStream {
+        &mut self.label_stream
+    }
+
+    fn flattened_value_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: &Label,
+        index: &Index,
+    ) -> sval::Result {
+        self.stream
+            .record_tuple_value_begin(tag, &prefix_label(self.prefix, label), index)
+    }
+
+    fn flattened_value_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: &Label,
+        index: &Index,
+    ) -> sval::Result {
+        self.stream
+            .record_tuple_value_end(tag, &prefix_label(self.prefix, label), index)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use sval_derive_macros::*;
@@ -646,6 +794,187 @@ mod tests {
                 ]
             },
         );
+
+        // Keys mixing numbers and text are concatenated
+        sval_test::assert_tokens(
+            &Outer {
+                a: 1,
+                i: sval::MapSlice::new(&[(("b", 1), 2), (("c", 2), 3)]),
+                d: 4,
+            },
+            {
+                use sval_test::Token::*;
+
+                &[
+                    RecordTupleBegin(None, Some(Label::new("Outer")), None, None),
+                    RecordTupleValueBegin(None, Label::new("a"), Index::new(0)),
+                    I32(1),
+                    RecordTupleValueEnd(None, Label::new("a"), Index::new(0)),
+                    RecordTupleValueBegin(None, Label::new("b1"), Index::new(1)),
+                    I32(2),
+                    RecordTupleValueEnd(None, Label::new("b1"), Index::new(1)),
+                    RecordTupleValueBegin(None, Label::new("c2"), Index::new(2)),
+                    I32(3),
+                    RecordTupleValueEnd(None, Label::new("c2"), Index::new(2)),
+                    RecordTupleValueBegin(None, Label::new("d"), Index::new(3)),
+                    I32(4),
+                    RecordTupleValueEnd(None, Label::new("d"), Index::new(3)),
+                    RecordTupleEnd(None, Some(Label::new("Outer")), None),
+                ]
+            },
+        );
+    }
+
+    #[test]
+    fn flatten_record_sized() {
+        #[derive(Value)]
+        #[sval(unindexed_fields)]
+        struct Inner {
+            b: i32,
+            c: i32,
+        }
+
+        struct SizedOuter<I> {
+            a: i32,
+            i: I,
+            i_len: usize,
+        }
+
+        impl<I: sval::Value> sval::Value for SizedOuter<I> {
+            fn stream<'sval, S: Stream<'sval> + ?Sized>(
+                &'sval self,
+                stream: &mut S,
+            ) -> sval::Result {
+                let mut offset = 0;
+
+                stream.record_tuple_begin(
+                    None,
+                    Some(&Label::new("Outer")),
+                    None,
+                    Some(1 + self.i_len),
+                )?;
+
+                stream.record_tuple_value_begin(
+                    None,
+                    &Label::new("a"),
+                    &Index::from(offset).with_tag(&sval::tags::VALUE_OFFSET),
+                )?;
+                stream.i32(self.a)?;
+                stream.record_tuple_value_end(
+                    None,
+                    &Label::new("a"),
+                    &Index::from(offset).with_tag(&sval::tags::VALUE_OFFSET),
+                )?;
+                offset += 1;
+
+                offset = flatten_to_record_tuple_sized(&mut *stream, &self.i, offset, self.i_len)?;
+
+                let _ = offset;
+                stream.record_tuple_end(None, Some(&Label::new("Outer")), None)
+            }
+        }
+
+        sval_test::assert_tokens(
+            &SizedOuter {
+                a: 1,
+                i: Inner { b: 2, c: 3 },
+                i_len: 2,
+            },
+            {
+                use sval_test::Token::*;
+
+                &[
+                    RecordTupleBegin(None, Some(Label::new("Outer")), None, Some(3)),
+                    RecordTupleValueBegin(None, Label::new("a"), Index::new(0)),
+                    I32(1),
+                    RecordTupleValueEnd(None, Label::new("a"), Index::new(0)),
+                    RecordTupleValueBegin(None, Label::new("b"), Index::new(1)),
+                    I32(2),
+                    RecordTupleValueEnd(None, Label::new("b"), Index::new(1)),
+                    RecordTupleValueBegin(None, Label::new("c"), Index::new(2)),
+                    I32(3),
+                    RecordTupleValueEnd(None, Label::new("c"), Index::new(2)),
+                    RecordTupleEnd(None, Some(Label::new("Outer")), None),
+                ]
+            },
+        );
+
+        assert!(sval_test::TokenBuf::new()
+            .value(&SizedOuter {
+                a: 1,
+                i: Inner { b: 2, c: 3 },
+                i_len: 3,
+            })
+            .is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn flatten_record_with_prefix() {
+        #[derive(Value)]
+        struct Inner {
+            city: &'static str,
+            zip: i32,
+        }
+
+        struct Prefixed {
+            a: Inner,
+            b: Inner,
+        }
+
+        impl sval::Value for Prefixed {
+            fn stream<'sval, S: Stream<'sval> + ?Sized>(
+                &'sval self,
+                stream: &mut S,
+            ) -> sval::Result {
+                let mut offset = 0;
+
+                stream.record_tuple_begin(None, Some(&Label::new("Prefixed")), None, None)?;
+
+                offset = flatten_to_record_tuple_with_prefix(&mut *stream, &self.a, offset, "a_")?;
+                offset = flatten_to_record_tuple_with_prefix(&mut *stream, &self.b, offset, "b_")?;
+
+                let _ = offset;
+                stream.record_tuple_end(None, Some(&Label::new("Prefixed")), None)
+            }
+        }
+
+        sval_test::assert_tokens(
+            &Prefixed {
+                a: Inner {
+                    city: "Brisbane",
+                    zip: 4000,
+                },
+                b: Inner {
+                    city: "Sydney",
+                    zip: 2000,
+                },
+            },
+            {
+                use sval_test::Token::*;
+
+                &[
+                    RecordTupleBegin(None, Some(Label::new("Prefixed")), None, None),
+                    RecordTupleValueBegin(None, Label::new("a_city"), Index::new(0)),
+                    TextBegin(Some(8)),
+                    TextFragment("Brisbane"),
+                    TextEnd,
+                    RecordTupleValueEnd(None, Label::new("a_city"), Index::new(0)),
+                    RecordTupleValueBegin(None, Label::new("a_zip"), Index::new(1)),
+                    I32(4000),
+                    RecordTupleValueEnd(None, Label::new("a_zip"), Index::new(1)),
+                    RecordTupleValueBegin(None, Label::new("b_city"), Index::new(2)),
+                    TextBegin(Some(6)),
+                    TextFragment("Sydney"),
+                    TextEnd,
+                    RecordTupleValueEnd(None, Label::new("b_city"), Index::new(2)),
+                    RecordTupleValueBegin(None, Label::new("b_zip"), Index::new(3)),
+                    I32(2000),
+                    RecordTupleValueEnd(None, Label::new("b_zip"), Index::new(3)),
+                    RecordTupleEnd(None, Some(Label::new("Prefixed")), None),
+                ]
+            },
+        );
     }
 
     #[test]
@@ -6,6 +6,10 @@ use sval::{Index, Label, Stream, Tag};
 
 /**
 Flatten the fields of a value onto a sequence.
+
+Each value in the flattened map, sequence, record, or tuple is streamed as a value
+in the parent sequence. Map keys, record labels, and tuple indexes are discarded.
+Any other value, like a primitive, is silently ignored.
  */
 pub fn flatten_to_seq<'sval>(
     stream: &mut (impl Stream<'sval> + ?Sized),
@@ -292,4 +296,28 @@ mod tests {
             ]
         });
     }
+
+    #[test]
+    fn flatten_primitive() {
+        let expected = {
+            use sval_test::Token::*;
+
+            &[
+                SeqBegin(None),
+                SeqValueBegin,
+                I32(1),
+                SeqValueEnd,
+                SeqValueBegin,
+                I32(4),
+                SeqValueEnd,
+                SeqEnd,
+            ]
+        };
+
+        sval_test::assert_tokens(&Outer(1, 2, 4), expected);
+        sval_test::assert_tokens(&Outer(1, true, 4), expected);
+        sval_test::assert_tokens(&Outer(1, "text", 4), expected);
+        sval_test::assert_tokens(&Outer(1, (), 4), expected);
+        sval_test::assert_tokens(&Outer(1, sval::BinarySlice::new(b"binary"), 4), expected);
+    }
 }
@@ -4,6 +4,9 @@ Treat any [`sval::Value`] as a [`core::fmt::Debug`].
 This crate provides [`ToFmt`], a wrapper around any [`sval::Value`]
 that formats it using the same output that you'd get if you
 derived [`core::fmt::Debug`].
+
+[`ToDisplay`] is a similar wrapper that formats text without quotes,
+for values that have a natural [`core::fmt::Display`].
 */
 
 #![no_std]
@@ -12,8 +15,12 @@ derived [`core::fmt::Debug`].
 #[cfg(any(test, feature = "alloc"))]
 extern crate alloc;
 
+#[cfg(feature = "std")]
+extern crate std;
+
 mod writer;
 
+mod to_display;
 mod to_fmt;
 mod to_value;
 mod to_write;
@@ -21,10 +28,18 @@ mod to_write;
 pub mod tags;
 mod token_write;
 
-pub use self::{to_fmt::*, to_value::*, to_write::*, token_write::*};
+pub use self::{to_display::*, to_fmt::*, to_value::*, to_write::*, token_write::*};
 
 #[cfg(feature = "alloc")]
 mod to_string;
+#[cfg(feature = "alloc")]
+mod to_tree;
 
 #[cfg(feature = "alloc")]
-pub use self::to_string::stream_to_string;
+pub use self::{to_string::stream_to_string, to_tree::stream_to_tree};
+
+#[cfg(feature = "std")]
+mod to_io;
+
+#[cfg(feature = "std")]
+pub use self::to_io::stream_to_io_write;
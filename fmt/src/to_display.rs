@@ -0,0 +1,66 @@
+use core::fmt;
+
+use crate::writer::Writer;
+
+/**
+Adapt an [`sval::Value`] into a [`fmt::Display`].
+
+Text that's the whole value is written as-is, without quotes or escaping, so `"a"` is
+formatted as `a`. Numbers, booleans, and other scalars are written the same as [`ToFmt`](crate::ToFmt).
+
+There's no natural display format for maps, sequences, records, or tuples, so they're
+written in the same style as [`fmt::Debug`]. Any text nested within them is still quoted
+and escaped, so the structure of the value stays unambiguous.
+
+```
+assert_eq!("a string", sval_fmt::ToDisplay::new("a string").to_string());
+assert_eq!("[\"a\", \"b\"]", sval_fmt::ToDisplay::new(["a", "b"]).to_string());
+```
+*/
+#[repr(transparent)]
+pub struct ToDisplay<V: ?Sized>(V);
+
+impl<V: sval::Value> ToDisplay<V> {
+    /**
+    Adapt an [`sval::Value`] into a [`fmt::Display`].
+    */
+    pub fn new(value: V) -> ToDisplay<V> {
+        ToDisplay(value)
+    }
+}
+
+impl<V: sval::Value + ?Sized> ToDisplay<V> {
+    /**
+    Adapt a reference to an [`sval::Value`] into a [`fmt::Display`].
+    */
+    pub fn new_borrowed(value: &V) -> &ToDisplay<V> {
+        // SAFETY: `&V` and `&ToDisplay<V>` have the same ABI
+        unsafe { &*(value as *const _ as *const ToDisplay<V>) }
+    }
+}
+
+/**
+Format a value into an underlying formatter, writing text that's the whole value as-is.
+
+See [`ToDisplay`] for details.
+*/
+pub fn stream_to_fmt_display(fmt: &mut fmt::Formatter, v: impl sval::Value) -> fmt::Result {
+    v.stream(&mut Writer::new(fmt).with_display())
+        .map_err(|_| fmt::Error)
+}
+
+impl<V: sval::Value> fmt::Debug for ToDisplay<V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&crate::ToFmt::new(&self.0), f)
+    }
+}
+
+impl<V: sval::Value> fmt::Display for ToDisplay<V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // See the `Display` impl for `ToFmt<V>`
+        match stream_to_fmt_display(f, &self.0) {
+            Ok(()) => Ok(()),
+            Err(e) => write!(f, "<{}>", e),
+        }
+    }
+}
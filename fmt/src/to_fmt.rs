@@ -15,6 +15,26 @@ impl<V: sval::Value> ToFmt<V> {
     pub fn new(value: V) -> ToFmt<V> {
         ToFmt(value)
     }
+
+    /**
+    Format floating point numbers in the value with a fixed number of decimal places.
+
+    Floats are written as if by `format!("{:.digits$}", value)`, so whole numbers still get
+    `digits` decimal places, and very large or very small numbers are written in full rather
+    than in exponent form.
+
+    ```
+    let fmt = sval_fmt::ToFmt::new(3.14159f64).with_float_precision(2);
+
+    assert_eq!("3.14", fmt.to_string());
+    ```
+    */
+    pub fn with_float_precision(self, digits: usize) -> ToFmt<FloatPrecision<V>> {
+        ToFmt(FloatPrecision {
+            value: self.0,
+            digits,
+        })
+    }
 }
 
 impl<V: sval::Value + ?Sized> ToFmt<V> {
@@ -34,6 +54,16 @@ pub fn stream_to_fmt(fmt: &mut fmt::Formatter, v: impl sval::Value) -> fmt::Resu
     v.stream(&mut Writer::new(fmt)).map_err(|_| fmt::Error)
 }
 
+/**
+A value formatted by [`ToFmt`] with a fixed number of decimal places for floats.
+
+This type is created by [`ToFmt::with_float_precision`].
+*/
+pub struct FloatPrecision<V> {
+    value: V,
+    digits: usize,
+}
+
 impl<V: sval::Value> fmt::Debug for ToFmt<V> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         fmt::Display::fmt(self, f)
@@ -51,3 +81,21 @@ impl<V: sval::Value> fmt::Display for ToFmt<V> {
         }
     }
 }
+
+impl<V: sval::Value> fmt::Debug for ToFmt<FloatPrecision<V>> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl<V: sval::Value> fmt::Display for ToFmt<FloatPrecision<V>> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut writer = Writer::new(&mut *f).with_float_precision(self.0.digits);
+
+        // See the `Display` impl for `ToFmt<V>`
+        match self.0.value.stream(&mut writer) {
+            Ok(()) => Ok(()),
+            Err(e) => write!(f, "<{}>", e),
+        }
+    }
+}
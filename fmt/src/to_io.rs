@@ -0,0 +1,33 @@
+use core::fmt;
+use std::io::{self, Write};
+
+/**
+Format a value into an underlying [`io::Write`].
+
+This method will use a default format that's like Rust's `Debug`.
+The formatted value is always valid UTF-8, so its bytes are written to `io` directly.
+*/
+pub fn stream_to_io_write(io: impl Write, v: impl sval::Value) -> io::Result<()> {
+    struct IoToFmt<W> {
+        io: W,
+        err: Option<io::Error>,
+    }
+
+    impl<W: Write> fmt::Write for IoToFmt<W> {
+        fn write_str(&mut self, v: &str) -> fmt::Result {
+            self.io.write_all(v.as_bytes()).map_err(|e| {
+                self.err = Some(e);
+                fmt::Error
+            })
+        }
+    }
+
+    let mut io = IoToFmt { io, err: None };
+
+    match crate::stream_to_write(&mut io, v) {
+        Ok(()) => Ok(()),
+        Err(_) => Err(io
+            .err
+            .unwrap_or_else(|| io::Error::other("failed to format a value"))),
+    }
+}
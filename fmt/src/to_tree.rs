@@ -0,0 +1,414 @@
+use alloc::{string::String, vec::Vec};
+use core::fmt::{self, Write};
+
+use crate::TokenWrite;
+
+/**
+Format a value into a tree with box-drawing characters.
+
+Each field, entry, or element of a map, record, sequence, or tuple is written on its own
+line, connected to its parent with `├──`, `└──`, and `│`. Nodes are labelled with their
+field name, key, or index. Containers are written with their type name, or with empty
+brackets if they don't have one. Leaf values and map keys are written inline using the same
+format as [`stream_to_string`](crate::stream_to_string).
+
+```text
+MapStruct
+├── field_0: 42
+├── field_1: []
+│   ├── 0: 1
+│   └── 1: 2
+└── field_2: "text"
+```
+*/
+pub fn stream_to_tree(v: impl sval::Value) -> Result<String, fmt::Error> {
+    let mut tree = TreeWriter {
+        out: String::new(),
+        levels: Vec::new(),
+        inline: 0,
+        at_value_start: true,
+        has_head: false,
+    };
+
+    crate::stream_to_token_write(&mut tree, v)?;
+
+    Ok(tree.out)
+}
+
+struct TreeWriter {
+    out: String,
+    levels: Vec<Level>,
+    inline: usize,
+    at_value_start: bool,
+    has_head: bool,
+}
+
+/**
+An open map, record, sequence, or tuple.
+*/
+enum Level {
+    /**
+    A container with a line for each of its values.
+    */
+    Tree {
+        column: usize,
+        last_child: Option<usize>,
+        next_index: usize,
+    },
+    /**
+    A labelled tuple that hasn't received any values yet.
+
+    Tagged values are written as tuples without fields, so this level will become either
+    a `Tree` or a `Wrapper` depending on what's written next.
+    */
+    Undetermined,
+    /**
+    A labelled tagged value that wraps another.
+    */
+    Wrapper { close: bool },
+}
+
+/**
+A writer for values that are written inline, like map keys.
+*/
+struct Inline<'a>(&'a mut String);
+
+impl<'a> Write for Inline<'a> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.write_str(s)
+    }
+}
+
+impl<'a> TokenWrite for Inline<'a> {}
+
+impl TreeWriter {
+    fn inline(&mut self) -> Inline<'_> {
+        Inline(&mut self.out)
+    }
+
+    fn column(&self) -> usize {
+        self.levels
+            .iter()
+            .filter(|level| matches!(level, Level::Tree { .. }))
+            .count()
+    }
+
+    fn value_begin(&mut self, is_container: bool) -> fmt::Result {
+        if !self.at_value_start {
+            return Ok(());
+        }
+
+        self.at_value_start = false;
+
+        if let Some(level @ Level::Undetermined) = self.levels.last_mut() {
+            if is_container {
+                *level = Level::Wrapper { close: false };
+                self.has_head = false;
+                self.out.write_str(" ")?;
+            } else {
+                *level = Level::Wrapper { close: true };
+                self.out.write_str("(")?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn container_begin(&mut self, empty: &str) -> fmt::Result {
+        self.value_begin(true)?;
+
+        if !self.has_head {
+            self.out.write_str(empty)?;
+        }
+
+        let column = self.column();
+        self.levels.push(Level::Tree {
+            column,
+            last_child: None,
+            next_index: 0,
+        });
+
+        Ok(())
+    }
+
+    fn child_begin(&mut self) -> fmt::Result {
+        let column = self.column();
+
+        if let Some(level @ Level::Undetermined) = self.levels.last_mut() {
+            *level = Level::Tree {
+                column,
+                last_child: None,
+                next_index: 0,
+            };
+
+            if !self.has_head {
+                self.out.write_str("()")?;
+            }
+        }
+
+        self.out.write_str("\n")?;
+        let start = self.out.len();
+
+        let column = match self.levels.last_mut() {
+            Some(Level::Tree {
+                column, last_child, ..
+            }) => {
+                *last_child = Some(start);
+                *column
+            }
+            _ => return Err(fmt::Error),
+        };
+
+        for _ in 0..column {
+            self.out.write_str("│   ")?;
+        }
+        self.out.write_str("├── ")?;
+
+        self.at_value_start = true;
+        self.has_head = false;
+
+        Ok(())
+    }
+
+    fn child_index_begin(&mut self) -> fmt::Result {
+        self.child_begin()?;
+
+        let index = match self.levels.last_mut() {
+            Some(Level::Tree { next_index, .. }) => {
+                let index = *next_index;
+                *next_index += 1;
+                index
+            }
+            _ => return Err(fmt::Error),
+        };
+
+        write!(self.out, "{}: ", index)
+    }
+
+    fn container_end(&mut self, empty: &str) -> fmt::Result {
+        match self.levels.pop() {
+            Some(Level::Tree {
+                column,
+                last_child: Some(last_child),
+                ..
+            }) => {
+                self.connect_last_child(column, last_child);
+                Ok(())
+            }
+            Some(Level::Tree {
+                last_child: None, ..
+            }) => Ok(()),
+            Some(Level::Undetermined) => {
+                if !self.has_head {
+                    self.out.write_str(empty)?;
+                }
+
+                Ok(())
+            }
+            Some(Level::Wrapper { close }) => {
+                if close {
+                    self.out.write_str(")")?;
+                }
+
+                Ok(())
+            }
+            None => Err(fmt::Error),
+        }
+    }
+
+    /**
+    Rewrite the connectors of the last child in a container once it's known to be the last.
+
+    The child's own line gets `└──` instead of `├──`, and the lines of its descendants
+    no longer continue the `│` from this container.
+    */
+    fn connect_last_child(&mut self, column: usize, last_child: usize) {
+        let tail = self.out.split_off(last_child);
+
+        for (i, line) in tail.split('\n').enumerate() {
+            if i > 0 {
+                self.out.push('\n');
+            }
+
+            let (prefix, rest) = split_at_char(line, column * 4);
+            let (_, rest) = split_at_char(rest, 4);
+
+            self.out.push_str(prefix);
+            self.out.push_str(if i == 0 { "└── " } else { "    " });
+            self.out.push_str(rest);
+        }
+    }
+}
+
+fn split_at_char(s: &str, n: usize) -> (&str, &str) {
+    match s.char_indices().nth(n) {
+        Some((i, _)) => s.split_at(i),
+        None => (s, ""),
+    }
+}
+
+impl Write for TreeWriter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.out.write_str(s)
+    }
+}
+
+impl TokenWrite for TreeWriter {
+    fn write_token<T: fmt::Display>(&mut self, tag: &sval::Tag, token: T) -> fmt::Result {
+        let _ = tag;
+
+        self.value_begin(false)?;
+        self.has_head = true;
+
+        write!(self.out, "{}", token)
+    }
+
+    fn write_map_begin(&mut self) -> fmt::Result {
+        if self.inline > 0 {
+            self.inline += 1;
+            return self.inline().write_map_begin();
+        }
+
+        self.container_begin("{}")
+    }
+
+    fn write_map_key_begin(&mut self, is_first: bool) -> fmt::Result {
+        if self.inline > 0 {
+            return self.inline().write_map_key_begin(is_first);
+        }
+
+        self.child_begin()?;
+
+        self.at_value_start = false;
+        self.inline = 1;
+
+        Ok(())
+    }
+
+    fn write_map_value_begin(&mut self, is_first: bool) -> fmt::Result {
+        if self.inline > 1 {
+            return self.inline().write_map_value_begin(is_first);
+        }
+
+        self.inline = 0;
+        self.at_value_start = true;
+        self.has_head = false;
+
+        self.out.write_str(": ")
+    }
+
+    fn write_map_end(&mut self, is_empty: bool) -> fmt::Result {
+        if self.inline > 0 {
+            self.inline -= 1;
+            return self.inline().write_map_end(is_empty);
+        }
+
+        self.container_end("{}")
+    }
+
+    fn write_record_type(&mut self, ty: &str) -> fmt::Result {
+        if self.inline > 0 {
+            return self.inline().write_record_type(ty);
+        }
+
+        self.value_begin(true)?;
+        self.has_head = true;
+
+        self.out.write_str(ty)
+    }
+
+    fn write_record_begin(&mut self) -> fmt::Result {
+        if self.inline > 0 {
+            self.inline += 1;
+            return self.inline().write_record_begin();
+        }
+
+        self.container_begin("{}")
+    }
+
+    fn write_record_value_begin(&mut self, field: &str, is_first: bool) -> fmt::Result {
+        if self.inline > 0 {
+            return self.inline().write_record_value_begin(field, is_first);
+        }
+
+        self.child_begin()?;
+
+        write!(self.out, "{}: ", field)
+    }
+
+    fn write_record_end(&mut self, is_empty: bool) -> fmt::Result {
+        if self.inline > 0 {
+            self.inline -= 1;
+            return self.inline().write_record_end(is_empty);
+        }
+
+        self.container_end("{}")
+    }
+
+    fn write_seq_begin(&mut self) -> fmt::Result {
+        if self.inline > 0 {
+            self.inline += 1;
+            return self.inline().write_seq_begin();
+        }
+
+        self.container_begin("[]")
+    }
+
+    fn write_seq_value_begin(&mut self, is_first: bool) -> fmt::Result {
+        if self.inline > 0 {
+            return self.inline().write_seq_value_begin(is_first);
+        }
+
+        self.child_index_begin()
+    }
+
+    fn write_seq_end(&mut self, is_empty: bool) -> fmt::Result {
+        if self.inline > 0 {
+            self.inline -= 1;
+            return self.inline().write_seq_end(is_empty);
+        }
+
+        self.container_end("[]")
+    }
+
+    fn write_tuple_type(&mut self, ty: &str) -> fmt::Result {
+        if self.inline > 0 {
+            return self.inline().write_tuple_type(ty);
+        }
+
+        self.value_begin(true)?;
+        self.has_head = true;
+
+        self.out.write_str(ty)
+    }
+
+    fn write_tuple_begin(&mut self) -> fmt::Result {
+        if self.inline > 0 {
+            self.inline += 1;
+            return self.inline().write_tuple_begin();
+        }
+
+        self.value_begin(true)?;
+        self.levels.push(Level::Undetermined);
+        self.at_value_start = true;
+
+        Ok(())
+    }
+
+    fn write_tuple_value_begin(&mut self, is_first: bool) -> fmt::Result {
+        if self.inline > 0 {
+            return self.inline().write_tuple_value_begin(is_first);
+        }
+
+        self.child_index_begin()
+    }
+
+    fn write_tuple_end(&mut self, is_empty: bool) -> fmt::Result {
+        if self.inline > 0 {
+            self.inline -= 1;
+            return self.inline().write_tuple_end(is_empty);
+        }
+
+        self.container_end("()")
+    }
+}
@@ -4,6 +4,9 @@ use core::fmt::{self, Display, Write};
 pub(crate) struct Writer<W> {
     is_current_depth_empty: bool,
     is_number: bool,
+    float_precision: Option<usize>,
+    is_display: bool,
+    depth: usize,
     out: W,
 }
 
@@ -128,9 +131,31 @@ impl<W> Writer<W> {
         Writer {
             is_current_depth_empty: true,
             is_number: false,
+            float_precision: None,
+            is_display: false,
+            depth: 0,
             out,
         }
     }
+
+    pub fn with_float_precision(mut self, digits: usize) -> Self {
+        self.float_precision = Some(digits);
+        self
+    }
+
+    pub fn with_display(mut self) -> Self {
+        self.is_display = true;
+        self
+    }
+
+    /**
+    Whether text is written as-is, without quotes or escaping.
+
+    This is only the case for text that isn't nested in any brackets in display mode.
+    */
+    fn is_text_unquoted(&self) -> bool {
+        self.is_display && self.depth == 0
+    }
 }
 
 impl<'sval, W: TokenWrite> sval::Stream<'sval> for Writer<W> {
@@ -147,7 +172,7 @@ impl<'sval, W: TokenWrite> sval::Stream<'sval> for Writer<W> {
     }
 
     fn text_begin(&mut self, _: Option<usize>) -> sval::Result {
-        if self.is_number {
+        if self.is_number || self.is_text_unquoted() {
             Ok(())
         } else {
             self.out.write_text_quote().map_err(|_| sval::Error::new())
@@ -159,6 +184,10 @@ impl<'sval, W: TokenWrite> sval::Stream<'sval> for Writer<W> {
             self.out
                 .write_number(fragment)
                 .map_err(|_| sval::Error::new())
+        } else if self.is_text_unquoted() {
+            self.out
+                .write_token(&tags::TEXT, fragment)
+                .map_err(|_| sval::Error::new())
         } else {
             self.out
                 .write_text(fragment)
@@ -167,7 +196,7 @@ impl<'sval, W: TokenWrite> sval::Stream<'sval> for Writer<W> {
     }
 
     fn text_end(&mut self) -> sval::Result {
-        if self.is_number {
+        if self.is_number || self.is_text_unquoted() {
             Ok(())
         } else {
             self.out.write_text_quote().map_err(|_| sval::Error::new())
@@ -253,13 +282,25 @@ impl<'sval, W: TokenWrite> sval::Stream<'sval> for Writer<W> {
     }
 
     fn f32(&mut self, value: f32) -> sval::Result {
-        self.out.write_f32(value).map_err(|_| sval::Error::new())?;
+        match self.float_precision {
+            Some(digits) => self
+                .out
+                .write_number(format_args!("{:.*}", digits, value))
+                .map_err(|_| sval::Error::new())?,
+            None => self.out.write_f32(value).map_err(|_| sval::Error::new())?,
+        }
 
         Ok(())
     }
 
     fn f64(&mut self, value: f64) -> sval::Result {
-        self.out.write_f64(value).map_err(|_| sval::Error::new())?;
+        match self.float_precision {
+            Some(digits) => self
+                .out
+                .write_number(format_args!("{:.*}", digits, value))
+                .map_err(|_| sval::Error::new())?,
+            None => self.out.write_f64(value).map_err(|_| sval::Error::new())?,
+        }
 
         Ok(())
     }
@@ -267,6 +308,7 @@ impl<'sval, W: TokenWrite> sval::Stream<'sval> for Writer<W> {
     fn map_begin(&mut self, _: Option<usize>) -> sval::Result {
         self.is_number = false;
         self.is_current_depth_empty = true;
+        self.depth += 1;
 
         self.out.write_map_begin().map_err(|_| sval::Error::new())?;
 
@@ -300,6 +342,8 @@ impl<'sval, W: TokenWrite> sval::Stream<'sval> for Writer<W> {
     }
 
     fn map_end(&mut self) -> sval::Result {
+        self.depth = self.depth.saturating_sub(1);
+
         self.out
             .write_map_end(self.is_current_depth_empty)
             .map_err(|_| sval::Error::new())?;
@@ -310,6 +354,7 @@ impl<'sval, W: TokenWrite> sval::Stream<'sval> for Writer<W> {
     fn seq_begin(&mut self, _: Option<usize>) -> sval::Result {
         self.is_number = false;
         self.is_current_depth_empty = true;
+        self.depth += 1;
 
         self.out.write_seq_begin().map_err(|_| sval::Error::new())?;
 
@@ -331,6 +376,8 @@ impl<'sval, W: TokenWrite> sval::Stream<'sval> for Writer<W> {
     }
 
     fn seq_end(&mut self) -> sval::Result {
+        self.depth = self.depth.saturating_sub(1);
+
         self.out
             .write_seq_end(self.is_current_depth_empty)
             .map_err(|_| sval::Error::new())?;
@@ -375,6 +422,8 @@ impl<'sval, W: TokenWrite> sval::Stream<'sval> for Writer<W> {
         }
 
         if let Some(label) = label {
+            self.depth += 1;
+
             self.out
                 .write_tuple_type(label.as_str())
                 .map_err(|_| sval::Error::new())?;
@@ -397,6 +446,8 @@ impl<'sval, W: TokenWrite> sval::Stream<'sval> for Writer<W> {
         }
 
         if label.is_some() {
+            self.depth = self.depth.saturating_sub(1);
+
             self.out
                 .write_tuple_end(false)
                 .map_err(|_| sval::Error::new())?;
@@ -433,6 +484,7 @@ impl<'sval, W: TokenWrite> sval::Stream<'sval> for Writer<W> {
     ) -> sval::Result {
         self.is_number = false;
         self.is_current_depth_empty = true;
+        self.depth += 1;
 
         if let Some(label) = label {
             self.out
@@ -467,6 +519,8 @@ impl<'sval, W: TokenWrite> sval::Stream<'sval> for Writer<W> {
         _: Option<&sval::Label>,
         _: Option<&sval::Index>,
     ) -> sval::Result {
+        self.depth = self.depth.saturating_sub(1);
+
         self.out
             .write_record_end(self.is_current_depth_empty)
             .map_err(|_| sval::Error::new())?;
@@ -483,6 +537,7 @@ impl<'sval, W: TokenWrite> sval::Stream<'sval> for Writer<W> {
     ) -> sval::Result {
         self.is_number = false;
         self.is_current_depth_empty = true;
+        self.depth += 1;
 
         if let Some(label) = label {
             self.out
@@ -517,6 +572,8 @@ impl<'sval, W: TokenWrite> sval::Stream<'sval> for Writer<W> {
         _: Option<&sval::Label>,
         _: Option<&sval::Index>,
     ) -> sval::Result {
+        self.depth = self.depth.saturating_sub(1);
+
         self.out
             .write_tuple_end(self.is_current_depth_empty)
             .map_err(|_| sval::Error::new())?;
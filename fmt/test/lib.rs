@@ -18,6 +18,11 @@ fn assert_fmt(v: impl sval::Value + fmt::Debug) {
     let buffered = sval_buffer::TextBuf::collect(&to_debug).unwrap();
 
     assert_eq!(expected, buffered.as_str());
+
+    let mut io = Vec::new();
+    sval_fmt::stream_to_io_write(&mut io, &v).unwrap();
+
+    assert_eq!(expected, String::from_utf8(io).unwrap());
 }
 
 #[derive(Value, Debug)]
@@ -75,6 +80,30 @@ fn debug_retains_flags() {
     assert_eq!("0042", format!("{:>04?}", sval_fmt::ToFmt::new(42i64)));
 }
 
+#[test]
+#[allow(clippy::approx_constant)]
+fn debug_float_precision() {
+    fn fmt(v: impl sval::Value, digits: usize) -> String {
+        format!("{:?}", sval_fmt::ToFmt::new(v).with_float_precision(digits))
+    }
+
+    assert_eq!("3.14", fmt(3.14159f64, 2));
+    assert_eq!("3.14", fmt(3.14159f32, 2));
+    assert_eq!("3", fmt(3.14159f64, 0));
+
+    // Whole numbers still get decimal places
+    assert_eq!("42.000", fmt(42.0f64, 3));
+
+    // Very large and small magnitudes are written in full
+    assert_eq!("10000000000000000000000.0", fmt(1e22f64, 1));
+    assert_eq!("0.00", fmt(1e-10f64, 2));
+    assert_eq!("-0.00", fmt(-1e-10f64, 2));
+
+    // Only floats are affected
+    assert_eq!("(42, 1.50, \"a\")", fmt((42, 1.5f64, "a"), 2));
+    assert_eq!("[inf, NaN]", fmt([f64::INFINITY, f64::NAN], 2));
+}
+
 #[test]
 fn debug_primitive() {
     assert_fmt(42i32);
@@ -82,6 +111,41 @@ fn debug_primitive() {
     assert_fmt("a string");
 }
 
+#[test]
+fn display_primitive() {
+    fn display(v: impl sval::Value) -> String {
+        sval_fmt::ToDisplay::new(v).to_string()
+    }
+
+    // Text is written without quotes or escaping
+    assert_eq!("a string", display("a string"));
+    assert_eq!("a \"quoted\"\nstring", display("a \"quoted\"\nstring"));
+
+    assert_eq!("42", display(42i32));
+    assert_eq!("-1.5", display(-1.5f64));
+    assert_eq!("true", display(true));
+}
+
+#[test]
+fn display_container() {
+    fn display(v: impl sval::Value) -> String {
+        sval_fmt::ToDisplay::new(v).to_string()
+    }
+
+    // Containers are written debug-style, with any text in them quoted
+    let map = std::collections::BTreeMap::from([("a", 1), ("b", 2)]);
+
+    assert_eq!(sval_fmt::ToFmt::new(&map).to_string(), display(&map));
+    assert_eq!("[\"a\", \"b\"]", display(["a", "b"]));
+    assert_eq!("Some(\"a\")", display(Some("a")));
+
+    // The value can still be formatted debug-style
+    assert_eq!(
+        "\"a string\"",
+        format!("{:?}", sval_fmt::ToDisplay::new("a string"))
+    );
+}
+
 #[test]
 fn debug_option() {
     assert_fmt(Some(42i32));
@@ -100,6 +164,28 @@ fn debug_map_struct() {
     });
 }
 
+#[test]
+fn debug_to_io_write() {
+    let v = MapStruct {
+        field_0: 42,
+        field_1: true,
+        field_2: EmptyMap {},
+        field_3: "Hello",
+        field_4: &[1, 2],
+        field_5: 17,
+    };
+
+    let mut io = Vec::new();
+    sval_fmt::stream_to_io_write(&mut io, &v).unwrap();
+
+    assert_eq!(sval_fmt::stream_to_string(&v).as_bytes(), &*io);
+
+    // Errors from the writer are returned
+    let err = sval_fmt::stream_to_io_write(&mut [0u8; 4][..], &v).unwrap_err();
+
+    assert_eq!(std::io::ErrorKind::WriteZero, err.kind());
+}
+
 #[test]
 fn debug_seq_struct() {
     assert_fmt(SeqStruct(42, true, EmptyMap {}, "Hello", &[], 17));
@@ -637,3 +723,56 @@ fn failing_value_does_not_panic_to_string() {
         .to_string()
     );
 }
+
+#[test]
+fn stream_to_tree() {
+    assert_eq!(
+        r#"MapStruct
+├── field_0: 42
+├── field_1: true
+├── field_2: {}
+├── field_3: "text \"in quotes\""
+├── field_4: []
+│   ├── 0: 1
+│   └── 1: 2
+└── field_5: 17"#,
+        sval_fmt::stream_to_tree(MapStruct {
+            field_0: 42,
+            field_1: true,
+            field_2: EmptyMap {},
+            field_3: "text \"in quotes\"",
+            field_4: &[1, 2],
+            field_5: 17,
+        })
+        .unwrap()
+    );
+
+    assert_eq!(
+        "Tagged(1)",
+        sval_fmt::stream_to_tree(Enum::Tagged(1)).unwrap()
+    );
+
+    assert_eq!("42", sval_fmt::stream_to_tree(42).unwrap());
+}
+
+#[test]
+fn stream_to_tree_nested() {
+    let mut map = std::collections::BTreeMap::new();
+    map.insert("a", vec![Some(vec![1, 2]), None]);
+    map.insert("b", vec![Some(vec![3])]);
+    map.insert("c", vec![]);
+
+    assert_eq!(
+        r#"{}
+├── "a": []
+│   ├── 0: Some []
+│   │   ├── 0: 1
+│   │   └── 1: 2
+│   └── 1: None
+├── "b": []
+│   └── 0: Some []
+│       └── 0: 3
+└── "c": []"#,
+        sval_fmt::stream_to_tree(&map).unwrap()
+    );
+}
@@ -14,6 +14,9 @@ pub(crate) enum ErrorKind {
     #[cfg(feature = "std")]
     IO(std::io::Error),
     InvalidKey,
+    #[cfg(feature = "std")]
+    InvalidEntries,
+    InvalidJson(usize),
 }
 
 impl fmt::Display for Error {
@@ -23,6 +26,11 @@ impl fmt::Display for Error {
             #[cfg(feature = "std")]
             ErrorKind::IO(_) => write!(f, "failed to write JSON"),
             ErrorKind::InvalidKey => write!(f, "attempt to serialize a non-string key"),
+            #[cfg(feature = "std")]
+            ErrorKind::InvalidEntries => {
+                write!(f, "attempt to serialize a non-map value as lines of entries")
+            }
+            ErrorKind::InvalidJson(offset) => write!(f, "failed to parse JSON at byte {}", offset),
         }
     }
 }
@@ -39,6 +47,26 @@ impl Error {
             kind: ErrorKind::InvalidKey,
         }
     }
+
+    pub(crate) fn invalid_json(offset: usize) -> Self {
+        Error {
+            kind: ErrorKind::InvalidJson(offset),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    pub(crate) fn io(err: std::io::Error) -> Self {
+        Error {
+            kind: ErrorKind::IO(err),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    pub(crate) fn invalid_entries() -> Self {
+        Error {
+            kind: ErrorKind::InvalidEntries,
+        }
+    }
 }
 
 #[cfg(feature = "std")]
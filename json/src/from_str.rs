@@ -0,0 +1,430 @@
+use crate::Error;
+
+/**
+The deepest that arrays and objects can nest before parsing fails.
+*/
+const MAX_DEPTH: usize = 128;
+
+/**
+Options for parsing JSON that isn't strictly valid, like hand-written configuration files.
+
+The default options only accept strict JSON.
+*/
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    /**
+    Accept a comma after the last value in an array or object, like `[1,2,]` or `{"a":1,}`.
+    */
+    pub allow_trailing_commas: bool,
+    /**
+    Skip `//` line comments and `/* */` block comments anywhere whitespace is allowed.
+    */
+    pub allow_comments: bool,
+}
+
+/**
+Parse strict JSON and stream it into a stream.
+
+Objects are streamed as maps, arrays as sequences, and strings as text. Strings
+without escapes are streamed as borrowed fragments of the input. Integers that fit in
+an `i64` or `u64` are streamed as them. Any other number is streamed as text tagged with
+[`sval::tags::NUMBER`], so no precision is lost.
+
+Arrays and objects can nest up to 128 levels deep.
+*/
+pub fn stream_from_str<'sval>(
+    stream: &mut (impl sval::Stream<'sval> + ?Sized),
+    json: &'sval str,
+) -> Result<(), Error> {
+    stream_from_str_with_options(stream, json, ParseOptions::default())
+}
+
+/**
+Parse JSON and stream it into a stream, using the given options to relax what's accepted.
+
+This is the same as [`stream_from_str`], except any syntax enabled in the
+[`ParseOptions`] is also accepted. Relaxed syntax, like comments, never reaches the stream.
+*/
+pub fn stream_from_str_with_options<'sval>(
+    stream: &mut (impl sval::Stream<'sval> + ?Sized),
+    json: &'sval str,
+    options: ParseOptions,
+) -> Result<(), Error> {
+    let mut parser = Parser {
+        src: json,
+        pos: 0,
+        depth: 0,
+        options,
+    };
+
+    parser.value(stream)?;
+    parser.end()
+}
+
+struct Parser<'sval> {
+    src: &'sval str,
+    pos: usize,
+    depth: usize,
+    options: ParseOptions,
+}
+
+fn stream_result(r: sval::Result) -> Result<(), Error> {
+    r.map_err(|_| Error::generic())
+}
+
+impl<'sval> Parser<'sval> {
+    fn peek(&self) -> Option<u8> {
+        self.src.as_bytes().get(self.pos).copied()
+    }
+
+    fn invalid(&self) -> Error {
+        Error::invalid_json(self.pos)
+    }
+
+    fn expect(&mut self, b: u8) -> Result<(), Error> {
+        if self.peek() == Some(b) {
+            self.pos += 1;
+
+            Ok(())
+        } else {
+            Err(self.invalid())
+        }
+    }
+
+    fn skip_whitespace(&mut self) -> Result<(), Error> {
+        loop {
+            match self.peek() {
+                Some(b' ' | b'\t' | b'\n' | b'\r') => self.pos += 1,
+                Some(b'/') if self.options.allow_comments => self.skip_comment()?,
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    fn skip_comment(&mut self) -> Result<(), Error> {
+        let rest = &self.src[self.pos..];
+
+        if let Some(comment) = rest.strip_prefix("//") {
+            self.pos += 2 + comment.find('\n').unwrap_or(comment.len());
+
+            Ok(())
+        } else if let Some(comment) = rest.strip_prefix("/*") {
+            let len = comment
+                .find("*/")
+                .ok_or_else(|| Error::invalid_json(self.src.len()))?;
+
+            self.pos += 2 + len + 2;
+
+            Ok(())
+        } else {
+            Err(self.invalid())
+        }
+    }
+
+    fn end(&mut self) -> Result<(), Error> {
+        self.skip_whitespace()?;
+
+        if self.pos == self.src.len() {
+            Ok(())
+        } else {
+            Err(self.invalid())
+        }
+    }
+
+    fn value<S: sval::Stream<'sval> + ?Sized>(&mut self, stream: &mut S) -> Result<(), Error> {
+        self.skip_whitespace()?;
+
+        match self.peek() {
+            Some(b'{') => self.object(stream),
+            Some(b'[') => self.array(stream),
+            Some(b'"') => self.string(stream),
+            Some(b'-' | b'0'..=b'9') => self.number(stream),
+            Some(b't') => self.literal("true", stream, |stream| stream.bool(true)),
+            Some(b'f') => self.literal("false", stream, |stream| stream.bool(false)),
+            Some(b'n') => self.literal("null", stream, |stream| stream.null()),
+            _ => Err(self.invalid()),
+        }
+    }
+
+    fn literal<S: sval::Stream<'sval> + ?Sized>(
+        &mut self,
+        literal: &str,
+        stream: &mut S,
+        f: impl FnOnce(&mut S) -> sval::Result,
+    ) -> Result<(), Error> {
+        if self.src[self.pos..].starts_with(literal) {
+            self.pos += literal.len();
+
+            stream_result(f(stream))
+        } else {
+            Err(self.invalid())
+        }
+    }
+
+    fn nest(&mut self) -> Result<(), Error> {
+        if self.depth == MAX_DEPTH {
+            return Err(self.invalid());
+        }
+
+        self.depth += 1;
+        self.pos += 1;
+
+        Ok(())
+    }
+
+    /**
+    Move past a comma between values, returning `true` if the value it separates was the last one.
+    */
+    fn separator(&mut self, close: u8) -> Result<bool, Error> {
+        self.skip_whitespace()?;
+
+        match self.peek() {
+            Some(b',') => {
+                self.pos += 1;
+
+                if self.options.allow_trailing_commas {
+                    self.skip_whitespace()?;
+
+                    return Ok(self.peek() == Some(close));
+                }
+
+                Ok(false)
+            }
+            Some(b) if b == close => Ok(true),
+            _ => Err(self.invalid()),
+        }
+    }
+
+    fn object<S: sval::Stream<'sval> + ?Sized>(&mut self, stream: &mut S) -> Result<(), Error> {
+        self.nest()?;
+        stream_result(stream.map_begin(None))?;
+
+        self.skip_whitespace()?;
+
+        if self.peek() != Some(b'}') {
+            loop {
+                self.skip_whitespace()?;
+
+                if self.peek() != Some(b'"') {
+                    return Err(self.invalid());
+                }
+
+                stream_result(stream.map_key_begin())?;
+                self.string(stream)?;
+                stream_result(stream.map_key_end())?;
+
+                self.skip_whitespace()?;
+                self.expect(b':')?;
+
+                stream_result(stream.map_value_begin())?;
+                self.value(stream)?;
+                stream_result(stream.map_value_end())?;
+
+                if self.separator(b'}')? {
+                    break;
+                }
+            }
+        }
+
+        self.expect(b'}')?;
+        self.depth -= 1;
+
+        stream_result(stream.map_end())
+    }
+
+    fn array<S: sval::Stream<'sval> + ?Sized>(&mut self, stream: &mut S) -> Result<(), Error> {
+        self.nest()?;
+        stream_result(stream.seq_begin(None))?;
+
+        self.skip_whitespace()?;
+
+        if self.peek() != Some(b']') {
+            loop {
+                stream_result(stream.seq_value_begin())?;
+                self.value(stream)?;
+                stream_result(stream.seq_value_end())?;
+
+                if self.separator(b']')? {
+                    break;
+                }
+            }
+        }
+
+        self.expect(b']')?;
+        self.depth -= 1;
+
+        stream_result(stream.seq_end())
+    }
+
+    fn string<S: sval::Stream<'sval> + ?Sized>(&mut self, stream: &mut S) -> Result<(), Error> {
+        self.expect(b'"')?;
+
+        let bytes = self.src.as_bytes();
+        let start = self.pos;
+
+        // Find the closing quote, checking whether there are any escapes to decode
+        let mut end = start;
+        let mut is_escaped = false;
+        loop {
+            match bytes.get(end) {
+                Some(b'"') => break,
+                Some(b'\\') => {
+                    is_escaped = true;
+                    end += 2;
+                }
+                Some(b) if *b >= 0x20 => end += 1,
+                _ => return Err(Error::invalid_json(end)),
+            }
+        }
+
+        self.pos = end + 1;
+
+        if !is_escaped {
+            let text = &self.src[start..end];
+
+            stream_result(stream.text_begin(Some(text.len())))?;
+            stream_result(stream.text_fragment(text))?;
+            return stream_result(stream.text_end());
+        }
+
+        stream_result(stream.text_begin(None))?;
+
+        let mut pos = start;
+        while pos < end {
+            let unescaped = self.src[pos..end].find('\\').map_or(end, |i| pos + i);
+
+            if unescaped > pos {
+                stream_result(stream.text_fragment(&self.src[pos..unescaped]))?;
+            }
+
+            if unescaped == end {
+                break;
+            }
+
+            let (c, len) = self.escape(unescaped, end)?;
+            stream_result(stream.text_fragment_computed(c.encode_utf8(&mut [0; 4])))?;
+
+            pos = unescaped + len;
+        }
+
+        stream_result(stream.text_end())
+    }
+
+    /**
+    Decode the escape at `pos`, returning the character and the number of bytes it used.
+    */
+    fn escape(&self, pos: usize, end: usize) -> Result<(char, usize), Error> {
+        let c = match self.src.as_bytes()[pos + 1] {
+            b'"' => '"',
+            b'\\' => '\\',
+            b'/' => '/',
+            b'b' => '\u{8}',
+            b'f' => '\u{c}',
+            b'n' => '\n',
+            b'r' => '\r',
+            b't' => '\t',
+            b'u' => {
+                let high = self.hex(pos, end)?;
+
+                // Characters outside the basic multilingual plane are escaped as a surrogate pair
+                if (0xd800..0xdc00).contains(&high) {
+                    if self.src.as_bytes().get(pos + 6) != Some(&b'\\') {
+                        return Err(Error::invalid_json(pos));
+                    }
+
+                    let low = self.hex(pos + 6, end)?;
+                    if !(0xdc00..0xe000).contains(&low) {
+                        return Err(Error::invalid_json(pos));
+                    }
+
+                    let c = 0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00);
+
+                    return Ok((char::from_u32(c).ok_or(Error::invalid_json(pos))?, 12));
+                }
+
+                return Ok((char::from_u32(high).ok_or(Error::invalid_json(pos))?, 6));
+            }
+            _ => return Err(Error::invalid_json(pos)),
+        };
+
+        Ok((c, 2))
+    }
+
+    /**
+    Decode the 4 hex digits in a `\u` escape at `pos`.
+    */
+    fn hex(&self, pos: usize, end: usize) -> Result<u32, Error> {
+        self.src
+            .get(pos + 2..pos + 6)
+            .filter(|_| pos + 6 <= end && self.src.as_bytes()[pos + 1] == b'u')
+            .filter(|digits| digits.bytes().all(|b| b.is_ascii_hexdigit()))
+            .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+            .ok_or(Error::invalid_json(pos))
+    }
+
+    fn number<S: sval::Stream<'sval> + ?Sized>(&mut self, stream: &mut S) -> Result<(), Error> {
+        let start = self.pos;
+
+        if self.peek() == Some(b'-') {
+            self.pos += 1;
+        }
+
+        match self.peek() {
+            Some(b'0') => self.pos += 1,
+            Some(b'1'..=b'9') => self.digits()?,
+            _ => return Err(self.invalid()),
+        }
+
+        let mut is_integer = true;
+
+        if self.peek() == Some(b'.') {
+            is_integer = false;
+
+            self.pos += 1;
+            self.digits()?;
+        }
+
+        if let Some(b'e' | b'E') = self.peek() {
+            is_integer = false;
+
+            self.pos += 1;
+            if let Some(b'+' | b'-') = self.peek() {
+                self.pos += 1;
+            }
+            self.digits()?;
+        }
+
+        let number = &self.src[start..self.pos];
+
+        if is_integer {
+            if let Ok(v) = number.parse::<i64>() {
+                return stream_result(stream.i64(v));
+            }
+
+            if let Ok(v) = number.parse::<u64>() {
+                return stream_result(stream.u64(v));
+            }
+        }
+
+        stream_result(stream.tagged_begin(Some(&sval::tags::NUMBER), None, None))?;
+        stream_result(stream.text_begin(Some(number.len())))?;
+        stream_result(stream.text_fragment(number))?;
+        stream_result(stream.text_end())?;
+        stream_result(stream.tagged_end(Some(&sval::tags::NUMBER), None, None))
+    }
+
+    fn digits(&mut self) -> Result<(), Error> {
+        let len = self.src.as_bytes()[self.pos..]
+            .iter()
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+
+        if len == 0 {
+            return Err(self.invalid());
+        }
+
+        self.pos += len;
+
+        Ok(())
+    }
+}
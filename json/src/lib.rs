@@ -21,17 +21,29 @@ pub use self::value::*;
 mod to_fmt;
 pub use self::{error::*, to_fmt::*};
 
+mod from_str;
+pub use self::from_str::*;
+
 pub mod tags;
 
 #[cfg(feature = "alloc")]
 mod to_string;
 
 #[cfg(feature = "alloc")]
-pub use self::to_string::*;
+mod to_string_sorted;
+
+#[cfg(feature = "alloc")]
+mod to_string_truncated;
+
+#[cfg(feature = "alloc")]
+pub use self::{to_string::*, to_string_sorted::*, to_string_truncated::*};
 
 #[cfg(feature = "std")]
 mod to_io;
 
+#[cfg(feature = "std")]
+mod to_lines;
+
 #[cfg(feature = "std")]
 pub use self::to_io::*;
 
@@ -14,6 +14,9 @@ pub const JSON_VALUE: sval::Tag = sval::Tag::new("JSON_VALUE");
 /**
 A tag for strings that either don't contain characters that need escaping or are already escaped.
 
+Text with this tag is written between quotes as-is. It isn't checked or escaped, so any
+quotes or control characters it contains must already be escaped.
+
 # Valid datatypes
 
 - `text`
@@ -24,10 +24,73 @@ macro_rules! _try_no_conv {
 
 /**
 Stream a value as JSON to an underlying formatter.
+
+Floating point numbers that are `NaN` or infinite are written as `null`.
 */
 pub fn stream_to_fmt_write(fmt: impl Write, v: impl sval::Value) -> Result<(), Error> {
-    let mut stream = Formatter::new(fmt);
+    stream_to_fmt_write_with(Formatter::new(fmt), v)
+}
+
+/**
+Stream a value as non-strict JSON to an underlying formatter.
+
+This is the same as [`stream_to_fmt_write`], except floating point numbers that are `NaN`
+or infinite are written as the JavaScript literals `NaN`, `Infinity`, and `-Infinity`
+instead of `null`. These literals aren't valid JSON, so the result can only be read by
+consumers that accept them, like JavaScript's `eval`.
+*/
+pub fn stream_to_fmt_write_non_strict(fmt: impl Write, v: impl sval::Value) -> Result<(), Error> {
+    stream_to_fmt_write_with(Formatter::new(fmt).non_strict(), v)
+}
 
+/**
+Options for streaming a value as JSON.
+
+Values streamed with options only accept text as map keys by default. Other options
+can relax that.
+*/
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct JsonOptions {
+    /**
+    Write integer map keys as quoted numbers, like `"42"`, instead of failing.
+
+    Keys that are floating point numbers, booleans, null, maps, or sequences still fail.
+    */
+    pub stringify_int_keys: bool,
+}
+
+/**
+Stream a value as JSON to an underlying formatter, using the given options.
+
+Unlike [`stream_to_fmt_write`], which writes any primitive map key inside quotes,
+this method fails on map keys that aren't text unless the [`JsonOptions`] allow them.
+*/
+pub fn stream_to_fmt_write_with_options(
+    fmt: impl Write,
+    v: impl sval::Value,
+    options: JsonOptions,
+) -> Result<(), Error> {
+    stream_to_fmt_write_with(Formatter::new(fmt).with_options(options), v)
+}
+
+/**
+Stream a value as JSON to an underlying formatter, escaping text with the given policy.
+
+This is the same as [`stream_to_fmt_write`], except any additional characters configured
+in the [`EscapePolicy`] are also escaped in text and keys.
+*/
+pub fn stream_to_fmt_write_with_escape(
+    fmt: impl Write,
+    escape: EscapePolicy,
+    v: impl sval::Value,
+) -> Result<(), Error> {
+    stream_to_fmt_write_with(Formatter::new(fmt).with_escape(escape), v)
+}
+
+fn stream_to_fmt_write_with(
+    mut stream: Formatter<impl Write>,
+    v: impl sval::Value,
+) -> Result<(), Error> {
     match v.stream(&mut stream) {
         Ok(()) => Ok(()),
         Err(_) => Err(stream.err.unwrap_or_else(Error::generic)),
@@ -38,6 +101,11 @@ pub(crate) struct Formatter<W> {
     is_internally_tagged: bool,
     is_current_depth_empty: bool,
     is_text_quoted: bool,
+    is_non_strict: bool,
+    is_key: bool,
+    is_strict_keys: bool,
+    is_int_keys_stringified: bool,
+    escape: EscapePolicy,
     text_handler: Option<TextHandler>,
     err: Option<Error>,
     out: W,
@@ -49,12 +117,42 @@ impl<W> Formatter<W> {
             is_internally_tagged: false,
             is_current_depth_empty: true,
             is_text_quoted: true,
+            is_non_strict: false,
+            is_key: false,
+            is_strict_keys: false,
+            is_int_keys_stringified: false,
+            escape: EscapePolicy::new(),
             text_handler: None,
             err: None,
             out,
         }
     }
 
+    /**
+    Write non-finite floating point numbers as JavaScript literals instead of `null`.
+    */
+    pub fn non_strict(mut self) -> Self {
+        self.is_non_strict = true;
+        self
+    }
+
+    /**
+    Only accept text map keys, and any others allowed by the options.
+    */
+    pub fn with_options(mut self, options: JsonOptions) -> Self {
+        self.is_strict_keys = true;
+        self.is_int_keys_stringified = options.stringify_int_keys;
+        self
+    }
+
+    /**
+    Escape text and keys using the given policy instead of the default one.
+    */
+    pub fn with_escape(mut self, escape: EscapePolicy) -> Self {
+        self.escape = escape;
+        self
+    }
+
     fn err(&mut self, e: Error) -> sval::Error {
         self.err = Some(e);
         sval::Error::new()
@@ -67,6 +165,11 @@ impl<W> fmt::Debug for Formatter<W> {
             .field("is_internally_tagged", &self.is_internally_tagged)
             .field("is_current_depth_empty", &self.is_current_depth_empty)
             .field("is_text_quoted", &self.is_text_quoted)
+            .field("is_non_strict", &self.is_non_strict)
+            .field("is_key", &self.is_key)
+            .field("is_strict_keys", &self.is_strict_keys)
+            .field("is_int_keys_stringified", &self.is_int_keys_stringified)
+            .field("escape", &self.escape)
             .field("err", &self.err)
             .field("text_handler", &self.text_handler.as_ref().map(|_| ()))
             .finish()
@@ -78,12 +181,14 @@ where
     W: Write,
 {
     fn null(&mut self) -> sval::Result {
+        _try_no_conv!(self.non_text_key(false));
         self.is_current_depth_empty = false;
 
         Ok(_try!(self.out.write_str("null")))
     }
 
     fn bool(&mut self, v: bool) -> sval::Result {
+        _try_no_conv!(self.non_text_key(false));
         self.is_current_depth_empty = false;
 
         Ok(_try!(self.out.write_str(if v { "true" } else { "false" })))
@@ -101,7 +206,7 @@ where
 
     fn text_fragment_computed(&mut self, v: &str) -> sval::Result {
         match self.text_handler {
-            None => _try!(escape_str(v, &mut self.out)),
+            None => _try!(escape_str(v, self.escape, &mut self.out)),
             Some(ref mut handler) => _try!(handler.text_fragment(v, &mut self.out)),
         }
 
@@ -117,90 +222,69 @@ where
     }
 
     fn u8(&mut self, v: u8) -> sval::Result {
+        self.integer(v)
+    }
+
+    fn num(&mut self, v: &sval::Number) -> sval::Result {
+        // Numbers within other text, like `JSON_TEXT`, are written as regular text
+        if self.text_handler.is_some() {
+            return sval::default_stream::num(self, v);
+        }
+
+        self.is_internally_tagged = false;
         self.is_current_depth_empty = false;
 
-        _try!(self.out.write_str(itoa::Buffer::new().format(v)));
+        // Numbers are already valid, but may still need adjusting to be valid JSON
+        let mut number = NumberTextHandler::new(self.is_non_strict);
+
+        _try!(number.text_fragment(v.as_str(), &mut self.out));
+        _try!(number.end(&mut self.out));
 
         Ok(())
     }
 
     fn u16(&mut self, v: u16) -> sval::Result {
-        self.is_current_depth_empty = false;
-
-        _try!(self.out.write_str(itoa::Buffer::new().format(v)));
-
-        Ok(())
+        self.integer(v)
     }
 
     fn u32(&mut self, v: u32) -> sval::Result {
-        self.is_current_depth_empty = false;
-
-        _try!(self.out.write_str(itoa::Buffer::new().format(v)));
-
-        Ok(())
+        self.integer(v)
     }
 
     fn u64(&mut self, v: u64) -> sval::Result {
-        self.is_current_depth_empty = false;
-
-        _try!(self.out.write_str(itoa::Buffer::new().format(v)));
-
-        Ok(())
+        self.integer(v)
     }
 
     fn u128(&mut self, v: u128) -> sval::Result {
-        self.is_current_depth_empty = false;
-
-        _try!(self.out.write_str(itoa::Buffer::new().format(v)));
-
-        Ok(())
+        self.integer(v)
     }
 
     fn i8(&mut self, v: i8) -> sval::Result {
-        self.is_current_depth_empty = false;
-
-        _try!(self.out.write_str(itoa::Buffer::new().format(v)));
-
-        Ok(())
+        self.integer(v)
     }
 
     fn i16(&mut self, v: i16) -> sval::Result {
-        self.is_current_depth_empty = false;
-
-        _try!(self.out.write_str(itoa::Buffer::new().format(v)));
-
-        Ok(())
+        self.integer(v)
     }
 
     fn i32(&mut self, v: i32) -> sval::Result {
-        self.is_current_depth_empty = false;
-
-        _try!(self.out.write_str(itoa::Buffer::new().format(v)));
-
-        Ok(())
+        self.integer(v)
     }
 
     fn i64(&mut self, v: i64) -> sval::Result {
-        self.is_current_depth_empty = false;
-
-        _try!(self.out.write_str(itoa::Buffer::new().format(v)));
-
-        Ok(())
+        self.integer(v)
     }
 
     fn i128(&mut self, v: i128) -> sval::Result {
-        self.is_current_depth_empty = false;
-
-        _try!(self.out.write_str(itoa::Buffer::new().format(v)));
-
-        Ok(())
+        self.integer(v)
     }
 
     fn f32(&mut self, v: f32) -> sval::Result {
+        _try_no_conv!(self.non_text_key(false));
         self.is_current_depth_empty = false;
 
         if v.is_nan() || v.is_infinite() {
-            self.null()
+            self.non_finite(v as f64)
         } else {
             _try!(self.out.write_str(ryu::Buffer::new().format_finite(v)));
 
@@ -209,10 +293,11 @@ where
     }
 
     fn f64(&mut self, v: f64) -> sval::Result {
+        _try_no_conv!(self.non_text_key(false));
         self.is_current_depth_empty = false;
 
         if v.is_nan() || v.is_infinite() {
-            self.null()
+            self.non_finite(v)
         } else {
             _try!(self.out.write_str(ryu::Buffer::new().format_finite(v)));
 
@@ -233,24 +318,15 @@ where
     }
 
     fn map_key_begin(&mut self) -> sval::Result {
-        self.is_text_quoted = false;
-        self.is_internally_tagged = false;
-
-        if !self.is_current_depth_empty {
-            _try!(self.out.write_str(",\""));
-        } else {
-            _try!(self.out.write_char('"'));
-        }
+        self.is_key = true;
 
-        Ok(())
+        self.key_begin()
     }
 
     fn map_key_end(&mut self) -> sval::Result {
-        _try!(self.out.write_str("\":"));
+        self.is_key = false;
 
-        self.is_text_quoted = true;
-
-        Ok(())
+        self.key_end()
     }
 
     fn map_value_begin(&mut self) -> sval::Result {
@@ -350,7 +426,7 @@ where
                 self.is_text_quoted = false;
 
                 if self.text_handler.is_none() {
-                    self.text_handler = Some(TextHandler::number());
+                    self.text_handler = Some(TextHandler::number(self.is_non_strict));
                 }
             }
             Some(&tags::JSON_NUMBER) => {
@@ -445,10 +521,11 @@ where
         }
 
         // If the label is a Rust identifier then it doesn't need escaping as JSON
-        if let Some(&sval::tags::VALUE_IDENT) = label.tag() {
+        // unless a custom policy is used, which could escape any ASCII character
+        if label.tag() == Some(&sval::tags::VALUE_IDENT) && self.escape.is_default() {
             _try!(self.out.write_str(label.as_str()));
         } else {
-            _try!(escape_str(label.as_str(), &mut self.out));
+            _try!(escape_str(label.as_str(), self.escape, &mut self.out));
         }
 
         _try!(self.out.write_str("\":"));
@@ -488,10 +565,105 @@ where
     }
 }
 
+#[cfg(feature = "std")]
+impl<W> Formatter<W>
+where
+    W: Write,
+{
+    pub fn newline(&mut self) -> sval::Result {
+        _try!(self.out.write_char('\n'));
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<W> Formatter<W>
+where
+    W: Write,
+{
+    pub fn take_err(&mut self) -> Option<Error> {
+        self.err.take()
+    }
+
+    /**
+    Write some already formatted JSON directly to the output.
+    */
+    pub fn write_raw(&mut self, json: &str) -> sval::Result {
+        _try!(self.out.write_str(json));
+
+        Ok(())
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.out
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+}
+
 impl<'sval, W> Formatter<W>
 where
     W: Write,
 {
+    fn integer(&mut self, v: impl itoa::Integer) -> sval::Result {
+        _try_no_conv!(self.non_text_key(true));
+        self.is_current_depth_empty = false;
+
+        // Integers are always written exactly, even within a `NUMBER`
+        // There's no text left for the number handler to finish
+        if let Some(TextHandler::Number(ref mut number)) = self.text_handler {
+            number.at_start = false;
+        }
+
+        _try!(self.out.write_str(itoa::Buffer::new().format(v)));
+
+        Ok(())
+    }
+
+    fn key_begin(&mut self) -> sval::Result {
+        self.is_text_quoted = false;
+        self.is_internally_tagged = false;
+
+        if !self.is_current_depth_empty {
+            _try!(self.out.write_str(",\""));
+        } else {
+            _try!(self.out.write_char('"'));
+        }
+
+        Ok(())
+    }
+
+    fn key_end(&mut self) -> sval::Result {
+        _try!(self.out.write_str("\":"));
+
+        self.is_text_quoted = true;
+
+        Ok(())
+    }
+
+    fn non_text_key(&mut self, is_integer: bool) -> sval::Result {
+        if self.is_key && self.is_strict_keys && !(is_integer && self.is_int_keys_stringified) {
+            return Err(self.err(Error::invalid_key()));
+        }
+
+        Ok(())
+    }
+
+    fn non_finite(&mut self, v: f64) -> sval::Result {
+        if !self.is_non_strict {
+            return self.null();
+        }
+
+        _try!(self
+            .out
+            .write_str(non_finite_literal(v.is_nan(), v.is_sign_negative())));
+
+        Ok(())
+    }
+
     fn internally_tagged_begin(
         &mut self,
         label: Option<&sval::Label>,
@@ -525,9 +697,9 @@ where
     fn internally_tagged_map_begin_label(&mut self, label: &str) -> sval::Result {
         _try_no_conv!(self.map_begin(Some(1)));
 
-        _try_no_conv!(self.map_key_begin());
-        _try!(escape_str(label, &mut self.out));
-        _try_no_conv!(self.map_key_end());
+        _try_no_conv!(self.key_begin());
+        _try!(escape_str(label, self.escape, &mut self.out));
+        _try_no_conv!(self.key_end());
 
         self.map_value_begin()
     }
@@ -535,9 +707,9 @@ where
     fn internally_tagged_map_begin_index(&mut self, index: i64) -> sval::Result {
         _try_no_conv!(self.map_begin(Some(1)));
 
-        _try_no_conv!(self.map_key_begin());
+        _try_no_conv!(self.key_begin());
         _try_no_conv!(self.i64(index));
-        _try_no_conv!(self.map_key_end());
+        _try_no_conv!(self.key_end());
 
         self.map_value_begin()
     }
@@ -558,6 +730,7 @@ struct NumberTextHandler {
     sign_negative: bool,
     leading_zeroes: usize,
     is_nan_or_infinity: bool,
+    is_non_strict: bool,
 }
 
 impl TextHandler {
@@ -565,13 +738,8 @@ impl TextHandler {
         TextHandler::Native
     }
 
-    const fn number() -> Self {
-        TextHandler::Number(NumberTextHandler {
-            sign_negative: false,
-            leading_zeroes: 0,
-            at_start: true,
-            is_nan_or_infinity: false,
-        })
+    const fn number(is_non_strict: bool) -> Self {
+        TextHandler::Number(NumberTextHandler::new(is_non_strict))
     }
 
     fn text_fragment(&mut self, v: &str, mut out: impl Write) -> fmt::Result {
@@ -583,6 +751,16 @@ impl TextHandler {
 }
 
 impl NumberTextHandler {
+    const fn new(is_non_strict: bool) -> Self {
+        NumberTextHandler {
+            sign_negative: false,
+            leading_zeroes: 0,
+            at_start: true,
+            is_nan_or_infinity: false,
+            is_non_strict,
+        }
+    }
+
     fn text_fragment(&mut self, v: &str, mut out: impl Write) -> fmt::Result {
         if !self.is_nan_or_infinity {
             let mut range = 0..0;
@@ -631,11 +809,20 @@ impl NumberTextHandler {
                         range.end += 1;
                     }
                     // `snan`, `nan`, `inf` in any casing should write `null`
+                    // In non-strict mode they write `NaN`, `Infinity`, or `-Infinity` instead
                     b's' | b'n' | b'i' | b'S' | b'N' | b'I' => {
                         self.is_nan_or_infinity = true;
                         self.at_start = false;
 
-                        _try_no_conv!(out.write_str("null"));
+                        if self.is_non_strict {
+                            let is_nan = !matches!(b, b'i' | b'I');
+
+                            _try_no_conv!(
+                                out.write_str(non_finite_literal(is_nan, self.sign_negative))
+                            );
+                        } else {
+                            _try_no_conv!(out.write_str("null"));
+                        }
 
                         range.start = 0;
                         range.end = 0;
@@ -661,18 +848,101 @@ impl NumberTextHandler {
     }
 }
 
+fn non_finite_literal(is_nan: bool, sign_negative: bool) -> &'static str {
+    if is_nan {
+        "NaN"
+    } else if sign_negative {
+        "-Infinity"
+    } else {
+        "Infinity"
+    }
+}
+
+/**
+A policy for which characters are escaped in JSON text and keys.
+
+The default policy only escapes the characters that JSON requires to be escaped: quotes,
+backslashes, and control characters. Additional ASCII characters can be escaped using
+[`EscapePolicy::escape_if`], like `/` to avoid writing `</script>` into HTML. A `/` is
+escaped as `\/`, and any other character as a `\u00XX` sequence.
+
+Characters that JSON requires to be escaped are always escaped, and non-ASCII characters
+are always written unchanged.
+*/
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct EscapePolicy {
+    // A bit for each ASCII character that's escaped in addition to the defaults
+    ascii: u128,
+}
+
+impl EscapePolicy {
+    /**
+    Create a policy that only escapes the characters JSON requires to be escaped.
+    */
+    pub const fn new() -> Self {
+        EscapePolicy { ascii: 0 }
+    }
+
+    /**
+    Also escape any ASCII characters that match the given predicate.
+
+    The predicate is called once for each ASCII character when the policy is built,
+    not while text is being escaped.
+    */
+    pub fn escape_if(mut self, f: impl Fn(char) -> bool) -> Self {
+        for byte in 0..128u8 {
+            if ESCAPE[byte as usize] == 0 && f(byte as char) {
+                self.ascii |= 1 << byte;
+            }
+        }
+
+        self
+    }
+
+    fn is_default(&self) -> bool {
+        self.ascii == 0
+    }
+
+    #[inline(always)]
+    fn escape(&self, byte: u8) -> u8 {
+        if byte < 128 && self.ascii & (1 << byte) != 0 {
+            if byte == b'/' {
+                SL
+            } else {
+                U
+            }
+        } else {
+            ESCAPE[byte as usize]
+        }
+    }
+}
+
+#[inline(always)]
+fn escape_str(value: &str, policy: EscapePolicy, out: impl Write) -> Result<(), fmt::Error> {
+    // Text escaped with the default policy only needs to check the lookup table
+    if policy.is_default() {
+        escape_str_with(value, |byte| ESCAPE[byte as usize], out)
+    } else {
+        escape_str_with(value, |byte| policy.escape(byte), out)
+    }
+}
+
 /*
 This `escape_str` implementation has been shamelessly lifted from dtolnay's `miniserde`:
 https://github.com/dtolnay/miniserde
 */
 
 #[inline(always)]
-fn escape_str(value: &str, mut out: impl Write) -> Result<(), fmt::Error> {
+fn escape_str_with(
+    value: &str,
+    escape: impl Fn(u8) -> u8,
+    mut out: impl Write,
+) -> Result<(), fmt::Error> {
     let bytes = value.as_bytes();
     let mut start = 0;
 
     for (i, &byte) in bytes.iter().enumerate() {
-        let escape = ESCAPE[byte as usize];
+        let escape = escape(byte);
         if escape == 0 {
             continue;
         }
@@ -689,6 +959,7 @@ fn escape_str(value: &str, mut out: impl Write) -> Result<(), fmt::Error> {
             RR => _try_no_conv!(out.write_str("\\r")),
             QU => _try_no_conv!(out.write_str("\\\"")),
             BS => _try_no_conv!(out.write_str("\\\\")),
+            SL => _try_no_conv!(out.write_str("\\/")),
             U => {
                 static HEX_DIGITS: [u8; 16] = *b"0123456789abcdef";
                 _try_no_conv!(out.write_str("\\u00"));
@@ -715,6 +986,7 @@ const FF: u8 = b'f'; // \x0C
 const RR: u8 = b'r'; // \x0D
 const QU: u8 = b'"'; // \x22
 const BS: u8 = b'\\'; // \x5C
+const SL: u8 = b'/'; // \x2F, only escaped by custom policies
 const U: u8 = b'u'; // \x00...\x1F except the ones above
 
 // Lookup table of escape sequences. A value of b'x' at index i means that byte
@@ -1,14 +1,89 @@
-use crate::{Error, ErrorKind};
+use crate::{Error, ErrorKind, EscapePolicy};
 
 use std::{
     fmt,
     io::{self, Write},
+    mem,
 };
 
 /**
 Stream a value as JSON to an underlying writer.
+
+Each fragment of JSON is written to the writer as it's produced, without buffering the
+whole document. Those fragments can be very small, so it's a good idea to wrap writers
+like files and sockets in a [`std::io::BufWriter`], or use [`stream_to_io_write_buffered`].
+
+If the writer fails, including with [`std::io::ErrorKind::WouldBlock`], streaming stops
+and the error is returned as the [`std::error::Error::source`] of the returned error.
+Some of the JSON may already have been written by that point, so the writer shouldn't
+be assumed to contain a complete document.
 */
 pub fn stream_to_io_write(io: impl Write, v: impl sval::Value) -> Result<(), Error> {
+    io_to_fmt(io, |fmt| crate::stream_to_fmt_write(fmt, v))
+}
+
+/**
+Stream a value as JSON to an underlying writer, escaping text with the given policy.
+
+This is like [`stream_to_io_write`], except any additional characters configured in the
+[`EscapePolicy`] are also escaped in text and keys.
+*/
+pub fn stream_to_io_write_with_escape(
+    io: impl Write,
+    escape: EscapePolicy,
+    v: impl sval::Value,
+) -> Result<(), Error> {
+    io_to_fmt(io, |fmt| {
+        crate::stream_to_fmt_write_with_escape(fmt, escape, v)
+    })
+}
+
+/**
+Stream a value as JSON to an underlying writer, using the given buffer to batch small writes.
+
+This is like [`stream_to_io_write`], but fragments of JSON are collected in `buf` and only
+written to the writer when it fills up, or once the value is complete. The buffer can be
+reused between calls and nothing is allocated along the way. Fragments that don't fit in
+the buffer are written to the writer directly.
+
+If the writer fails, streaming stops and any JSON left in the buffer is discarded.
+*/
+pub fn stream_to_io_write_buffered(
+    io: impl Write,
+    buf: &mut [u8],
+    v: impl sval::Value,
+) -> Result<(), Error> {
+    let mut io = Buffered { io, buf, len: 0 };
+
+    io_to_fmt(&mut io, |fmt| crate::stream_to_fmt_write(fmt, v))?;
+
+    io.flush_buf().map_err(Error::io)
+}
+
+/**
+Stream the entries of a map or record as JSON Lines to an underlying writer.
+
+Each entry is written as a standalone JSON object with a single key on its own line,
+so a record like `{"a":1,"b":true}` is written as:
+
+```text
+{"a":1}
+{"b":true}
+```
+
+Values within each entry are written as regular JSON. This method will fail if the
+value isn't a map or record, or if it contains complex values as keys.
+*/
+pub fn stream_entries_to_io_write(io: impl Write, v: impl sval::Value) -> Result<(), Error> {
+    io_to_fmt(io, |fmt| {
+        crate::to_lines::stream_entries_to_fmt_write(fmt, v)
+    })
+}
+
+fn io_to_fmt(
+    io: impl Write,
+    f: impl FnOnce(&mut dyn fmt::Write) -> Result<(), Error>,
+) -> Result<(), Error> {
     struct IoToFmt<W> {
         io: W,
         err: Option<io::Error>,
@@ -16,29 +91,18 @@ pub fn stream_to_io_write(io: impl Write, v: impl sval::Value) -> Result<(), Err
 
     impl<W: Write> fmt::Write for IoToFmt<W> {
         fn write_str(&mut self, v: &str) -> fmt::Result {
-            let mut buf = v.as_bytes();
-
-            while buf.len() > 0 {
-                match self.io.write(buf) {
-                    Ok(0) => break,
-                    Ok(n) => {
-                        buf = &buf[n..];
-                    }
-                    Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
-                    Err(e) => {
-                        self.err = Some(e);
-                        return Err(fmt::Error);
-                    }
-                }
-            }
-
-            Ok(())
+            // A writer that accepts no bytes fails with `WriteZero`
+            // instead of silently truncating the JSON
+            self.io.write_all(v.as_bytes()).map_err(|e| {
+                self.err = Some(e);
+                fmt::Error
+            })
         }
     }
 
     let mut io = IoToFmt { io, err: None };
 
-    match crate::stream_to_fmt_write(&mut io, v) {
+    match f(&mut io) {
         Ok(()) => Ok(()),
         Err(mut e) => {
             if let Some(io) = io.err {
@@ -49,3 +113,39 @@ pub fn stream_to_io_write(io: impl Write, v: impl sval::Value) -> Result<(), Err
         }
     }
 }
+
+struct Buffered<'a, W> {
+    io: W,
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl<'a, W: Write> Buffered<'a, W> {
+    fn flush_buf(&mut self) -> io::Result<()> {
+        let len = mem::take(&mut self.len);
+
+        self.io.write_all(&self.buf[..len])
+    }
+}
+
+impl<'a, W: Write> Write for Buffered<'a, W> {
+    fn write(&mut self, v: &[u8]) -> io::Result<usize> {
+        if self.buf.len() - self.len < v.len() {
+            self.flush_buf()?;
+        }
+
+        if v.len() >= self.buf.len() {
+            return self.io.write(v);
+        }
+
+        self.buf[self.len..self.len + v.len()].copy_from_slice(v);
+        self.len += v.len();
+
+        Ok(v.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.flush_buf()?;
+        self.io.flush()
+    }
+}
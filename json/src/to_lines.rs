@@ -0,0 +1,395 @@
+use core::fmt::Write;
+
+use crate::{to_fmt::Formatter, Error};
+
+/**
+Stream the entries of a map or record as JSON to an underlying formatter, one entry per line.
+*/
+pub(crate) fn stream_entries_to_fmt_write(
+    fmt: impl Write,
+    v: impl sval::Value,
+) -> Result<(), Error> {
+    let mut stream = EntryLines {
+        inner: Formatter::new(fmt),
+        depth: 0,
+        err: None,
+    };
+
+    match v.stream(&mut stream) {
+        Ok(()) => Ok(()),
+        Err(_) => Err(stream
+            .err
+            .or_else(|| stream.inner.take_err())
+            .unwrap_or_else(Error::generic)),
+    }
+}
+
+/**
+A stream that writes each entry of the top-level map or record as its own single-entry JSON object.
+
+Values nested in those entries are written by the wrapped formatter as regular JSON.
+*/
+struct EntryLines<W> {
+    inner: Formatter<W>,
+    depth: usize,
+    err: Option<Error>,
+}
+
+impl<W: Write> EntryLines<W> {
+    fn is_entry(&self) -> bool {
+        self.depth == 1
+    }
+
+    fn begin(&mut self) -> sval::Result {
+        if self.depth == 0 {
+            self.err = Some(Error::invalid_entries());
+            return sval::error();
+        }
+
+        self.depth += 1;
+
+        Ok(())
+    }
+
+    fn begin_entries(&mut self, f: impl FnOnce(&mut Formatter<W>) -> sval::Result) -> sval::Result {
+        if self.depth == 0 {
+            self.depth = 1;
+
+            Ok(())
+        } else {
+            self.depth += 1;
+
+            f(&mut self.inner)
+        }
+    }
+
+    fn end(&mut self, f: impl FnOnce(&mut Formatter<W>) -> sval::Result) -> sval::Result {
+        self.depth -= 1;
+
+        if self.depth == 0 {
+            Ok(())
+        } else {
+            f(&mut self.inner)
+        }
+    }
+
+    fn value(&mut self, f: impl FnOnce(&mut Formatter<W>) -> sval::Result) -> sval::Result {
+        if self.depth == 0 {
+            self.err = Some(Error::invalid_entries());
+            return sval::error();
+        }
+
+        f(&mut self.inner)
+    }
+}
+
+impl<'sval, W: Write> sval::Stream<'sval> for EntryLines<W> {
+    fn null(&mut self) -> sval::Result {
+        self.value(|stream| stream.null())
+    }
+
+    fn bool(&mut self, value: bool) -> sval::Result {
+        self.value(|stream| stream.bool(value))
+    }
+
+    fn text_begin(&mut self, num_bytes: Option<usize>) -> sval::Result {
+        self.value(|stream| stream.text_begin(num_bytes))
+    }
+
+    fn text_fragment(&mut self, fragment: &'sval str) -> sval::Result {
+        self.inner.text_fragment(fragment)
+    }
+
+    fn text_fragment_computed(&mut self, fragment: &str) -> sval::Result {
+        self.inner.text_fragment_computed(fragment)
+    }
+
+    fn text_end(&mut self) -> sval::Result {
+        self.inner.text_end()
+    }
+
+    fn binary_begin(&mut self, num_bytes: Option<usize>) -> sval::Result {
+        self.value(|stream| stream.binary_begin(num_bytes))
+    }
+
+    fn binary_fragment(&mut self, fragment: &'sval [u8]) -> sval::Result {
+        self.inner.binary_fragment(fragment)
+    }
+
+    fn binary_fragment_computed(&mut self, fragment: &[u8]) -> sval::Result {
+        self.inner.binary_fragment_computed(fragment)
+    }
+
+    fn binary_end(&mut self) -> sval::Result {
+        self.inner.binary_end()
+    }
+
+    fn u8(&mut self, value: u8) -> sval::Result {
+        self.value(|stream| stream.u8(value))
+    }
+
+    fn u16(&mut self, value: u16) -> sval::Result {
+        self.value(|stream| stream.u16(value))
+    }
+
+    fn u32(&mut self, value: u32) -> sval::Result {
+        self.value(|stream| stream.u32(value))
+    }
+
+    fn u64(&mut self, value: u64) -> sval::Result {
+        self.value(|stream| stream.u64(value))
+    }
+
+    fn u128(&mut self, value: u128) -> sval::Result {
+        self.value(|stream| stream.u128(value))
+    }
+
+    fn i8(&mut self, value: i8) -> sval::Result {
+        self.value(|stream| stream.i8(value))
+    }
+
+    fn i16(&mut self, value: i16) -> sval::Result {
+        self.value(|stream| stream.i16(value))
+    }
+
+    fn i32(&mut self, value: i32) -> sval::Result {
+        self.value(|stream| stream.i32(value))
+    }
+
+    fn i64(&mut self, value: i64) -> sval::Result {
+        self.value(|stream| stream.i64(value))
+    }
+
+    fn i128(&mut self, value: i128) -> sval::Result {
+        self.value(|stream| stream.i128(value))
+    }
+
+    fn f32(&mut self, value: f32) -> sval::Result {
+        self.value(|stream| stream.f32(value))
+    }
+
+    fn f64(&mut self, value: f64) -> sval::Result {
+        self.value(|stream| stream.f64(value))
+    }
+
+    fn num(&mut self, num: &sval::Number) -> sval::Result {
+        self.value(|stream| stream.num(num))
+    }
+
+    fn map_begin(&mut self, num_entries: Option<usize>) -> sval::Result {
+        self.begin_entries(|stream| stream.map_begin(num_entries))
+    }
+
+    fn map_key_begin(&mut self) -> sval::Result {
+        if self.is_entry() {
+            self.inner.map_begin(Some(1))?;
+        }
+
+        self.inner.map_key_begin()
+    }
+
+    fn map_key_end(&mut self) -> sval::Result {
+        self.inner.map_key_end()
+    }
+
+    fn map_value_begin(&mut self) -> sval::Result {
+        self.inner.map_value_begin()
+    }
+
+    fn map_value_end(&mut self) -> sval::Result {
+        self.inner.map_value_end()?;
+
+        if self.is_entry() {
+            self.inner.map_end()?;
+            self.inner.newline()?;
+        }
+
+        Ok(())
+    }
+
+    fn map_end(&mut self) -> sval::Result {
+        self.end(|stream| stream.map_end())
+    }
+
+    fn seq_begin(&mut self, num_entries: Option<usize>) -> sval::Result {
+        self.begin()?;
+        self.inner.seq_begin(num_entries)
+    }
+
+    fn seq_value_begin(&mut self) -> sval::Result {
+        self.inner.seq_value_begin()
+    }
+
+    fn seq_value_end(&mut self) -> sval::Result {
+        self.inner.seq_value_end()
+    }
+
+    fn seq_end(&mut self) -> sval::Result {
+        self.end(|stream| stream.seq_end())
+    }
+
+    fn enum_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.begin()?;
+        self.inner.enum_begin(tag, label, index)
+    }
+
+    fn enum_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.end(|stream| stream.enum_end(tag, label, index))
+    }
+
+    fn tagged_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.begin()?;
+        self.inner.tagged_begin(tag, label, index)
+    }
+
+    fn tagged_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.end(|stream| stream.tagged_end(tag, label, index))
+    }
+
+    fn tag(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.value(|stream| stream.tag(tag, label, index))
+    }
+
+    fn tag_hint(&mut self, tag: &sval::Tag) -> sval::Result {
+        self.inner.tag_hint(tag)
+    }
+
+    fn record_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+        num_entries: Option<usize>,
+    ) -> sval::Result {
+        self.begin_entries(|stream| stream.record_begin(tag, label, index, num_entries))
+    }
+
+    fn record_value_begin(&mut self, tag: Option<&sval::Tag>, label: &sval::Label) -> sval::Result {
+        if self.is_entry() {
+            self.inner.record_begin(None, None, None, Some(1))?;
+        }
+
+        self.inner.record_value_begin(tag, label)
+    }
+
+    fn record_value_end(&mut self, tag: Option<&sval::Tag>, label: &sval::Label) -> sval::Result {
+        self.inner.record_value_end(tag, label)?;
+
+        if self.is_entry() {
+            self.inner.record_end(None, None, None)?;
+            self.inner.newline()?;
+        }
+
+        Ok(())
+    }
+
+    fn record_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.end(|stream| stream.record_end(tag, label, index))
+    }
+
+    fn tuple_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+        num_entries: Option<usize>,
+    ) -> sval::Result {
+        self.begin()?;
+        self.inner.tuple_begin(tag, label, index, num_entries)
+    }
+
+    fn tuple_value_begin(&mut self, tag: Option<&sval::Tag>, index: &sval::Index) -> sval::Result {
+        self.inner.tuple_value_begin(tag, index)
+    }
+
+    fn tuple_value_end(&mut self, tag: Option<&sval::Tag>, index: &sval::Index) -> sval::Result {
+        self.inner.tuple_value_end(tag, index)
+    }
+
+    fn tuple_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.end(|stream| stream.tuple_end(tag, label, index))
+    }
+
+    fn record_tuple_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+        num_entries: Option<usize>,
+    ) -> sval::Result {
+        self.begin_entries(|stream| stream.record_tuple_begin(tag, label, index, num_entries))
+    }
+
+    fn record_tuple_value_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: &sval::Label,
+        index: &sval::Index,
+    ) -> sval::Result {
+        if self.is_entry() {
+            self.inner.record_begin(None, None, None, Some(1))?;
+        }
+
+        self.inner.record_tuple_value_begin(tag, label, index)
+    }
+
+    fn record_tuple_value_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: &sval::Label,
+        index: &sval::Index,
+    ) -> sval::Result {
+        self.inner.record_tuple_value_end(tag, label, index)?;
+
+        if self.is_entry() {
+            self.inner.record_end(None, None, None)?;
+            self.inner.newline()?;
+        }
+
+        Ok(())
+    }
+
+    fn record_tuple_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.end(|stream| stream.record_tuple_end(tag, label, index))
+    }
+}
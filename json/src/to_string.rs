@@ -1,4 +1,4 @@
-use crate::{Error, JsonStr};
+use crate::{Error, EscapePolicy, JsonOptions, JsonStr};
 
 use alloc::{boxed::Box, string::String};
 
@@ -14,9 +14,63 @@ pub fn stream_to_string(v: impl sval::Value) -> Result<String, Error> {
     Ok(out)
 }
 
+/**
+Stream a value as non-strict JSON into a string.
+
+Floating point numbers that are `NaN` or infinite are written as the JavaScript literals
+`NaN`, `Infinity`, and `-Infinity`, which aren't valid JSON. See
+[`stream_to_fmt_write_non_strict`](crate::stream_to_fmt_write_non_strict) for details.
+
+This method will fail if the value contains complex values as keys.
+*/
+pub fn stream_to_string_non_strict(v: impl sval::Value) -> Result<String, Error> {
+    let mut out = String::new();
+    crate::stream_to_fmt_write_non_strict(&mut out, v)?;
+
+    Ok(out)
+}
+
+/**
+Stream a value as JSON into a string, using the given options.
+
+See [`stream_to_fmt_write_with_options`](crate::stream_to_fmt_write_with_options) for details.
+*/
+pub fn stream_to_string_with_options(
+    v: impl sval::Value,
+    options: JsonOptions,
+) -> Result<String, Error> {
+    let mut out = String::new();
+    crate::stream_to_fmt_write_with_options(&mut out, v, options)?;
+
+    Ok(out)
+}
+
+/**
+Stream a value as JSON into a string, escaping text with the given policy.
+
+See [`stream_to_fmt_write_with_escape`](crate::stream_to_fmt_write_with_escape) for details.
+
+This method will fail if the value contains complex values as keys.
+*/
+pub fn stream_to_string_with_escape(
+    escape: EscapePolicy,
+    v: impl sval::Value,
+) -> Result<String, Error> {
+    let mut out = String::new();
+    crate::stream_to_fmt_write_with_escape(&mut out, escape, v)?;
+
+    Ok(out)
+}
+
 /**
 Stream a value as JSON into a `JsonStr`.
 
+The result can be streamed again as a value. It will be embedded directly into any JSON
+it's written to, rather than being escaped as a string. Text tagged with
+[`tags::JSON_TEXT`](crate::tags::JSON_TEXT) is also copied verbatim, without running it
+through the escape table. Use it for text that's already escaped, or doesn't contain
+any characters that need escaping. All other text is escaped as usual.
+
 This method will fail if the value contains complex values as keys.
 */
 pub fn stream_to_json_str(v: impl sval::Value) -> Result<Box<JsonStr>, Error> {
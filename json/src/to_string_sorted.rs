@@ -0,0 +1,393 @@
+use crate::{to_fmt::Formatter, Error};
+
+use alloc::{string::String, vec::Vec};
+
+use core::mem;
+
+/**
+Stream a value as JSON into a string, with the keys of maps sorted.
+
+Sorting keys makes the output deterministic even for maps that don't have a stable order
+of their own, like `HashMap`, which is useful for snapshot tests and content hashing.
+Each map is buffered until all of its entries have been written, and then its entries are
+sorted by their JSON key text. Nested maps are sorted independently. Duplicate keys are
+all kept, in the order they were streamed.
+
+Only maps are sorted. The fields of records are still written in the order they're streamed.
+
+This method will fail if the value contains complex values as keys.
+*/
+pub fn stream_to_string_sorted(v: impl sval::Value) -> Result<String, Error> {
+    let mut stream = SortedKeys {
+        root: Formatter::new(String::new()),
+        maps: Vec::new(),
+        err: None,
+    };
+
+    match v.stream(&mut stream) {
+        Ok(()) => Ok(stream.root.into_inner()),
+        Err(_) => Err(stream.err.unwrap_or_else(Error::generic)),
+    }
+}
+
+struct SortedKeys {
+    root: Formatter<String>,
+    maps: Vec<SortedMap>,
+    err: Option<Error>,
+}
+
+/**
+A map that's being buffered so its entries can be sorted.
+
+Each key and value is written by its own formatter, which will buffer
+any maps nested within them.
+*/
+struct SortedMap {
+    entries: Vec<(String, String)>,
+    key: Option<String>,
+    current: Formatter<String>,
+}
+
+impl SortedKeys {
+    fn current(&mut self) -> &mut Formatter<String> {
+        match self.maps.last_mut() {
+            Some(map) => &mut map.current,
+            None => &mut self.root,
+        }
+    }
+
+    fn with_current(
+        &mut self,
+        f: impl FnOnce(&mut Formatter<String>) -> sval::Result,
+    ) -> sval::Result {
+        let current = self.current();
+
+        match f(current) {
+            Ok(()) => Ok(()),
+            Err(e) => {
+                let err = current.take_err();
+                self.err = err;
+
+                Err(e)
+            }
+        }
+    }
+
+    fn map(&mut self) -> sval::Result<&mut SortedMap> {
+        self.maps.last_mut().ok_or_else(sval::Error::new)
+    }
+}
+
+impl<'sval> sval::Stream<'sval> for SortedKeys {
+    fn null(&mut self) -> sval::Result {
+        self.with_current(|stream| stream.null())
+    }
+
+    fn bool(&mut self, value: bool) -> sval::Result {
+        self.with_current(|stream| stream.bool(value))
+    }
+
+    fn text_begin(&mut self, num_bytes: Option<usize>) -> sval::Result {
+        self.with_current(|stream| stream.text_begin(num_bytes))
+    }
+
+    fn text_fragment_computed(&mut self, fragment: &str) -> sval::Result {
+        self.with_current(|stream| stream.text_fragment_computed(fragment))
+    }
+
+    fn text_end(&mut self) -> sval::Result {
+        self.with_current(|stream| stream.text_end())
+    }
+
+    fn binary_begin(&mut self, num_bytes: Option<usize>) -> sval::Result {
+        self.with_current(|stream| stream.binary_begin(num_bytes))
+    }
+
+    fn binary_fragment_computed(&mut self, fragment: &[u8]) -> sval::Result {
+        self.with_current(|stream| stream.binary_fragment_computed(fragment))
+    }
+
+    fn binary_end(&mut self) -> sval::Result {
+        self.with_current(|stream| stream.binary_end())
+    }
+
+    fn u8(&mut self, value: u8) -> sval::Result {
+        self.with_current(|stream| stream.u8(value))
+    }
+
+    fn u16(&mut self, value: u16) -> sval::Result {
+        self.with_current(|stream| stream.u16(value))
+    }
+
+    fn u32(&mut self, value: u32) -> sval::Result {
+        self.with_current(|stream| stream.u32(value))
+    }
+
+    fn u64(&mut self, value: u64) -> sval::Result {
+        self.with_current(|stream| stream.u64(value))
+    }
+
+    fn u128(&mut self, value: u128) -> sval::Result {
+        self.with_current(|stream| stream.u128(value))
+    }
+
+    fn i8(&mut self, value: i8) -> sval::Result {
+        self.with_current(|stream| stream.i8(value))
+    }
+
+    fn i16(&mut self, value: i16) -> sval::Result {
+        self.with_current(|stream| stream.i16(value))
+    }
+
+    fn i32(&mut self, value: i32) -> sval::Result {
+        self.with_current(|stream| stream.i32(value))
+    }
+
+    fn i64(&mut self, value: i64) -> sval::Result {
+        self.with_current(|stream| stream.i64(value))
+    }
+
+    fn i128(&mut self, value: i128) -> sval::Result {
+        self.with_current(|stream| stream.i128(value))
+    }
+
+    fn f32(&mut self, value: f32) -> sval::Result {
+        self.with_current(|stream| stream.f32(value))
+    }
+
+    fn f64(&mut self, value: f64) -> sval::Result {
+        self.with_current(|stream| stream.f64(value))
+    }
+
+    fn num(&mut self, num: &sval::Number) -> sval::Result {
+        self.with_current(|stream| stream.num(num))
+    }
+
+    fn map_begin(&mut self, num_entries: Option<usize>) -> sval::Result {
+        // Begin the map in the formatter that contains it
+        // This will fail if the map is being used as a key
+        self.with_current(|stream| stream.map_begin(num_entries))?;
+
+        self.maps.push(SortedMap {
+            entries: Vec::with_capacity(num_entries.unwrap_or(0)),
+            key: None,
+            current: Formatter::new(String::new()),
+        });
+
+        Ok(())
+    }
+
+    fn map_key_begin(&mut self) -> sval::Result {
+        let map = self.map()?;
+        map.current = Formatter::new(String::new());
+
+        self.with_current(|stream| stream.map_key_begin())
+    }
+
+    fn map_key_end(&mut self) -> sval::Result {
+        self.with_current(|stream| stream.map_key_end())?;
+
+        let map = self.map()?;
+        let key = mem::replace(&mut map.current, Formatter::new(String::new())).into_inner();
+
+        // Keys are written as `"key":`, but are sorted and stored without the quotes
+        map.key = Some(String::from(&key[1..key.len() - 2]));
+
+        Ok(())
+    }
+
+    fn map_value_begin(&mut self) -> sval::Result {
+        Ok(())
+    }
+
+    fn map_value_end(&mut self) -> sval::Result {
+        let map = self.map()?;
+
+        let key = map.key.take().ok_or_else(sval::Error::new)?;
+        let value = mem::replace(&mut map.current, Formatter::new(String::new()));
+
+        map.entries.push((key, value.into_inner()));
+
+        Ok(())
+    }
+
+    fn map_end(&mut self) -> sval::Result {
+        let mut map = self.maps.pop().ok_or_else(sval::Error::new)?;
+
+        // The sort is stable, so duplicate keys keep their original order
+        map.entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        self.with_current(|stream| {
+            let mut first = true;
+            for (key, value) in &map.entries {
+                if !first {
+                    stream.write_raw(",")?;
+                }
+                first = false;
+
+                stream.write_raw("\"")?;
+                stream.write_raw(key)?;
+                stream.write_raw("\":")?;
+                stream.write_raw(value)?;
+            }
+
+            stream.map_end()
+        })
+    }
+
+    fn seq_begin(&mut self, num_entries: Option<usize>) -> sval::Result {
+        self.with_current(|stream| stream.seq_begin(num_entries))
+    }
+
+    fn seq_value_begin(&mut self) -> sval::Result {
+        self.with_current(|stream| stream.seq_value_begin())
+    }
+
+    fn seq_value_end(&mut self) -> sval::Result {
+        self.with_current(|stream| stream.seq_value_end())
+    }
+
+    fn seq_end(&mut self) -> sval::Result {
+        self.with_current(|stream| stream.seq_end())
+    }
+
+    fn enum_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.with_current(|stream| stream.enum_begin(tag, label, index))
+    }
+
+    fn enum_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.with_current(|stream| stream.enum_end(tag, label, index))
+    }
+
+    fn tagged_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.with_current(|stream| stream.tagged_begin(tag, label, index))
+    }
+
+    fn tagged_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.with_current(|stream| stream.tagged_end(tag, label, index))
+    }
+
+    fn tag(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.with_current(|stream| stream.tag(tag, label, index))
+    }
+
+    fn tag_hint(&mut self, tag: &sval::Tag) -> sval::Result {
+        self.with_current(|stream| stream.tag_hint(tag))
+    }
+
+    fn record_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+        num_entries: Option<usize>,
+    ) -> sval::Result {
+        self.with_current(|stream| stream.record_begin(tag, label, index, num_entries))
+    }
+
+    fn record_value_begin(&mut self, tag: Option<&sval::Tag>, label: &sval::Label) -> sval::Result {
+        self.with_current(|stream| stream.record_value_begin(tag, label))
+    }
+
+    fn record_value_end(&mut self, tag: Option<&sval::Tag>, label: &sval::Label) -> sval::Result {
+        self.with_current(|stream| stream.record_value_end(tag, label))
+    }
+
+    fn record_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.with_current(|stream| stream.record_end(tag, label, index))
+    }
+
+    fn tuple_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+        num_entries: Option<usize>,
+    ) -> sval::Result {
+        self.with_current(|stream| stream.tuple_begin(tag, label, index, num_entries))
+    }
+
+    fn tuple_value_begin(&mut self, tag: Option<&sval::Tag>, index: &sval::Index) -> sval::Result {
+        self.with_current(|stream| stream.tuple_value_begin(tag, index))
+    }
+
+    fn tuple_value_end(&mut self, tag: Option<&sval::Tag>, index: &sval::Index) -> sval::Result {
+        self.with_current(|stream| stream.tuple_value_end(tag, index))
+    }
+
+    fn tuple_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.with_current(|stream| stream.tuple_end(tag, label, index))
+    }
+
+    fn record_tuple_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+        num_entries: Option<usize>,
+    ) -> sval::Result {
+        self.with_current(|stream| stream.record_tuple_begin(tag, label, index, num_entries))
+    }
+
+    fn record_tuple_value_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: &sval::Label,
+        index: &sval::Index,
+    ) -> sval::Result {
+        self.with_current(|stream| stream.record_tuple_value_begin(tag, label, index))
+    }
+
+    fn record_tuple_value_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: &sval::Label,
+        index: &sval::Index,
+    ) -> sval::Result {
+        self.with_current(|stream| stream.record_tuple_value_end(tag, label, index))
+    }
+
+    fn record_tuple_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.with_current(|stream| stream.record_tuple_end(tag, label, index))
+    }
+}
@@ -0,0 +1,399 @@
+use crate::{to_fmt::Formatter, Error};
+
+use alloc::{string::String, vec::Vec};
+
+/**
+The marker written in place of the entries that were cut off by a budget.
+*/
+const TRUNCATED: &str = "\"\u{2026}\"";
+
+/**
+Stream a value as JSON into a string, stopping once the output reaches `max_bytes`.
+
+The value is written normally until the output reaches `max_bytes`. From then on, no more
+entries are written. Instead, a `"…"` marker is added to the innermost open array, or a
+`"…":"…"` entry to the innermost open object, and all open arrays and objects are closed.
+
+The result is always valid JSON, but it's semantically truncated. It may not match the
+shape of the original value, and shouldn't be deserialized back into it. This is useful
+for logs or previews where the size of the output needs to be bounded.
+
+The budget is only checked between the entries of arrays and objects, so the output may
+exceed `max_bytes` by the size of the last entry written, plus the marker and closing
+brackets. A value that isn't an array or object is always written in full.
+
+This method will fail if the value contains complex values as keys.
+*/
+pub fn stream_to_string_truncated(v: impl sval::Value, max_bytes: usize) -> Result<String, Error> {
+    let mut stream = Truncated {
+        inner: Formatter::new(String::new()),
+        max_bytes,
+        is_truncated: false,
+        err: None,
+    };
+
+    match v.stream(&mut stream) {
+        Ok(()) => Ok(stream.inner.into_inner()),
+        Err(_) if stream.is_truncated => Ok(close_truncated(stream.inner.into_inner())),
+        Err(_) => Err(stream.err.unwrap_or_else(Error::generic)),
+    }
+}
+
+/**
+Close all arrays and objects left open when the output was truncated.
+
+The output is always cut off between entries, so the last character is either an
+opening bracket or the end of a complete entry.
+*/
+fn close_truncated(mut out: String) -> String {
+    let mut open = Vec::new();
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for b in out.bytes() {
+        if in_string {
+            match b {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => in_string = false,
+                _ => (),
+            }
+
+            continue;
+        }
+
+        match b {
+            b'"' => in_string = true,
+            b'[' => open.push(b']'),
+            b'{' => open.push(b'}'),
+            b']' | b'}' => {
+                open.pop();
+            }
+            _ => (),
+        }
+    }
+
+    if let Some(&innermost) = open.last() {
+        if !out.ends_with('[') && !out.ends_with('{') {
+            out.push(',');
+        }
+
+        out.push_str(TRUNCATED);
+
+        if innermost == b'}' {
+            out.push(':');
+            out.push_str(TRUNCATED);
+        }
+    }
+
+    while let Some(close) = open.pop() {
+        out.push(close as char);
+    }
+
+    out
+}
+
+struct Truncated {
+    inner: Formatter<String>,
+    max_bytes: usize,
+    is_truncated: bool,
+    err: Option<Error>,
+}
+
+impl Truncated {
+    fn with_inner(
+        &mut self,
+        f: impl FnOnce(&mut Formatter<String>) -> sval::Result,
+    ) -> sval::Result {
+        match f(&mut self.inner) {
+            Ok(()) => Ok(()),
+            Err(e) => {
+                self.err = self.inner.take_err();
+
+                Err(e)
+            }
+        }
+    }
+
+    /**
+    Begin a new entry, unless the output has already reached its budget.
+    */
+    fn with_entry(
+        &mut self,
+        f: impl FnOnce(&mut Formatter<String>) -> sval::Result,
+    ) -> sval::Result {
+        if self.inner.get_ref().len() >= self.max_bytes {
+            self.is_truncated = true;
+
+            return Err(sval::Error::new());
+        }
+
+        self.with_inner(f)
+    }
+}
+
+impl<'sval> sval::Stream<'sval> for Truncated {
+    fn null(&mut self) -> sval::Result {
+        self.with_inner(|stream| stream.null())
+    }
+
+    fn bool(&mut self, value: bool) -> sval::Result {
+        self.with_inner(|stream| stream.bool(value))
+    }
+
+    fn text_begin(&mut self, num_bytes: Option<usize>) -> sval::Result {
+        self.with_inner(|stream| stream.text_begin(num_bytes))
+    }
+
+    fn text_fragment_computed(&mut self, fragment: &str) -> sval::Result {
+        self.with_inner(|stream| stream.text_fragment_computed(fragment))
+    }
+
+    fn text_end(&mut self) -> sval::Result {
+        self.with_inner(|stream| stream.text_end())
+    }
+
+    fn binary_begin(&mut self, num_bytes: Option<usize>) -> sval::Result {
+        self.with_inner(|stream| stream.binary_begin(num_bytes))
+    }
+
+    fn binary_fragment_computed(&mut self, fragment: &[u8]) -> sval::Result {
+        self.with_inner(|stream| stream.binary_fragment_computed(fragment))
+    }
+
+    fn binary_end(&mut self) -> sval::Result {
+        self.with_inner(|stream| stream.binary_end())
+    }
+
+    fn u8(&mut self, value: u8) -> sval::Result {
+        self.with_inner(|stream| stream.u8(value))
+    }
+
+    fn u16(&mut self, value: u16) -> sval::Result {
+        self.with_inner(|stream| stream.u16(value))
+    }
+
+    fn u32(&mut self, value: u32) -> sval::Result {
+        self.with_inner(|stream| stream.u32(value))
+    }
+
+    fn u64(&mut self, value: u64) -> sval::Result {
+        self.with_inner(|stream| stream.u64(value))
+    }
+
+    fn u128(&mut self, value: u128) -> sval::Result {
+        self.with_inner(|stream| stream.u128(value))
+    }
+
+    fn i8(&mut self, value: i8) -> sval::Result {
+        self.with_inner(|stream| stream.i8(value))
+    }
+
+    fn i16(&mut self, value: i16) -> sval::Result {
+        self.with_inner(|stream| stream.i16(value))
+    }
+
+    fn i32(&mut self, value: i32) -> sval::Result {
+        self.with_inner(|stream| stream.i32(value))
+    }
+
+    fn i64(&mut self, value: i64) -> sval::Result {
+        self.with_inner(|stream| stream.i64(value))
+    }
+
+    fn i128(&mut self, value: i128) -> sval::Result {
+        self.with_inner(|stream| stream.i128(value))
+    }
+
+    fn f32(&mut self, value: f32) -> sval::Result {
+        self.with_inner(|stream| stream.f32(value))
+    }
+
+    fn f64(&mut self, value: f64) -> sval::Result {
+        self.with_inner(|stream| stream.f64(value))
+    }
+
+    fn num(&mut self, num: &sval::Number) -> sval::Result {
+        self.with_inner(|stream| stream.num(num))
+    }
+
+    fn map_begin(&mut self, num_entries: Option<usize>) -> sval::Result {
+        self.with_inner(|stream| stream.map_begin(num_entries))
+    }
+
+    fn map_key_begin(&mut self) -> sval::Result {
+        self.with_entry(|stream| stream.map_key_begin())
+    }
+
+    fn map_key_end(&mut self) -> sval::Result {
+        self.with_inner(|stream| stream.map_key_end())
+    }
+
+    fn map_value_begin(&mut self) -> sval::Result {
+        self.with_inner(|stream| stream.map_value_begin())
+    }
+
+    fn map_value_end(&mut self) -> sval::Result {
+        self.with_inner(|stream| stream.map_value_end())
+    }
+
+    fn map_end(&mut self) -> sval::Result {
+        self.with_inner(|stream| stream.map_end())
+    }
+
+    fn seq_begin(&mut self, num_entries: Option<usize>) -> sval::Result {
+        self.with_inner(|stream| stream.seq_begin(num_entries))
+    }
+
+    fn seq_value_begin(&mut self) -> sval::Result {
+        self.with_entry(|stream| stream.seq_value_begin())
+    }
+
+    fn seq_value_end(&mut self) -> sval::Result {
+        self.with_inner(|stream| stream.seq_value_end())
+    }
+
+    fn seq_end(&mut self) -> sval::Result {
+        self.with_inner(|stream| stream.seq_end())
+    }
+
+    fn enum_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.with_inner(|stream| stream.enum_begin(tag, label, index))
+    }
+
+    fn enum_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.with_inner(|stream| stream.enum_end(tag, label, index))
+    }
+
+    fn tagged_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.with_inner(|stream| stream.tagged_begin(tag, label, index))
+    }
+
+    fn tagged_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.with_inner(|stream| stream.tagged_end(tag, label, index))
+    }
+
+    fn tag(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.with_inner(|stream| stream.tag(tag, label, index))
+    }
+
+    fn tag_hint(&mut self, tag: &sval::Tag) -> sval::Result {
+        self.with_inner(|stream| stream.tag_hint(tag))
+    }
+
+    fn record_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+        num_entries: Option<usize>,
+    ) -> sval::Result {
+        self.with_inner(|stream| stream.record_begin(tag, label, index, num_entries))
+    }
+
+    fn record_value_begin(&mut self, tag: Option<&sval::Tag>, label: &sval::Label) -> sval::Result {
+        self.with_entry(|stream| stream.record_value_begin(tag, label))
+    }
+
+    fn record_value_end(&mut self, tag: Option<&sval::Tag>, label: &sval::Label) -> sval::Result {
+        self.with_inner(|stream| stream.record_value_end(tag, label))
+    }
+
+    fn record_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.with_inner(|stream| stream.record_end(tag, label, index))
+    }
+
+    fn tuple_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+        num_entries: Option<usize>,
+    ) -> sval::Result {
+        self.with_inner(|stream| stream.tuple_begin(tag, label, index, num_entries))
+    }
+
+    fn tuple_value_begin(&mut self, tag: Option<&sval::Tag>, index: &sval::Index) -> sval::Result {
+        self.with_entry(|stream| stream.tuple_value_begin(tag, index))
+    }
+
+    fn tuple_value_end(&mut self, tag: Option<&sval::Tag>, index: &sval::Index) -> sval::Result {
+        self.with_inner(|stream| stream.tuple_value_end(tag, index))
+    }
+
+    fn tuple_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.with_inner(|stream| stream.tuple_end(tag, label, index))
+    }
+
+    fn record_tuple_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+        num_entries: Option<usize>,
+    ) -> sval::Result {
+        self.with_inner(|stream| stream.record_tuple_begin(tag, label, index, num_entries))
+    }
+
+    fn record_tuple_value_begin(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: &sval::Label,
+        index: &sval::Index,
+    ) -> sval::Result {
+        self.with_entry(|stream| stream.record_tuple_value_begin(tag, label, index))
+    }
+
+    fn record_tuple_value_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: &sval::Label,
+        index: &sval::Index,
+    ) -> sval::Result {
+        self.with_inner(|stream| stream.record_tuple_value_end(tag, label, index))
+    }
+
+    fn record_tuple_end(
+        &mut self,
+        tag: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.with_inner(|stream| stream.record_tuple_end(tag, label, index))
+    }
+}
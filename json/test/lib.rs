@@ -120,6 +120,9 @@ fn stream_primitive() {
     assert_json(false);
     assert_json("abc");
     assert_json("a\nb");
+    assert_json('a');
+    assert_json('é');
+    assert_json('\n');
 }
 
 #[test]
@@ -150,6 +153,153 @@ fn stream_native_number() {
     }
 }
 
+#[test]
+fn stream_num() {
+    for (num, expected) in [
+        ("0", "0"),
+        ("001", "1"),
+        ("+1", "1"),
+        ("-0.5", "-0.5"),
+        ("+00123.456e+789", "123.456e+789"),
+        (
+            "84621599231797873982892348534.48343235787975583989258932",
+            "84621599231797873982892348534.48343235787975583989258932",
+        ),
+    ] {
+        let num = sval::Number::new(num).unwrap();
+
+        assert_stream(expected, num);
+        assert_stream(&format!("[{}]", expected), [num]);
+        assert_stream(
+            &format!("{{\"{}\":1}}", expected),
+            sval::MapSlice::new(&[(num, 1)]),
+        );
+    }
+}
+
+#[test]
+fn stream_native_number_integer() {
+    assert_stream(
+        "-170141183460469231731687303715884105728",
+        Number(i128::MIN),
+    );
+    assert_stream("340282366920938463463374607431768211455", Number(u128::MAX));
+    assert_stream("0", Number(0u8));
+    assert_stream("-1", Number(-1i64));
+
+    assert_stream("-170141183460469231731687303715884105728", i128::MIN);
+    assert_stream("340282366920938463463374607431768211455", u128::MAX);
+}
+
+#[test]
+fn stream_non_finite() {
+    assert_stream(
+        "[null,null,null]",
+        [f64::NAN, f64::INFINITY, f64::NEG_INFINITY],
+    );
+    assert_stream("[null,null]", [f32::NAN, f32::NEG_INFINITY]);
+
+    assert_stream(
+        "[null,null,null]",
+        [Number("NaN"), Number("inf"), Number("-inf")],
+    );
+}
+
+#[test]
+fn stream_non_finite_non_strict() {
+    assert_eq!(
+        "[NaN,Infinity,-Infinity,1.5]",
+        sval_json::stream_to_string_non_strict([f64::NAN, f64::INFINITY, f64::NEG_INFINITY, 1.5])
+            .unwrap()
+    );
+    assert_eq!(
+        "[NaN,-Infinity]",
+        sval_json::stream_to_string_non_strict([f32::NAN, f32::NEG_INFINITY]).unwrap()
+    );
+
+    assert_eq!(
+        "[NaN,NaN,Infinity,-Infinity,-1.5]",
+        sval_json::stream_to_string_non_strict([
+            Number("NaN"),
+            Number("snan"),
+            Number("+inf"),
+            Number("-Infinity"),
+            Number("-1.5")
+        ])
+        .unwrap()
+    );
+}
+
+#[test]
+fn stream_deeply_nested() {
+    struct Nested(usize);
+
+    impl sval::Value for Nested {
+        fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(
+            &'sval self,
+            stream: &mut S,
+        ) -> sval::Result {
+            stream.seq_begin(None)?;
+
+            if self.0 > 0 {
+                stream.seq_value_begin()?;
+                stream.value_computed(&Nested(self.0 - 1))?;
+                stream.seq_value_end()?;
+            }
+
+            stream.seq_end()
+        }
+    }
+
+    // The formatter doesn't keep a stack of its own, so nesting is only limited by the value
+    let json = sval_json::stream_to_string(Nested(1000)).unwrap();
+
+    assert_eq!(format!("{}{}", "[".repeat(1001), "]".repeat(1001)), json);
+}
+
+#[test]
+fn stream_custom_escape() {
+    #[derive(Value)]
+    struct Html<'a> {
+        body: &'a str,
+    }
+
+    let value = Html {
+        body: "<script>\"a\"</script>",
+    };
+
+    // The default policy doesn't escape `/`
+    assert_eq!(
+        "{\"body\":\"<script>\\\"a\\\"</script>\"}",
+        sval_json::stream_to_string(&value).unwrap()
+    );
+
+    let escape = sval_json::EscapePolicy::new().escape_if(|c| c == '/');
+
+    assert_eq!(
+        "{\"body\":\"<script>\\\"a\\\"<\\/script>\"}",
+        sval_json::stream_to_string_with_escape(escape, &value).unwrap()
+    );
+
+    // Other characters are escaped as unicode, including in keys
+    let escape = sval_json::EscapePolicy::new().escape_if(|c| c == '<' || c == 'b');
+
+    assert_eq!(
+        "{\"\\u0062ody\":\"\\u003cscript>\\\"a\\\"\\u003c/script>\"}",
+        sval_json::stream_to_string_with_escape(escape, &value).unwrap()
+    );
+
+    // Characters that JSON requires to be escaped keep their usual escapes
+    assert_eq!(
+        sval_json::stream_to_string(&value).unwrap(),
+        sval_json::stream_to_string_with_escape(
+            sval_json::EscapePolicy::new().escape_if(|c| c == '"'),
+            &value
+        )
+        .unwrap()
+    );
+}
+
 #[test]
 fn stream_native_text() {
     for str in ["abc", "a\nb"] {
@@ -170,6 +320,32 @@ fn stream_native_text_nested() {
     assert_stream("{\"text\":\"a\nb\"}", MapStruct { text: "a\nb" });
 }
 
+#[test]
+fn stream_native_text_mixed() {
+    #[derive(Value)]
+    struct MapStruct<'a> {
+        #[sval(data_tag = "sval_json::tags::JSON_TEXT")]
+        native: &'a str,
+        text: &'a str,
+    }
+
+    // Only the untagged text is escaped
+    assert_stream(
+        "{\"native\":\"a\\nb\",\"text\":\"a\\\\nb\"}",
+        MapStruct {
+            native: "a\\nb",
+            text: "a\\nb",
+        },
+    );
+    assert_stream(
+        "{\"native\":\"a\\\"b\",\"text\":\"a\\\"b\"}",
+        MapStruct {
+            native: "a\\\"b",
+            text: "a\"b",
+        },
+    );
+}
+
 #[test]
 fn stream_native_number_nested() {
     #[derive(Value)]
@@ -247,6 +423,263 @@ fn stream_map_struct() {
     });
 }
 
+#[test]
+fn stream_filtered_map() {
+    let map = {
+        let mut map = std::collections::BTreeMap::new();
+
+        map.insert("a", 1);
+        map.insert("b", 2);
+        map.insert("c", 3);
+
+        map
+    };
+
+    assert_stream(
+        "{\"a\":1,\"c\":3}",
+        sval::FilteredMap::new(&map, |k: &&str, _: &i32| *k != "b"),
+    );
+    assert_stream(
+        "{}",
+        sval::FilteredMap::new(&map, |_: &&str, _: &i32| false),
+    );
+}
+
+#[test]
+fn stream_integer_keys() {
+    let map = std::collections::BTreeMap::from([(1u64, "a"), (42u64, "b")]);
+
+    assert_stream("{\"1\":\"a\",\"42\":\"b\"}", &map);
+
+    let map = std::collections::BTreeMap::from([(-1i64, "a"), (i64::MAX, "b")]);
+
+    assert_stream("{\"-1\":\"a\",\"9223372036854775807\":\"b\"}", &map);
+
+    // Keys that are themselves maps or sequences can't be written
+    let map = std::collections::BTreeMap::from([(vec![1, 2], "a")]);
+
+    assert!(sval_json::stream_to_string(&map).is_err());
+}
+
+#[test]
+fn stream_integer_keys_with_options() {
+    let strict = sval_json::JsonOptions::default();
+    let stringify = sval_json::JsonOptions {
+        stringify_int_keys: true,
+    };
+
+    let map = std::collections::BTreeMap::from([(1u64, "a"), (42u64, "b")]);
+
+    assert_eq!(
+        "{\"1\":\"a\",\"42\":\"b\"}",
+        sval_json::stream_to_string_with_options(&map, stringify).unwrap()
+    );
+    assert!(sval_json::stream_to_string_with_options(&map, strict).is_err());
+
+    let map = std::collections::BTreeMap::from([(-1i64, "a"), (i64::MAX, "b")]);
+
+    assert_eq!(
+        "{\"-1\":\"a\",\"9223372036854775807\":\"b\"}",
+        sval_json::stream_to_string_with_options(&map, stringify).unwrap()
+    );
+
+    // Text keys are always accepted
+    let map = std::collections::BTreeMap::from([("a", 1u64)]);
+
+    assert_eq!(
+        "{\"a\":1}",
+        sval_json::stream_to_string_with_options(&map, strict).unwrap()
+    );
+
+    // Only integer keys are stringified
+    assert!(sval_json::stream_to_string_with_options(
+        sval::MapSlice::new(&[(1.5f64, "a")]),
+        stringify
+    )
+    .is_err());
+    assert!(sval_json::stream_to_string_with_options(
+        sval::MapSlice::new(&[(true, "a")]),
+        stringify
+    )
+    .is_err());
+    assert!(sval_json::stream_to_string_with_options(
+        std::collections::BTreeMap::from([(vec![1, 2], "a")]),
+        stringify
+    )
+    .is_err());
+
+    // Enums with an index but no label are still written as objects keyed by their index
+    assert_eq!(
+        "{\"1\":true}",
+        sval_json::stream_to_string_with_options(IndexedVariant, strict).unwrap()
+    );
+}
+
+struct IndexedVariant;
+
+impl sval::Value for IndexedVariant {
+    fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> sval::Result {
+        stream.enum_begin(None, None, None)?;
+        stream.tagged_begin(None, None, Some(&sval::Index::new(1)))?;
+        stream.bool(true)?;
+        stream.tagged_end(None, None, Some(&sval::Index::new(1)))?;
+        stream.enum_end(None, None, None)
+    }
+}
+
+#[test]
+fn stream_sorted_map() {
+    let map = std::collections::HashMap::from([("c", 3), ("a", 1), ("b", 2)]);
+
+    assert_eq!(
+        "{\"a\":1,\"b\":2,\"c\":3}",
+        sval_json::stream_to_string(sval::sorted(&map)).unwrap()
+    );
+}
+
+#[test]
+fn stream_to_string_sorted() {
+    use std::collections::HashMap;
+
+    #[derive(Value)]
+    struct Record {
+        z: HashMap<&'static str, i32>,
+        a: Vec<HashMap<&'static str, HashMap<&'static str, i32>>>,
+    }
+
+    let map = HashMap::from([("c", 3), ("a", 1), ("b", 2), ("aa", 4)]);
+
+    assert_eq!(
+        "{\"a\":1,\"aa\":4,\"b\":2,\"c\":3}",
+        sval_json::stream_to_string_sorted(&map).unwrap()
+    );
+
+    // Each level of nested maps is sorted independently, but record fields aren't
+    let record = Record {
+        z: HashMap::from([("y", 2), ("x", 1)]),
+        a: vec![HashMap::from([
+            ("n", HashMap::from([("q", 2), ("p", 1)])),
+            ("m", HashMap::new()),
+        ])],
+    };
+
+    assert_eq!(
+        "{\"z\":{\"x\":1,\"y\":2},\"a\":[{\"m\":{},\"n\":{\"p\":1,\"q\":2}}]}",
+        sval_json::stream_to_string_sorted(&record).unwrap()
+    );
+
+    // Duplicate keys are kept in the order they were streamed
+    let map = sval::MapSlice::new(&[("b", 1), ("a", 2), ("b", 3), ("a", 4)]);
+
+    assert_eq!(
+        "{\"a\":2,\"a\":4,\"b\":1,\"b\":3}",
+        sval_json::stream_to_string_sorted(map).unwrap()
+    );
+
+    // Values that aren't maps are written the same as unsorted
+    assert_eq!(
+        sval_json::stream_to_string((1, "a", [Some(true), None])).unwrap(),
+        sval_json::stream_to_string_sorted((1, "a", [Some(true), None])).unwrap()
+    );
+
+    let map = std::collections::BTreeMap::from([(vec![1, 2], "a")]);
+
+    assert!(sval_json::stream_to_string_sorted(&map).is_err());
+
+    // Arbitrary precision numbers are written exactly
+    let b = sval::Number::new("+0012345678901234567890.5").unwrap();
+    let a = sval::Number::new("-1e400").unwrap();
+
+    assert_eq!(
+        "{\"a\":-1e400,\"b\":12345678901234567890.5}",
+        sval_json::stream_to_string_sorted(sval::MapSlice::new(&[("b", b), ("a", a)])).unwrap()
+    );
+}
+
+#[test]
+fn stream_to_string_truncated() {
+    let large = (0..1000).collect::<Vec<i32>>();
+
+    let json = sval_json::stream_to_string_truncated(&large, 20).unwrap();
+
+    assert_eq!("[0,1,2,3,4,5,6,7,8,9,\"…\"]", json);
+
+    let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(11, parsed.as_array().unwrap().len());
+
+    // Open objects get a marker entry and all containers are closed
+    let nested = MapStruct {
+        field_0: vec![MapStruct {
+            field_0: "a",
+            field_1: &large,
+        }],
+        field_1: 1,
+    };
+
+    let json = sval_json::stream_to_string_truncated(&nested, 40).unwrap();
+
+    assert_eq!(
+        "{\"field_0\":[{\"field_0\":\"a\",\"field_1\":[0,1,\"…\"]}]}",
+        json
+    );
+
+    let _: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+    let json = sval_json::stream_to_string_truncated(&nested, 13).unwrap();
+
+    assert_eq!("{\"field_0\":[{\"…\":\"…\"}]}", json);
+
+    let _: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+    // Values that fit within the budget are written in full
+    assert_eq!(
+        sval_json::stream_to_string(&nested).unwrap(),
+        sval_json::stream_to_string_truncated(&nested, usize::MAX).unwrap()
+    );
+
+    // Arbitrary precision numbers are written exactly
+    let num = sval::Number::new("+0012345678901234567890.5").unwrap();
+
+    assert_eq!(
+        "[12345678901234567890.5]",
+        sval_json::stream_to_string_truncated([num], usize::MAX).unwrap()
+    );
+
+    let map = std::collections::BTreeMap::from([(vec![1, 2], "a")]);
+
+    assert!(sval_json::stream_to_string_truncated(&map, usize::MAX).is_err());
+}
+
+#[test]
+fn stream_diff() {
+    let old = MapStruct {
+        field_0: vec![1, 2, 3],
+        field_1: Enum::<i32, i32>::Tagged(1),
+    };
+    let new = MapStruct {
+        field_0: vec![1, 5],
+        field_1: Enum::<i32, i32>::Constant,
+    };
+
+    assert_eq!(
+        "[{\"op\":\"replace\",\"path\":\"/field_0/1\",\"value\":5},{\"op\":\"remove\",\"path\":\"/field_0/2\"},{\"op\":\"replace\",\"path\":\"/field_1\",\"value\":\"Constant\"}]",
+        sval_json::stream_to_string(sval::diff(&old, &new).unwrap()).unwrap()
+    );
+
+    let old = std::collections::BTreeMap::from([("a/b", 1)]);
+    let new = std::collections::BTreeMap::from([("a/b", 1), ("c~", 2)]);
+
+    assert_eq!(
+        "[{\"op\":\"add\",\"path\":\"/c~0\",\"value\":2}]",
+        sval_json::stream_to_string(sval::diff(&old, &new).unwrap()).unwrap()
+    );
+
+    assert_eq!(
+        "[]",
+        sval_json::stream_to_string(sval::diff(&old, &old).unwrap()).unwrap()
+    );
+}
+
 #[test]
 fn stream_seq_struct() {
     assert_json(SeqStruct(42, true));
@@ -912,3 +1345,228 @@ fn stream_to_io() {
         String::from_utf8(buf).unwrap()
     );
 }
+
+#[test]
+fn stream_to_io_large() {
+    let value = vec![42u8; 1024 * 1024];
+    let expected = sval_json::stream_to_string(&value).unwrap();
+
+    let mut buf = Vec::new();
+    sval_json::stream_to_io_write(&mut buf, &value).unwrap();
+
+    assert_eq!(expected, String::from_utf8(buf).unwrap());
+
+    let mut scratch = [0; 256];
+
+    let mut buf = Vec::new();
+    sval_json::stream_to_io_write_buffered(&mut buf, &mut scratch, &value).unwrap();
+
+    assert_eq!(expected, String::from_utf8(buf).unwrap());
+
+    // The scratch buffer can be reused, and fragments larger than it are written directly
+    let value = "a".repeat(1024);
+
+    let mut buf = Vec::new();
+    sval_json::stream_to_io_write_buffered(&mut buf, &mut scratch, &value).unwrap();
+
+    assert_eq!(
+        sval_json::stream_to_string(&value).unwrap(),
+        String::from_utf8(buf).unwrap()
+    );
+}
+
+#[test]
+fn stream_to_io_err() {
+    use std::{error::Error, io};
+
+    type Write = fn() -> io::Result<usize>;
+
+    // A writer that accepts a limited number of bytes before failing
+    struct Limited {
+        remaining: usize,
+        err: Write,
+    }
+
+    impl io::Write for Limited {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if self.remaining == 0 {
+                return (self.err)();
+            }
+
+            let n = buf.len().min(self.remaining);
+            self.remaining -= n;
+
+            Ok(n)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn io_err_kind(err: sval_json::Error) -> io::ErrorKind {
+        err.source()
+            .and_then(|err| err.downcast_ref::<io::Error>())
+            .map(|err| err.kind())
+            .unwrap()
+    }
+
+    let value = vec![42u8; 1024];
+
+    // Writers that fail, or stop accepting bytes, part way through the value
+    let writers: [(Write, io::ErrorKind); 2] = [
+        (
+            || Err(io::ErrorKind::WouldBlock.into()),
+            io::ErrorKind::WouldBlock,
+        ),
+        (|| Ok(0), io::ErrorKind::WriteZero),
+    ];
+
+    for (write, expected) in writers {
+        let err = sval_json::stream_to_io_write(
+            Limited {
+                remaining: 100,
+                err: write,
+            },
+            &value,
+        )
+        .unwrap_err();
+
+        assert_eq!(expected, io_err_kind(err));
+
+        let err = sval_json::stream_to_io_write_buffered(
+            Limited {
+                remaining: 100,
+                err: write,
+            },
+            &mut [0; 64],
+            &value,
+        )
+        .unwrap_err();
+
+        assert_eq!(expected, io_err_kind(err));
+    }
+}
+
+#[test]
+fn stream_entries_to_io() {
+    let mut buf = Vec::new();
+
+    sval_json::stream_entries_to_io_write(
+        &mut buf,
+        MapStruct {
+            field_0: SeqStruct(1, "a"),
+            field_1: MapStruct {
+                field_0: true,
+                field_1: None::<i32>,
+            },
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        "{\"field_0\":[1,\"a\"]}\n{\"field_1\":{\"field_0\":true,\"field_1\":null}}\n",
+        String::from_utf8(buf).unwrap()
+    );
+
+    let mut buf = Vec::new();
+
+    sval_json::stream_entries_to_io_write(&mut buf, sval::MapSlice::new(&[("a", 1), ("b", 2)]))
+        .unwrap();
+
+    assert_eq!("{\"a\":1}\n{\"b\":2}\n", String::from_utf8(buf).unwrap());
+
+    assert!(sval_json::stream_entries_to_io_write(Vec::new(), 42).is_err());
+    assert!(sval_json::stream_entries_to_io_write(Vec::new(), SeqStruct(1, 2)).is_err());
+
+    let num = sval::Number::new("+0012345678901234567890.5").unwrap();
+
+    let mut buf = Vec::new();
+
+    sval_json::stream_entries_to_io_write(&mut buf, sval::MapSlice::new(&[("a", num)])).unwrap();
+
+    assert_eq!(
+        "{\"a\":12345678901234567890.5}\n",
+        String::from_utf8(buf).unwrap()
+    );
+}
+
+struct Parsed<'a>(&'a str, sval_json::ParseOptions);
+
+impl<'a> sval::Value for Parsed<'a> {
+    fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> sval::Result {
+        sval_json::stream_from_str_with_options(stream, self.0, self.1)
+            .map_err(|_| sval::Error::new())
+    }
+}
+
+fn parse(json: &str, options: sval_json::ParseOptions) -> Option<String> {
+    sval_json::stream_to_string(Parsed(json, options)).ok()
+}
+
+#[test]
+fn stream_from_str() {
+    let strict = sval_json::ParseOptions::default();
+
+    assert_eq!(
+        Some(
+            "{\"a\":[1,-2,18446744073709551615,123456789012345678901234,1.5e3,-0.25],\"b\":{},\"c\":[true,false,null]}"
+                .to_owned()
+        ),
+        parse(
+            " { \"a\" : [1, -2, 18446744073709551615, 123456789012345678901234, 1.5e3, -0.25], \"b\": {}, \"c\": [true, false, null] } ",
+            strict
+        )
+    );
+
+    assert_eq!(
+        Some("\"a\\\"b\\\\c/\\n\u{e9}\u{1f600}\"".to_owned()),
+        parse("\"a\\\"b\\\\c\\/\\n\\u00e9\\ud83d\\ude00\"", strict)
+    );
+
+    for invalid in [
+        "",
+        "[1 2]",
+        "{\"a\" 1}",
+        "{1:2}",
+        "[01]",
+        "[1.]",
+        "\"abc",
+        "\"\\x\"",
+        "\"\\ud83d\"",
+        "tru",
+        "1 2",
+        "[1,]",
+        "{\"a\":1,}",
+        "// comment\n1",
+        "[1 /* comment */]",
+    ] {
+        assert_eq!(None, parse(invalid, strict), "{}", invalid);
+    }
+
+    let mut nested = "[".repeat(129);
+    nested.push_str(&"]".repeat(129));
+    assert_eq!(None, parse(&nested, strict));
+}
+
+#[test]
+fn stream_from_str_with_options() {
+    let relaxed = sval_json::ParseOptions {
+        allow_trailing_commas: true,
+        allow_comments: true,
+    };
+
+    assert_eq!(Some("[1,2]".to_owned()), parse("[1,2,]", relaxed));
+    assert_eq!(Some("{\"a\":1}".to_owned()), parse("{\"a\":1,}", relaxed));
+    assert_eq!(
+        Some("{\"a\":1,\"b\":[2]}".to_owned()),
+        parse(
+            "// settings\n{\n    \"a\": 1, /* inline */\n    \"b\": [2, /* last */],\n}\n// end",
+            relaxed
+        )
+    );
+
+    for invalid in ["[,]", "[1,,]", "{,}", "[1 /* unterminated ]", "/ 1"] {
+        assert_eq!(None, parse(invalid, relaxed), "{}", invalid);
+    }
+}
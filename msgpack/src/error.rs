@@ -0,0 +1,72 @@
+use core::fmt;
+
+/**
+An error encountered streaming MessagePack.
+*/
+#[derive(Debug)]
+pub struct Error {
+    pub(crate) kind: ErrorKind,
+}
+
+#[derive(Debug)]
+pub(crate) enum ErrorKind {
+    #[cfg(feature = "alloc")]
+    Generic,
+    #[cfg(feature = "alloc")]
+    InvalidLength { expected: u64, actual: u64 },
+    #[cfg(feature = "alloc")]
+    OutOfRange(&'static str),
+}
+
+impl fmt::Display for Error {
+    // Errors are only produced when encoding, which requires `alloc`
+    #[cfg_attr(not(feature = "alloc"), allow(unused_variables))]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            #[cfg(feature = "alloc")]
+            ErrorKind::Generic => write!(f, "an error occurred serializing a value to MessagePack"),
+            #[cfg(feature = "alloc")]
+            ErrorKind::InvalidLength { expected, actual } => write!(
+                f,
+                "a length hint of {} didn't match the actual length of {}",
+                expected, actual
+            ),
+            #[cfg(feature = "alloc")]
+            ErrorKind::OutOfRange(what) => {
+                write!(f, "the {} is too large to encode as MessagePack", what)
+            }
+        }
+    }
+}
+
+impl Error {
+    #[cfg(feature = "alloc")]
+    pub(crate) fn generic() -> Self {
+        Error {
+            kind: ErrorKind::Generic,
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub(crate) fn invalid_length(expected: u64, actual: u64) -> Self {
+        Error {
+            kind: ErrorKind::InvalidLength { expected, actual },
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub(crate) fn out_of_range(what: &'static str) -> Self {
+        Error {
+            kind: ErrorKind::OutOfRange(what),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+mod std_support {
+    use super::*;
+
+    use std::error;
+
+    impl error::Error for Error {}
+}
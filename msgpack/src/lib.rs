@@ -0,0 +1,32 @@
+/*!
+MessagePack support for `sval`.
+
+Values are encoded in a `serde`-compatible way, using the smallest encoding
+that can hold each integer and length. Records are encoded as maps from their
+field names to their values.
+
+MessagePack needs the length of maps, arrays, strings, and binary up front.
+When a `*_begin` method isn't given a length hint, the value is encoded first
+and its length is inserted in front of it once it's known.
+
+Some functionality requires the `alloc` Cargo feature to be enabled.
+*/
+
+#![no_std]
+#![deny(missing_docs)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+mod error;
+
+pub use self::error::*;
+
+#[cfg(feature = "alloc")]
+mod to_vec;
+
+#[cfg(feature = "alloc")]
+pub use self::to_vec::*;
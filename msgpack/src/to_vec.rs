@@ -0,0 +1,609 @@
+use crate::Error;
+
+use alloc::vec::Vec;
+
+const NIL: u8 = 0xc0;
+const FALSE: u8 = 0xc2;
+const TRUE: u8 = 0xc3;
+const F32: u8 = 0xca;
+const F64: u8 = 0xcb;
+
+const UINT8: u8 = 0xcc;
+const UINT16: u8 = 0xcd;
+const UINT32: u8 = 0xce;
+const UINT64: u8 = 0xcf;
+const INT8: u8 = 0xd0;
+const INT16: u8 = 0xd1;
+const INT32: u8 = 0xd2;
+const INT64: u8 = 0xd3;
+
+const FIXMAP: u8 = 0x80;
+const FIXARRAY: u8 = 0x90;
+const FIXSTR: u8 = 0xa0;
+const STR8: u8 = 0xd9;
+const STR16: u8 = 0xda;
+const STR32: u8 = 0xdb;
+const BIN8: u8 = 0xc4;
+const BIN16: u8 = 0xc5;
+const BIN32: u8 = 0xc6;
+const ARRAY16: u8 = 0xdc;
+const ARRAY32: u8 = 0xdd;
+const MAP16: u8 = 0xde;
+const MAP32: u8 = 0xdf;
+
+/**
+Stream a value as MessagePack into a byte buffer.
+
+MessagePack has no integers wider than 64 bits, so this method will fail if the value
+contains a 128bit integer that doesn't fit in 64 bits. It will also fail if a length hint
+doesn't match the actual length of its value, or if a length doesn't fit in 32 bits.
+*/
+pub fn stream_to_vec(v: impl sval::Value) -> Result<Vec<u8>, Error> {
+    let mut stream = Encoder::new();
+
+    match v.stream(&mut stream) {
+        Ok(()) => Ok(stream.out),
+        Err(_) => Err(stream.err.unwrap_or_else(Error::generic)),
+    }
+}
+
+struct Encoder {
+    out: Vec<u8>,
+    containers: Vec<Length>,
+    fragments: Length,
+    is_variant: bool,
+    err: Option<Error>,
+}
+
+/**
+A kind of value that's prefixed by its length.
+*/
+#[derive(Clone, Copy)]
+enum Kind {
+    Map,
+    Array,
+    Str,
+    Bin,
+}
+
+impl Kind {
+    fn describe(self) -> &'static str {
+        match self {
+            Kind::Map => "map",
+            Kind::Array => "array",
+            Kind::Str => "string",
+            Kind::Bin => "binary value",
+        }
+    }
+
+    /**
+    Encode the smallest header for a value of this kind with the given length.
+
+    This method returns `None` if the length doesn't fit in 32 bits.
+    */
+    fn header(self, len: u64, buf: &mut [u8; 5]) -> Option<&[u8]> {
+        let len = u32::try_from(len).ok()?;
+
+        let header_len = match (self, len) {
+            (Kind::Map, 0..=15) => {
+                buf[0] = FIXMAP | len as u8;
+                1
+            }
+            (Kind::Array, 0..=15) => {
+                buf[0] = FIXARRAY | len as u8;
+                1
+            }
+            (Kind::Str, 0..=31) => {
+                buf[0] = FIXSTR | len as u8;
+                1
+            }
+            (Kind::Str, 0..=0xff) => {
+                buf[0] = STR8;
+                buf[1] = len as u8;
+                2
+            }
+            (Kind::Bin, 0..=0xff) => {
+                buf[0] = BIN8;
+                buf[1] = len as u8;
+                2
+            }
+            (_, 0..=0xffff) => {
+                buf[0] = match self {
+                    Kind::Map => MAP16,
+                    Kind::Array => ARRAY16,
+                    Kind::Str => STR16,
+                    Kind::Bin => BIN16,
+                };
+                buf[1..3].copy_from_slice(&(len as u16).to_be_bytes());
+                3
+            }
+            (_, _) => {
+                buf[0] = match self {
+                    Kind::Map => MAP32,
+                    Kind::Array => ARRAY32,
+                    Kind::Str => STR32,
+                    Kind::Bin => BIN32,
+                };
+                buf[1..5].copy_from_slice(&len.to_be_bytes());
+                5
+            }
+        };
+
+        Some(&buf[..header_len])
+    }
+}
+
+/**
+The expected and actual length of a map, array, string, or binary value.
+
+Values without an expected length have their header inserted at `offset`
+once they've been fully encoded.
+*/
+#[derive(Clone, Copy)]
+struct Length {
+    kind: Kind,
+    expected: Option<u64>,
+    actual: u64,
+    offset: usize,
+}
+
+impl Length {
+    fn new(kind: Kind, expected: Option<usize>, offset: usize) -> Self {
+        Length {
+            kind,
+            expected: expected.map(|len| len as u64),
+            actual: 0,
+            offset,
+        }
+    }
+}
+
+impl Encoder {
+    fn new() -> Self {
+        Encoder {
+            out: Vec::new(),
+            containers: Vec::new(),
+            fragments: Length::new(Kind::Str, None, 0),
+            is_variant: false,
+            err: None,
+        }
+    }
+
+    fn fail(&mut self, err: Error) -> sval::Result {
+        self.err = Some(err);
+        sval::error()
+    }
+
+    fn header(&mut self, kind: Kind, len: u64) -> sval::Result {
+        let mut buf = [0; 5];
+
+        match kind.header(len, &mut buf) {
+            Some(header) => {
+                self.out.extend_from_slice(header);
+
+                Ok(())
+            }
+            None => self.fail(Error::out_of_range(kind.describe())),
+        }
+    }
+
+    fn unsigned(&mut self, v: u64) -> sval::Result {
+        if v <= 0x7f {
+            self.out.push(v as u8);
+        } else if v <= u8::MAX as u64 {
+            self.out.push(UINT8);
+            self.out.push(v as u8);
+        } else if v <= u16::MAX as u64 {
+            self.out.push(UINT16);
+            self.out.extend_from_slice(&(v as u16).to_be_bytes());
+        } else if v <= u32::MAX as u64 {
+            self.out.push(UINT32);
+            self.out.extend_from_slice(&(v as u32).to_be_bytes());
+        } else {
+            self.out.push(UINT64);
+            self.out.extend_from_slice(&v.to_be_bytes());
+        }
+
+        Ok(())
+    }
+
+    fn signed(&mut self, v: i64) -> sval::Result {
+        if v >= 0 {
+            return self.unsigned(v as u64);
+        }
+
+        if v >= -32 {
+            // Negative fixints are the two's complement of the value itself
+            self.out.push(v as u8);
+        } else if v >= i8::MIN as i64 {
+            self.out.push(INT8);
+            self.out.push(v as i8 as u8);
+        } else if v >= i16::MIN as i64 {
+            self.out.push(INT16);
+            self.out.extend_from_slice(&(v as i16).to_be_bytes());
+        } else if v >= i32::MIN as i64 {
+            self.out.push(INT32);
+            self.out.extend_from_slice(&(v as i32).to_be_bytes());
+        } else {
+            self.out.push(INT64);
+            self.out.extend_from_slice(&v.to_be_bytes());
+        }
+
+        Ok(())
+    }
+
+    fn length_begin(&mut self, kind: Kind, len: Option<usize>) -> sval::Result<Length> {
+        let length = Length::new(kind, len, self.out.len());
+
+        if let Some(len) = length.expected {
+            self.header(kind, len)?;
+        }
+
+        Ok(length)
+    }
+
+    fn fragments_begin(&mut self, kind: Kind, num_bytes: Option<usize>) -> sval::Result {
+        self.fragments = self.length_begin(kind, num_bytes)?;
+
+        Ok(())
+    }
+
+    fn fragment(&mut self, fragment: &[u8]) -> sval::Result {
+        self.fragments.actual += fragment.len() as u64;
+        self.out.extend_from_slice(fragment);
+
+        Ok(())
+    }
+
+    fn fragments_end(&mut self) -> sval::Result {
+        let fragments = self.fragments;
+
+        self.end(fragments)
+    }
+
+    fn container_begin(&mut self, kind: Kind, num_entries: Option<usize>) -> sval::Result {
+        // Values within an enum that isn't a variant aren't wrapped
+        self.is_variant = false;
+
+        let container = self.length_begin(kind, num_entries)?;
+        self.containers.push(container);
+
+        Ok(())
+    }
+
+    fn container_next(&mut self) -> sval::Result {
+        match self.containers.last_mut() {
+            Some(container) => {
+                container.actual += 1;
+
+                Ok(())
+            }
+            None => self.fail(Error::generic()),
+        }
+    }
+
+    fn container_end(&mut self) -> sval::Result {
+        match self.containers.pop() {
+            Some(container) => self.end(container),
+            None => self.fail(Error::generic()),
+        }
+    }
+
+    fn end(&mut self, length: Length) -> sval::Result {
+        match length.expected {
+            Some(expected) if expected != length.actual => {
+                self.fail(Error::invalid_length(expected, length.actual))
+            }
+            Some(_) => Ok(()),
+            None => {
+                // Now that the length is known, insert the header in front of the value
+                // Any values nested within this one have already inserted their own headers
+                let mut buf = [0; 5];
+
+                match length.kind.header(length.actual, &mut buf) {
+                    Some(header) => {
+                        self.out
+                            .splice(length.offset..length.offset, header.iter().copied());
+
+                        Ok(())
+                    }
+                    None => self.fail(Error::out_of_range(length.kind.describe())),
+                }
+            }
+        }
+    }
+
+    fn label(&mut self, label: &sval::Label) -> sval::Result {
+        self.header(Kind::Str, label.as_str().len() as u64)?;
+        self.out.extend_from_slice(label.as_str().as_bytes());
+
+        Ok(())
+    }
+
+    /**
+    Begin a variant of an enum.
+
+    Variants with a label or index are encoded as a single-entry map from that label
+    or index to their value, like `serde`'s externally tagged enums.
+    */
+    fn variant_begin(
+        &mut self,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        if !self.is_variant {
+            return Ok(());
+        }
+
+        self.is_variant = false;
+
+        if let Some(label) = label {
+            self.out.push(FIXMAP | 1);
+            self.label(label)
+        } else if let Some(index) = index.and_then(|index| index.to_i64()) {
+            self.out.push(FIXMAP | 1);
+            self.signed(index)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<'sval> sval::Stream<'sval> for Encoder {
+    fn null(&mut self) -> sval::Result {
+        self.out.push(NIL);
+
+        Ok(())
+    }
+
+    fn bool(&mut self, value: bool) -> sval::Result {
+        self.out.push(if value { TRUE } else { FALSE });
+
+        Ok(())
+    }
+
+    fn text_begin(&mut self, num_bytes: Option<usize>) -> sval::Result {
+        self.fragments_begin(Kind::Str, num_bytes)
+    }
+
+    fn text_fragment_computed(&mut self, fragment: &str) -> sval::Result {
+        self.fragment(fragment.as_bytes())
+    }
+
+    fn text_end(&mut self) -> sval::Result {
+        self.fragments_end()
+    }
+
+    fn binary_begin(&mut self, num_bytes: Option<usize>) -> sval::Result {
+        self.fragments_begin(Kind::Bin, num_bytes)
+    }
+
+    fn binary_fragment_computed(&mut self, fragment: &[u8]) -> sval::Result {
+        self.fragment(fragment)
+    }
+
+    fn binary_end(&mut self) -> sval::Result {
+        self.fragments_end()
+    }
+
+    fn u8(&mut self, value: u8) -> sval::Result {
+        self.unsigned(value as u64)
+    }
+
+    fn u16(&mut self, value: u16) -> sval::Result {
+        self.unsigned(value as u64)
+    }
+
+    fn u32(&mut self, value: u32) -> sval::Result {
+        self.unsigned(value as u64)
+    }
+
+    fn u64(&mut self, value: u64) -> sval::Result {
+        self.unsigned(value)
+    }
+
+    fn u128(&mut self, value: u128) -> sval::Result {
+        match u64::try_from(value) {
+            Ok(value) => self.unsigned(value),
+            Err(_) => self.fail(Error::out_of_range("integer")),
+        }
+    }
+
+    fn i8(&mut self, value: i8) -> sval::Result {
+        self.signed(value as i64)
+    }
+
+    fn i16(&mut self, value: i16) -> sval::Result {
+        self.signed(value as i64)
+    }
+
+    fn i32(&mut self, value: i32) -> sval::Result {
+        self.signed(value as i64)
+    }
+
+    fn i64(&mut self, value: i64) -> sval::Result {
+        self.signed(value)
+    }
+
+    fn i128(&mut self, value: i128) -> sval::Result {
+        if value >= 0 {
+            self.u128(value as u128)
+        } else {
+            match i64::try_from(value) {
+                Ok(value) => self.signed(value),
+                Err(_) => self.fail(Error::out_of_range("integer")),
+            }
+        }
+    }
+
+    fn f32(&mut self, value: f32) -> sval::Result {
+        self.out.push(F32);
+        self.out.extend_from_slice(&value.to_be_bytes());
+
+        Ok(())
+    }
+
+    fn f64(&mut self, value: f64) -> sval::Result {
+        self.out.push(F64);
+        self.out.extend_from_slice(&value.to_be_bytes());
+
+        Ok(())
+    }
+
+    fn map_begin(&mut self, num_entries: Option<usize>) -> sval::Result {
+        self.container_begin(Kind::Map, num_entries)
+    }
+
+    fn map_key_begin(&mut self) -> sval::Result {
+        self.container_next()
+    }
+
+    fn map_key_end(&mut self) -> sval::Result {
+        Ok(())
+    }
+
+    fn map_value_begin(&mut self) -> sval::Result {
+        Ok(())
+    }
+
+    fn map_value_end(&mut self) -> sval::Result {
+        Ok(())
+    }
+
+    fn map_end(&mut self) -> sval::Result {
+        self.container_end()
+    }
+
+    fn seq_begin(&mut self, num_entries: Option<usize>) -> sval::Result {
+        self.container_begin(Kind::Array, num_entries)
+    }
+
+    fn seq_value_begin(&mut self) -> sval::Result {
+        self.container_next()
+    }
+
+    fn seq_value_end(&mut self) -> sval::Result {
+        Ok(())
+    }
+
+    fn seq_end(&mut self) -> sval::Result {
+        self.container_end()
+    }
+
+    fn enum_begin(
+        &mut self,
+        _: Option<&sval::Tag>,
+        _: Option<&sval::Label>,
+        _: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.is_variant = true;
+
+        Ok(())
+    }
+
+    fn enum_end(
+        &mut self,
+        _: Option<&sval::Tag>,
+        _: Option<&sval::Label>,
+        _: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.is_variant = false;
+
+        Ok(())
+    }
+
+    fn tagged_begin(
+        &mut self,
+        _: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.variant_begin(label, index)
+    }
+
+    fn tagged_end(
+        &mut self,
+        _: Option<&sval::Tag>,
+        _: Option<&sval::Label>,
+        _: Option<&sval::Index>,
+    ) -> sval::Result {
+        Ok(())
+    }
+
+    fn tag(
+        &mut self,
+        _: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+    ) -> sval::Result {
+        // Unit variants are encoded as just their label or index
+        if self.is_variant {
+            self.is_variant = false;
+
+            if let Some(label) = label {
+                return self.label(label);
+            } else if let Some(index) = index.and_then(|index| index.to_i64()) {
+                return self.signed(index);
+            }
+        }
+
+        self.null()
+    }
+
+    fn record_begin(
+        &mut self,
+        _: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+        num_entries: Option<usize>,
+    ) -> sval::Result {
+        self.variant_begin(label, index)?;
+        self.container_begin(Kind::Map, num_entries)
+    }
+
+    fn record_value_begin(&mut self, _: Option<&sval::Tag>, label: &sval::Label) -> sval::Result {
+        self.container_next()?;
+        self.label(label)
+    }
+
+    fn record_value_end(&mut self, _: Option<&sval::Tag>, _: &sval::Label) -> sval::Result {
+        Ok(())
+    }
+
+    fn record_end(
+        &mut self,
+        _: Option<&sval::Tag>,
+        _: Option<&sval::Label>,
+        _: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.container_end()
+    }
+
+    fn tuple_begin(
+        &mut self,
+        _: Option<&sval::Tag>,
+        label: Option<&sval::Label>,
+        index: Option<&sval::Index>,
+        num_entries: Option<usize>,
+    ) -> sval::Result {
+        self.variant_begin(label, index)?;
+        self.container_begin(Kind::Array, num_entries)
+    }
+
+    fn tuple_value_begin(&mut self, _: Option<&sval::Tag>, _: &sval::Index) -> sval::Result {
+        self.container_next()
+    }
+
+    fn tuple_value_end(&mut self, _: Option<&sval::Tag>, _: &sval::Index) -> sval::Result {
+        Ok(())
+    }
+
+    fn tuple_end(
+        &mut self,
+        _: Option<&sval::Tag>,
+        _: Option<&sval::Label>,
+        _: Option<&sval::Index>,
+    ) -> sval::Result {
+        self.container_end()
+    }
+}
@@ -0,0 +1,434 @@
+#![cfg(test)]
+
+#[macro_use]
+extern crate sval_derive;
+
+#[macro_use]
+extern crate serde_derive;
+
+#[path = "../../serde/test/twitter.rs"]
+mod twitter;
+
+use serde_json::{json, Value};
+
+fn assert_msgpack(expected: &[u8], v: impl sval::Value) {
+    assert_eq!(expected, &*sval_msgpack::stream_to_vec(v).unwrap());
+}
+
+fn assert_msgpack_value(v: impl sval::Value + serde::Serialize) {
+    let expected = serde_json::to_value(&v).unwrap();
+    let actual = decode(&sval_msgpack::stream_to_vec(&v).unwrap());
+
+    assert_eq!(expected, actual);
+}
+
+/**
+Decode MessagePack into a JSON value.
+
+This supports just enough of the format to check the output of `sval_msgpack`.
+*/
+fn decode(bytes: &[u8]) -> Value {
+    let mut reader = Reader(bytes);
+    let value = reader.value();
+
+    assert!(reader.0.is_empty(), "unexpected trailing bytes");
+
+    value
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> &'a [u8] {
+        let (taken, rest) = self.0.split_at(len);
+        self.0 = rest;
+
+        taken
+    }
+
+    fn be<const N: usize>(&mut self) -> [u8; N] {
+        self.take(N).try_into().unwrap()
+    }
+
+    fn u8(&mut self) -> u8 {
+        self.take(1)[0]
+    }
+
+    fn u16(&mut self) -> u16 {
+        u16::from_be_bytes(self.be())
+    }
+
+    fn u32(&mut self) -> u32 {
+        u32::from_be_bytes(self.be())
+    }
+
+    fn value(&mut self) -> Value {
+        match self.u8() {
+            b @ 0x00..=0x7f => json!(b),
+            b @ 0x80..=0x8f => self.map((b & 0x0f) as usize),
+            b @ 0x90..=0x9f => self.array((b & 0x0f) as usize),
+            b @ 0xa0..=0xbf => self.str((b & 0x1f) as usize),
+            0xc0 => Value::Null,
+            0xc2 => json!(false),
+            0xc3 => json!(true),
+            0xc4 => {
+                let len = self.u8() as usize;
+                self.bin(len)
+            }
+            0xc5 => {
+                let len = self.u16() as usize;
+                self.bin(len)
+            }
+            0xc6 => {
+                let len = self.u32() as usize;
+                self.bin(len)
+            }
+            0xca => json!(f32::from_be_bytes(self.be())),
+            0xcb => json!(f64::from_be_bytes(self.be())),
+            0xcc => json!(self.u8()),
+            0xcd => json!(self.u16()),
+            0xce => json!(self.u32()),
+            0xcf => json!(u64::from_be_bytes(self.be())),
+            0xd0 => json!(self.u8() as i8),
+            0xd1 => json!(i16::from_be_bytes(self.be())),
+            0xd2 => json!(i32::from_be_bytes(self.be())),
+            0xd3 => json!(i64::from_be_bytes(self.be())),
+            0xd9 => {
+                let len = self.u8() as usize;
+                self.str(len)
+            }
+            0xda => {
+                let len = self.u16() as usize;
+                self.str(len)
+            }
+            0xdb => {
+                let len = self.u32() as usize;
+                self.str(len)
+            }
+            0xdc => {
+                let len = self.u16() as usize;
+                self.array(len)
+            }
+            0xdd => {
+                let len = self.u32() as usize;
+                self.array(len)
+            }
+            0xde => {
+                let len = self.u16() as usize;
+                self.map(len)
+            }
+            0xdf => {
+                let len = self.u32() as usize;
+                self.map(len)
+            }
+            b @ 0xe0..=0xff => json!(b as i8),
+            b => panic!("unsupported marker {:#x}", b),
+        }
+    }
+
+    fn str(&mut self, len: usize) -> Value {
+        json!(std::str::from_utf8(self.take(len)).unwrap())
+    }
+
+    fn bin(&mut self, len: usize) -> Value {
+        json!(self.take(len))
+    }
+
+    fn array(&mut self, len: usize) -> Value {
+        Value::Array((0..len).map(|_| self.value()).collect())
+    }
+
+    fn map(&mut self, len: usize) -> Value {
+        let mut map = serde_json::Map::new();
+
+        for _ in 0..len {
+            let key = match self.value() {
+                Value::String(key) => key,
+                key => key.to_string(),
+            };
+
+            map.insert(key, self.value());
+        }
+
+        Value::Object(map)
+    }
+}
+
+#[derive(Value, Serialize)]
+struct MapStruct {
+    field_0: i32,
+    field_1: bool,
+    field_2: &'static str,
+}
+
+#[derive(Value, Serialize)]
+struct SeqStruct(i32, bool, &'static str);
+
+#[derive(Value, Serialize)]
+struct Tagged(i32);
+
+#[derive(Value, Serialize)]
+enum Enum {
+    Constant,
+    Tagged(i32),
+    MapStruct {
+        field_0: i32,
+        field_1: bool,
+        field_2: &'static str,
+    },
+    SeqStruct(i32, bool, &'static str),
+}
+
+struct Fragments<'a>(&'a [&'a str]);
+
+impl<'a> sval::Value for Fragments<'a> {
+    fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> sval::Result {
+        stream.text_begin(None)?;
+
+        for fragment in self.0 {
+            stream.text_fragment(fragment)?;
+        }
+
+        stream.text_end()
+    }
+}
+
+struct UnsizedSeq<T>(Vec<T>);
+
+impl<T: sval::Value> sval::Value for UnsizedSeq<T> {
+    fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> sval::Result {
+        stream.seq_begin(None)?;
+
+        for v in &self.0 {
+            stream.seq_value_begin()?;
+            stream.value(v)?;
+            stream.seq_value_end()?;
+        }
+
+        stream.seq_end()
+    }
+}
+
+struct UnsizedMap(usize);
+
+impl sval::Value for UnsizedMap {
+    fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> sval::Result {
+        stream.map_begin(None)?;
+
+        for i in 0..self.0 {
+            stream.map_key_begin()?;
+            stream.value_computed(&*format!("k{}", i))?;
+            stream.map_key_end()?;
+
+            stream.map_value_begin()?;
+            stream.u64(i as u64)?;
+            stream.map_value_end()?;
+        }
+
+        stream.map_end()
+    }
+}
+
+#[test]
+fn stream_primitive() {
+    assert_msgpack(&[0xc0], ());
+    assert_msgpack(&[0xc0], None::<i32>);
+    assert_msgpack(&[0x2a], Some(42i32));
+    assert_msgpack(&[0xc2], false);
+    assert_msgpack(&[0xc3], true);
+    assert_msgpack(&[0xca, 0x3f, 0xc0, 0x00, 0x00], 1.5f32);
+    assert_msgpack(&[0xcb, 0x3f, 0xf8, 0, 0, 0, 0, 0, 0], 1.5f64);
+}
+
+#[test]
+fn stream_integer() {
+    // Integers use the smallest encoding that can hold their value
+    assert_msgpack(&[0x00], 0u8);
+    assert_msgpack(&[0x7f], 127u64);
+    assert_msgpack(&[0xcc, 0x80], 128i32);
+    assert_msgpack(&[0xcc, 0xff], 255u16);
+    assert_msgpack(&[0xcd, 0x01, 0x00], 256u16);
+    assert_msgpack(&[0xce, 0x00, 0x01, 0x00, 0x00], 65536i64);
+    assert_msgpack(
+        &[0xcf, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+        u64::MAX,
+    );
+
+    assert_msgpack(&[0xff], -1i8);
+    assert_msgpack(&[0xe0], -32i64);
+    assert_msgpack(&[0xd0, 0xdf], -33i32);
+    assert_msgpack(&[0xd0, 0x80], i8::MIN);
+    assert_msgpack(&[0xd1, 0xff, 0x7f], -129i16);
+    assert_msgpack(&[0xd2, 0xff, 0xff, 0x7f, 0xff], -32769i32);
+    assert_msgpack(&[0xd3, 0x80, 0, 0, 0, 0, 0, 0, 0], i64::MIN);
+}
+
+#[test]
+fn stream_128bit() {
+    assert_msgpack(&[0x2a], 42u128);
+    assert_msgpack(&[0xff], -1i128);
+    assert_msgpack(
+        &[0xcf, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+        u64::MAX as u128,
+    );
+    assert_msgpack(&[0xd3, 0x80, 0, 0, 0, 0, 0, 0, 0], i64::MIN as i128);
+
+    // MessagePack doesn't have integers wider than 64 bits
+    assert!(sval_msgpack::stream_to_vec(u64::MAX as u128 + 1).is_err());
+    assert!(sval_msgpack::stream_to_vec(i64::MIN as i128 - 1).is_err());
+}
+
+#[test]
+fn stream_str() {
+    fn header(v: &str) -> Vec<u8> {
+        let encoded = sval_msgpack::stream_to_vec(v).unwrap();
+
+        assert!(encoded.ends_with(v.as_bytes()));
+
+        encoded[..encoded.len() - v.len()].to_vec()
+    }
+
+    assert_eq!(vec![0xa0], header(""));
+    assert_eq!(vec![0xa3], header("abc"));
+    assert_eq!(vec![0xbf], header(&"a".repeat(31)));
+    assert_eq!(vec![0xd9, 0x20], header(&"a".repeat(32)));
+    assert_eq!(vec![0xd9, 0xff], header(&"a".repeat(255)));
+    assert_eq!(vec![0xda, 0x01, 0x00], header(&"a".repeat(256)));
+    assert_eq!(
+        vec![0xdb, 0x00, 0x01, 0x00, 0x00],
+        header(&"a".repeat(65536))
+    );
+}
+
+#[test]
+fn stream_binary() {
+    assert_msgpack(&[0xc4, 0x03, 1, 2, 3], sval::BinarySlice::new(&[1, 2, 3]));
+
+    let encoded = sval_msgpack::stream_to_vec(sval::BinarySlice::new(&[0; 256])).unwrap();
+    assert_eq!(&[0xc5, 0x01, 0x00], &encoded[..3]);
+    assert_eq!(259, encoded.len());
+}
+
+#[test]
+fn stream_collections() {
+    assert_msgpack(&[0x93, 0x01, 0x02, 0x03], vec![1, 2, 3]);
+    assert_msgpack(&[0x90], Vec::<i32>::new());
+    assert_msgpack(
+        &[0x92, 0x92, 0x01, 0x02, 0x92, 0x03, 0x04],
+        [[1, 2], [3, 4]],
+    );
+    assert_msgpack(&[0x93, 0x01, 0xc3, 0xa1, b'a'], (1, true, "a"));
+    assert_msgpack(&[0x82, 0xa1, b'a', 0x01, 0xa1, b'b', 0x02], {
+        let mut map = std::collections::BTreeMap::new();
+        map.insert("a", 1);
+        map.insert("b", 2);
+        map
+    });
+
+    let encoded = sval_msgpack::stream_to_vec((0..16).collect::<Vec<_>>()).unwrap();
+    assert_eq!(&[0xdc, 0x00, 0x10], &encoded[..3]);
+    assert_msgpack_value((0..16).collect::<Vec<_>>());
+}
+
+#[test]
+fn stream_unsized() {
+    // Values without a length hint have their length inserted once they're done
+    assert_msgpack(&[0xa3, b'a', b'b', b'c'], Fragments(&["a", "bc"]));
+    assert_msgpack(&[0x92, 0x01, 0x02], UnsizedSeq(vec![1, 2]));
+    assert_msgpack(&[0x90], UnsizedSeq(Vec::<i32>::new()));
+    assert_msgpack(&[0x80], UnsizedMap(0));
+
+    // Nested values insert their own lengths
+    assert_msgpack(
+        &[0x92, 0x91, 0xa1, b'a', 0x92, 0xa1, b'b', 0xa2, b'c', b'd'],
+        UnsizedSeq(vec![
+            UnsizedSeq(vec![Fragments(&["a"])]),
+            UnsizedSeq(vec![Fragments(&["b"]), Fragments(&["c", "d"])]),
+        ]),
+    );
+
+    // Lengths that don't fit in a fix header shift the contents along
+    let encoded = sval_msgpack::stream_to_vec(UnsizedMap(16)).unwrap();
+    assert_eq!(&[0xde, 0x00, 0x10], &encoded[..3]);
+    assert_eq!(
+        json!((0..16)
+            .map(|i| (format!("k{}", i), json!(i)))
+            .collect::<serde_json::Map<_, _>>()),
+        decode(&encoded)
+    );
+
+    let encoded =
+        sval_msgpack::stream_to_vec(Fragments(&[&"a".repeat(200), &"b".repeat(100)])).unwrap();
+    assert_eq!(&[0xda, 0x01, 0x2c], &encoded[..3]);
+    assert_eq!(json!("a".repeat(200) + &"b".repeat(100)), decode(&encoded));
+}
+
+#[test]
+fn stream_invalid_length() {
+    struct ShortSeq;
+
+    impl sval::Value for ShortSeq {
+        fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(
+            &'sval self,
+            stream: &mut S,
+        ) -> sval::Result {
+            stream.seq_begin(Some(2))?;
+
+            stream.seq_value_begin()?;
+            stream.i32(1)?;
+            stream.seq_value_end()?;
+
+            stream.seq_end()
+        }
+    }
+
+    assert!(sval_msgpack::stream_to_vec(ShortSeq).is_err());
+}
+
+#[test]
+fn stream_struct() {
+    assert_msgpack(
+        &[
+            0x83, 0xa7, b'f', b'i', b'e', b'l', b'd', b'_', b'0', 0x01, 0xa7, b'f', b'i', b'e',
+            b'l', b'd', b'_', b'1', 0xc3, 0xa7, b'f', b'i', b'e', b'l', b'd', b'_', b'2', 0xa1,
+            b'a',
+        ],
+        MapStruct {
+            field_0: 1,
+            field_1: true,
+            field_2: "a",
+        },
+    );
+
+    assert_msgpack_value(MapStruct {
+        field_0: 1,
+        field_1: true,
+        field_2: "a",
+    });
+    assert_msgpack_value(SeqStruct(1, true, "a"));
+    assert_msgpack_value(Tagged(1));
+}
+
+#[test]
+fn stream_enum() {
+    assert_msgpack(
+        &[0xa8, b'C', b'o', b'n', b's', b't', b'a', b'n', b't'],
+        Enum::Constant,
+    );
+
+    assert_msgpack_value(Enum::Constant);
+    assert_msgpack_value(Enum::Tagged(1));
+    assert_msgpack_value(Enum::MapStruct {
+        field_0: 1,
+        field_1: true,
+        field_2: "a",
+    });
+    assert_msgpack_value(Enum::SeqStruct(1, true, "a"));
+}
+
+#[test]
+fn stream_twitter() {
+    let value = twitter::input_struct();
+
+    assert_msgpack_value(&value);
+}
@@ -24,6 +24,8 @@ mod std {
 #[cfg(all(not(feature = "alloc"), not(feature = "std")))]
 extern crate core as std;
 
+mod option;
+mod primitive;
 mod seq;
 
 /**
@@ -0,0 +1,63 @@
+use sval::{tags, Index, Label, Result, Stream};
+
+use crate::ValueRef;
+
+impl<'sval, T: ValueRef<'sval>> ValueRef<'sval> for Option<T> {
+    fn stream_ref<S: Stream<'sval> + ?Sized>(&self, stream: &mut S) -> Result {
+        if let Some(some) = self {
+            stream.tagged_begin(
+                Some(&tags::RUST_OPTION_SOME),
+                Some(&Label::new("Some").with_tag(&tags::VALUE_IDENT)),
+                Some(&Index::new(1).with_tag(&tags::VALUE_OFFSET)),
+            )?;
+
+            crate::stream_ref(&mut *stream, some)?;
+
+            stream.tagged_end(
+                Some(&tags::RUST_OPTION_SOME),
+                Some(&Label::new("Some").with_tag(&tags::VALUE_IDENT)),
+                Some(&Index::new(1).with_tag(&tags::VALUE_OFFSET)),
+            )
+        } else {
+            stream.tag(
+                Some(&tags::RUST_OPTION_NONE),
+                Some(&Label::new("None").with_tag(&tags::VALUE_IDENT)),
+                Some(&Index::new(0).with_tag(&tags::VALUE_OFFSET)),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::{compat_case, Ref, Token};
+
+    #[test]
+    fn option_compat() {
+        compat_case(
+            &Some(Ref(&1)),
+            &[
+                Token::TaggedBegin(
+                    Some(sval::tags::RUST_OPTION_SOME),
+                    Some(sval::Label::new("Some")),
+                    Some(sval::Index::new(1)),
+                ),
+                Token::I32(1),
+                Token::TaggedEnd(
+                    Some(sval::tags::RUST_OPTION_SOME),
+                    Some(sval::Label::new("Some")),
+                    Some(sval::Index::new(1)),
+                ),
+            ],
+        );
+
+        compat_case(
+            &None::<Ref<&i32>>,
+            &[Token::Tag(
+                Some(sval::tags::RUST_OPTION_NONE),
+                Some(sval::Label::new("None")),
+                Some(sval::Index::new(0)),
+            )],
+        );
+    }
+}
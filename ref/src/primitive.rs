@@ -0,0 +1,135 @@
+use sval::{Result, Stream};
+
+use crate::ValueRef;
+
+macro_rules! impl_value_ref {
+    ($($ty:ty,)*) => {
+        $(
+            impl<'sval> ValueRef<'sval> for $ty {
+                fn stream_ref<S: Stream<'sval> + ?Sized>(&self, stream: &mut S) -> Result {
+                    stream.value_computed(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_value_ref!(
+    (),
+    bool,
+    char,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    f32,
+    f64,
+);
+
+impl<'sval> ValueRef<'sval> for &'sval str {
+    fn stream_ref<S: Stream<'sval> + ?Sized>(&self, stream: &mut S) -> Result {
+        stream.value(*self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+mod alloc_support {
+    use super::*;
+
+    use crate::std::borrow::Cow;
+
+    /*
+    Only the `Borrowed` variant holds data that lives for `'sval`,
+    so the `Owned` variant is streamed as computed.
+    */
+    impl<'sval> ValueRef<'sval> for Cow<'sval, str> {
+        fn stream_ref<S: Stream<'sval> + ?Sized>(&self, stream: &mut S) -> Result {
+            match self {
+                Cow::Borrowed(v) => v.stream_ref(stream),
+                Cow::Owned(v) => stream.value_computed(&**v),
+            }
+        }
+    }
+
+    impl<'sval> ValueRef<'sval> for Cow<'sval, [u8]> {
+        fn stream_ref<S: Stream<'sval> + ?Sized>(&self, stream: &mut S) -> Result {
+            match self {
+                Cow::Borrowed(v) => stream.value(sval::BinarySlice::new(v)),
+                Cow::Owned(v) => stream.value_computed(sval::BinarySlice::new(v)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::{compat_case, Token};
+
+    #[test]
+    fn primitive_compat() {
+        compat_case(&1i32, &[Token::I32(1)]);
+        compat_case(&true, &[Token::Bool(true)]);
+        compat_case(&(), &[Token::Tag(Some(sval::tags::RUST_UNIT), None, None)]);
+    }
+
+    #[test]
+    fn str_compat() {
+        compat_case(
+            &"a string",
+            &[
+                Token::TextBegin(Some(8)),
+                Token::TextFragment("a string"),
+                Token::TextEnd,
+            ],
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn cow_ref() {
+        use crate::test::assert_tokens_ref;
+        use std::borrow::Cow;
+
+        assert_tokens_ref(
+            Cow::Borrowed("abc"),
+            &[
+                Token::TextBegin(Some(3)),
+                Token::TextFragment("abc"),
+                Token::TextEnd,
+            ],
+        );
+
+        assert_tokens_ref(
+            Cow::<str>::Owned("abc".into()),
+            &[
+                Token::TextBegin(Some(3)),
+                Token::TextFragmentComputed("abc".into()),
+                Token::TextEnd,
+            ],
+        );
+
+        assert_tokens_ref(
+            Cow::Borrowed(b"abc" as &[u8]),
+            &[
+                Token::BinaryBegin(Some(3)),
+                Token::BinaryFragment(b"abc"),
+                Token::BinaryEnd,
+            ],
+        );
+
+        assert_tokens_ref(
+            Cow::<[u8]>::Owned(b"abc".to_vec()),
+            &[
+                Token::BinaryBegin(Some(3)),
+                Token::BinaryFragmentComputed(b"abc".to_vec()),
+                Token::BinaryEnd,
+            ],
+        );
+    }
+}
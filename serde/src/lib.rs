@@ -4,6 +4,13 @@ Treat any [`sval::Value`] as a [`serde::Serialize`].
 This crate provides `ToSerialize`, a wrapper around any `sval::Value`
 that forwards it through `serde`.
 
+# Deserialization
+
+Add the `alloc` feature to deserialize any [`serde::Deserialize`] from an
+`sval::Value` using `from_value`. The value is buffered first and then
+deserialized in a self-describing way, so text borrowed from the value can
+be deserialized into borrowed types like `&str`.
+
 # Buffering
 
 Add the `alloc` feature to enable buffering for values that need it.
@@ -20,7 +27,16 @@ produce errors during serialization.
 #![no_std]
 #![deny(missing_docs)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 mod to_serialize;
 mod to_value;
 
 pub use self::{to_serialize::*, to_value::*};
+
+#[cfg(feature = "alloc")]
+mod to_deserializer;
+
+#[cfg(feature = "alloc")]
+pub use self::to_deserializer::*;
@@ -0,0 +1,709 @@
+use core::marker::PhantomData;
+
+use alloc::{borrow::Cow, boxed::Box, vec::Vec};
+
+use serde::de::{
+    value::{Error, MapDeserializer, SeqDeserializer},
+    Error as _, IntoDeserializer,
+};
+
+use sval_nested::{
+    Stream, StreamEnum, StreamMap, StreamRecord, StreamSeq, StreamTuple, Unsupported,
+};
+
+/**
+Deserialize a [`serde::Deserialize`] from an [`sval::Value`].
+
+This method requires the `alloc` feature.
+*/
+pub fn from_value<'de, T: serde::Deserialize<'de>>(
+    value: &'de (impl sval::Value + ?Sized),
+) -> Result<T, Error> {
+    T::deserialize(to_deserializer(value)?)
+}
+
+/**
+Adapt an [`sval::Value`] into a [`serde::Deserializer`].
+
+This method requires the `alloc` feature.
+*/
+pub fn to_deserializer<'de>(
+    value: &'de (impl sval::Value + ?Sized),
+) -> Result<ToDeserializer<'de>, Error> {
+    ToDeserializer::new(value)
+}
+
+/**
+A [`serde::Deserializer`] over a buffered [`sval::Value`].
+
+The value is buffered upfront so that `serde` can drive deserialization. Text and binary
+fragments that borrow from the original value are retained, so they can be deserialized
+into borrowed types like `&'de str`.
+
+Deserialization is self-describing. Records and maps are visited as maps, sequences and tuples
+as sequences, and enum variants as either their label or a map with a single entry with their
+label as the key. Enum variants can also be deserialized directly into `serde` enums.
+
+This type requires the `alloc` feature.
+*/
+pub struct ToDeserializer<'de>(Content<'de>);
+
+impl<'de> ToDeserializer<'de> {
+    /**
+    Buffer an [`sval::Value`] into a [`serde::Deserializer`].
+    */
+    pub fn new(value: &'de (impl sval::Value + ?Sized)) -> Result<Self, Error> {
+        sval_nested::stream_ref(ToContent::default(), value)
+            .map(ToDeserializer)
+            .map_err(Error::custom)
+    }
+}
+
+enum Content<'de> {
+    Null,
+    Unit,
+    Bool(bool),
+    I64(i64),
+    U64(u64),
+    I128(i128),
+    U128(u128),
+    F64(f64),
+    Text(Cow<'de, str>),
+    Binary(Cow<'de, [u8]>),
+    Some(Box<Content<'de>>),
+    Newtype(Box<Content<'de>>),
+    Seq(Vec<Content<'de>>),
+    Map(Vec<(Content<'de>, Content<'de>)>),
+    Variant(Box<Variant<'de>>),
+}
+
+struct Variant<'de> {
+    tag: Content<'de>,
+    value: Option<Content<'de>>,
+}
+
+struct VariantValue<'de>(Option<Content<'de>>);
+
+impl<'de> IntoDeserializer<'de, Error> for Content<'de> {
+    type Deserializer = ToDeserializer<'de>;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        ToDeserializer(self)
+    }
+}
+
+impl<'de> serde::Deserializer<'de> for ToDeserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: serde::de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Content::Null | Content::Unit => visitor.visit_unit(),
+            Content::Bool(v) => visitor.visit_bool(v),
+            Content::I64(v) => visitor.visit_i64(v),
+            Content::U64(v) => visitor.visit_u64(v),
+            Content::I128(v) => visitor.visit_i128(v),
+            Content::U128(v) => visitor.visit_u128(v),
+            Content::F64(v) => visitor.visit_f64(v),
+            Content::Text(Cow::Borrowed(v)) => visitor.visit_borrowed_str(v),
+            Content::Text(Cow::Owned(v)) => visitor.visit_string(v),
+            Content::Binary(Cow::Borrowed(v)) => visitor.visit_borrowed_bytes(v),
+            Content::Binary(Cow::Owned(v)) => visitor.visit_byte_buf(v),
+            Content::Some(v) => visitor.visit_some(ToDeserializer(*v)),
+            Content::Newtype(v) => ToDeserializer(*v).deserialize_any(visitor),
+            Content::Seq(v) => {
+                let mut seq = SeqDeserializer::new(v.into_iter());
+                let value = visitor.visit_seq(&mut seq)?;
+                seq.end()?;
+
+                Ok(value)
+            }
+            Content::Map(v) => {
+                let mut map = MapDeserializer::new(v.into_iter());
+                let value = visitor.visit_map(&mut map)?;
+                map.end()?;
+
+                Ok(value)
+            }
+            Content::Variant(v) => match v.value {
+                None => ToDeserializer(v.tag).deserialize_any(visitor),
+                Some(value) => {
+                    let mut map = MapDeserializer::new(Some((v.tag, value)).into_iter());
+                    let value = visitor.visit_map(&mut map)?;
+                    map.end()?;
+
+                    Ok(value)
+                }
+            },
+        }
+    }
+
+    fn deserialize_option<V: serde::de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Content::Null => visitor.visit_none(),
+            Content::Some(v) => visitor.visit_some(ToDeserializer(*v)),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: serde::de::Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self.0 {
+            Content::Newtype(v) => visitor.visit_newtype_struct(ToDeserializer(*v)),
+            _ => visitor.visit_newtype_struct(self),
+        }
+    }
+
+    fn deserialize_enum<V: serde::de::Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self.0 {
+            Content::Variant(v) => visitor.visit_enum(*v),
+            Content::Newtype(v) => ToDeserializer(*v).deserialize_enum("", &[], visitor),
+            tag @ (Content::Text(_) | Content::U64(_)) => {
+                visitor.visit_enum(Variant { tag, value: None })
+            }
+            Content::Map(mut entries) if entries.len() == 1 => {
+                let (tag, value) = entries.pop().expect("missing map entry");
+
+                visitor.visit_enum(Variant {
+                    tag,
+                    value: Some(value),
+                })
+            }
+            _ => Err(Error::custom("the value is not an enum variant")),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+impl<'de> serde::de::EnumAccess<'de> for Variant<'de> {
+    type Error = Error;
+
+    type Variant = VariantValue<'de>;
+
+    fn variant_seed<V: serde::de::DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self::Variant), Error> {
+        let tag = seed.deserialize(ToDeserializer(self.tag))?;
+
+        Ok((tag, VariantValue(self.value)))
+    }
+}
+
+impl<'de> serde::de::VariantAccess<'de> for VariantValue<'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        match self.0 {
+            None | Some(Content::Null) | Some(Content::Unit) => Ok(()),
+            Some(_) => Err(Error::custom("expected a unit variant")),
+        }
+    }
+
+    fn newtype_variant_seed<T: serde::de::DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, Error> {
+        match self.0 {
+            Some(value) => seed.deserialize(ToDeserializer(value)),
+            None => Err(Error::custom("expected a newtype variant")),
+        }
+    }
+
+    fn tuple_variant<V: serde::de::Visitor<'de>>(
+        self,
+        _: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self.0 {
+            Some(value) => serde::Deserializer::deserialize_any(ToDeserializer(value), visitor),
+            None => Err(Error::custom("expected a tuple variant")),
+        }
+    }
+
+    fn struct_variant<V: serde::de::Visitor<'de>>(
+        self,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self.0 {
+            Some(value) => serde::Deserializer::deserialize_any(ToDeserializer(value), visitor),
+            None => Err(Error::custom("expected a struct variant")),
+        }
+    }
+}
+
+fn variant_tag<'de>(
+    label: Option<sval::Label>,
+    index: Option<sval::Index>,
+) -> sval_nested::Result<Content<'de>> {
+    match (label, index.and_then(|index| index.to_u64())) {
+        (Some(label), _) => Ok(label_content(label)),
+        (None, Some(index)) => Ok(Content::U64(index)),
+        (None, None) => Err(sval_nested::Error::invalid_value(
+            "enum variant must have a label or index",
+        )),
+    }
+}
+
+fn variant<'de>(tag: Content<'de>, value: Option<Content<'de>>) -> Content<'de> {
+    Content::Variant(Box::new(Variant { tag, value }))
+}
+
+fn label_content<'de>(label: sval::Label) -> Content<'de> {
+    match label.as_static_str() {
+        Some(label) => Content::Text(Cow::Borrowed(label)),
+        None => Content::Text(Cow::Owned(label.as_str().into())),
+    }
+}
+
+fn tagged_content(tag: Option<sval::Tag>, value: Content) -> Content {
+    match tag {
+        Some(sval::tags::RUST_OPTION_SOME) => Content::Some(Box::new(value)),
+        _ => Content::Newtype(Box::new(value)),
+    }
+}
+
+struct ToContent<'de>(PhantomData<Content<'de>>);
+
+impl<'de> Default for ToContent<'de> {
+    fn default() -> Self {
+        ToContent(PhantomData)
+    }
+}
+
+struct ToSeq<'de> {
+    seq: Vec<Content<'de>>,
+}
+
+struct ToMap<'de> {
+    key: Option<Content<'de>>,
+    map: Vec<(Content<'de>, Content<'de>)>,
+}
+
+struct ToEnum<'de>(PhantomData<Content<'de>>);
+
+struct ToVariant<'de, S> {
+    tag: Content<'de>,
+    stream: S,
+}
+
+impl<'de> Stream<'de> for ToContent<'de> {
+    type Ok = Content<'de>;
+
+    type Seq = ToSeq<'de>;
+
+    type Map = ToMap<'de>;
+
+    type Tuple = ToSeq<'de>;
+
+    type Record = ToMap<'de>;
+
+    type Enum = ToEnum<'de>;
+
+    fn null(self) -> sval_nested::Result<Self::Ok> {
+        Ok(Content::Null)
+    }
+
+    fn bool(self, value: bool) -> sval_nested::Result<Self::Ok> {
+        Ok(Content::Bool(value))
+    }
+
+    fn i64(self, value: i64) -> sval_nested::Result<Self::Ok> {
+        Ok(Content::I64(value))
+    }
+
+    fn i128(self, value: i128) -> sval_nested::Result<Self::Ok> {
+        match value.try_into() {
+            Ok(value) => Ok(Content::I64(value)),
+            Err(_) => Ok(Content::I128(value)),
+        }
+    }
+
+    fn u64(self, value: u64) -> sval_nested::Result<Self::Ok> {
+        Ok(Content::U64(value))
+    }
+
+    fn u128(self, value: u128) -> sval_nested::Result<Self::Ok> {
+        match value.try_into() {
+            Ok(value) => Ok(Content::U64(value)),
+            Err(_) => Ok(Content::U128(value)),
+        }
+    }
+
+    fn f64(self, value: f64) -> sval_nested::Result<Self::Ok> {
+        Ok(Content::F64(value))
+    }
+
+    fn text(self, text: &'de str) -> sval_nested::Result<Self::Ok> {
+        Ok(Content::Text(Cow::Borrowed(text)))
+    }
+
+    fn text_computed(self, text: &str) -> sval_nested::Result<Self::Ok> {
+        Ok(Content::Text(Cow::Owned(text.into())))
+    }
+
+    fn binary(self, binary: &'de [u8]) -> sval_nested::Result<Self::Ok> {
+        Ok(Content::Binary(Cow::Borrowed(binary)))
+    }
+
+    fn binary_computed(self, binary: &[u8]) -> sval_nested::Result<Self::Ok> {
+        Ok(Content::Binary(Cow::Owned(binary.into())))
+    }
+
+    fn tag(
+        self,
+        tag: Option<sval::Tag>,
+        _: Option<sval::Label>,
+        _: Option<sval::Index>,
+    ) -> sval_nested::Result<Self::Ok> {
+        match tag {
+            Some(sval::tags::RUST_OPTION_NONE) => Ok(Content::Null),
+            _ => Ok(Content::Unit),
+        }
+    }
+
+    fn tagged<V: sval_nested::ValueRef<'de>>(
+        self,
+        tag: Option<sval::Tag>,
+        _: Option<sval::Label>,
+        _: Option<sval::Index>,
+        value: V,
+    ) -> sval_nested::Result<Self::Ok> {
+        let value = ToContent::default().value(value)?;
+
+        Ok(tagged_content(tag, value))
+    }
+
+    fn tagged_computed<V: sval::Value>(
+        self,
+        tag: Option<sval::Tag>,
+        _: Option<sval::Label>,
+        _: Option<sval::Index>,
+        value: V,
+    ) -> sval_nested::Result<Self::Ok> {
+        let value = ToContent::default().value_computed(value)?;
+
+        Ok(tagged_content(tag, value))
+    }
+
+    fn seq_begin(self, num_entries: Option<usize>) -> sval_nested::Result<Self::Seq> {
+        Ok(ToSeq {
+            seq: Vec::with_capacity(num_entries.unwrap_or(0)),
+        })
+    }
+
+    fn map_begin(self, num_entries: Option<usize>) -> sval_nested::Result<Self::Map> {
+        Ok(ToMap {
+            key: None,
+            map: Vec::with_capacity(num_entries.unwrap_or(0)),
+        })
+    }
+
+    fn tuple_begin(
+        self,
+        _: Option<sval::Tag>,
+        _: Option<sval::Label>,
+        _: Option<sval::Index>,
+        num_entries: Option<usize>,
+    ) -> sval_nested::Result<Self::Tuple> {
+        self.seq_begin(num_entries)
+    }
+
+    fn record_begin(
+        self,
+        _: Option<sval::Tag>,
+        _: Option<sval::Label>,
+        _: Option<sval::Index>,
+        num_entries: Option<usize>,
+    ) -> sval_nested::Result<Self::Record> {
+        self.map_begin(num_entries)
+    }
+
+    fn enum_begin(
+        self,
+        _: Option<sval::Tag>,
+        _: Option<sval::Label>,
+        _: Option<sval::Index>,
+    ) -> sval_nested::Result<Self::Enum> {
+        Ok(ToEnum(PhantomData))
+    }
+}
+
+impl<'de> ToMap<'de> {
+    fn push_value(&mut self, value: Content<'de>) -> sval_nested::Result {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| sval_nested::Error::invalid_value("missing map key"))?;
+
+        self.map.push((key, value));
+
+        Ok(())
+    }
+}
+
+impl<'de> StreamSeq<'de> for ToSeq<'de> {
+    type Ok = Content<'de>;
+
+    fn value<V: sval_nested::ValueRef<'de>>(&mut self, value: V) -> sval_nested::Result {
+        self.seq.push(ToContent::default().value(value)?);
+
+        Ok(())
+    }
+
+    fn value_computed<V: sval::Value>(&mut self, value: V) -> sval_nested::Result {
+        self.seq.push(ToContent::default().value_computed(value)?);
+
+        Ok(())
+    }
+
+    fn end(self) -> sval_nested::Result<Self::Ok> {
+        Ok(Content::Seq(self.seq))
+    }
+}
+
+impl<'de> StreamTuple<'de> for ToSeq<'de> {
+    type Ok = Content<'de>;
+
+    fn value<V: sval_nested::ValueRef<'de>>(
+        &mut self,
+        _: Option<sval::Tag>,
+        _: sval::Index,
+        value: V,
+    ) -> sval_nested::Result {
+        StreamSeq::value(self, value)
+    }
+
+    fn value_computed<V: sval::Value>(
+        &mut self,
+        _: Option<sval::Tag>,
+        _: sval::Index,
+        value: V,
+    ) -> sval_nested::Result {
+        StreamSeq::value_computed(self, value)
+    }
+
+    fn end(self) -> sval_nested::Result<Self::Ok> {
+        StreamSeq::end(self)
+    }
+}
+
+impl<'de> StreamMap<'de> for ToMap<'de> {
+    type Ok = Content<'de>;
+
+    fn key<V: sval_nested::ValueRef<'de>>(&mut self, key: V) -> sval_nested::Result {
+        self.key = Some(ToContent::default().value(key)?);
+
+        Ok(())
+    }
+
+    fn key_computed<V: sval::Value>(&mut self, key: V) -> sval_nested::Result {
+        self.key = Some(ToContent::default().value_computed(key)?);
+
+        Ok(())
+    }
+
+    fn value<V: sval_nested::ValueRef<'de>>(&mut self, value: V) -> sval_nested::Result {
+        let value = ToContent::default().value(value)?;
+
+        self.push_value(value)
+    }
+
+    fn value_computed<V: sval::Value>(&mut self, value: V) -> sval_nested::Result {
+        let value = ToContent::default().value_computed(value)?;
+
+        self.push_value(value)
+    }
+
+    fn end(self) -> sval_nested::Result<Self::Ok> {
+        Ok(Content::Map(self.map))
+    }
+}
+
+impl<'de> StreamRecord<'de> for ToMap<'de> {
+    type Ok = Content<'de>;
+
+    fn value<V: sval_nested::ValueRef<'de>>(
+        &mut self,
+        _: Option<sval::Tag>,
+        label: sval::Label,
+        value: V,
+    ) -> sval_nested::Result {
+        let value = ToContent::default().value(value)?;
+
+        self.map.push((label_content(label), value));
+
+        Ok(())
+    }
+
+    fn value_computed<V: sval::Value>(
+        &mut self,
+        _: Option<sval::Tag>,
+        label: sval::Label,
+        value: V,
+    ) -> sval_nested::Result {
+        let value = ToContent::default().value_computed(value)?;
+
+        self.map.push((label_content(label), value));
+
+        Ok(())
+    }
+
+    fn end(self) -> sval_nested::Result<Self::Ok> {
+        Ok(Content::Map(self.map))
+    }
+}
+
+impl<'de> StreamEnum<'de> for ToEnum<'de> {
+    type Ok = Content<'de>;
+
+    type Tuple = ToVariant<'de, ToSeq<'de>>;
+
+    type Record = ToVariant<'de, ToMap<'de>>;
+
+    type Nested = Unsupported<Self::Ok>;
+
+    fn tag(
+        self,
+        _: Option<sval::Tag>,
+        label: Option<sval::Label>,
+        index: Option<sval::Index>,
+    ) -> sval_nested::Result<Self::Ok> {
+        Ok(variant(variant_tag(label, index)?, None))
+    }
+
+    fn tagged<V: sval_nested::ValueRef<'de>>(
+        self,
+        _: Option<sval::Tag>,
+        label: Option<sval::Label>,
+        index: Option<sval::Index>,
+        value: V,
+    ) -> sval_nested::Result<Self::Ok> {
+        let tag = variant_tag(label, index)?;
+        let value = ToContent::default().value(value)?;
+
+        Ok(variant(tag, Some(value)))
+    }
+
+    fn tagged_computed<V: sval::Value>(
+        self,
+        _: Option<sval::Tag>,
+        label: Option<sval::Label>,
+        index: Option<sval::Index>,
+        value: V,
+    ) -> sval_nested::Result<Self::Ok> {
+        let tag = variant_tag(label, index)?;
+        let value = ToContent::default().value_computed(value)?;
+
+        Ok(variant(tag, Some(value)))
+    }
+
+    fn tuple_begin(
+        self,
+        _: Option<sval::Tag>,
+        label: Option<sval::Label>,
+        index: Option<sval::Index>,
+        num_entries: Option<usize>,
+    ) -> sval_nested::Result<Self::Tuple> {
+        Ok(ToVariant {
+            tag: variant_tag(label, index)?,
+            stream: ToContent::default().seq_begin(num_entries)?,
+        })
+    }
+
+    fn record_begin(
+        self,
+        _: Option<sval::Tag>,
+        label: Option<sval::Label>,
+        index: Option<sval::Index>,
+        num_entries: Option<usize>,
+    ) -> sval_nested::Result<Self::Record> {
+        Ok(ToVariant {
+            tag: variant_tag(label, index)?,
+            stream: ToContent::default().map_begin(num_entries)?,
+        })
+    }
+
+    fn nested<
+        F: FnOnce(Self::Nested) -> sval_nested::Result<<Self::Nested as StreamEnum<'de>>::Ok>,
+    >(
+        self,
+        _: Option<sval::Tag>,
+        _: Option<sval::Label>,
+        _: Option<sval::Index>,
+        _: F,
+    ) -> sval_nested::Result<Self::Ok> {
+        Err(sval_nested::Error::invalid_value(
+            "nested enums aren't supported",
+        ))
+    }
+
+    fn empty(self) -> sval_nested::Result<Self::Ok> {
+        Ok(Content::Unit)
+    }
+}
+
+impl<'de> StreamTuple<'de> for ToVariant<'de, ToSeq<'de>> {
+    type Ok = Content<'de>;
+
+    fn value<V: sval_nested::ValueRef<'de>>(
+        &mut self,
+        tag: Option<sval::Tag>,
+        index: sval::Index,
+        value: V,
+    ) -> sval_nested::Result {
+        StreamTuple::value(&mut self.stream, tag, index, value)
+    }
+
+    fn value_computed<V: sval::Value>(
+        &mut self,
+        tag: Option<sval::Tag>,
+        index: sval::Index,
+        value: V,
+    ) -> sval_nested::Result {
+        StreamTuple::value_computed(&mut self.stream, tag, index, value)
+    }
+
+    fn end(self) -> sval_nested::Result<Self::Ok> {
+        Ok(variant(self.tag, Some(StreamTuple::end(self.stream)?)))
+    }
+}
+
+impl<'de> StreamRecord<'de> for ToVariant<'de, ToMap<'de>> {
+    type Ok = Content<'de>;
+
+    fn value<V: sval_nested::ValueRef<'de>>(
+        &mut self,
+        tag: Option<sval::Tag>,
+        label: sval::Label,
+        value: V,
+    ) -> sval_nested::Result {
+        StreamRecord::value(&mut self.stream, tag, label, value)
+    }
+
+    fn value_computed<V: sval::Value>(
+        &mut self,
+        tag: Option<sval::Tag>,
+        label: sval::Label,
+        value: V,
+    ) -> sval_nested::Result {
+        StreamRecord::value_computed(&mut self.stream, tag, label, value)
+    }
+
+    fn end(self) -> sval_nested::Result<Self::Ok> {
+        Ok(variant(self.tag, Some(StreamRecord::end(self.stream)?)))
+    }
+}
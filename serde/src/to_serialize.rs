@@ -160,6 +160,11 @@ impl<'sval, S: serde::Serializer> Stream<'sval> for Serializer<S> {
         Ok(self.serializer.serialize_f64(value))
     }
 
+    fn text(self, text: &'sval str) -> sval_nested::Result<Self::Ok> {
+        // Text streamed as a single borrowed fragment is passed through without buffering
+        Ok(self.serializer.serialize_str(text))
+    }
+
     fn text_computed(self, text: &str) -> sval_nested::Result<Self::Ok> {
         Ok(self.serializer.serialize_str(text))
     }
@@ -200,6 +205,8 @@ impl<'sval, S: serde::Serializer> Stream<'sval> for Serializer<S> {
             Some(sval::tags::RUST_OPTION_SOME) => {
                 Ok(self.serializer.serialize_some(&ToSerialize::new(value)))
             }
+            // Chars are serialized as their text, which `serde` formats accept for chars
+            Some(sval::tags::CHAR) => Ok(ToSerialize::new(value).serialize(self.serializer)),
             _ => {
                 let name = label
                     .and_then(|label| label.as_static_str())
@@ -500,7 +507,7 @@ impl<'sval, S: serde::Serializer> StreamEnum<'sval> for SerializeEnum<S> {
             })?;
 
         let variant_index = index.and_then(|index| index.to_u32()).ok_or_else(|| {
-            sval_nested::Error::invalid_value("unit variant index must a 32bit value")
+            sval_nested::Error::invalid_value("unit variant index must be a 32bit value")
         })?;
 
         Ok(self
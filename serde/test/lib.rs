@@ -44,6 +44,9 @@ enum Enum {
         field_2: &'static str,
     },
     SeqStruct(i32, bool, &'static str),
+    EmptyMapStruct {},
+    EmptySeqStruct(),
+    Unit(()),
 }
 
 fn test_case(
@@ -161,6 +164,55 @@ fn u128_to_serialize() {
     );
 }
 
+#[test]
+fn i128_u128_round_trip() {
+    // `sval` to `serde` and back again
+    assert_tokens(
+        &sval_serde::ToValue::new(sval_serde::ToSerialize::new(&u128::MAX)),
+        &[sval_test::Token::U128(u128::MAX)],
+    );
+    assert_tokens(
+        &sval_serde::ToValue::new(sval_serde::ToSerialize::new(&i128::MIN)),
+        &[sval_test::Token::I128(i128::MIN)],
+    );
+
+    // Serializers that support 128bit numbers receive them in full
+    assert_eq!(
+        u128::MAX.to_string(),
+        serde_json::to_string(&sval_serde::ToSerialize::new(&u128::MAX)).unwrap()
+    );
+
+    // Serializers that don't support them fail rather than truncating
+    assert!(serde_json::to_value(sval_serde::ToSerialize::new(&u128::MAX)).is_err());
+    assert!(serde_json::to_value(sval_serde::ToSerialize::new(&i128::MIN)).is_err());
+}
+
+#[test]
+fn char_to_serialize() {
+    // NOTE: `sval` chars are serialized as strings, so the `serde`
+    // end of this only roundtrips through `sval`
+    for (v, len, text) in [('a', 1, "a"), ('é', 2, "é")] {
+        let sval = &{
+            use sval_test::Token::*;
+
+            [
+                TaggedBegin(Some(sval::tags::CHAR), None, None),
+                TextBegin(Some(len)),
+                TextFragmentComputed(text.to_owned()),
+                TextEnd,
+                TaggedEnd(Some(sval::tags::CHAR), None, None),
+            ]
+        };
+
+        assert_ser_tokens(
+            &sval_serde::ToSerialize::new(v),
+            &[serde_test::Token::Str(text)],
+        );
+
+        assert_tokens(&sval_serde::ToValue::new(v), sval);
+    }
+}
+
 #[test]
 fn map_to_serialize() {
     test_case(
@@ -630,6 +682,196 @@ fn enum_tuple_to_serialize() {
     );
 }
 
+#[test]
+fn enum_empty_record_to_serialize() {
+    test_case(
+        Enum::EmptyMapStruct {},
+        {
+            use serde_test::Token::*;
+
+            &[
+                StructVariant {
+                    name: "Enum",
+                    variant: "EmptyMapStruct",
+                    len: 0,
+                },
+                StructVariantEnd,
+            ]
+        },
+        {
+            use sval_test::Token::*;
+
+            &[
+                EnumBegin(None, Some(sval::Label::new("Enum")), None),
+                RecordBegin(
+                    None,
+                    Some(sval::Label::new("EmptyMapStruct")),
+                    Some(sval::Index::new(4)),
+                    Some(0),
+                ),
+                RecordEnd(
+                    None,
+                    Some(sval::Label::new("EmptyMapStruct")),
+                    Some(sval::Index::new(4)),
+                ),
+                EnumEnd(None, Some(sval::Label::new("Enum")), None),
+            ]
+        },
+    );
+}
+
+#[test]
+fn enum_empty_tuple_to_serialize() {
+    test_case(
+        Enum::EmptySeqStruct(),
+        {
+            use serde_test::Token::*;
+
+            &[
+                TupleVariant {
+                    name: "Enum",
+                    variant: "EmptySeqStruct",
+                    len: 0,
+                },
+                TupleVariantEnd,
+            ]
+        },
+        {
+            use sval_test::Token::*;
+
+            &[
+                EnumBegin(None, Some(sval::Label::new("Enum")), None),
+                TupleBegin(
+                    None,
+                    Some(sval::Label::new("EmptySeqStruct")),
+                    Some(sval::Index::new(5)),
+                    Some(0),
+                ),
+                TupleEnd(
+                    None,
+                    Some(sval::Label::new("EmptySeqStruct")),
+                    Some(sval::Index::new(5)),
+                ),
+                EnumEnd(None, Some(sval::Label::new("Enum")), None),
+            ]
+        },
+    );
+}
+
+#[test]
+fn enum_tagged_unit_to_serialize() {
+    test_case(
+        Enum::Unit(()),
+        {
+            use serde_test::Token::*;
+
+            &[
+                NewtypeVariant {
+                    name: "Enum",
+                    variant: "Unit",
+                },
+                Unit,
+            ]
+        },
+        {
+            use sval_test::Token::*;
+
+            &[
+                EnumBegin(None, Some(sval::Label::new("Enum")), None),
+                TaggedBegin(
+                    None,
+                    Some(sval::Label::new("Unit")),
+                    Some(sval::Index::new(6)),
+                ),
+                Tag(Some(sval::tags::RUST_UNIT), None, None),
+                TaggedEnd(
+                    None,
+                    Some(sval::Label::new("Unit")),
+                    Some(sval::Index::new(6)),
+                ),
+                EnumEnd(None, Some(sval::Label::new("Enum")), None),
+            ]
+        },
+    );
+}
+
+
+mod borrowed_text {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        cell::Cell,
+    };
+
+    struct CountingAlloc;
+
+    thread_local! {
+        static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    unsafe impl GlobalAlloc for CountingAlloc {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let _ = ALLOCATIONS.try_with(|allocations| allocations.set(allocations.get() + 1));
+
+            System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout)
+        }
+    }
+
+    #[global_allocator]
+    static ALLOC: CountingAlloc = CountingAlloc;
+
+    struct Fragments<'a>(&'a [&'a str]);
+
+    impl<'a> sval::Value for Fragments<'a> {
+        fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(
+            &'sval self,
+            stream: &mut S,
+        ) -> sval::Result {
+            stream.text_begin(None)?;
+
+            for fragment in self.0 {
+                stream.text_fragment(fragment)?;
+            }
+
+            stream.text_end()
+        }
+    }
+
+    fn count_allocations(value: impl sval::Value) -> (usize, String) {
+        let mut buf = Vec::with_capacity(64);
+
+        let before = ALLOCATIONS.with(|allocations| allocations.get());
+        serde_json::to_writer(&mut buf, &sval_serde::ToSerialize::new(value)).unwrap();
+        let after = ALLOCATIONS.with(|allocations| allocations.get());
+
+        (after - before, String::from_utf8(buf).unwrap())
+    }
+
+    #[test]
+    fn borrowed_fragment_does_not_allocate() {
+        assert_eq!((0, "\"abc\"".to_owned()), count_allocations("abc"));
+        assert_eq!(
+            (0, "\"abc\"".to_owned()),
+            count_allocations(Fragments(&["abc"]))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn multiple_fragments_are_buffered() {
+        let (allocations, json) = count_allocations(Fragments(&["a", "bc"]));
+
+        assert_ne!(0, allocations);
+        assert_eq!("\"abc\"", json);
+    }
+}
+
+#[cfg(feature = "alloc")]
+mod twitter;
+
 #[cfg(feature = "alloc")]
 mod alloc_support {
     use super::*;
@@ -662,4 +904,158 @@ mod alloc_support {
             },
         );
     }
+
+    #[test]
+    fn from_value_primitive() {
+        assert_eq!(42, sval_serde::from_value::<i32>(&42i32).unwrap());
+        assert!(sval_serde::from_value::<bool>(&true).unwrap());
+        assert_eq!(1.5, sval_serde::from_value::<f64>(&1.5f64).unwrap());
+        assert_eq!((), sval_serde::from_value::<()>(&()).unwrap());
+        assert_eq!(
+            u128::MAX,
+            sval_serde::from_value::<u128>(&u128::MAX).unwrap()
+        );
+        assert_eq!(
+            Some(1),
+            sval_serde::from_value::<Option<i32>>(&Some(1)).unwrap()
+        );
+        assert_eq!(
+            None,
+            sval_serde::from_value::<Option<i32>>(&None::<i32>).unwrap()
+        );
+    }
+
+    #[test]
+    fn from_value_i128_u128() {
+        assert_eq!(
+            u128::MAX,
+            sval_serde::from_value::<u128>(&sval_serde::ToValue::new(&u128::MAX)).unwrap()
+        );
+        assert_eq!(
+            i128::MIN,
+            sval_serde::from_value::<i128>(&sval_serde::ToValue::new(&i128::MIN)).unwrap()
+        );
+    }
+
+    #[test]
+    fn from_value_borrowed_str() {
+        #[derive(Value, Deserialize, PartialEq, Debug)]
+        struct Borrowed<'a> {
+            text: &'a str,
+            list: Vec<&'a str>,
+        }
+
+        let value = Borrowed {
+            text: "a",
+            list: vec!["b", "c"],
+        };
+
+        assert_eq!(value, sval_serde::from_value::<Borrowed>(&value).unwrap());
+    }
+
+    #[test]
+    fn from_value_collections() {
+        let seq: Seq = vec![1, 2, 3];
+        let map: BTreeMap<String, i32> = [("a".into(), 1), ("b".into(), 2)].into_iter().collect();
+
+        assert_eq!(seq, sval_serde::from_value::<Seq>(&seq).unwrap());
+        assert_eq!(
+            map,
+            sval_serde::from_value::<BTreeMap<String, i32>>(&map).unwrap()
+        );
+        assert_eq!(
+            (1, true),
+            sval_serde::from_value::<(i32, bool)>(&(1, true)).unwrap()
+        );
+    }
+
+    #[test]
+    fn from_value_struct() {
+        #[derive(Value, Deserialize, PartialEq, Debug)]
+        struct Record {
+            a: i32,
+            b: Option<String>,
+            c: Newtype,
+            d: Tuple,
+        }
+
+        #[derive(Value, Deserialize, PartialEq, Debug)]
+        struct Newtype(i32);
+
+        #[derive(Value, Deserialize, PartialEq, Debug)]
+        struct Tuple(i32, bool);
+
+        let value = Record {
+            a: 1,
+            b: Some("text".into()),
+            c: Newtype(2),
+            d: Tuple(3, true),
+        };
+
+        assert_eq!(value, sval_serde::from_value::<Record>(&value).unwrap());
+    }
+
+    #[test]
+    fn from_value_enum() {
+        #[derive(Value, Deserialize, PartialEq, Debug)]
+        enum Enum {
+            Constant,
+            Tagged(i32),
+            Record { a: i32, b: bool },
+            Tuple(i32, bool),
+        }
+
+        for value in [
+            Enum::Constant,
+            Enum::Tagged(1),
+            Enum::Record { a: 1, b: true },
+            Enum::Tuple(1, true),
+        ] {
+            assert_eq!(value, sval_serde::from_value::<Enum>(&value).unwrap());
+        }
+    }
+
+    #[test]
+    fn from_value_any() {
+        #[derive(Value)]
+        enum Enum {
+            Constant,
+            Tagged(i32),
+        }
+
+        assert_eq!(
+            serde_json::json!({ "field_0": 1, "field_1": true, "field_2": "text" }),
+            sval_serde::from_value::<serde_json::Value>(&MapStruct {
+                field_0: 1,
+                field_1: true,
+                field_2: "text",
+            })
+            .unwrap()
+        );
+        assert_eq!(
+            serde_json::json!([1, true, "text"]),
+            sval_serde::from_value::<serde_json::Value>(&SeqStruct(1, true, "text")).unwrap()
+        );
+
+        assert_eq!(
+            serde_json::json!("Constant"),
+            sval_serde::from_value::<serde_json::Value>(&Enum::Constant).unwrap()
+        );
+        assert_eq!(
+            serde_json::json!({ "Tagged": 1 }),
+            sval_serde::from_value::<serde_json::Value>(&Enum::Tagged(1)).unwrap()
+        );
+    }
+
+    #[test]
+    fn from_value_twitter() {
+        let value = twitter::input_struct();
+
+        let roundtrip: twitter::Twitter = sval_serde::from_value(&value).unwrap();
+
+        assert_eq!(
+            serde_json::to_string(&value).unwrap(),
+            serde_json::to_string(&roundtrip).unwrap()
+        );
+    }
 }
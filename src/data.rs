@@ -1,9 +1,21 @@
 mod binary;
+mod cell;
+#[cfg(any(feature = "ordinal_date", feature = "iso_week_date"))]
+mod date;
+mod ffi;
+mod iter;
 mod map;
+#[cfg(feature = "std")]
+mod net;
 mod number;
 mod option;
+mod range;
+mod result;
 mod seq;
+mod tagged;
 mod text;
+mod time;
+mod uuid;
 
 pub mod tags;
 
@@ -21,7 +33,12 @@ use crate::{
 #[cfg(feature = "alloc")]
 use crate::std::boxed::Box;
 
-pub use self::{binary::*, map::*, number::*, option::*, text::*};
+pub use self::{
+    binary::*, iter::*, map::*, number::*, option::*, tagged::*, text::*, time::*, uuid::*,
+};
+
+#[cfg(any(feature = "ordinal_date", feature = "iso_week_date"))]
+pub use self::date::*;
 
 /**
 A textual label for some value.
@@ -299,6 +316,15 @@ impl Tag {
             data: self.data,
         }
     }
+
+    /**
+    The identifier of the tag.
+
+    Tags with the same identifier are equal.
+    */
+    pub(crate) const fn id(&self) -> u64 {
+        self.id
+    }
 }
 
 impl fmt::Debug for Tag {
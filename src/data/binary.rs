@@ -108,6 +108,28 @@ impl<const N: usize> Value for BinaryArray<N> {
     }
 }
 
+#[cfg(feature = "alloc")]
+mod alloc_support {
+    use super::*;
+
+    use crate::std::borrow::Cow;
+
+    /*
+    `Cow<[u8]>` is treated as binary rather than as a sequence of `u8`s.
+    Both variants are borrowed for `'sval`, so neither needs to copy its bytes.
+    */
+    impl<'a> Value for Cow<'a, [u8]> {
+        fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> Result {
+            BinarySlice::new(self).stream(stream)
+        }
+
+        #[inline(always)]
+        fn to_binary(&self) -> Option<&[u8]> {
+            Some(self)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,55 @@
+use crate::{
+    std::cell::{Cell, OnceCell, RefCell},
+    Error, Result, Stream, Value,
+};
+
+impl<T: Value> Value for OnceCell<T> {
+    fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> Result {
+        if let Some(value) = self.get() {
+            stream.value(value)
+        } else {
+            stream.null()
+        }
+    }
+}
+
+impl<T: Value + ?Sized> Value for RefCell<T> {
+    fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> Result {
+        // If the value is already mutably borrowed then fail instead of panicking
+        let value = self.try_borrow().map_err(|_| Error::new())?;
+
+        stream.value_computed(&*value)
+    }
+}
+
+impl<T: Value + Copy> Value for Cell<T> {
+    fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> Result {
+        stream.value_computed(&self.get())
+    }
+}
+
+#[cfg(feature = "std")]
+mod std_support {
+    use super::*;
+
+    use crate::std::sync::{Mutex, OnceLock};
+
+    impl<T: Value> Value for OnceLock<T> {
+        fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> Result {
+            if let Some(value) = self.get() {
+                stream.value(value)
+            } else {
+                stream.null()
+            }
+        }
+    }
+
+    impl<T: Value + ?Sized> Value for Mutex<T> {
+        fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> Result {
+            // If the lock is poisoned then fail instead of streaming a value that may be invalid
+            let value = self.lock().map_err(|_| Error::new())?;
+
+            stream.value_computed(&*value)
+        }
+    }
+}
@@ -0,0 +1,225 @@
+use crate::{
+    std::{
+        fmt,
+        string::ToString,
+        time::{SystemTime, UNIX_EPOCH},
+    },
+    tags, Result, Stream, Value,
+};
+
+/**
+Stream a [`SystemTime`] as an ordinal date, like `2024-060`.
+
+The date is calculated in UTC and streamed as text conforming to [`tags::ORDINAL_DATE`].
+
+This type requires the `ordinal_date` feature.
+*/
+#[cfg(feature = "ordinal_date")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OrdinalDate(pub SystemTime);
+
+#[cfg(feature = "ordinal_date")]
+impl fmt::Display for OrdinalDate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let days = days_since_epoch(self.0);
+        let (year, _, _) = civil_from_days(days);
+
+        write_year(f, year)?;
+        write!(f, "-{:03}", days - days_from_civil(year, 1, 1) + 1)
+    }
+}
+
+#[cfg(feature = "ordinal_date")]
+impl Value for OrdinalDate {
+    fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> Result {
+        stream.tagged_begin(Some(&tags::ORDINAL_DATE), None, None)?;
+        stream.value_computed(&*self.to_string())?;
+        stream.tagged_end(Some(&tags::ORDINAL_DATE), None, None)
+    }
+}
+
+/**
+Stream a [`SystemTime`] as an ISO week date, like `2024-W09-4`.
+
+The date is calculated in UTC and streamed as text conforming to [`tags::ISO_WEEK_DATE`].
+The year is the ISO week-numbering year, which differs from the calendar year for days
+in the first or last week of some years.
+
+This type requires the `iso_week_date` feature.
+*/
+#[cfg(feature = "iso_week_date")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IsoWeekDate(pub SystemTime);
+
+#[cfg(feature = "iso_week_date")]
+impl fmt::Display for IsoWeekDate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let days = days_since_epoch(self.0);
+        let (year, _, _) = civil_from_days(days);
+
+        // 1970-01-01 was a Thursday
+        let weekday = (days + 3).rem_euclid(7) + 1;
+        let ordinal = days - days_from_civil(year, 1, 1) + 1;
+
+        let (year, week) = match (ordinal - weekday + 10) / 7 {
+            0 => (year - 1, weeks_in_year(year - 1)),
+            week if week > weeks_in_year(year) => (year + 1, 1),
+            week => (year, week),
+        };
+
+        write_year(f, year)?;
+        write!(f, "-W{:02}-{}", week, weekday)
+    }
+}
+
+#[cfg(feature = "iso_week_date")]
+impl Value for IsoWeekDate {
+    fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> Result {
+        stream.tagged_begin(Some(&tags::ISO_WEEK_DATE), None, None)?;
+        stream.value_computed(&*self.to_string())?;
+        stream.tagged_end(Some(&tags::ISO_WEEK_DATE), None, None)
+    }
+}
+
+fn write_year(f: &mut fmt::Formatter, year: i64) -> fmt::Result {
+    if (0..=9999).contains(&year) {
+        write!(f, "{:04}", year)
+    } else {
+        write!(f, "{:+05}", year)
+    }
+}
+
+/**
+Get the number of whole days between the Unix epoch and a time, rounding towards the past.
+*/
+fn days_since_epoch(time: SystemTime) -> i64 {
+    const SECS_PER_DAY: u64 = 86_400;
+
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(since) => (since.as_secs() / SECS_PER_DAY) as i64,
+        Err(before) => {
+            let before = before.duration();
+            let days = (before.as_secs() / SECS_PER_DAY) as i64;
+
+            if before.as_secs() % SECS_PER_DAY == 0 && before.subsec_nanos() == 0 {
+                -days
+            } else {
+                -days - 1
+            }
+        }
+    }
+}
+
+/**
+Convert days since the Unix epoch into a proleptic Gregorian `(year, month, day)`.
+
+This is Howard Hinnant's `civil_from_days` algorithm.
+*/
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (year, month, day)
+}
+
+/**
+Convert a proleptic Gregorian `(year, month, day)` into days since the Unix epoch.
+
+This is Howard Hinnant's `days_from_civil` algorithm.
+*/
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = if month > 2 { month - 3 } else { month + 9 };
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+    era * 146_097 + doe - 719_468
+}
+
+#[cfg(feature = "iso_week_date")]
+fn weeks_in_year(year: i64) -> i64 {
+    let jan_1 = (days_from_civil(year, 1, 1) + 3).rem_euclid(7) + 1;
+    let is_leap = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+
+    if jan_1 == 4 || (is_leap && jan_1 == 3) {
+        53
+    } else {
+        52
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(rfc3339: &str) -> SystemTime {
+        humantime::parse_rfc3339(rfc3339).unwrap()
+    }
+
+    #[test]
+    fn civil_roundtrip() {
+        for days in -800_000..800_000 {
+            let (year, month, day) = civil_from_days(days);
+
+            assert_eq!(days, days_from_civil(year, month, day));
+        }
+    }
+
+    #[test]
+    fn days_before_epoch() {
+        use crate::std::time::Duration;
+
+        assert_eq!(
+            -1,
+            days_since_epoch(UNIX_EPOCH - Duration::from_secs(86_400))
+        );
+        assert_eq!(
+            -1,
+            days_since_epoch(UNIX_EPOCH - Duration::from_millis(500))
+        );
+        assert_eq!(
+            -2,
+            days_since_epoch(UNIX_EPOCH - Duration::from_secs(86_401))
+        );
+        assert_eq!(0, days_since_epoch(UNIX_EPOCH));
+    }
+
+    #[test]
+    #[cfg(feature = "ordinal_date")]
+    fn ordinal_date() {
+        for (time_str, expected) in [
+            ("1970-01-01T00:00:00Z", "1970-001"),
+            ("2023-12-31T23:59:59Z", "2023-365"),
+            ("2024-02-29T12:00:00Z", "2024-060"),
+            ("2024-12-31T00:00:00Z", "2024-366"),
+            ("2000-03-01T00:00:00Z", "2000-061"),
+        ] {
+            assert_eq!(expected, OrdinalDate(time(time_str)).to_string());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "iso_week_date")]
+    fn iso_week_date() {
+        for (time_str, expected) in [
+            ("1970-01-01T00:00:00Z", "1970-W01-4"),
+            ("2005-01-01T00:00:00Z", "2004-W53-6"),
+            ("2007-12-31T00:00:00Z", "2008-W01-1"),
+            ("2008-12-28T00:00:00Z", "2008-W52-7"),
+            ("2009-12-31T00:00:00Z", "2009-W53-4"),
+            ("2010-01-03T00:00:00Z", "2009-W53-7"),
+            ("2024-02-29T12:00:00Z", "2024-W09-4"),
+        ] {
+            assert_eq!(expected, IsoWeekDate(time(time_str)).to_string());
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use core::ffi::CStr;
+
+use crate::{tags, BinarySlice, Result, Stream, Value};
+
+// `CStr` streams as its bytes without the nul terminator, since it isn't guaranteed to be UTF8
+impl Value for CStr {
+    fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> Result {
+        stream.tagged_begin(Some(&tags::C_STRING), None, None)?;
+        stream.value(BinarySlice::new(self.to_bytes()))?;
+        stream.tagged_end(Some(&tags::C_STRING), None, None)
+    }
+}
+
+#[cfg(feature = "alloc")]
+mod alloc_support {
+    use super::*;
+
+    use crate::std::ffi::CString;
+
+    impl Value for CString {
+        fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> Result {
+            (**self).stream(stream)
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+mod std_support {
+    use super::*;
+
+    use crate::std::ffi::{OsStr, OsString};
+
+    // `OsStr` streams as text, replacing any sequences that aren't valid UTF8
+    impl Value for OsStr {
+        fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> Result {
+            stream.tagged_begin(Some(&tags::OS_STRING), None, None)?;
+
+            match self.to_str() {
+                Some(text) => stream.value(text)?,
+                None => stream.value_computed(&*self.to_string_lossy())?,
+            }
+
+            stream.tagged_end(Some(&tags::OS_STRING), None, None)
+        }
+    }
+
+    impl Value for OsString {
+        fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> Result {
+            (**self).stream(stream)
+        }
+    }
+}
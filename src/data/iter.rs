@@ -0,0 +1,65 @@
+use crate::{Result, Stream, Value};
+
+/**
+Stream the items of an iterator as a sequence, without collecting them first.
+
+See [`Iter`] for details.
+
+```
+let value = sval::iter((0..3).map(|i| i * 2));
+```
+*/
+pub fn iter<I: Clone + IntoIterator>(iter: I) -> Iter<I>
+where
+    I::Item: Value,
+{
+    Iter(iter)
+}
+
+/**
+An adapter that streams the items of an iterator as a sequence.
+
+A value may be streamed any number of times, so the iterator is cloned each time it's
+streamed and consumed from the clone. Iterators like ranges and the adapters built on
+them are cheap to clone. Items are produced while the value is streaming, so they're
+streamed as computed values.
+
+If the iterator knows exactly how many items it will produce then that's used as a length
+hint for the sequence. If an item fails to stream then the sequence stops there and the
+error is returned.
+*/
+#[derive(Debug, Clone)]
+pub struct Iter<I>(I);
+
+impl<I> Iter<I> {
+    /**
+    Get the wrapped iterator.
+    */
+    pub fn into_inner(self) -> I {
+        self.0
+    }
+}
+
+impl<I: Clone + IntoIterator> Value for Iter<I>
+where
+    I::Item: Value,
+{
+    fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> Result {
+        let iter = self.0.clone().into_iter();
+
+        let num_entries = match iter.size_hint() {
+            (lower, Some(upper)) if lower == upper => Some(lower),
+            _ => None,
+        };
+
+        stream.seq_begin(num_entries)?;
+
+        for item in iter {
+            stream.seq_value_begin()?;
+            stream.value_computed(&item)?;
+            stream.seq_value_end()?;
+        }
+
+        stream.seq_end()
+    }
+}
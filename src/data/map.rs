@@ -1,4 +1,4 @@
-use crate::{std::fmt, Result, Stream, Value};
+use crate::{std::fmt, tags, Label, Result, Stream, Value};
 
 /**
 An adapter that streams a slice of key-value pairs as a map.
@@ -53,11 +53,198 @@ impl<K: Value, V: Value> Value for MapSlice<K, V> {
     }
 }
 
+/**
+An adapter that streams only the entries of a map that match a predicate.
+
+The map can be any type where a reference to it iterates over key-value pairs,
+like `BTreeMap` or `HashMap`. Entries that don't match the predicate are skipped
+without needing to clone the map first.
+ */
+pub struct FilteredMap<'a, M: ?Sized, F>(pub &'a M, pub F);
+
+impl<'a, M: ?Sized, F> FilteredMap<'a, M, F> {
+    /**
+    Filter the entries of a map with the given predicate.
+     */
+    pub const fn new(map: &'a M, predicate: F) -> Self {
+        FilteredMap(map, predicate)
+    }
+}
+
+impl<'a, M: ?Sized + fmt::Debug, F> fmt::Debug for FilteredMap<'a, M, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("FilteredMap").field(&self.0).finish()
+    }
+}
+
+impl<'a, M: ?Sized, F, K: Value + 'a, V: Value + 'a> Value for FilteredMap<'a, M, F>
+where
+    &'a M: IntoIterator<Item = (&'a K, &'a V)>,
+    F: Fn(&K, &V) -> bool,
+{
+    fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> Result {
+        let len = self.0.into_iter().filter(|(k, v)| (self.1)(k, v)).count();
+
+        stream.map_begin(Some(len))?;
+
+        for (k, v) in self.0.into_iter().filter(|(k, v)| (self.1)(k, v)) {
+            stream.map_key_begin()?;
+            stream.value(k)?;
+            stream.map_key_end()?;
+
+            stream.map_value_begin()?;
+            stream.value(v)?;
+            stream.map_value_end()?;
+        }
+
+        stream.map_end()
+    }
+}
+
+/**
+An adapter that streams a map as a record of parallel sequences of its keys and values.
+
+The map is streamed as a record with two fields, `keys` and `values`, where the value at
+each position in `values` belongs to the key at the same position in `keys`:
+
+```text
+{ keys: [1, 2], values: ["a", "b"] }
+```
+
+The map can be any type where a reference to it iterates over key-value pairs,
+like `BTreeMap` or `HashMap`. It's iterated twice, once for its keys and once for
+its values, so it must yield its entries in the same order each time.
+ */
+pub struct SplitMap<'a, M: ?Sized>(pub &'a M);
+
+impl<'a, M: ?Sized> SplitMap<'a, M> {
+    /**
+    Split a map into parallel sequences of its keys and values.
+     */
+    pub const fn new(map: &'a M) -> Self {
+        SplitMap(map)
+    }
+}
+
+impl<'a, M: ?Sized + fmt::Debug> fmt::Debug for SplitMap<'a, M> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("SplitMap").field(&self.0).finish()
+    }
+}
+
+impl<'a, M: ?Sized, K: Value + 'a, V: Value + 'a> Value for SplitMap<'a, M>
+where
+    &'a M: IntoIterator<Item = (&'a K, &'a V)>,
+{
+    fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> Result {
+        let keys = Label::new("keys").with_tag(&tags::VALUE_IDENT);
+        let values = Label::new("values").with_tag(&tags::VALUE_IDENT);
+
+        let len = match self.0.into_iter().size_hint() {
+            (lower, Some(upper)) if lower == upper => Some(lower),
+            _ => None,
+        };
+
+        stream.record_begin(None, None, None, Some(2))?;
+
+        stream.record_value_begin(None, &keys)?;
+        stream.seq_begin(len)?;
+
+        for (k, _) in self.0 {
+            stream.seq_value_begin()?;
+            stream.value(k)?;
+            stream.seq_value_end()?;
+        }
+
+        stream.seq_end()?;
+        stream.record_value_end(None, &keys)?;
+
+        stream.record_value_begin(None, &values)?;
+        stream.seq_begin(len)?;
+
+        for (_, v) in self.0 {
+            stream.seq_value_begin()?;
+            stream.value(v)?;
+            stream.seq_value_end()?;
+        }
+
+        stream.seq_end()?;
+        stream.record_value_end(None, &values)?;
+
+        stream.record_end(None, None, None)
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub use self::alloc_support::*;
+
 #[cfg(feature = "alloc")]
 mod alloc_support {
     use super::*;
     use crate::std::collections::BTreeMap;
 
+    /**
+    An adapter that streams a map with each of its values passed through a function.
+
+    The map can be any type where a reference to it iterates over key-value pairs,
+    like `BTreeMap` or `HashMap`. Keys are streamed as-is, and each value is streamed
+    as the result of calling the function on it, without needing to build a transformed
+    copy of the map first:
+
+    ```
+    # use std::collections::BTreeMap;
+    let map = BTreeMap::from([("a", 1), ("b", 2)]);
+
+    let value = sval::MappedMap::new(&map, |v: &i32| *v * 1000);
+    ```
+
+    This type requires the `alloc` feature.
+     */
+    pub struct MappedMap<'a, M: ?Sized, F>(pub &'a M, pub F);
+
+    impl<'a, M: ?Sized, F> MappedMap<'a, M, F> {
+        /**
+        Map the values of a map with the given function.
+         */
+        pub const fn new(map: &'a M, f: F) -> Self {
+            MappedMap(map, f)
+        }
+    }
+
+    impl<'a, M: ?Sized + fmt::Debug, F> fmt::Debug for MappedMap<'a, M, F> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.debug_tuple("MappedMap").field(&self.0).finish()
+        }
+    }
+
+    impl<'a, M: ?Sized, F, K: Value + 'a, V: 'a, T: Value> Value for MappedMap<'a, M, F>
+    where
+        &'a M: IntoIterator<Item = (&'a K, &'a V)>,
+        F: Fn(&V) -> T,
+    {
+        fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> Result {
+            let len = match self.0.into_iter().size_hint() {
+                (lower, Some(upper)) if lower == upper => Some(lower),
+                _ => None,
+            };
+
+            stream.map_begin(len)?;
+
+            for (k, v) in self.0 {
+                stream.map_key_begin()?;
+                stream.value(k)?;
+                stream.map_key_end()?;
+
+                // The mapped value is a temporary, so it's streamed as computed
+                stream.map_value_begin()?;
+                stream.value_computed(&(self.1)(v))?;
+                stream.map_value_end()?;
+            }
+
+            stream.map_end()
+        }
+    }
+
     impl<K: Value, V: Value> Value for BTreeMap<K, V> {
         fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> Result {
             stream.map_begin(Some(self.len()))?;
@@ -77,10 +264,77 @@ mod alloc_support {
     }
 }
 
+#[cfg(feature = "std")]
+pub use self::std_support::*;
+
 #[cfg(feature = "std")]
 mod std_support {
     use super::*;
-    use crate::std::{collections::HashMap, hash::BuildHasher};
+    use crate::std::{collections::HashMap, hash::BuildHasher, vec::Vec};
+
+    /**
+    Stream a `HashMap` with its entries sorted by key.
+
+    The order of entries in a `HashMap` is arbitrary, and can change between runs
+    of a program. Sorting them makes the output deterministic:
+
+    ```
+    # use std::collections::HashMap;
+    let map = HashMap::from([("b", 2), ("a", 1)]);
+
+    let value = sval::sorted(&map);
+    ```
+
+    This function requires the `std` feature.
+     */
+    pub fn sorted<K: Ord, V, H>(map: &HashMap<K, V, H>) -> SortedMap<'_, K, V, H> {
+        SortedMap::new(map)
+    }
+
+    /**
+    An adapter that streams a `HashMap` with its entries sorted by key.
+
+    The entries are sorted each time the map is streamed, which requires allocating.
+
+    This type requires the `std` feature.
+     */
+    pub struct SortedMap<'a, K, V, H>(&'a HashMap<K, V, H>);
+
+    impl<'a, K: Ord, V, H> SortedMap<'a, K, V, H> {
+        /**
+        Sort the entries of a map by key.
+         */
+        pub const fn new(map: &'a HashMap<K, V, H>) -> Self {
+            SortedMap(map)
+        }
+    }
+
+    impl<'a, K: fmt::Debug, V: fmt::Debug, H> fmt::Debug for SortedMap<'a, K, V, H> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.debug_tuple("SortedMap").field(&self.0).finish()
+        }
+    }
+
+    impl<'a, K: Ord + Value, V: Value, H> Value for SortedMap<'a, K, V, H> {
+        fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> Result {
+            let mut entries = self.0.iter().collect::<Vec<_>>();
+            entries.sort_by_key(|&(k, _)| k);
+
+            stream.map_begin(Some(entries.len()))?;
+
+            for (k, v) in entries {
+                stream.map_key_begin()?;
+                stream.value(k)?;
+                stream.map_key_end()?;
+
+                stream.map_value_begin()?;
+                stream.value(v)?;
+                stream.map_value_end()?;
+            }
+
+            stream.map_end()
+        }
+    }
 
     impl<K: Value, V: Value, H: BuildHasher> Value for HashMap<K, V, H> {
         fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> Result {
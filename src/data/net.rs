@@ -0,0 +1,55 @@
+use crate::{
+    std::{
+        net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
+        string::ToString,
+    },
+    tags, Result, Stream, Tag, Value,
+};
+
+// `IpAddr` streams as tagged text rather than as an enum of its variants
+// The version of an address is unambiguous from its text
+impl Value for IpAddr {
+    fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> Result {
+        stream_tagged_text(stream, &tags::IP_ADDR, self.to_string())
+    }
+}
+
+impl Value for Ipv4Addr {
+    fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> Result {
+        stream_tagged_text(stream, &tags::IP_ADDR, self.to_string())
+    }
+}
+
+impl Value for Ipv6Addr {
+    fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> Result {
+        stream_tagged_text(stream, &tags::IP_ADDR, self.to_string())
+    }
+}
+
+impl Value for SocketAddr {
+    fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> Result {
+        stream_tagged_text(stream, &tags::SOCKET_ADDR, self.to_string())
+    }
+}
+
+impl Value for SocketAddrV4 {
+    fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> Result {
+        stream_tagged_text(stream, &tags::SOCKET_ADDR, self.to_string())
+    }
+}
+
+impl Value for SocketAddrV6 {
+    fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> Result {
+        stream_tagged_text(stream, &tags::SOCKET_ADDR, self.to_string())
+    }
+}
+
+fn stream_tagged_text<'sval>(
+    stream: &mut (impl Stream<'sval> + ?Sized),
+    tag: &Tag,
+    text: impl AsRef<str>,
+) -> Result {
+    stream.tagged_begin(Some(tag), None, None)?;
+    stream.value_computed(text.as_ref())?;
+    stream.tagged_end(Some(tag), None, None)
+}
@@ -65,6 +65,92 @@ pub fn stream_number<'sval>(
     stream.tagged_end(Some(&tags::NUMBER), None, None)
 }
 
+/**
+An arbitrary precision number, borrowed as text.
+
+The text is validated against the grammar of [`tags::NUMBER`], so it can hold integers
+and decimals that are too large or too precise for any of the builtin number types.
+
+```
+let number = sval::Number::new("123456789012345678901234567890.5").unwrap();
+
+assert_eq!("123456789012345678901234567890.5", number.as_str());
+assert!(sval::Number::new("1.").is_none());
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Number<'a>(&'a str);
+
+impl<'a> Number<'a> {
+    /**
+    Try create a number from text.
+
+    This method will return `None` if the text doesn't conform to the grammar of [`tags::NUMBER`].
+    */
+    pub fn new(num: &'a str) -> Option<Self> {
+        if is_number(num.as_bytes()) {
+            Some(Number(num))
+        } else {
+            None
+        }
+    }
+
+    /**
+    Get the number as text.
+    */
+    pub const fn as_str(&self) -> &'a str {
+        self.0
+    }
+}
+
+impl<'a> fmt::Display for Number<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+impl<'a> Value for Number<'a> {
+    fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> Result {
+        stream.num(self)
+    }
+}
+
+fn is_number(num: &[u8]) -> bool {
+    // Consume a run of at least one digit, returning the remaining input
+    fn digits(num: &[u8]) -> Option<&[u8]> {
+        let len = num.iter().take_while(|b| b.is_ascii_digit()).count();
+
+        if len == 0 {
+            None
+        } else {
+            Some(&num[len..])
+        }
+    }
+
+    fn sign(num: &[u8]) -> &[u8] {
+        match num {
+            [b'+' | b'-', rest @ ..] => rest,
+            _ => num,
+        }
+    }
+
+    fn parse(num: &[u8]) -> Option<&[u8]> {
+        let mut num = digits(sign(num))?;
+
+        if let [b'.', rest @ ..] = num {
+            num = digits(rest)?;
+        }
+
+        if let [b'e' | b'E', rest @ ..] = num {
+            num = digits(sign(rest))?;
+        }
+
+        Some(num)
+    }
+
+    parse(num).map(|rest| rest.is_empty()).unwrap_or(false)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -116,4 +202,38 @@ mod tests {
 
         assert_eq!(Some(tags::NUMBER), Number("42").tag());
     }
+
+    #[test]
+    fn number_new() {
+        for valid in [
+            "1",
+            "+1",
+            "-1",
+            "001",
+            "123.456",
+            "1e2",
+            "123.456e789",
+            "123.456E+789",
+            "123.456e-789",
+        ] {
+            assert_eq!(
+                valid,
+                super::Number::new(valid).expect("invalid number").as_str()
+            );
+        }
+
+        for invalid in [
+            "", "+", "-", "1.", ".1", "1e", "1e+", "1.e2", "1 ", " 1", "1_000", "inf", "NaN",
+            "0x1", "1.2.3",
+        ] {
+            assert!(super::Number::new(invalid).is_none(), "{invalid:?}");
+        }
+    }
+
+    #[test]
+    fn number_value() {
+        let number = super::Number::new("-42.5e3").unwrap();
+
+        assert_eq!(Some(tags::NUMBER), number.tag());
+    }
 }
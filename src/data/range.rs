@@ -0,0 +1,39 @@
+use crate::{
+    std::ops::{Range, RangeInclusive},
+    tags, Label, Result, Stream, Value,
+};
+
+impl<T: Value> Value for Range<T> {
+    fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> Result {
+        stream_range(stream, "Range", &self.start, &self.end)
+    }
+}
+
+impl<T: Value> Value for RangeInclusive<T> {
+    fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> Result {
+        stream_range(stream, "RangeInclusive", self.start(), self.end())
+    }
+}
+
+fn stream_range<'sval, S: Stream<'sval> + ?Sized, T: Value>(
+    stream: &mut S,
+    label: &'static str,
+    start: &'sval T,
+    end: &'sval T,
+) -> Result {
+    let label = Label::new(label).with_tag(&tags::VALUE_IDENT);
+    let start_label = Label::new("start").with_tag(&tags::VALUE_IDENT);
+    let end_label = Label::new("end").with_tag(&tags::VALUE_IDENT);
+
+    stream.record_begin(Some(&tags::RANGE), Some(&label), None, Some(2))?;
+
+    stream.record_value_begin(None, &start_label)?;
+    stream.value(start)?;
+    stream.record_value_end(None, &start_label)?;
+
+    stream.record_value_begin(None, &end_label)?;
+    stream.value(end)?;
+    stream.record_value_end(None, &end_label)?;
+
+    stream.record_end(Some(&tags::RANGE), Some(&label), None)
+}
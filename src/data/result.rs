@@ -0,0 +1,44 @@
+use crate::{tags, Index, Label, Result, Stream, Value};
+
+impl<T: Value, E: Value> Value for Result<T, E> {
+    fn stream<'a, S: Stream<'a> + ?Sized>(&'a self, stream: &mut S) -> Result {
+        let label = Label::new("Result").with_tag(&tags::VALUE_IDENT);
+
+        stream.enum_begin(None, Some(&label), None)?;
+
+        match self {
+            Ok(ok) => {
+                stream.tagged_begin(
+                    Some(&tags::RUST_RESULT_OK),
+                    Some(&Label::new("Ok").with_tag(&tags::VALUE_IDENT)),
+                    Some(&Index::new(0).with_tag(&tags::VALUE_OFFSET)),
+                )?;
+
+                stream.value(ok)?;
+
+                stream.tagged_end(
+                    Some(&tags::RUST_RESULT_OK),
+                    Some(&Label::new("Ok").with_tag(&tags::VALUE_IDENT)),
+                    Some(&Index::new(0).with_tag(&tags::VALUE_OFFSET)),
+                )?;
+            }
+            Err(err) => {
+                stream.tagged_begin(
+                    Some(&tags::RUST_RESULT_ERR),
+                    Some(&Label::new("Err").with_tag(&tags::VALUE_IDENT)),
+                    Some(&Index::new(1).with_tag(&tags::VALUE_OFFSET)),
+                )?;
+
+                stream.value(err)?;
+
+                stream.tagged_end(
+                    Some(&tags::RUST_RESULT_ERR),
+                    Some(&Label::new("Err").with_tag(&tags::VALUE_IDENT)),
+                    Some(&Index::new(1).with_tag(&tags::VALUE_OFFSET)),
+                )?;
+            }
+        }
+
+        stream.enum_end(None, Some(&label), None)
+    }
+}
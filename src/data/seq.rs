@@ -2,15 +2,7 @@ use crate::{tags, Index, Result, Stream, Value};
 
 impl<T: Value> Value for [T] {
     fn stream<'a, S: Stream<'a> + ?Sized>(&'a self, stream: &mut S) -> Result {
-        stream.seq_begin(Some(self.len()))?;
-
-        for elem in self {
-            stream.seq_value_begin()?;
-            stream.value(elem)?;
-            stream.seq_value_end()?;
-        }
-
-        stream.seq_end()
+        stream_iter(stream, self.len(), self)
     }
 }
 
@@ -219,13 +211,86 @@ tuple! {
 mod alloc_support {
     use super::*;
 
-    use crate::std::vec::Vec;
+    use crate::std::{
+        collections::{BTreeSet, BinaryHeap, LinkedList, VecDeque},
+        vec::Vec,
+    };
 
     impl<T: Value> Value for Vec<T> {
         fn stream<'a, S: Stream<'a> + ?Sized>(&'a self, stream: &mut S) -> Result {
             (&**self).stream(stream)
         }
     }
+
+    impl<T: Value> Value for VecDeque<T> {
+        fn stream<'a, S: Stream<'a> + ?Sized>(&'a self, stream: &mut S) -> Result {
+            stream_iter(stream, self.len(), self)
+        }
+    }
+
+    impl<T: Value> Value for LinkedList<T> {
+        fn stream<'a, S: Stream<'a> + ?Sized>(&'a self, stream: &mut S) -> Result {
+            stream_iter(stream, self.len(), self)
+        }
+    }
+
+    impl<T: Value> Value for BTreeSet<T> {
+        fn stream<'a, S: Stream<'a> + ?Sized>(&'a self, stream: &mut S) -> Result {
+            stream_iter(stream, self.len(), self)
+        }
+    }
+
+    /**
+    Stream a `BinaryHeap` as a sequence.
+
+    Elements are streamed in the heap's internal order, the same as `BinaryHeap::iter`.
+    That order is arbitrary rather than sorted, apart from the greatest element always
+    coming first. To stream the elements in sorted order, stream the result of
+    `BinaryHeap::into_sorted_vec` instead.
+    */
+    impl<T: Value> Value for BinaryHeap<T> {
+        fn stream<'a, S: Stream<'a> + ?Sized>(&'a self, stream: &mut S) -> Result {
+            stream_iter(stream, self.len(), self)
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+mod std_support {
+    use super::*;
+
+    use crate::std::{collections::HashSet, hash::BuildHasher};
+
+    /**
+    Stream a `HashSet` as a sequence.
+
+    The order of elements in a `HashSet` is arbitrary, and can change between runs
+    of a program, so the order they're streamed in is arbitrary too.
+    */
+    impl<T: Value, H: BuildHasher> Value for HashSet<T, H> {
+        fn stream<'a, S: Stream<'a> + ?Sized>(&'a self, stream: &mut S) -> Result {
+            stream_iter(stream, self.len(), self)
+        }
+    }
+}
+
+/**
+Stream the elements of a collection as a sequence.
+*/
+fn stream_iter<'a, T: Value + 'a>(
+    stream: &mut (impl Stream<'a> + ?Sized),
+    len: usize,
+    elems: impl IntoIterator<Item = &'a T>,
+) -> Result {
+    stream.seq_begin(Some(len))?;
+
+    for elem in elems {
+        stream.seq_value_begin()?;
+        stream.value(elem)?;
+        stream.seq_value_end()?;
+    }
+
+    stream.seq_end()
 }
 
 #[cfg(test)]
@@ -0,0 +1,115 @@
+use crate::{
+    std::fmt::{self, Debug},
+    Index, Label, Result, Stream, Tag, Value,
+};
+
+/**
+Stream a value as if it carried the given tag.
+
+The inner value is wrapped in [`Stream::tagged_begin`] and [`Stream::tagged_end`], the same way
+a type using `#[sval(tag)]` would be. A label and index can also be attached using
+[`Tagged::with_label`] and [`Tagged::with_index`].
+
+```
+use sval::Value;
+
+// Present some text as an arbitrary precision number
+let number = sval::tagged(sval::tags::NUMBER, "1.5");
+
+assert_eq!(Some(sval::tags::NUMBER), number.tag());
+```
+*/
+pub fn tagged<'a, V: Value + ?Sized>(tag: Tag, value: &'a V) -> Tagged<'a, V> {
+    Tagged {
+        tag,
+        label: None,
+        index: None,
+        value,
+        is_computed: false,
+    }
+}
+
+/**
+Stream a value as if it carried the given tag, without retaining any data it borrows.
+
+This is the same as [`tagged`], but the inner value is streamed with
+[`Stream::value_computed`], so streams won't see any of its text or binary fragments as
+borrowed.
+*/
+pub fn tagged_computed<'a, V: Value + ?Sized>(tag: Tag, value: &'a V) -> Tagged<'a, V> {
+    Tagged {
+        is_computed: true,
+        ..tagged(tag, value)
+    }
+}
+
+/**
+A [`Value`] that wraps another in a tag.
+
+See [`tagged`] and [`tagged_computed`] for details.
+*/
+pub struct Tagged<'a, V: ?Sized> {
+    tag: Tag,
+    label: Option<Label<'a>>,
+    index: Option<Index>,
+    value: &'a V,
+    is_computed: bool,
+}
+
+impl<'a, V: ?Sized> Tagged<'a, V> {
+    /**
+    Attach a label to the tagged value.
+    */
+    pub fn with_label(mut self, label: Label<'a>) -> Self {
+        self.label = Some(label);
+        self
+    }
+
+    /**
+    Attach an index to the tagged value.
+    */
+    pub fn with_index(mut self, index: Index) -> Self {
+        self.index = Some(index);
+        self
+    }
+
+    /**
+    Get a reference to the inner value.
+    */
+    pub fn inner(&self) -> &'a V {
+        self.value
+    }
+}
+
+impl<'a, V: Debug + ?Sized> Debug for Tagged<'a, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Tagged")
+            .field("tag", &self.tag)
+            .field("label", &self.label)
+            .field("index", &self.index)
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+impl<'a, V: Value + ?Sized> Value for Tagged<'a, V> {
+    fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> Result {
+        let tag = Some(&self.tag);
+        let label = self.label.as_ref();
+        let index = self.index.as_ref();
+
+        stream.tagged_begin(tag, label, index)?;
+
+        if self.is_computed {
+            stream.value_computed(self.value)?;
+        } else {
+            stream.value(self.value)?;
+        }
+
+        stream.tagged_end(tag, label, index)
+    }
+
+    fn tag(&self) -> Option<Tag> {
+        Some(self.tag.clone())
+    }
+}
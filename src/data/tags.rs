@@ -22,6 +22,24 @@ A tag for a value that represents the `None` variant of a Rust `Option`.
 */
 pub const RUST_OPTION_NONE: Tag = Tag::new("RUST_OPTION_NONE");
 
+/**
+A tag for a value that represents the `Ok` variant of a Rust `Result`.
+
+# Valid datatypes
+
+- `tagged`
+*/
+pub const RUST_RESULT_OK: Tag = Tag::new("RUST_RESULT_OK");
+
+/**
+A tag for a value that represents the `Err` variant of a Rust `Result`.
+
+# Valid datatypes
+
+- `tagged`
+*/
+pub const RUST_RESULT_ERR: Tag = Tag::new("RUST_RESULT_ERR");
+
 /**
 A tag for Rust's `()` type.
 
@@ -31,6 +49,18 @@ A tag for Rust's `()` type.
 */
 pub const RUST_UNIT: Tag = Tag::new("RUST_UNIT");
 
+/**
+A tag for Rust's `char` type.
+
+The text is a single Unicode scalar value. Its length is the number of bytes
+in its UTF-8 encoding, which may be more than 1.
+
+# Valid datatypes
+
+- `text`
+*/
+pub const CHAR: Tag = Tag::new("CHAR");
+
 /**
 A tag for arbitrary-precision decimal numbers.
 
@@ -95,3 +125,129 @@ A tag for indexes that are zero-based non-negative offsets into a larger structu
 - `index`
 */
 pub const VALUE_OFFSET: Tag = Tag::new("VALUE_OFFSET");
+
+/**
+A tag for IP addresses.
+
+Values with this tag are in the textual form produced by Rust's `IpAddr`, like `127.0.0.1`
+or `::1`. Both IPv4 and IPv6 addresses use this tag; the version is determined by the text.
+
+# Valid datatypes
+
+- `text`
+*/
+pub const IP_ADDR: Tag = Tag::new("IP_ADDR");
+
+/**
+A tag for socket addresses.
+
+Values with this tag are in the textual form produced by Rust's `SocketAddr`, like
+`127.0.0.1:8080` or `[::1]:8080`. IPv6 socket addresses may include a zone identifier
+as a numeric scope id, like `[fe80::1%2]:8080`.
+
+# Valid datatypes
+
+- `text`
+*/
+pub const SOCKET_ADDR: Tag = Tag::new("SOCKET_ADDR");
+
+/**
+A tag for UUIDs.
+
+Values with this tag are the 16 bytes of a UUID, in the order they're written in its
+hyphenated form. Formats that want text can render them as hyphenated lowercase hex,
+like `67e55044-10b1-426f-9247-bb680e5fe0c8`.
+
+# Valid datatypes
+
+- `binary`
+*/
+pub const UUID: Tag = Tag::new("UUID");
+
+/**
+A tag for ordinal dates.
+
+Values with this tag are a year followed by the one-based day of that year, like `2024-060`.
+
+# Valid datatypes
+
+- `text`
+*/
+pub const ORDINAL_DATE: Tag = Tag::new("ORDINAL_DATE");
+
+/**
+A tag for ISO 8601 week dates.
+
+Values with this tag are an ISO week-numbering year, week, and one-based weekday starting
+from Monday, like `2024-W09-4`.
+
+# Valid datatypes
+
+- `text`
+*/
+pub const ISO_WEEK_DATE: Tag = Tag::new("ISO_WEEK_DATE");
+
+/**
+A tag for values that carry the version of their schema.
+
+The tagged value's index is the version number, and its contents are the versioned value.
+Consumers can use the version to migrate data written with older schemas.
+`sval_derive` uses this tag for types with a `#[sval(version = N)]` attribute.
+
+# Valid datatypes
+
+- `tagged`
+*/
+pub const SCHEMA_VERSION: Tag = Tag::new("SCHEMA_VERSION");
+
+/**
+A tag for Rust's `Range` and `RangeInclusive` types.
+
+Values with this tag are records with a `start` and `end` field. The label of the record is
+`Range` if `end` is excluded from the range, or `RangeInclusive` if it's included.
+Empty ranges, where `start` is not less than `end`, are streamed the same way as any other.
+
+# Valid datatypes
+
+- `record`
+*/
+pub const RANGE: Tag = Tag::new("RANGE");
+
+/**
+A tag for C strings, like Rust's `CStr`.
+
+Values with this tag are the bytes of the string, without its trailing nul terminator.
+They can't contain any interior nul bytes, but aren't required to be valid UTF8.
+
+# Valid datatypes
+
+- `binary`
+*/
+pub const C_STRING: Tag = Tag::new("C_STRING");
+
+/**
+A tag for platform strings, like Rust's `OsStr`.
+
+Values with this tag are the string converted to UTF8. Platform strings that aren't valid
+UTF8, like arbitrary bytes on Unix or unpaired surrogates on Windows, are converted lossily,
+with invalid sequences replaced by `U+FFFD`. Platform strings may contain interior nul
+characters, which are kept as-is.
+
+# Valid datatypes
+
+- `text`
+*/
+pub const OS_STRING: Tag = Tag::new("OS_STRING");
+
+/**
+A tag for spans of time, like Rust's `Duration`.
+
+Values with this tag are record tuples with a `secs` field containing a `u64` number of
+whole seconds, and a `nanos` field containing a `u32` number of remaining nanoseconds,
+which is always less than one billion.
+
+# Valid datatypes
+
+- `record_tuple`
+*/
+pub const DURATION: Tag = Tag::new("DURATION");
@@ -1,6 +1,6 @@
 use crate::{
     std::fmt::{self, Write as _},
-    Error, Result, Stream, Tag, Value,
+    tags, Error, Result, Stream, Tag, Value,
 };
 
 /**
@@ -94,9 +94,11 @@ impl Value for char {
         let mut buf = [0; 4];
         let value = &*self.encode_utf8(&mut buf);
 
+        stream.tagged_begin(Some(&tags::CHAR), None, None)?;
         stream.text_begin(Some(value.len()))?;
         stream.text_fragment_computed(value)?;
-        stream.text_end()
+        stream.text_end()?;
+        stream.tagged_end(Some(&tags::CHAR), None, None)
     }
 }
 
@@ -120,7 +122,7 @@ impl Value for str {
 mod alloc_support {
     use super::*;
 
-    use crate::std::string::String;
+    use crate::std::{borrow::Cow, string::String};
 
     impl Value for String {
         fn stream<'a, S: Stream<'a> + ?Sized>(&'a self, stream: &mut S) -> Result {
@@ -136,6 +138,21 @@ mod alloc_support {
             Some(self)
         }
     }
+
+    impl<'a> Value for Cow<'a, str> {
+        fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> Result {
+            (&**self).stream(stream)
+        }
+
+        fn tag(&self) -> Option<Tag> {
+            None
+        }
+
+        #[inline]
+        fn to_text(&self) -> Option<&str> {
+            Some(self)
+        }
+    }
 }
 
 #[cfg(test)]
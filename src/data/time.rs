@@ -0,0 +1,116 @@
+use crate::{std::time::Duration, tags, Index, Label, Result, Stream, Value};
+
+#[cfg(feature = "std")]
+use crate::std::time::Instant;
+
+// `Duration` streams as a record tuple of its whole seconds and remaining nanoseconds
+// This is lossless, and matches the way `serde` serializes it
+impl Value for Duration {
+    fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> Result {
+        stream_duration(stream, *self)
+    }
+}
+
+/**
+Stream a [`Duration`] as a single `f64` number of fractional seconds, like `1.5`.
+
+This is useful for consumers that expect a duration to be a number instead of a structured value.
+An `f64` can only represent whole nanoseconds exactly up to 2<sup>53</sup> nanoseconds,
+which is a little over 104 days. Longer durations will lose precision from their fractional part,
+so if that matters, stream the `Duration` itself instead.
+
+```
+use std::time::Duration;
+
+assert_eq!(1.5, sval::DurationSeconds(Duration::from_millis(1500)).as_secs_f64());
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DurationSeconds(pub Duration);
+
+impl DurationSeconds {
+    /**
+    Get the duration as a number of fractional seconds.
+    */
+    pub fn as_secs_f64(&self) -> f64 {
+        self.0.as_secs_f64()
+    }
+}
+
+impl Value for DurationSeconds {
+    fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> Result {
+        stream.f64(self.as_secs_f64())
+    }
+}
+
+/**
+Stream the time elapsed between two [`Instant`]s as a [`Duration`].
+
+An `Instant` on its own doesn't mean anything outside of the process that created it,
+but the time between two of them does. If `at` is earlier than `base`, which can happen
+on platforms with unreliable clocks, then the elapsed time saturates to zero.
+
+```
+use std::time::{Duration, Instant};
+
+let base = Instant::now();
+let at = base + Duration::from_millis(1500);
+
+assert_eq!(Duration::from_millis(1500), sval::elapsed(base, at).duration());
+assert_eq!(Duration::ZERO, sval::elapsed(at, base).duration());
+```
+
+This function requires the `std` feature.
+*/
+#[cfg(feature = "std")]
+pub fn elapsed(base: Instant, at: Instant) -> Elapsed {
+    Elapsed(at.saturating_duration_since(base))
+}
+
+/**
+The time elapsed between two [`Instant`]s.
+
+See [`elapsed`] for details.
+
+This type requires the `std` feature.
+*/
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Elapsed(Duration);
+
+#[cfg(feature = "std")]
+impl Elapsed {
+    /**
+    Get the elapsed time as a [`Duration`].
+    */
+    pub fn duration(&self) -> Duration {
+        self.0
+    }
+}
+
+#[cfg(feature = "std")]
+impl Value for Elapsed {
+    fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> Result {
+        stream_duration(stream, self.0)
+    }
+}
+
+fn stream_duration<'sval, S: Stream<'sval> + ?Sized>(stream: &mut S, duration: Duration) -> Result {
+    let label = Label::new("Duration").with_tag(&tags::VALUE_IDENT);
+    let secs_label = Label::new("secs").with_tag(&tags::VALUE_IDENT);
+    let nanos_label = Label::new("nanos").with_tag(&tags::VALUE_IDENT);
+
+    let tag = Some(&tags::DURATION);
+
+    stream.record_tuple_begin(tag, Some(&label), None, Some(2))?;
+
+    stream.record_tuple_value_begin(None, &secs_label, &Index::new(0))?;
+    stream.u64(duration.as_secs())?;
+    stream.record_tuple_value_end(None, &secs_label, &Index::new(0))?;
+
+    stream.record_tuple_value_begin(None, &nanos_label, &Index::new(1))?;
+    stream.u32(duration.subsec_nanos())?;
+    stream.record_tuple_value_end(None, &nanos_label, &Index::new(1))?;
+
+    stream.record_tuple_end(tag, Some(&label), None)
+}
@@ -0,0 +1,59 @@
+use crate::{
+    std::fmt::{self, Write as _},
+    tags, Result, Stream, Value,
+};
+
+/**
+A UUID, as its 16 bytes.
+
+UUIDs are streamed as binary with the [`tags::UUID`] tag, so binary formats can keep them
+compact while text formats can recognize them and render their hyphenated form.
+
+```
+let uuid = sval::Uuid([
+    0x67, 0xe5, 0x50, 0x44, 0x10, 0xb1, 0x42, 0x6f, 0x92, 0x47, 0xbb, 0x68, 0x0e, 0x5f, 0xe0, 0xc8,
+]);
+
+assert_eq!("67e55044-10b1-426f-9247-bb680e5fe0c8", uuid.to_string());
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Uuid(pub [u8; 16]);
+
+impl Uuid {
+    /**
+    Get the bytes of the UUID.
+    */
+    pub const fn as_bytes(&self) -> &[u8; 16] {
+        &self.0
+    }
+}
+
+impl fmt::Display for Uuid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, b) in self.0.iter().enumerate() {
+            if let 4 | 6 | 8 | 10 = i {
+                f.write_char('-')?;
+            }
+
+            write!(f, "{:02x}", b)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Value for Uuid {
+    fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> Result {
+        stream.tagged_begin(Some(&tags::UUID), None, None)?;
+        stream.binary_begin(Some(self.0.len()))?;
+        stream.binary_fragment(&self.0)?;
+        stream.binary_end()?;
+        stream.tagged_end(Some(&tags::UUID), None, None)
+    }
+
+    #[inline(always)]
+    fn to_binary(&self) -> Option<&[u8]> {
+        Some(&self.0)
+    }
+}
@@ -93,9 +93,12 @@ extern crate core;
 #[cfg(all(feature = "alloc", not(feature = "std")))]
 mod std {
     pub use crate::{
-        alloc::{borrow, boxed, collections, string, vec},
-        core::{cmp, convert, fmt, hash, marker, mem, ops, result, str, write},
+        alloc::{borrow, boxed, collections, ffi, rc, string, vec},
+        core::{any, cell, cmp, fmt, hash, marker, mem, num, ops, result, str, time},
     };
+
+    #[cfg(target_has_atomic = "ptr")]
+    pub use crate::alloc::sync;
 }
 
 #[cfg(all(not(feature = "alloc"), not(feature = "std")))]
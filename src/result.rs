@@ -3,27 +3,96 @@ use crate::std::fmt;
 /**
 An error encountered while streaming a value.
 
-Errors don't capture details of failures, that responsibility is left
-to the stream to surface.
+Errors carry a broad [`ErrorKind`] that code can branch on, but don't capture
+details of failures. That responsibility is left to the stream to surface.
 */
 #[derive(Debug)]
-pub struct Error(());
+pub struct Error(ErrorKind);
+
+/**
+The broad reason a value failed to stream.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /**
+    The stream doesn't support the kind of value it was given.
+    */
+    Unsupported,
+    /**
+    Text or binary data that was expected to be UTF8 wasn't.
+    */
+    InvalidUtf8,
+    /**
+    A value couldn't be buffered.
+    */
+    Buffer,
+    /**
+    Any other failure.
+    */
+    Other,
+}
 
 impl Error {
     /**
-    Create a new error.
+    Create a new error with the kind [`ErrorKind::Other`].
 
     More detailed diagnostic information will need to be stored elsewhere.
     */
     #[inline(always)]
     pub fn new() -> Self {
-        Error(())
+        Error(ErrorKind::Other)
+    }
+
+    /**
+    Create a new error with the kind [`ErrorKind::Unsupported`].
+    */
+    #[inline(always)]
+    pub fn unsupported() -> Self {
+        Error(ErrorKind::Unsupported)
+    }
+
+    /**
+    Create a new error with the kind [`ErrorKind::InvalidUtf8`].
+    */
+    #[inline(always)]
+    pub fn invalid_utf8() -> Self {
+        Error(ErrorKind::InvalidUtf8)
+    }
+
+    /**
+    Create a new error with the kind [`ErrorKind::Buffer`].
+    */
+    #[inline(always)]
+    pub fn buffer() -> Self {
+        Error(ErrorKind::Buffer)
+    }
+
+    /**
+    Get the kind of this error.
+    */
+    #[inline(always)]
+    pub fn kind(&self) -> ErrorKind {
+        self.0
+    }
+
+    /**
+    Whether this error was caused by a stream not supporting the kind of value it was given.
+    */
+    #[inline(always)]
+    pub fn is_unsupported(&self) -> bool {
+        self.0 == ErrorKind::Unsupported
     }
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "failed to stream data")
+        match self.0 {
+            ErrorKind::Unsupported => write!(f, "failed to stream data: unsupported value"),
+            ErrorKind::InvalidUtf8 => write!(f, "failed to stream data: invalid UTF8"),
+            ErrorKind::Buffer => write!(f, "failed to stream data: failed to buffer value"),
+            ErrorKind::Other => write!(f, "failed to stream data"),
+        }
     }
 }
 
@@ -1,4 +1,406 @@
-use crate::{data, tags, Index, Label, Result, Tag, Value};
+use crate::{data, tags, Index, Label, Number, Result, Tag, Value};
+
+/**
+Implement [`Stream`] methods by forwarding them unchanged to the stream in a field.
+
+Adapters use this for the methods they don't need to change, and implement the rest themselves.
+*/
+macro_rules! forward_stream {
+    ($field:ident => $($method:ident),* $(,)?) => {
+        $(forward_stream!(@ $field, $method);)*
+    };
+    (@ $field:ident, null) => {
+        fn null(&mut self) -> $crate::Result {
+            self.$field.null()
+        }
+    };
+    (@ $field:ident, bool) => {
+        fn bool(&mut self, value: bool) -> $crate::Result {
+            self.$field.bool(value)
+        }
+    };
+    (@ $field:ident, text_begin) => {
+        fn text_begin(&mut self, num_bytes: Option<usize>) -> $crate::Result {
+            self.$field.text_begin(num_bytes)
+        }
+    };
+    (@ $field:ident, text_fragment) => {
+        fn text_fragment(&mut self, fragment: &'sval str) -> $crate::Result {
+            self.$field.text_fragment(fragment)
+        }
+    };
+    (@ $field:ident, text_fragment_computed) => {
+        fn text_fragment_computed(&mut self, fragment: &str) -> $crate::Result {
+            self.$field.text_fragment_computed(fragment)
+        }
+    };
+    (@ $field:ident, text_end) => {
+        fn text_end(&mut self) -> $crate::Result {
+            self.$field.text_end()
+        }
+    };
+    (@ $field:ident, binary_begin) => {
+        fn binary_begin(&mut self, num_bytes: Option<usize>) -> $crate::Result {
+            self.$field.binary_begin(num_bytes)
+        }
+    };
+    (@ $field:ident, binary_fragment) => {
+        fn binary_fragment(&mut self, fragment: &'sval [u8]) -> $crate::Result {
+            self.$field.binary_fragment(fragment)
+        }
+    };
+    (@ $field:ident, binary_fragment_computed) => {
+        fn binary_fragment_computed(&mut self, fragment: &[u8]) -> $crate::Result {
+            self.$field.binary_fragment_computed(fragment)
+        }
+    };
+    (@ $field:ident, binary_end) => {
+        fn binary_end(&mut self) -> $crate::Result {
+            self.$field.binary_end()
+        }
+    };
+    (@ $field:ident, u8) => {
+        fn u8(&mut self, value: u8) -> $crate::Result {
+            self.$field.u8(value)
+        }
+    };
+    (@ $field:ident, u16) => {
+        fn u16(&mut self, value: u16) -> $crate::Result {
+            self.$field.u16(value)
+        }
+    };
+    (@ $field:ident, u32) => {
+        fn u32(&mut self, value: u32) -> $crate::Result {
+            self.$field.u32(value)
+        }
+    };
+    (@ $field:ident, u64) => {
+        fn u64(&mut self, value: u64) -> $crate::Result {
+            self.$field.u64(value)
+        }
+    };
+    (@ $field:ident, u128) => {
+        fn u128(&mut self, value: u128) -> $crate::Result {
+            self.$field.u128(value)
+        }
+    };
+    (@ $field:ident, i8) => {
+        fn i8(&mut self, value: i8) -> $crate::Result {
+            self.$field.i8(value)
+        }
+    };
+    (@ $field:ident, i16) => {
+        fn i16(&mut self, value: i16) -> $crate::Result {
+            self.$field.i16(value)
+        }
+    };
+    (@ $field:ident, i32) => {
+        fn i32(&mut self, value: i32) -> $crate::Result {
+            self.$field.i32(value)
+        }
+    };
+    (@ $field:ident, i64) => {
+        fn i64(&mut self, value: i64) -> $crate::Result {
+            self.$field.i64(value)
+        }
+    };
+    (@ $field:ident, i128) => {
+        fn i128(&mut self, value: i128) -> $crate::Result {
+            self.$field.i128(value)
+        }
+    };
+    (@ $field:ident, f32) => {
+        fn f32(&mut self, value: f32) -> $crate::Result {
+            self.$field.f32(value)
+        }
+    };
+    (@ $field:ident, f64) => {
+        fn f64(&mut self, value: f64) -> $crate::Result {
+            self.$field.f64(value)
+        }
+    };
+    (@ $field:ident, num) => {
+        fn num(&mut self, num: &$crate::Number) -> $crate::Result {
+            self.$field.num(num)
+        }
+    };
+    (@ $field:ident, map_begin) => {
+        fn map_begin(&mut self, num_entries: Option<usize>) -> $crate::Result {
+            self.$field.map_begin(num_entries)
+        }
+    };
+    (@ $field:ident, map_key_begin) => {
+        fn map_key_begin(&mut self) -> $crate::Result {
+            self.$field.map_key_begin()
+        }
+    };
+    (@ $field:ident, map_key_end) => {
+        fn map_key_end(&mut self) -> $crate::Result {
+            self.$field.map_key_end()
+        }
+    };
+    (@ $field:ident, map_value_begin) => {
+        fn map_value_begin(&mut self) -> $crate::Result {
+            self.$field.map_value_begin()
+        }
+    };
+    (@ $field:ident, map_value_end) => {
+        fn map_value_end(&mut self) -> $crate::Result {
+            self.$field.map_value_end()
+        }
+    };
+    (@ $field:ident, map_end) => {
+        fn map_end(&mut self) -> $crate::Result {
+            self.$field.map_end()
+        }
+    };
+    (@ $field:ident, seq_begin) => {
+        fn seq_begin(&mut self, num_entries: Option<usize>) -> $crate::Result {
+            self.$field.seq_begin(num_entries)
+        }
+    };
+    (@ $field:ident, seq_value_begin) => {
+        fn seq_value_begin(&mut self) -> $crate::Result {
+            self.$field.seq_value_begin()
+        }
+    };
+    (@ $field:ident, seq_value_end) => {
+        fn seq_value_end(&mut self) -> $crate::Result {
+            self.$field.seq_value_end()
+        }
+    };
+    (@ $field:ident, seq_end) => {
+        fn seq_end(&mut self) -> $crate::Result {
+            self.$field.seq_end()
+        }
+    };
+    (@ $field:ident, enum_begin) => {
+        fn enum_begin(
+            &mut self,
+            tag: Option<&$crate::Tag>,
+            label: Option<&$crate::Label>,
+            index: Option<&$crate::Index>,
+        ) -> $crate::Result {
+            self.$field.enum_begin(tag, label, index)
+        }
+    };
+    (@ $field:ident, enum_end) => {
+        fn enum_end(
+            &mut self,
+            tag: Option<&$crate::Tag>,
+            label: Option<&$crate::Label>,
+            index: Option<&$crate::Index>,
+        ) -> $crate::Result {
+            self.$field.enum_end(tag, label, index)
+        }
+    };
+    (@ $field:ident, tagged_begin) => {
+        fn tagged_begin(
+            &mut self,
+            tag: Option<&$crate::Tag>,
+            label: Option<&$crate::Label>,
+            index: Option<&$crate::Index>,
+        ) -> $crate::Result {
+            self.$field.tagged_begin(tag, label, index)
+        }
+    };
+    (@ $field:ident, tagged_end) => {
+        fn tagged_end(
+            &mut self,
+            tag: Option<&$crate::Tag>,
+            label: Option<&$crate::Label>,
+            index: Option<&$crate::Index>,
+        ) -> $crate::Result {
+            self.$field.tagged_end(tag, label, index)
+        }
+    };
+    (@ $field:ident, tag) => {
+        fn tag(
+            &mut self,
+            tag: Option<&$crate::Tag>,
+            label: Option<&$crate::Label>,
+            index: Option<&$crate::Index>,
+        ) -> $crate::Result {
+            self.$field.tag(tag, label, index)
+        }
+    };
+    (@ $field:ident, tag_hint) => {
+        fn tag_hint(&mut self, tag: &$crate::Tag) -> $crate::Result {
+            self.$field.tag_hint(tag)
+        }
+    };
+    (@ $field:ident, record_begin) => {
+        fn record_begin(
+            &mut self,
+            tag: Option<&$crate::Tag>,
+            label: Option<&$crate::Label>,
+            index: Option<&$crate::Index>,
+            num_entries: Option<usize>,
+        ) -> $crate::Result {
+            self.$field.record_begin(tag, label, index, num_entries)
+        }
+    };
+    (@ $field:ident, record_value_begin) => {
+        fn record_value_begin(
+            &mut self,
+            tag: Option<&$crate::Tag>,
+            label: &$crate::Label,
+        ) -> $crate::Result {
+            self.$field.record_value_begin(tag, label)
+        }
+    };
+    (@ $field:ident, record_value_end) => {
+        fn record_value_end(
+            &mut self,
+            tag: Option<&$crate::Tag>,
+            label: &$crate::Label,
+        ) -> $crate::Result {
+            self.$field.record_value_end(tag, label)
+        }
+    };
+    (@ $field:ident, record_end) => {
+        fn record_end(
+            &mut self,
+            tag: Option<&$crate::Tag>,
+            label: Option<&$crate::Label>,
+            index: Option<&$crate::Index>,
+        ) -> $crate::Result {
+            self.$field.record_end(tag, label, index)
+        }
+    };
+    (@ $field:ident, tuple_begin) => {
+        fn tuple_begin(
+            &mut self,
+            tag: Option<&$crate::Tag>,
+            label: Option<&$crate::Label>,
+            index: Option<&$crate::Index>,
+            num_entries: Option<usize>,
+        ) -> $crate::Result {
+            self.$field.tuple_begin(tag, label, index, num_entries)
+        }
+    };
+    (@ $field:ident, tuple_value_begin) => {
+        fn tuple_value_begin(
+            &mut self,
+            tag: Option<&$crate::Tag>,
+            index: &$crate::Index,
+        ) -> $crate::Result {
+            self.$field.tuple_value_begin(tag, index)
+        }
+    };
+    (@ $field:ident, tuple_value_end) => {
+        fn tuple_value_end(
+            &mut self,
+            tag: Option<&$crate::Tag>,
+            index: &$crate::Index,
+        ) -> $crate::Result {
+            self.$field.tuple_value_end(tag, index)
+        }
+    };
+    (@ $field:ident, tuple_end) => {
+        fn tuple_end(
+            &mut self,
+            tag: Option<&$crate::Tag>,
+            label: Option<&$crate::Label>,
+            index: Option<&$crate::Index>,
+        ) -> $crate::Result {
+            self.$field.tuple_end(tag, label, index)
+        }
+    };
+    (@ $field:ident, record_tuple_begin) => {
+        fn record_tuple_begin(
+            &mut self,
+            tag: Option<&$crate::Tag>,
+            label: Option<&$crate::Label>,
+            index: Option<&$crate::Index>,
+            num_entries: Option<usize>,
+        ) -> $crate::Result {
+            self.$field.record_tuple_begin(tag, label, index, num_entries)
+        }
+    };
+    (@ $field:ident, record_tuple_value_begin) => {
+        fn record_tuple_value_begin(
+            &mut self,
+            tag: Option<&$crate::Tag>,
+            label: &$crate::Label,
+            index: &$crate::Index,
+        ) -> $crate::Result {
+            self.$field.record_tuple_value_begin(tag, label, index)
+        }
+    };
+    (@ $field:ident, record_tuple_value_end) => {
+        fn record_tuple_value_end(
+            &mut self,
+            tag: Option<&$crate::Tag>,
+            label: &$crate::Label,
+            index: &$crate::Index,
+        ) -> $crate::Result {
+            self.$field.record_tuple_value_end(tag, label, index)
+        }
+    };
+    (@ $field:ident, record_tuple_end) => {
+        fn record_tuple_end(
+            &mut self,
+            tag: Option<&$crate::Tag>,
+            label: Option<&$crate::Label>,
+            index: Option<&$crate::Index>,
+        ) -> $crate::Result {
+            self.$field.record_tuple_end(tag, label, index)
+        }
+    };
+}
+
+mod assert_primitive;
+mod binary_as_text;
+#[cfg(feature = "alloc")]
+mod canonical_encoding;
+#[cfg(feature = "alloc")]
+mod cycle_guard;
+#[cfg(feature = "alloc")]
+mod diff;
+mod ext;
+#[cfg(feature = "alloc")]
+mod field_profiler;
+mod hash_stream;
+#[cfg(feature = "alloc")]
+mod infer_schema;
+mod inspect;
+mod integers_as_floats;
+mod limit_depth;
+#[cfg(feature = "alloc")]
+mod map_labels;
+mod max_depth;
+#[cfg(feature = "alloc")]
+mod max_field_text_len;
+mod normalize_ints;
+#[cfg(feature = "alloc")]
+mod records_as_maps;
+#[cfg(feature = "alloc")]
+mod require_all_variants_handled;
+#[cfg(feature = "alloc")]
+mod schema_intern;
+#[cfg(feature = "alloc")]
+mod select_fields;
+#[cfg(feature = "alloc")]
+mod shape;
+#[cfg(feature = "alloc")]
+mod size_text;
+mod tee;
+mod untagged;
+#[cfg(feature = "alloc")]
+mod with_provenance;
+
+pub use self::{
+    assert_primitive::*, binary_as_text::*, ext::*, hash_stream::*, inspect::*,
+    integers_as_floats::*, limit_depth::*, max_depth::*, normalize_ints::*, tee::*, untagged::*,
+};
+
+#[cfg(feature = "alloc")]
+pub use self::{
+    canonical_encoding::*, cycle_guard::*, diff::*, field_profiler::*, infer_schema::*,
+    map_labels::*, max_field_text_len::*, records_as_maps::*, require_all_variants_handled::*,
+    schema_intern::*, select_fields::*, shape::*, size_text::*, with_provenance::*,
+};
 
 /**
 A consumer of structured data.
@@ -169,6 +571,14 @@ pub trait Stream<'sval> {
     */
     fn f64(&mut self, value: f64) -> Result;
 
+    /**
+    Stream an arbitrary precision number.
+    */
+    #[inline]
+    fn num(&mut self, num: &Number) -> Result {
+        default_stream::num(self, num)
+    }
+
     /**
     Start a homogenous mapping of arbitrary keys to values.
     */
@@ -544,6 +954,12 @@ macro_rules! impl_stream_forward {
                 ($($forward)*).f64(value)
             }
 
+            #[inline]
+            fn num(&mut self, num: &Number) -> Result {
+                let $bind = self;
+                ($($forward)*).num(num)
+            }
+
             #[inline]
             fn bool(&mut self, value: bool) -> Result {
                 let $bind = self;
@@ -880,6 +1296,11 @@ impl<'a, 'b, S: Stream<'a> + ?Sized> Stream<'b> for Computed<S> {
         self.0.f64(v)
     }
 
+    #[inline]
+    fn num(&mut self, num: &Number) -> Result {
+        self.0.num(num)
+    }
+
     #[inline]
     fn bool(&mut self, v: bool) -> Result {
         self.0.bool(v)
@@ -1277,6 +1698,19 @@ pub mod default_stream {
         stream.f64(value as f64)
     }
 
+    /**
+    Stream an arbitrary precision number.
+
+    The number will be streamed as text tagged with [`tags::NUMBER`].
+    */
+    pub fn num<'sval>(stream: &mut (impl Stream<'sval> + ?Sized), num: &Number) -> Result {
+        stream.tagged_begin(Some(&tags::NUMBER), None, None)?;
+        stream.text_begin(Some(num.as_str().len()))?;
+        stream.text_fragment_computed(num.as_str())?;
+        stream.text_end()?;
+        stream.tagged_end(Some(&tags::NUMBER), None, None)
+    }
+
     /**
     Start a homogenous mapping of arbitrary keys to values.
     */
@@ -0,0 +1,130 @@
+use crate::{Error, Index, Label, Result, Stream, Tag};
+
+/**
+Get a [`Stream`] that only accepts primitive values.
+
+The stream accepts null, booleans, numbers, text, and tags, along with tagged values
+wrapping them. Binary, maps, sequences, records, tuples, and enums fail. The stream
+doesn't retain anything, so it can be used to cheaply validate map keys in formats
+that restrict them to primitives. Values that aren't primitives fail with
+[`ErrorKind::Unsupported`](crate::ErrorKind::Unsupported).
+*/
+pub fn assert_primitive() -> AssertPrimitive {
+    AssertPrimitive
+}
+
+/**
+A [`Stream`] that only accepts primitive values.
+
+See [`assert_primitive`] for details.
+*/
+#[derive(Debug, Default, Clone, Copy)]
+pub struct AssertPrimitive;
+
+impl<'sval> Stream<'sval> for AssertPrimitive {
+    fn null(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn bool(&mut self, _: bool) -> Result {
+        Ok(())
+    }
+
+    fn text_begin(&mut self, _: Option<usize>) -> Result {
+        Ok(())
+    }
+
+    fn text_fragment_computed(&mut self, _: &str) -> Result {
+        Ok(())
+    }
+
+    fn text_end(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn binary_begin(&mut self, _: Option<usize>) -> Result {
+        Err(Error::unsupported())
+    }
+
+    fn binary_fragment_computed(&mut self, _: &[u8]) -> Result {
+        Err(Error::unsupported())
+    }
+
+    fn binary_end(&mut self) -> Result {
+        Err(Error::unsupported())
+    }
+
+    fn i64(&mut self, _: i64) -> Result {
+        Ok(())
+    }
+
+    fn f64(&mut self, _: f64) -> Result {
+        Ok(())
+    }
+
+    fn map_begin(&mut self, _: Option<usize>) -> Result {
+        Err(Error::unsupported())
+    }
+
+    fn seq_begin(&mut self, _: Option<usize>) -> Result {
+        Err(Error::unsupported())
+    }
+
+    fn seq_value_begin(&mut self) -> Result {
+        Err(Error::unsupported())
+    }
+
+    fn seq_value_end(&mut self) -> Result {
+        Err(Error::unsupported())
+    }
+
+    fn seq_end(&mut self) -> Result {
+        Err(Error::unsupported())
+    }
+
+    fn enum_begin(&mut self, _: Option<&Tag>, _: Option<&Label>, _: Option<&Index>) -> Result {
+        Err(Error::unsupported())
+    }
+
+    fn tagged_begin(&mut self, _: Option<&Tag>, _: Option<&Label>, _: Option<&Index>) -> Result {
+        Ok(())
+    }
+
+    fn tagged_end(&mut self, _: Option<&Tag>, _: Option<&Label>, _: Option<&Index>) -> Result {
+        Ok(())
+    }
+
+    fn tag(&mut self, _: Option<&Tag>, _: Option<&Label>, _: Option<&Index>) -> Result {
+        Ok(())
+    }
+
+    fn record_begin(
+        &mut self,
+        _: Option<&Tag>,
+        _: Option<&Label>,
+        _: Option<&Index>,
+        _: Option<usize>,
+    ) -> Result {
+        Err(Error::unsupported())
+    }
+
+    fn tuple_begin(
+        &mut self,
+        _: Option<&Tag>,
+        _: Option<&Label>,
+        _: Option<&Index>,
+        _: Option<usize>,
+    ) -> Result {
+        Err(Error::unsupported())
+    }
+
+    fn record_tuple_begin(
+        &mut self,
+        _: Option<&Tag>,
+        _: Option<&Label>,
+        _: Option<&Index>,
+        _: Option<usize>,
+    ) -> Result {
+        Err(Error::unsupported())
+    }
+}
@@ -0,0 +1,157 @@
+use crate::{
+    std::str::{self, Utf8Error},
+    Error, Result, Stream,
+};
+
+/**
+Wrap a stream in a [`BinaryAsText`] that forwards binary values as UTF8 text.
+*/
+pub fn binary_as_text<'sval, S: Stream<'sval>>(stream: S) -> BinaryAsText<S> {
+    BinaryAsText::new(stream)
+}
+
+/**
+A [`Stream`] that validates binary values as UTF8 and forwards them as text.
+
+Each binary value is forwarded to the wrapped stream as text, with the same length hint.
+Fragments are validated as they're received, so the whole value is never buffered.
+If a character is split across fragments, only its leading bytes are held on to until
+the rest of it arrives. Binary that isn't valid UTF8, including a character that's
+still incomplete when the value ends, fails the stream.
+
+Fragments that are already complete UTF8 are forwarded as borrowed text without copying.
+All other data is forwarded to the wrapped stream unchanged.
+*/
+#[derive(Debug)]
+pub struct BinaryAsText<S> {
+    stream: S,
+    partial: [u8; 4],
+    partial_len: usize,
+}
+
+impl<S> BinaryAsText<S> {
+    /**
+    Wrap a stream, forwarding binary values to it as text.
+    */
+    pub fn new(stream: S) -> Self {
+        BinaryAsText {
+            stream,
+            partial: [0; 4],
+            partial_len: 0,
+        }
+    }
+
+    /**
+    Get the wrapped stream.
+    */
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+
+    /**
+    Split the valid UTF8 at the start of a fragment from any incomplete character at its end.
+
+    The incomplete character is held on to until the next fragment.
+    */
+    fn valid_prefix<'a>(&mut self, fragment: &'a [u8]) -> Result<&'a str> {
+        match str::from_utf8(fragment) {
+            Ok(text) => Ok(text),
+            Err(err) => {
+                let (valid, partial) = fragment.split_at(err.valid_up_to());
+
+                if !is_incomplete(err) {
+                    return Err(Error::invalid_utf8());
+                }
+
+                self.partial[..partial.len()].copy_from_slice(partial);
+                self.partial_len = partial.len();
+
+                // SAFETY: `valid` is the prefix that was validated as UTF8
+                Ok(unsafe { str::from_utf8_unchecked(valid) })
+            }
+        }
+    }
+}
+
+impl<'sval, S: Stream<'sval>> BinaryAsText<S> {
+    /**
+    Complete a character that was split across fragments, returning the rest of the fragment.
+    */
+    fn complete_partial<'a>(&mut self, mut fragment: &'a [u8]) -> Result<&'a [u8]> {
+        while self.partial_len > 0 {
+            let (next, rest) = match fragment.split_first() {
+                Some(split) => split,
+                None => break,
+            };
+
+            self.partial[self.partial_len] = *next;
+            self.partial_len += 1;
+            fragment = rest;
+
+            match str::from_utf8(&self.partial[..self.partial_len]) {
+                Ok(text) => {
+                    self.stream.text_fragment_computed(text)?;
+                    self.partial_len = 0;
+                }
+                Err(err) if is_incomplete(err) => continue,
+                Err(_) => return Err(Error::invalid_utf8()),
+            }
+        }
+
+        Ok(fragment)
+    }
+}
+
+fn is_incomplete(err: Utf8Error) -> bool {
+    // An error without a length is a character that's cut off at the end of the input
+    err.error_len().is_none()
+}
+
+impl<'sval, S: Stream<'sval>> Stream<'sval> for BinaryAsText<S> {
+    forward_stream!(stream =>
+        null, bool, text_begin, text_fragment, text_fragment_computed, text_end, u8, u16, u32, u64,
+        u128, i8, i16, i32, i64, i128, f32, f64, num, map_begin, map_key_begin, map_key_end,
+        map_value_begin, map_value_end, map_end, seq_begin, seq_value_begin, seq_value_end, seq_end,
+        enum_begin, enum_end, tagged_begin, tagged_end, tag, tag_hint, record_begin,
+        record_value_begin, record_value_end, record_end, tuple_begin, tuple_value_begin,
+        tuple_value_end, tuple_end, record_tuple_begin, record_tuple_value_begin,
+        record_tuple_value_end, record_tuple_end,
+    );
+
+    fn binary_begin(&mut self, num_bytes: Option<usize>) -> Result {
+        self.partial_len = 0;
+
+        self.stream.text_begin(num_bytes)
+    }
+
+    fn binary_fragment(&mut self, fragment: &'sval [u8]) -> Result {
+        let fragment = self.complete_partial(fragment)?;
+        let text = self.valid_prefix(fragment)?;
+
+        if text.is_empty() {
+            return Ok(());
+        }
+
+        self.stream.text_fragment(text)
+    }
+
+    fn binary_fragment_computed(&mut self, fragment: &[u8]) -> Result {
+        let fragment = self.complete_partial(fragment)?;
+        let text = self.valid_prefix(fragment)?;
+
+        if text.is_empty() {
+            return Ok(());
+        }
+
+        self.stream.text_fragment_computed(text)
+    }
+
+    fn binary_end(&mut self) -> Result {
+        // The binary ended partway through a character
+        if self.partial_len > 0 {
+            return Err(Error::invalid_utf8());
+        }
+
+        self.stream.text_end()
+    }
+}
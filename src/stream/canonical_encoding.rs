@@ -0,0 +1,631 @@
+use crate::{
+    std::{cmp::Ordering, string::String, vec::Vec},
+    Error, Index, Label, Result, Stream, Tag,
+};
+
+/**
+Wrap a stream in a [`CanonicalEncoding`] that normalizes the data streamed through it.
+*/
+pub fn canonical_encoding<'sval, S: Stream<'sval>>(stream: S) -> CanonicalEncoding<S> {
+    CanonicalEncoding::new(stream)
+}
+
+/**
+A [`Stream`] that normalizes the way data is represented, so equivalent values always produce
+the same calls on the wrapped stream.
+
+This is useful when the exact output of a stream matters, like when signing serialized data.
+The normalizations made are:
+
+- Integers are streamed using the narrowest type that holds them exactly. Non-negative
+  integers use unsigned types, like [`Stream::u8`], and negative integers use signed types,
+  like [`Stream::i8`].
+- Floating point numbers are always streamed as [`Stream::f64`].
+- Text and binary values are streamed as a single computed fragment, with their exact length.
+- Hints for the number of entries in maps, sequences, records, and tuples are removed.
+- Tag hints, including those attached to labels, are removed.
+- The entries of maps are sorted by their keys. Keys are ordered by comparing the normalized
+  data they stream. Integers are compared numerically, as are floating point numbers, and
+  text and binary are compared bytewise. Keys that compare equal keep their original order.
+
+Sorting the entries of a map requires buffering it, so this type requires the `alloc` feature.
+*/
+#[derive(Debug)]
+pub struct CanonicalEncoding<S> {
+    stream: S,
+    maps: Vec<MapBuf>,
+    text: Option<String>,
+    binary: Option<Vec<u8>>,
+}
+
+#[derive(Debug, Default)]
+struct MapBuf {
+    entries: Vec<(Vec<Token>, Vec<Token>)>,
+    is_key: bool,
+}
+
+impl<S> CanonicalEncoding<S> {
+    /**
+    Wrap a stream, normalizing the data streamed through it.
+    */
+    pub fn new(stream: S) -> Self {
+        CanonicalEncoding {
+            stream,
+            maps: Vec::new(),
+            text: None,
+            binary: None,
+        }
+    }
+
+    /**
+    Get the wrapped stream.
+    */
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+impl<'sval, S: Stream<'sval>> CanonicalEncoding<S> {
+    fn emit(&mut self, token: Token) -> Result {
+        match self.maps.last_mut() {
+            None => token.stream(&mut self.stream),
+            Some(map) => {
+                let (key, value) = map.entries.last_mut().ok_or_else(Error::new)?;
+
+                if map.is_key {
+                    key.push(token);
+                } else {
+                    value.push(token);
+                }
+
+                Ok(())
+            }
+        }
+    }
+
+    fn signed(&mut self, v: i128) -> Result {
+        if v >= 0 {
+            return self.unsigned(v as u128);
+        }
+
+        self.emit(if let Ok(v) = i8::try_from(v) {
+            Token::I8(v)
+        } else if let Ok(v) = i16::try_from(v) {
+            Token::I16(v)
+        } else if let Ok(v) = i32::try_from(v) {
+            Token::I32(v)
+        } else if let Ok(v) = i64::try_from(v) {
+            Token::I64(v)
+        } else {
+            Token::I128(v)
+        })
+    }
+
+    fn unsigned(&mut self, v: u128) -> Result {
+        self.emit(if let Ok(v) = u8::try_from(v) {
+            Token::U8(v)
+        } else if let Ok(v) = u16::try_from(v) {
+            Token::U16(v)
+        } else if let Ok(v) = u32::try_from(v) {
+            Token::U32(v)
+        } else if let Ok(v) = u64::try_from(v) {
+            Token::U64(v)
+        } else {
+            Token::U128(v)
+        })
+    }
+}
+
+impl<'sval, S: Stream<'sval>> Stream<'sval> for CanonicalEncoding<S> {
+    fn null(&mut self) -> Result {
+        self.emit(Token::Null)
+    }
+
+    fn bool(&mut self, value: bool) -> Result {
+        self.emit(Token::Bool(value))
+    }
+
+    fn text_begin(&mut self, num_bytes: Option<usize>) -> Result {
+        self.text = Some(String::with_capacity(num_bytes.unwrap_or(0)));
+
+        Ok(())
+    }
+
+    fn text_fragment_computed(&mut self, fragment: &str) -> Result {
+        self.text
+            .as_mut()
+            .ok_or_else(Error::new)?
+            .push_str(fragment);
+
+        Ok(())
+    }
+
+    fn text_end(&mut self) -> Result {
+        let text = self.text.take().ok_or_else(Error::new)?;
+
+        self.emit(Token::Text(text))
+    }
+
+    fn binary_begin(&mut self, num_bytes: Option<usize>) -> Result {
+        self.binary = Some(Vec::with_capacity(num_bytes.unwrap_or(0)));
+
+        Ok(())
+    }
+
+    fn binary_fragment_computed(&mut self, fragment: &[u8]) -> Result {
+        self.binary
+            .as_mut()
+            .ok_or_else(Error::new)?
+            .extend_from_slice(fragment);
+
+        Ok(())
+    }
+
+    fn binary_end(&mut self) -> Result {
+        let binary = self.binary.take().ok_or_else(Error::new)?;
+
+        self.emit(Token::Binary(binary))
+    }
+
+    fn u8(&mut self, value: u8) -> Result {
+        self.unsigned(value as u128)
+    }
+
+    fn u16(&mut self, value: u16) -> Result {
+        self.unsigned(value as u128)
+    }
+
+    fn u32(&mut self, value: u32) -> Result {
+        self.unsigned(value as u128)
+    }
+
+    fn u64(&mut self, value: u64) -> Result {
+        self.unsigned(value as u128)
+    }
+
+    fn u128(&mut self, value: u128) -> Result {
+        self.unsigned(value)
+    }
+
+    fn i8(&mut self, value: i8) -> Result {
+        self.signed(value as i128)
+    }
+
+    fn i16(&mut self, value: i16) -> Result {
+        self.signed(value as i128)
+    }
+
+    fn i32(&mut self, value: i32) -> Result {
+        self.signed(value as i128)
+    }
+
+    fn i64(&mut self, value: i64) -> Result {
+        self.signed(value as i128)
+    }
+
+    fn i128(&mut self, value: i128) -> Result {
+        self.signed(value)
+    }
+
+    fn f32(&mut self, value: f32) -> Result {
+        self.emit(Token::F64(value as f64))
+    }
+
+    fn f64(&mut self, value: f64) -> Result {
+        self.emit(Token::F64(value))
+    }
+
+    fn map_begin(&mut self, _: Option<usize>) -> Result {
+        self.maps.push(MapBuf::default());
+
+        Ok(())
+    }
+
+    fn map_key_begin(&mut self) -> Result {
+        let map = self.maps.last_mut().ok_or_else(Error::new)?;
+
+        map.entries.push((Vec::new(), Vec::new()));
+        map.is_key = true;
+
+        Ok(())
+    }
+
+    fn map_key_end(&mut self) -> Result {
+        self.maps.last_mut().ok_or_else(Error::new)?.is_key = false;
+
+        Ok(())
+    }
+
+    fn map_value_begin(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn map_value_end(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn map_end(&mut self) -> Result {
+        let mut map = self.maps.pop().ok_or_else(Error::new)?;
+
+        map.entries.sort_by(|(a, _), (b, _)| Token::cmp_all(a, b));
+
+        self.emit(Token::MapBegin)?;
+
+        for (key, value) in map.entries {
+            self.emit(Token::MapKeyBegin)?;
+            for token in key {
+                self.emit(token)?;
+            }
+            self.emit(Token::MapKeyEnd)?;
+
+            self.emit(Token::MapValueBegin)?;
+            for token in value {
+                self.emit(token)?;
+            }
+            self.emit(Token::MapValueEnd)?;
+        }
+
+        self.emit(Token::MapEnd)
+    }
+
+    fn seq_begin(&mut self, _: Option<usize>) -> Result {
+        self.emit(Token::SeqBegin)
+    }
+
+    fn seq_value_begin(&mut self) -> Result {
+        self.emit(Token::SeqValueBegin)
+    }
+
+    fn seq_value_end(&mut self) -> Result {
+        self.emit(Token::SeqValueEnd)
+    }
+
+    fn seq_end(&mut self) -> Result {
+        self.emit(Token::SeqEnd)
+    }
+
+    fn enum_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.emit(Token::EnumBegin(meta(tag, label, index)))
+    }
+
+    fn enum_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.emit(Token::EnumEnd(meta(tag, label, index)))
+    }
+
+    fn tagged_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.emit(Token::TaggedBegin(meta(tag, label, index)))
+    }
+
+    fn tagged_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.emit(Token::TaggedEnd(meta(tag, label, index)))
+    }
+
+    fn tag(&mut self, tag: Option<&Tag>, label: Option<&Label>, index: Option<&Index>) -> Result {
+        self.emit(Token::Tag(meta(tag, label, index)))
+    }
+
+    fn tag_hint(&mut self, _: &Tag) -> Result {
+        Ok(())
+    }
+
+    fn record_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+        _: Option<usize>,
+    ) -> Result {
+        self.emit(Token::RecordBegin(meta(tag, label, index)))
+    }
+
+    fn record_value_begin(&mut self, tag: Option<&Tag>, label: &Label) -> Result {
+        self.emit(Token::RecordValueBegin(tag.cloned(), label.to_owned()))
+    }
+
+    fn record_value_end(&mut self, tag: Option<&Tag>, label: &Label) -> Result {
+        self.emit(Token::RecordValueEnd(tag.cloned(), label.to_owned()))
+    }
+
+    fn record_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.emit(Token::RecordEnd(meta(tag, label, index)))
+    }
+
+    fn tuple_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+        _: Option<usize>,
+    ) -> Result {
+        self.emit(Token::TupleBegin(meta(tag, label, index)))
+    }
+
+    fn tuple_value_begin(&mut self, tag: Option<&Tag>, index: &Index) -> Result {
+        self.emit(Token::TupleValueBegin(tag.cloned(), index.clone()))
+    }
+
+    fn tuple_value_end(&mut self, tag: Option<&Tag>, index: &Index) -> Result {
+        self.emit(Token::TupleValueEnd(tag.cloned(), index.clone()))
+    }
+
+    fn tuple_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.emit(Token::TupleEnd(meta(tag, label, index)))
+    }
+
+    fn record_tuple_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+        _: Option<usize>,
+    ) -> Result {
+        self.emit(Token::RecordTupleBegin(meta(tag, label, index)))
+    }
+
+    fn record_tuple_value_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: &Label,
+        index: &Index,
+    ) -> Result {
+        self.emit(Token::RecordTupleValueBegin(
+            tag.cloned(),
+            label.to_owned(),
+            index.clone(),
+        ))
+    }
+
+    fn record_tuple_value_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: &Label,
+        index: &Index,
+    ) -> Result {
+        self.emit(Token::RecordTupleValueEnd(
+            tag.cloned(),
+            label.to_owned(),
+            index.clone(),
+        ))
+    }
+
+    fn record_tuple_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.emit(Token::RecordTupleEnd(meta(tag, label, index)))
+    }
+}
+
+type Meta = (Option<Tag>, Option<Label<'static>>, Option<Index>);
+
+fn meta(tag: Option<&Tag>, label: Option<&Label>, index: Option<&Index>) -> Meta {
+    (
+        tag.cloned(),
+        label.map(|label| label.to_owned()),
+        index.cloned(),
+    )
+}
+
+/**
+A normalized call to a [`Stream`] method.
+*/
+#[derive(Debug)]
+enum Token {
+    Null,
+    Bool(bool),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    I128(i128),
+    F64(f64),
+    Text(String),
+    Binary(Vec<u8>),
+    MapBegin,
+    MapKeyBegin,
+    MapKeyEnd,
+    MapValueBegin,
+    MapValueEnd,
+    MapEnd,
+    SeqBegin,
+    SeqValueBegin,
+    SeqValueEnd,
+    SeqEnd,
+    EnumBegin(Meta),
+    EnumEnd(Meta),
+    TaggedBegin(Meta),
+    TaggedEnd(Meta),
+    Tag(Meta),
+    RecordBegin(Meta),
+    RecordValueBegin(Option<Tag>, Label<'static>),
+    RecordValueEnd(Option<Tag>, Label<'static>),
+    RecordEnd(Meta),
+    TupleBegin(Meta),
+    TupleValueBegin(Option<Tag>, Index),
+    TupleValueEnd(Option<Tag>, Index),
+    TupleEnd(Meta),
+    RecordTupleBegin(Meta),
+    RecordTupleValueBegin(Option<Tag>, Label<'static>, Index),
+    RecordTupleValueEnd(Option<Tag>, Label<'static>, Index),
+    RecordTupleEnd(Meta),
+}
+
+/**
+The part of a token that's compared when sorting map keys.
+*/
+#[derive(PartialEq, PartialOrd)]
+enum SortKey<'a> {
+    Null,
+    Bool(bool),
+    Signed(i128),
+    Unsigned(u128),
+    Float(f64),
+    Text(&'a str),
+    Binary(&'a [u8]),
+    Other(Option<&'a str>, Option<&'a Index>),
+}
+
+impl Token {
+    fn cmp_all(a: &[Token], b: &[Token]) -> Ordering {
+        for (a, b) in a.iter().zip(b) {
+            match a.sort_key().partial_cmp(&b.sort_key()) {
+                Some(Ordering::Equal) | None => continue,
+                Some(ord) => return ord,
+            }
+        }
+
+        a.len().cmp(&b.len())
+    }
+
+    fn sort_key(&self) -> SortKey<'_> {
+        match self {
+            Token::Null => SortKey::Null,
+            Token::Bool(v) => SortKey::Bool(*v),
+            Token::I8(v) => SortKey::Signed(*v as i128),
+            Token::I16(v) => SortKey::Signed(*v as i128),
+            Token::I32(v) => SortKey::Signed(*v as i128),
+            Token::I64(v) => SortKey::Signed(*v as i128),
+            Token::I128(v) => SortKey::Signed(*v),
+            Token::U8(v) => SortKey::Unsigned(*v as u128),
+            Token::U16(v) => SortKey::Unsigned(*v as u128),
+            Token::U32(v) => SortKey::Unsigned(*v as u128),
+            Token::U64(v) => SortKey::Unsigned(*v as u128),
+            Token::U128(v) => SortKey::Unsigned(*v),
+            Token::F64(v) => SortKey::Float(*v),
+            Token::Text(v) => SortKey::Text(v),
+            Token::Binary(v) => SortKey::Binary(v),
+            Token::EnumBegin((_, label, index))
+            | Token::TaggedBegin((_, label, index))
+            | Token::Tag((_, label, index))
+            | Token::RecordBegin((_, label, index))
+            | Token::TupleBegin((_, label, index))
+            | Token::RecordTupleBegin((_, label, index)) => {
+                SortKey::Other(label.as_ref().map(|label| label.as_str()), index.as_ref())
+            }
+            Token::RecordValueBegin(_, label) => SortKey::Other(Some(label.as_str()), None),
+            Token::TupleValueBegin(_, index) => SortKey::Other(None, Some(index)),
+            Token::RecordTupleValueBegin(_, label, index) => {
+                SortKey::Other(Some(label.as_str()), Some(index))
+            }
+            _ => SortKey::Other(None, None),
+        }
+    }
+
+    fn stream<'sval, S: Stream<'sval> + ?Sized>(self, stream: &mut S) -> Result {
+        match self {
+            Token::Null => stream.null(),
+            Token::Bool(v) => stream.bool(v),
+            Token::U8(v) => stream.u8(v),
+            Token::U16(v) => stream.u16(v),
+            Token::U32(v) => stream.u32(v),
+            Token::U64(v) => stream.u64(v),
+            Token::U128(v) => stream.u128(v),
+            Token::I8(v) => stream.i8(v),
+            Token::I16(v) => stream.i16(v),
+            Token::I32(v) => stream.i32(v),
+            Token::I64(v) => stream.i64(v),
+            Token::I128(v) => stream.i128(v),
+            Token::F64(v) => stream.f64(v),
+            Token::Text(v) => {
+                stream.text_begin(Some(v.len()))?;
+                stream.text_fragment_computed(&v)?;
+                stream.text_end()
+            }
+            Token::Binary(v) => {
+                stream.binary_begin(Some(v.len()))?;
+                stream.binary_fragment_computed(&v)?;
+                stream.binary_end()
+            }
+            Token::MapBegin => stream.map_begin(None),
+            Token::MapKeyBegin => stream.map_key_begin(),
+            Token::MapKeyEnd => stream.map_key_end(),
+            Token::MapValueBegin => stream.map_value_begin(),
+            Token::MapValueEnd => stream.map_value_end(),
+            Token::MapEnd => stream.map_end(),
+            Token::SeqBegin => stream.seq_begin(None),
+            Token::SeqValueBegin => stream.seq_value_begin(),
+            Token::SeqValueEnd => stream.seq_value_end(),
+            Token::SeqEnd => stream.seq_end(),
+            Token::EnumBegin((tag, label, index)) => {
+                stream.enum_begin(tag.as_ref(), label.as_ref(), index.as_ref())
+            }
+            Token::EnumEnd((tag, label, index)) => {
+                stream.enum_end(tag.as_ref(), label.as_ref(), index.as_ref())
+            }
+            Token::TaggedBegin((tag, label, index)) => {
+                stream.tagged_begin(tag.as_ref(), label.as_ref(), index.as_ref())
+            }
+            Token::TaggedEnd((tag, label, index)) => {
+                stream.tagged_end(tag.as_ref(), label.as_ref(), index.as_ref())
+            }
+            Token::Tag((tag, label, index)) => {
+                stream.tag(tag.as_ref(), label.as_ref(), index.as_ref())
+            }
+            Token::RecordBegin((tag, label, index)) => {
+                stream.record_begin(tag.as_ref(), label.as_ref(), index.as_ref(), None)
+            }
+            Token::RecordValueBegin(tag, label) => stream.record_value_begin(tag.as_ref(), &label),
+            Token::RecordValueEnd(tag, label) => stream.record_value_end(tag.as_ref(), &label),
+            Token::RecordEnd((tag, label, index)) => {
+                stream.record_end(tag.as_ref(), label.as_ref(), index.as_ref())
+            }
+            Token::TupleBegin((tag, label, index)) => {
+                stream.tuple_begin(tag.as_ref(), label.as_ref(), index.as_ref(), None)
+            }
+            Token::TupleValueBegin(tag, index) => stream.tuple_value_begin(tag.as_ref(), &index),
+            Token::TupleValueEnd(tag, index) => stream.tuple_value_end(tag.as_ref(), &index),
+            Token::TupleEnd((tag, label, index)) => {
+                stream.tuple_end(tag.as_ref(), label.as_ref(), index.as_ref())
+            }
+            Token::RecordTupleBegin((tag, label, index)) => {
+                stream.record_tuple_begin(tag.as_ref(), label.as_ref(), index.as_ref(), None)
+            }
+            Token::RecordTupleValueBegin(tag, label, index) => {
+                stream.record_tuple_value_begin(tag.as_ref(), &label, &index)
+            }
+            Token::RecordTupleValueEnd(tag, label, index) => {
+                stream.record_tuple_value_end(tag.as_ref(), &label, &index)
+            }
+            Token::RecordTupleEnd((tag, label, index)) => {
+                stream.record_tuple_end(tag.as_ref(), label.as_ref(), index.as_ref())
+            }
+        }
+    }
+}
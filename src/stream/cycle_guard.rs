@@ -0,0 +1,105 @@
+use crate::{
+    default_stream,
+    std::{any::type_name, vec::Vec},
+    Error, Result, Stream, Value,
+};
+
+/**
+Wrap a stream in a [`CycleGuard`] that fails if a value is nested within itself.
+*/
+pub fn cycle_guard<'sval, S: Stream<'sval>>(stream: S) -> CycleGuard<S> {
+    CycleGuard::new(stream)
+}
+
+/**
+A [`Stream`] that fails if a value is nested within itself.
+
+Values that share their contents through `Rc<RefCell<T>>` or similar types can form
+cycles, which would otherwise recurse until the stack overflows. The guard keeps the
+address and type of each nested value that's currently being streamed through
+[`Stream::value`] or [`Stream::value_computed`], and fails if one of those values is
+nested within itself again.
+
+Only values that are still being streamed are tracked, so the same shared value can
+appear more than once, as long as it's not within itself. Cycles are detected when the
+values in them are streamed as nested values, like the contents of a `RefCell`.
+All data is forwarded to the wrapped stream unchanged until a cycle is found.
+
+Values are identified by their address along with their type name, because a value and
+its first field can share the same address. Type names aren't guaranteed to be unique,
+so a value nested at the start of a different type with the same name will be reported
+as a cycle. Each nested value is checked against all of the values containing it, so the
+cost of streaming through the guard grows with the depth of nesting.
+
+This type requires the `alloc` feature.
+*/
+#[derive(Debug)]
+pub struct CycleGuard<S> {
+    stream: S,
+    visiting: Vec<(usize, &'static str)>,
+}
+
+impl<S> CycleGuard<S> {
+    /**
+    Wrap a stream, failing if a value is nested within itself.
+    */
+    pub fn new(stream: S) -> Self {
+        CycleGuard {
+            stream,
+            visiting: Vec::new(),
+        }
+    }
+
+    /**
+    Get the wrapped stream.
+    */
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+
+    fn visit_begin<V: Value + ?Sized>(&mut self, v: &V) -> Result {
+        // The type is tracked along with the address because a value and
+        // its first field can share the same address
+        let visit = (v as *const V as *const () as usize, type_name::<V>());
+
+        if self.visiting.contains(&visit) {
+            return Err(Error::new());
+        }
+
+        self.visiting.push(visit);
+
+        Ok(())
+    }
+
+    fn visit_end(&mut self) {
+        self.visiting.pop();
+    }
+}
+
+impl<'sval, S: Stream<'sval>> Stream<'sval> for CycleGuard<S> {
+    forward_stream!(stream =>
+        null, bool, text_begin, text_fragment, text_fragment_computed, text_end, binary_begin,
+        binary_fragment, binary_fragment_computed, binary_end, u8, u16, u32, u64, u128, i8, i16,
+        i32, i64, i128, f32, f64, num, map_begin, map_key_begin, map_key_end, map_value_begin,
+        map_value_end, map_end, seq_begin, seq_value_begin, seq_value_end, seq_end, enum_begin,
+        enum_end, tagged_begin, tagged_end, tag, tag_hint, record_begin, record_value_begin,
+        record_value_end, record_end, tuple_begin, tuple_value_begin, tuple_value_end, tuple_end,
+        record_tuple_begin, record_tuple_value_begin, record_tuple_value_end, record_tuple_end,
+    );
+
+    fn value<V: Value + ?Sized>(&mut self, v: &'sval V) -> Result {
+        self.visit_begin(v)?;
+        let r = default_stream::value(self, v);
+        self.visit_end();
+
+        r
+    }
+
+    fn value_computed<V: Value + ?Sized>(&mut self, v: &V) -> Result {
+        self.visit_begin(v)?;
+        let r = default_stream::value_computed(self, v);
+        self.visit_end();
+
+        r
+    }
+}
@@ -0,0 +1,674 @@
+use crate::{
+    std::{
+        mem,
+        string::{String, ToString},
+        vec::Vec,
+    },
+    tags, BinarySlice, Error, Index, Label, Result, Stream, StreamExt, Tag, Value,
+};
+
+/**
+Compute a [`Patch`] that transforms `old` into `new`.
+
+Both values are buffered and then walked in parallel, producing a list of operations in the
+style of JSON Patch (RFC 6902). Values are compared the way `sval_json` writes them, so records
+and maps are both objects, tuples are arrays, and enum variants are objects with a single
+property for the variant, or strings for unit variants.
+
+Maps with keys that can't be converted into strings can't be diffed and will fail.
+
+This function requires the `alloc` feature.
+
+```
+# fn main() -> sval::Result {
+let old = [("name", "sval"), ("version", "1")];
+let new = [("name", "sval"), ("version", "2")];
+
+let patch = sval::diff(&old, &new)?;
+
+let op = patch.iter().next().unwrap();
+
+assert_eq!(1, patch.len());
+assert_eq!(sval::PatchOp::Replace, op.op());
+assert_eq!("/1/1", op.path());
+# Ok(())
+# }
+```
+*/
+pub fn diff(old: &(impl Value + ?Sized), new: &(impl Value + ?Sized)) -> Result<Patch> {
+    let old = Node::collect(old)?;
+    let new = Node::collect(new)?;
+
+    let mut patch = Patch { ops: Vec::new() };
+    patch.diff(&mut String::new(), &old, &new);
+
+    Ok(patch)
+}
+
+/**
+A list of operations that transform one value into another, produced by [`diff`].
+
+`Patch` implements [`Value`] by streaming itself as a JSON Patch document, so it can be
+written directly by `sval_json`.
+
+This type requires the `alloc` feature.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct Patch {
+    ops: Vec<PatchOperation>,
+}
+
+/**
+A single operation in a [`Patch`].
+
+This type requires the `alloc` feature.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatchOperation {
+    op: PatchOp,
+    path: String,
+    value: Option<Node>,
+}
+
+/**
+The kind of change made by a [`PatchOperation`].
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PatchOp {
+    /**
+    A value was added to an object or array.
+    */
+    Add,
+    /**
+    A value was removed from an object or array.
+    */
+    Remove,
+    /**
+    A value was replaced with a different one.
+    */
+    Replace,
+}
+
+impl PatchOp {
+    /**
+    Get the name of this operation in JSON Patch.
+    */
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            PatchOp::Add => "add",
+            PatchOp::Remove => "remove",
+            PatchOp::Replace => "replace",
+        }
+    }
+}
+
+impl Patch {
+    /**
+    Whether the two values were the same, so there are no operations.
+    */
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+
+    /**
+    Get the number of operations.
+    */
+    pub fn len(&self) -> usize {
+        self.ops.len()
+    }
+
+    /**
+    Iterate over the operations in the order they should be applied.
+    */
+    pub fn iter(&self) -> impl Iterator<Item = &PatchOperation> + '_ {
+        self.ops.iter()
+    }
+
+    fn push(&mut self, op: PatchOp, path: &str, value: Option<&Node>) {
+        self.ops.push(PatchOperation {
+            op,
+            path: String::from(path),
+            value: value.cloned(),
+        });
+    }
+
+    fn diff(&mut self, path: &mut String, old: &Node, new: &Node) {
+        match (old, new) {
+            (Node::Object(old), Node::Object(new)) => {
+                for (key, old) in old {
+                    let len = path_push(path, key);
+
+                    match new.iter().find(|(k, _)| k == key) {
+                        Some((_, new)) => self.diff(path, old, new),
+                        None => self.push(PatchOp::Remove, path, None),
+                    }
+
+                    path.truncate(len);
+                }
+
+                for (key, new) in new {
+                    if !old.iter().any(|(k, _)| k == key) {
+                        let len = path_push(path, key);
+                        self.push(PatchOp::Add, path, Some(new));
+                        path.truncate(len);
+                    }
+                }
+            }
+            (Node::Seq(old), Node::Seq(new)) => {
+                let common = old.len().min(new.len());
+
+                for (i, (old, new)) in old.iter().zip(new).enumerate() {
+                    let len = path_push(path, &i.to_string());
+                    self.diff(path, old, new);
+                    path.truncate(len);
+                }
+
+                // Elements are removed from the end so earlier indexes stay valid
+                for i in (common..old.len()).rev() {
+                    let len = path_push(path, &i.to_string());
+                    self.push(PatchOp::Remove, path, None);
+                    path.truncate(len);
+                }
+
+                for (i, new) in new.iter().enumerate().skip(common) {
+                    let len = path_push(path, &i.to_string());
+                    self.push(PatchOp::Add, path, Some(new));
+                    path.truncate(len);
+                }
+            }
+            (old, new) if old == new => (),
+            (_, new) => self.push(PatchOp::Replace, path, Some(new)),
+        }
+    }
+}
+
+impl PatchOperation {
+    /**
+    Get the kind of change made by this operation.
+    */
+    pub fn op(&self) -> PatchOp {
+        self.op
+    }
+
+    /**
+    Get the JSON Pointer (RFC 6901) to the value changed by this operation.
+
+    The root value is the empty path.
+    */
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+}
+
+/**
+Append a segment to a JSON Pointer, returning the length of the path before it.
+*/
+fn path_push(path: &mut String, segment: &str) -> usize {
+    let len = path.len();
+
+    path.push('/');
+    for c in segment.chars() {
+        match c {
+            '~' => path.push_str("~0"),
+            '/' => path.push_str("~1"),
+            c => path.push(c),
+        }
+    }
+
+    len
+}
+
+impl Value for Patch {
+    fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> Result {
+        stream.seq_begin(Some(self.ops.len()))?;
+
+        for op in &self.ops {
+            stream.seq_value(op)?;
+        }
+
+        stream.seq_end()
+    }
+}
+
+impl Value for PatchOperation {
+    fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> Result {
+        stream.map_begin(Some(if self.value.is_some() { 3 } else { 2 }))?;
+
+        stream.map_entry("op", self.op.as_str())?;
+        stream.map_entry("path", &*self.path)?;
+
+        if let Some(ref value) = self.value {
+            stream.map_entry("value", value)?;
+        }
+
+        stream.map_end()
+    }
+}
+
+/**
+A buffered value in the JSON data model.
+*/
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Null,
+    Bool(bool),
+    Int(i128),
+    UInt(u128),
+    Float(f64),
+    Number(String),
+    Text(String),
+    Binary(Vec<u8>),
+    Seq(Vec<Node>),
+    Object(Vec<(String, Node)>),
+}
+
+impl Node {
+    fn collect(v: &(impl Value + ?Sized)) -> Result<Node> {
+        let mut collect = Collect::default();
+        crate::stream_computed(&mut collect, v)?;
+
+        collect.root.ok_or_else(Error::new)
+    }
+
+    fn into_key(self) -> Result<String> {
+        match self {
+            Node::Text(key) | Node::Number(key) => Ok(key),
+            Node::Bool(key) => Ok(key.to_string()),
+            Node::Int(key) => Ok(key.to_string()),
+            Node::UInt(key) => Ok(key.to_string()),
+            _ => Err(Error::unsupported()),
+        }
+    }
+}
+
+impl Value for Node {
+    fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> Result {
+        match self {
+            Node::Null => stream.null(),
+            Node::Bool(v) => stream.bool(*v),
+            Node::Int(v) => stream.i128(*v),
+            Node::UInt(v) => stream.u128(*v),
+            Node::Float(v) => stream.f64(*v),
+            Node::Number(v) => crate::stream_number(stream, v),
+            Node::Text(v) => stream.value(&**v),
+            Node::Binary(v) => stream.value(BinarySlice::new(v)),
+            Node::Seq(v) => {
+                stream.seq_begin(Some(v.len()))?;
+
+                for v in v {
+                    stream.seq_value(v)?;
+                }
+
+                stream.seq_end()
+            }
+            Node::Object(v) => {
+                stream.map_begin(Some(v.len()))?;
+
+                for (k, v) in v {
+                    stream.map_entry(&**k, v)?;
+                }
+
+                stream.map_end()
+            }
+        }
+    }
+}
+
+/**
+A [`Stream`] that buffers a value into a [`Node`].
+*/
+#[derive(Debug, Default)]
+struct Collect {
+    root: Option<Node>,
+    frames: Vec<Frame>,
+    text: Option<String>,
+    binary: Option<Vec<u8>>,
+    is_variant: bool,
+    is_number: bool,
+}
+
+#[derive(Debug)]
+enum Frame {
+    Seq(Vec<Node>),
+    Object {
+        entries: Vec<(String, Node)>,
+        key: Option<String>,
+    },
+    Key,
+    Variant(String),
+}
+
+impl Collect {
+    fn push(&mut self, mut node: Node) -> Result {
+        self.is_variant = false;
+
+        loop {
+            match self.frames.last_mut() {
+                None => {
+                    self.root = Some(node);
+                    return Ok(());
+                }
+                Some(Frame::Seq(values)) => {
+                    values.push(node);
+                    return Ok(());
+                }
+                Some(Frame::Object { entries, key }) => {
+                    let key = key.take().ok_or_else(Error::new)?;
+                    entries.push((key, node));
+                    return Ok(());
+                }
+                Some(Frame::Key) => {
+                    self.frames.pop();
+
+                    match self.frames.last_mut() {
+                        Some(Frame::Object { key, .. }) => {
+                            *key = Some(node.into_key()?);
+                            return Ok(());
+                        }
+                        _ => return Err(Error::new()),
+                    }
+                }
+                Some(Frame::Variant(_)) => {
+                    // A variant is an object with a single property
+                    if let Some(Frame::Variant(label)) = self.frames.pop() {
+                        node = Node::Object(Vec::from([(label, node)]));
+                    }
+                }
+            }
+        }
+    }
+
+    fn variant_begin(&mut self, label: Option<&Label>) {
+        if let Some(label) = label {
+            if mem::take(&mut self.is_variant) {
+                self.frames
+                    .push(Frame::Variant(String::from(label.as_str())));
+            }
+        }
+    }
+
+    fn seq_begin(&mut self, label: Option<&Label>) -> Result {
+        self.variant_begin(label);
+        self.is_variant = false;
+        self.frames.push(Frame::Seq(Vec::new()));
+
+        Ok(())
+    }
+
+    fn seq_end(&mut self) -> Result {
+        match self.frames.pop() {
+            Some(Frame::Seq(values)) => self.push(Node::Seq(values)),
+            _ => Err(Error::new()),
+        }
+    }
+
+    fn object_begin(&mut self, label: Option<&Label>) -> Result {
+        self.variant_begin(label);
+        self.is_variant = false;
+        self.frames.push(Frame::Object {
+            entries: Vec::new(),
+            key: None,
+        });
+
+        Ok(())
+    }
+
+    fn object_key(&mut self, label: &Label) -> Result {
+        match self.frames.last_mut() {
+            Some(Frame::Object { key, .. }) => {
+                *key = Some(String::from(label.as_str()));
+                Ok(())
+            }
+            _ => Err(Error::new()),
+        }
+    }
+
+    fn object_end(&mut self) -> Result {
+        match self.frames.pop() {
+            Some(Frame::Object { entries, .. }) => self.push(Node::Object(entries)),
+            _ => Err(Error::new()),
+        }
+    }
+}
+
+impl<'sval> Stream<'sval> for Collect {
+    fn null(&mut self) -> Result {
+        self.push(Node::Null)
+    }
+
+    fn bool(&mut self, value: bool) -> Result {
+        self.push(Node::Bool(value))
+    }
+
+    fn text_begin(&mut self, num_bytes: Option<usize>) -> Result {
+        self.text = Some(String::with_capacity(num_bytes.unwrap_or(0)));
+
+        Ok(())
+    }
+
+    fn text_fragment_computed(&mut self, fragment: &str) -> Result {
+        self.text
+            .as_mut()
+            .ok_or_else(Error::new)?
+            .push_str(fragment);
+
+        Ok(())
+    }
+
+    fn text_end(&mut self) -> Result {
+        let text = self.text.take().ok_or_else(Error::new)?;
+
+        if self.is_number {
+            self.push(Node::Number(text))
+        } else {
+            self.push(Node::Text(text))
+        }
+    }
+
+    fn binary_begin(&mut self, num_bytes: Option<usize>) -> Result {
+        self.binary = Some(Vec::with_capacity(num_bytes.unwrap_or(0)));
+
+        Ok(())
+    }
+
+    fn binary_fragment_computed(&mut self, fragment: &[u8]) -> Result {
+        self.binary
+            .as_mut()
+            .ok_or_else(Error::new)?
+            .extend_from_slice(fragment);
+
+        Ok(())
+    }
+
+    fn binary_end(&mut self) -> Result {
+        let binary = self.binary.take().ok_or_else(Error::new)?;
+
+        self.push(Node::Binary(binary))
+    }
+
+    fn i64(&mut self, value: i64) -> Result {
+        self.push(Node::Int(value as i128))
+    }
+
+    fn u64(&mut self, value: u64) -> Result {
+        self.push(Node::Int(value as i128))
+    }
+
+    fn i128(&mut self, value: i128) -> Result {
+        self.push(Node::Int(value))
+    }
+
+    fn u128(&mut self, value: u128) -> Result {
+        match i128::try_from(value) {
+            Ok(value) => self.push(Node::Int(value)),
+            Err(_) => self.push(Node::UInt(value)),
+        }
+    }
+
+    fn f64(&mut self, value: f64) -> Result {
+        self.push(Node::Float(value))
+    }
+
+    fn map_begin(&mut self, _: Option<usize>) -> Result {
+        self.object_begin(None)
+    }
+
+    fn map_key_begin(&mut self) -> Result {
+        self.frames.push(Frame::Key);
+
+        Ok(())
+    }
+
+    fn map_key_end(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn map_value_begin(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn map_value_end(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn map_end(&mut self) -> Result {
+        self.object_end()
+    }
+
+    fn seq_begin(&mut self, _: Option<usize>) -> Result {
+        self.seq_begin(None)
+    }
+
+    fn seq_value_begin(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn seq_value_end(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn seq_end(&mut self) -> Result {
+        self.seq_end()
+    }
+
+    fn enum_begin(&mut self, _: Option<&Tag>, _: Option<&Label>, _: Option<&Index>) -> Result {
+        self.is_variant = true;
+
+        Ok(())
+    }
+
+    fn enum_end(&mut self, _: Option<&Tag>, _: Option<&Label>, _: Option<&Index>) -> Result {
+        self.is_variant = false;
+
+        Ok(())
+    }
+
+    fn tagged_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        _: Option<&Index>,
+    ) -> Result {
+        self.variant_begin(label);
+
+        if let Some(&tags::NUMBER) = tag {
+            self.is_number = true;
+        }
+
+        Ok(())
+    }
+
+    fn tagged_end(&mut self, tag: Option<&Tag>, _: Option<&Label>, _: Option<&Index>) -> Result {
+        if let Some(&tags::NUMBER) = tag {
+            self.is_number = false;
+        }
+
+        Ok(())
+    }
+
+    fn tag(&mut self, tag: Option<&Tag>, label: Option<&Label>, index: Option<&Index>) -> Result {
+        match tag {
+            Some(&tags::RUST_OPTION_NONE) | Some(&tags::RUST_UNIT) => self.push(Node::Null),
+            _ => {
+                if let Some(label) = label {
+                    self.push(Node::Text(String::from(label.as_str())))
+                } else if let Some(index) = index.and_then(|index| index.to_i64()) {
+                    self.push(Node::Int(index as i128))
+                } else {
+                    self.push(Node::Null)
+                }
+            }
+        }
+    }
+
+    fn record_begin(
+        &mut self,
+        _: Option<&Tag>,
+        label: Option<&Label>,
+        _: Option<&Index>,
+        _: Option<usize>,
+    ) -> Result {
+        self.object_begin(label)
+    }
+
+    fn record_value_begin(&mut self, _: Option<&Tag>, label: &Label) -> Result {
+        self.object_key(label)
+    }
+
+    fn record_value_end(&mut self, _: Option<&Tag>, _: &Label) -> Result {
+        Ok(())
+    }
+
+    fn record_end(&mut self, _: Option<&Tag>, _: Option<&Label>, _: Option<&Index>) -> Result {
+        self.object_end()
+    }
+
+    fn tuple_begin(
+        &mut self,
+        _: Option<&Tag>,
+        label: Option<&Label>,
+        _: Option<&Index>,
+        _: Option<usize>,
+    ) -> Result {
+        self.seq_begin(label)
+    }
+
+    fn tuple_value_begin(&mut self, _: Option<&Tag>, _: &Index) -> Result {
+        Ok(())
+    }
+
+    fn tuple_value_end(&mut self, _: Option<&Tag>, _: &Index) -> Result {
+        Ok(())
+    }
+
+    fn tuple_end(&mut self, _: Option<&Tag>, _: Option<&Label>, _: Option<&Index>) -> Result {
+        self.seq_end()
+    }
+
+    fn record_tuple_begin(
+        &mut self,
+        _: Option<&Tag>,
+        label: Option<&Label>,
+        _: Option<&Index>,
+        _: Option<usize>,
+    ) -> Result {
+        self.object_begin(label)
+    }
+
+    fn record_tuple_value_begin(&mut self, _: Option<&Tag>, label: &Label, _: &Index) -> Result {
+        self.object_key(label)
+    }
+
+    fn record_tuple_value_end(&mut self, _: Option<&Tag>, _: &Label, _: &Index) -> Result {
+        Ok(())
+    }
+
+    fn record_tuple_end(
+        &mut self,
+        _: Option<&Tag>,
+        _: Option<&Label>,
+        _: Option<&Index>,
+    ) -> Result {
+        self.object_end()
+    }
+}
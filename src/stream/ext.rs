@@ -0,0 +1,109 @@
+use crate::{Result, Stream, Value};
+
+/**
+Convenience methods for streaming the values of sequences and maps.
+
+Each method bundles the `begin`, value, and `end` calls for a single element
+so they can't get out of balance. This trait is implemented for all streams.
+
+```
+use sval::{Stream, StreamExt, Value};
+
+struct Scores<'a> {
+    names: &'a [&'a str],
+    points: &'a [u32],
+}
+
+impl<'a> Value for Scores<'a> {
+    fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> sval::Result {
+        stream.map_begin(Some(self.names.len()))?;
+
+        for (name, points) in self.names.iter().zip(self.points) {
+            // The name and points are borrowed from `self`
+            stream.map_entry(name, points)?;
+        }
+
+        stream.map_end()
+    }
+}
+
+struct Doubled<'a>(&'a [u32]);
+
+impl<'a> Value for Doubled<'a> {
+    fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> sval::Result {
+        stream.seq_begin(Some(self.0.len()))?;
+
+        for n in self.0 {
+            // The doubled value is a temporary, so it needs to be streamed as computed
+            stream.seq_value_computed(&(n * 2))?;
+        }
+
+        stream.seq_end()
+    }
+}
+```
+*/
+pub trait StreamExt<'sval>: Stream<'sval> {
+    /**
+    Stream a borrowed value as an element of a sequence.
+
+    This is the same as calling [`Stream::seq_value_begin`], [`Stream::value`],
+    and [`Stream::seq_value_end`].
+    */
+    fn seq_value<V: Value + ?Sized>(&mut self, v: &'sval V) -> Result {
+        self.seq_value_begin()?;
+        self.value(v)?;
+        self.seq_value_end()
+    }
+
+    /**
+    Stream a value borrowed for some arbitrarily short lifetime as an element of a sequence.
+
+    This is the same as calling [`Stream::seq_value_begin`], [`Stream::value_computed`],
+    and [`Stream::seq_value_end`].
+    */
+    fn seq_value_computed<V: Value + ?Sized>(&mut self, v: &V) -> Result {
+        self.seq_value_begin()?;
+        self.value_computed(v)?;
+        self.seq_value_end()
+    }
+
+    /**
+    Stream a borrowed key and value as an entry of a map.
+
+    This is the same as streaming the key between [`Stream::map_key_begin`] and
+    [`Stream::map_key_end`], and the value between [`Stream::map_value_begin`] and
+    [`Stream::map_value_end`].
+    */
+    fn map_entry<K: Value + ?Sized, V: Value + ?Sized>(
+        &mut self,
+        k: &'sval K,
+        v: &'sval V,
+    ) -> Result {
+        self.map_key_begin()?;
+        self.value(k)?;
+        self.map_key_end()?;
+
+        self.map_value_begin()?;
+        self.value(v)?;
+        self.map_value_end()
+    }
+
+    /**
+    Stream a key and value borrowed for some arbitrarily short lifetime as an entry of a map.
+
+    This is the same as [`StreamExt::map_entry`], but streams the key and value using
+    [`Stream::value_computed`].
+    */
+    fn map_entry_computed<K: Value + ?Sized, V: Value + ?Sized>(&mut self, k: &K, v: &V) -> Result {
+        self.map_key_begin()?;
+        self.value_computed(k)?;
+        self.map_key_end()?;
+
+        self.map_value_begin()?;
+        self.value_computed(v)?;
+        self.map_value_end()
+    }
+}
+
+impl<'sval, S: Stream<'sval> + ?Sized> StreamExt<'sval> for S {}
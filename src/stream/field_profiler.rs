@@ -0,0 +1,427 @@
+use crate::{
+    std::{
+        collections::{BTreeMap, BTreeSet},
+        string::String,
+    },
+    tags, Index, Label, Result, Stream, Tag,
+};
+
+/**
+Statistics collected for a record field by [`FieldProfiler`].
+
+This type requires the `alloc` feature.
+*/
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FieldStats {
+    count: usize,
+    null_count: usize,
+    min: Option<f64>,
+    max: Option<f64>,
+    distinct: BTreeSet<String>,
+    is_distinct_capped: bool,
+}
+
+impl FieldStats {
+    /**
+    The number of records the field appeared on.
+    */
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /**
+    The number of records where the field was null or `None`.
+    */
+    pub fn null_count(&self) -> usize {
+        self.null_count
+    }
+
+    /**
+    The smallest number seen in the field.
+
+    Integers are converted to `f64` to compare, so very large ones may lose precision.
+    */
+    pub fn min(&self) -> Option<f64> {
+        self.min
+    }
+
+    /**
+    The largest number seen in the field.
+
+    Integers are converted to `f64` to compare, so very large ones may lose precision.
+    */
+    pub fn max(&self) -> Option<f64> {
+        self.max
+    }
+
+    /**
+    The number of distinct text values seen in the field.
+
+    This count stops at the maximum configured by [`FieldProfiler::with_max_distinct`].
+    */
+    pub fn distinct_count(&self) -> usize {
+        self.distinct.len()
+    }
+
+    /**
+    Whether more distinct text values were seen than could be counted.
+    */
+    pub fn is_distinct_capped(&self) -> bool {
+        self.is_distinct_capped
+    }
+
+    fn number(&mut self, v: f64) {
+        if v.is_nan() {
+            return;
+        }
+
+        self.min = Some(self.min.map_or(v, |min| min.min(v)));
+        self.max = Some(self.max.map_or(v, |max| max.max(v)));
+    }
+
+    fn text(&mut self, v: String, max_distinct: usize) {
+        if self.distinct.contains(&v) {
+            return;
+        }
+
+        if self.distinct.len() < max_distinct {
+            self.distinct.insert(v);
+        } else {
+            self.is_distinct_capped = true;
+        }
+    }
+}
+
+/**
+A [`Stream`] that collects [`FieldStats`] for the fields of records.
+
+Any number of records can be streamed, either one at a time or within sequences. The fields
+of each outermost record are profiled by counting them, counting null values, tracking the
+range of numbers, and counting distinct text values. Values nested within fields, like
+sequences or other records, only contribute to the count of their field.
+
+This type requires the `alloc` feature.
+*/
+#[derive(Debug)]
+pub struct FieldProfiler {
+    fields: BTreeMap<String, FieldStats>,
+    max_distinct: usize,
+    field: Option<String>,
+    depth: usize,
+    text: Option<String>,
+    is_number: bool,
+}
+
+impl Default for FieldProfiler {
+    fn default() -> Self {
+        FieldProfiler::new()
+    }
+}
+
+impl FieldProfiler {
+    /**
+    Create a profiler that hasn't seen any records.
+
+    Up to 1024 distinct text values are counted for each field.
+    */
+    pub fn new() -> Self {
+        FieldProfiler {
+            fields: BTreeMap::new(),
+            max_distinct: 1024,
+            field: None,
+            depth: 0,
+            text: None,
+            is_number: false,
+        }
+    }
+
+    /**
+    Count up to `max_distinct` distinct text values for each field.
+    */
+    pub fn with_max_distinct(mut self, max_distinct: usize) -> Self {
+        self.max_distinct = max_distinct;
+        self
+    }
+
+    /**
+    Get the statistics collected so far.
+    */
+    pub fn fields(&self) -> &BTreeMap<String, FieldStats> {
+        &self.fields
+    }
+
+    /**
+    Get the statistics collected for each field.
+    */
+    pub fn into_fields(self) -> BTreeMap<String, FieldStats> {
+        self.fields
+    }
+
+    fn stats(&mut self) -> Option<&mut FieldStats> {
+        if self.depth > 0 {
+            return None;
+        }
+
+        let field = self.field.as_ref()?;
+
+        self.fields.get_mut(field)
+    }
+
+    fn null_value(&mut self) -> Result {
+        if let Some(stats) = self.stats() {
+            stats.null_count += 1;
+        }
+
+        Ok(())
+    }
+
+    fn number_value(&mut self, v: f64) -> Result {
+        if let Some(stats) = self.stats() {
+            stats.number(v);
+        }
+
+        Ok(())
+    }
+
+    fn container_begin(&mut self) -> Result {
+        if self.field.is_some() {
+            self.depth += 1;
+        }
+
+        Ok(())
+    }
+
+    fn container_end(&mut self) -> Result {
+        if self.field.is_some() {
+            self.depth -= 1;
+        }
+
+        Ok(())
+    }
+
+    fn field_begin(&mut self, label: &Label) -> Result {
+        if self.field.is_none() {
+            let field = String::from(label.as_str());
+
+            self.fields.entry(field.clone()).or_default().count += 1;
+            self.field = Some(field);
+        }
+
+        Ok(())
+    }
+
+    fn field_end(&mut self) -> Result {
+        if self.depth == 0 {
+            self.field = None;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'sval> Stream<'sval> for FieldProfiler {
+    fn null(&mut self) -> Result {
+        self.null_value()
+    }
+
+    fn bool(&mut self, _: bool) -> Result {
+        Ok(())
+    }
+
+    fn text_begin(&mut self, num_bytes: Option<usize>) -> Result {
+        if self.stats().is_some() {
+            self.text = Some(String::with_capacity(num_bytes.unwrap_or(0)));
+        }
+
+        Ok(())
+    }
+
+    fn text_fragment_computed(&mut self, fragment: &str) -> Result {
+        if let Some(ref mut text) = self.text {
+            text.push_str(fragment);
+        }
+
+        Ok(())
+    }
+
+    fn text_end(&mut self) -> Result {
+        if let Some(text) = self.text.take() {
+            if self.is_number {
+                if let Ok(v) = text.parse() {
+                    self.number_value(v)?;
+                }
+            } else {
+                let max_distinct = self.max_distinct;
+
+                if let Some(stats) = self.stats() {
+                    stats.text(text, max_distinct);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn i64(&mut self, v: i64) -> Result {
+        self.number_value(v as f64)
+    }
+
+    fn u64(&mut self, v: u64) -> Result {
+        self.number_value(v as f64)
+    }
+
+    fn i128(&mut self, v: i128) -> Result {
+        self.number_value(v as f64)
+    }
+
+    fn u128(&mut self, v: u128) -> Result {
+        self.number_value(v as f64)
+    }
+
+    fn f64(&mut self, v: f64) -> Result {
+        self.number_value(v)
+    }
+
+    fn map_begin(&mut self, _: Option<usize>) -> Result {
+        self.container_begin()
+    }
+
+    fn map_key_begin(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn map_key_end(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn map_value_begin(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn map_value_end(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn map_end(&mut self) -> Result {
+        self.container_end()
+    }
+
+    fn seq_begin(&mut self, _: Option<usize>) -> Result {
+        self.container_begin()
+    }
+
+    fn seq_value_begin(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn seq_value_end(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn seq_end(&mut self) -> Result {
+        self.container_end()
+    }
+
+    fn enum_begin(&mut self, _: Option<&Tag>, _: Option<&Label>, _: Option<&Index>) -> Result {
+        Ok(())
+    }
+
+    fn enum_end(&mut self, _: Option<&Tag>, _: Option<&Label>, _: Option<&Index>) -> Result {
+        Ok(())
+    }
+
+    fn tagged_begin(&mut self, tag: Option<&Tag>, _: Option<&Label>, _: Option<&Index>) -> Result {
+        if let Some(&tags::NUMBER) = tag {
+            self.is_number = true;
+        }
+
+        Ok(())
+    }
+
+    fn tagged_end(&mut self, tag: Option<&Tag>, _: Option<&Label>, _: Option<&Index>) -> Result {
+        if let Some(&tags::NUMBER) = tag {
+            self.is_number = false;
+        }
+
+        Ok(())
+    }
+
+    fn tag(&mut self, tag: Option<&Tag>, _: Option<&Label>, _: Option<&Index>) -> Result {
+        if let Some(&tags::RUST_OPTION_NONE) = tag {
+            self.null_value()?;
+        }
+
+        Ok(())
+    }
+
+    fn record_begin(
+        &mut self,
+        _: Option<&Tag>,
+        _: Option<&Label>,
+        _: Option<&Index>,
+        _: Option<usize>,
+    ) -> Result {
+        self.container_begin()
+    }
+
+    fn record_value_begin(&mut self, _: Option<&Tag>, label: &Label) -> Result {
+        self.field_begin(label)
+    }
+
+    fn record_value_end(&mut self, _: Option<&Tag>, _: &Label) -> Result {
+        self.field_end()
+    }
+
+    fn record_end(&mut self, _: Option<&Tag>, _: Option<&Label>, _: Option<&Index>) -> Result {
+        self.container_end()
+    }
+
+    fn tuple_begin(
+        &mut self,
+        _: Option<&Tag>,
+        _: Option<&Label>,
+        _: Option<&Index>,
+        _: Option<usize>,
+    ) -> Result {
+        self.container_begin()
+    }
+
+    fn tuple_value_begin(&mut self, _: Option<&Tag>, _: &Index) -> Result {
+        Ok(())
+    }
+
+    fn tuple_value_end(&mut self, _: Option<&Tag>, _: &Index) -> Result {
+        Ok(())
+    }
+
+    fn tuple_end(&mut self, _: Option<&Tag>, _: Option<&Label>, _: Option<&Index>) -> Result {
+        self.container_end()
+    }
+
+    fn record_tuple_begin(
+        &mut self,
+        _: Option<&Tag>,
+        _: Option<&Label>,
+        _: Option<&Index>,
+        _: Option<usize>,
+    ) -> Result {
+        self.container_begin()
+    }
+
+    fn record_tuple_value_begin(&mut self, _: Option<&Tag>, label: &Label, _: &Index) -> Result {
+        self.field_begin(label)
+    }
+
+    fn record_tuple_value_end(&mut self, _: Option<&Tag>, _: &Label, _: &Index) -> Result {
+        self.field_end()
+    }
+
+    fn record_tuple_end(
+        &mut self,
+        _: Option<&Tag>,
+        _: Option<&Label>,
+        _: Option<&Index>,
+    ) -> Result {
+        self.container_end()
+    }
+}
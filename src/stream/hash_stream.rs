@@ -0,0 +1,549 @@
+use crate::{std::hash::Hasher, Index, Label, Result, Stream, Tag};
+
+/**
+Wrap a hasher in a [`HashStream`] that hashes the data streamed through it.
+*/
+pub fn hash_stream<H: Hasher>(hasher: H) -> HashStream<H> {
+    HashStream::new(hasher)
+}
+
+/**
+A [`Stream`] that feeds the data streamed through it into a [`Hasher`].
+
+This is useful for detecting changes to a value's content without serializing it
+into some format first. Each call is hashed as a byte identifying the method, followed
+by its arguments. Labels are hashed as their text, and tags and indexes by their value.
+
+The hash is deterministic: the same calls always produce the same hash. Text and binary
+are hashed as their bytes, regardless of how they're split into fragments or whether those
+fragments are borrowed or computed. Integers and floating point numbers are hashed in
+little-endian byte order, so the hash doesn't depend on the platform, as long as the
+hasher itself doesn't either. Hints, like the number of entries in a map or the
+[`Stream::tag_hint`] method, don't affect the hash.
+
+The hash doesn't normalize values, so the same number streamed as a [`Stream::u8`] and
+as a [`Stream::i64`] will produce different hashes.
+*/
+#[derive(Debug)]
+pub struct HashStream<H> {
+    hasher: H,
+    buf: [u8; CHUNK_LEN],
+    buf_len: usize,
+    fragments_len: u64,
+}
+
+/**
+The number of bytes of text or binary to buffer before writing them to the hasher.
+
+Fragments are always written in chunks of this size so their boundaries don't affect the hash.
+*/
+const CHUNK_LEN: usize = 32;
+
+#[repr(u8)]
+enum Kind {
+    Null,
+    Bool,
+    TextBegin,
+    TextEnd,
+    BinaryBegin,
+    BinaryEnd,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    F32,
+    F64,
+    MapBegin,
+    MapKeyBegin,
+    MapKeyEnd,
+    MapValueBegin,
+    MapValueEnd,
+    MapEnd,
+    SeqBegin,
+    SeqValueBegin,
+    SeqValueEnd,
+    SeqEnd,
+    EnumBegin,
+    EnumEnd,
+    TaggedBegin,
+    TaggedEnd,
+    Tag,
+    RecordBegin,
+    RecordValueBegin,
+    RecordValueEnd,
+    RecordEnd,
+    TupleBegin,
+    TupleValueBegin,
+    TupleValueEnd,
+    TupleEnd,
+    RecordTupleBegin,
+    RecordTupleValueBegin,
+    RecordTupleValueEnd,
+    RecordTupleEnd,
+}
+
+impl<H: Hasher> HashStream<H> {
+    /**
+    Wrap a hasher, feeding the data streamed through it into the hasher.
+    */
+    pub fn new(hasher: H) -> Self {
+        HashStream {
+            hasher,
+            buf: [0; CHUNK_LEN],
+            buf_len: 0,
+            fragments_len: 0,
+        }
+    }
+
+    /**
+    Get the hash of the data streamed so far.
+    */
+    pub fn finish(&self) -> u64 {
+        self.hasher.finish()
+    }
+
+    /**
+    Get the wrapped hasher.
+    */
+    pub fn into_inner(self) -> H {
+        self.hasher
+    }
+
+    fn kind(&mut self, kind: Kind) -> Result {
+        self.hasher.write_u8(kind as u8);
+
+        Ok(())
+    }
+
+    fn fragment(&mut self, mut fragment: &[u8]) -> Result {
+        self.fragments_len += fragment.len() as u64;
+
+        while !fragment.is_empty() {
+            let len = (CHUNK_LEN - self.buf_len).min(fragment.len());
+
+            let (head, tail) = fragment.split_at(len);
+            self.buf[self.buf_len..self.buf_len + len].copy_from_slice(head);
+            self.buf_len += len;
+            fragment = tail;
+
+            if self.buf_len == CHUNK_LEN {
+                self.hasher.write(&self.buf);
+                self.buf_len = 0;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn fragments_end(&mut self, kind: Kind) -> Result {
+        self.hasher.write(&self.buf[..self.buf_len]);
+        self.buf_len = 0;
+
+        // Hashing the length keeps the end of the value from being confused with its content
+        let len = self.fragments_len;
+        self.fragments_len = 0;
+
+        self.hasher.write(&len.to_le_bytes());
+        self.kind(kind)
+    }
+
+    fn tag_label_index(
+        &mut self,
+        kind: Kind,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.kind(kind)?;
+
+        self.tag_arg(tag);
+
+        match label {
+            Some(label) => {
+                self.hasher.write_u8(1);
+                self.label_arg(label);
+            }
+            None => self.hasher.write_u8(0),
+        }
+
+        match index {
+            Some(index) => {
+                self.hasher.write_u8(1);
+                self.index_arg(index);
+            }
+            None => self.hasher.write_u8(0),
+        }
+
+        Ok(())
+    }
+
+    fn tag_arg(&mut self, tag: Option<&Tag>) {
+        match tag {
+            Some(tag) => {
+                self.hasher.write_u8(1);
+                self.hasher.write(&tag.id().to_le_bytes());
+            }
+            None => self.hasher.write_u8(0),
+        }
+    }
+
+    fn label_arg(&mut self, label: &Label) {
+        let label = label.as_str();
+
+        self.hasher.write(&(label.len() as u64).to_le_bytes());
+        self.hasher.write(label.as_bytes());
+    }
+
+    fn index_arg(&mut self, index: &Index) {
+        // Indexes are only ever constructed from values that fit in either an `i64` or a `u64`
+        let index = index
+            .to_i64()
+            .map(i128::from)
+            .or_else(|| index.to_u64().map(i128::from))
+            .unwrap_or_default();
+
+        self.hasher.write(&index.to_le_bytes());
+    }
+}
+
+impl<'sval, H: Hasher> Stream<'sval> for HashStream<H> {
+    fn null(&mut self) -> Result {
+        self.kind(Kind::Null)
+    }
+
+    fn bool(&mut self, value: bool) -> Result {
+        self.kind(Kind::Bool)?;
+        self.hasher.write_u8(value as u8);
+
+        Ok(())
+    }
+
+    fn text_begin(&mut self, _: Option<usize>) -> Result {
+        self.buf_len = 0;
+        self.fragments_len = 0;
+
+        self.kind(Kind::TextBegin)
+    }
+
+    fn text_fragment(&mut self, fragment: &'sval str) -> Result {
+        self.fragment(fragment.as_bytes())
+    }
+
+    fn text_fragment_computed(&mut self, fragment: &str) -> Result {
+        self.fragment(fragment.as_bytes())
+    }
+
+    fn text_end(&mut self) -> Result {
+        self.fragments_end(Kind::TextEnd)
+    }
+
+    fn binary_begin(&mut self, _: Option<usize>) -> Result {
+        self.buf_len = 0;
+        self.fragments_len = 0;
+
+        self.kind(Kind::BinaryBegin)
+    }
+
+    fn binary_fragment(&mut self, fragment: &'sval [u8]) -> Result {
+        self.fragment(fragment)
+    }
+
+    fn binary_fragment_computed(&mut self, fragment: &[u8]) -> Result {
+        self.fragment(fragment)
+    }
+
+    fn binary_end(&mut self) -> Result {
+        self.fragments_end(Kind::BinaryEnd)
+    }
+
+    fn u8(&mut self, value: u8) -> Result {
+        self.kind(Kind::U8)?;
+        self.hasher.write(&value.to_le_bytes());
+
+        Ok(())
+    }
+
+    fn u16(&mut self, value: u16) -> Result {
+        self.kind(Kind::U16)?;
+        self.hasher.write(&value.to_le_bytes());
+
+        Ok(())
+    }
+
+    fn u32(&mut self, value: u32) -> Result {
+        self.kind(Kind::U32)?;
+        self.hasher.write(&value.to_le_bytes());
+
+        Ok(())
+    }
+
+    fn u64(&mut self, value: u64) -> Result {
+        self.kind(Kind::U64)?;
+        self.hasher.write(&value.to_le_bytes());
+
+        Ok(())
+    }
+
+    fn u128(&mut self, value: u128) -> Result {
+        self.kind(Kind::U128)?;
+        self.hasher.write(&value.to_le_bytes());
+
+        Ok(())
+    }
+
+    fn i8(&mut self, value: i8) -> Result {
+        self.kind(Kind::I8)?;
+        self.hasher.write(&value.to_le_bytes());
+
+        Ok(())
+    }
+
+    fn i16(&mut self, value: i16) -> Result {
+        self.kind(Kind::I16)?;
+        self.hasher.write(&value.to_le_bytes());
+
+        Ok(())
+    }
+
+    fn i32(&mut self, value: i32) -> Result {
+        self.kind(Kind::I32)?;
+        self.hasher.write(&value.to_le_bytes());
+
+        Ok(())
+    }
+
+    fn i64(&mut self, value: i64) -> Result {
+        self.kind(Kind::I64)?;
+        self.hasher.write(&value.to_le_bytes());
+
+        Ok(())
+    }
+
+    fn i128(&mut self, value: i128) -> Result {
+        self.kind(Kind::I128)?;
+        self.hasher.write(&value.to_le_bytes());
+
+        Ok(())
+    }
+
+    fn f32(&mut self, value: f32) -> Result {
+        self.kind(Kind::F32)?;
+        self.hasher.write(&value.to_bits().to_le_bytes());
+
+        Ok(())
+    }
+
+    fn f64(&mut self, value: f64) -> Result {
+        self.kind(Kind::F64)?;
+        self.hasher.write(&value.to_bits().to_le_bytes());
+
+        Ok(())
+    }
+
+    fn map_begin(&mut self, _: Option<usize>) -> Result {
+        self.kind(Kind::MapBegin)
+    }
+
+    fn map_key_begin(&mut self) -> Result {
+        self.kind(Kind::MapKeyBegin)
+    }
+
+    fn map_key_end(&mut self) -> Result {
+        self.kind(Kind::MapKeyEnd)
+    }
+
+    fn map_value_begin(&mut self) -> Result {
+        self.kind(Kind::MapValueBegin)
+    }
+
+    fn map_value_end(&mut self) -> Result {
+        self.kind(Kind::MapValueEnd)
+    }
+
+    fn map_end(&mut self) -> Result {
+        self.kind(Kind::MapEnd)
+    }
+
+    fn seq_begin(&mut self, _: Option<usize>) -> Result {
+        self.kind(Kind::SeqBegin)
+    }
+
+    fn seq_value_begin(&mut self) -> Result {
+        self.kind(Kind::SeqValueBegin)
+    }
+
+    fn seq_value_end(&mut self) -> Result {
+        self.kind(Kind::SeqValueEnd)
+    }
+
+    fn seq_end(&mut self) -> Result {
+        self.kind(Kind::SeqEnd)
+    }
+
+    fn enum_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.tag_label_index(Kind::EnumBegin, tag, label, index)
+    }
+
+    fn enum_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.tag_label_index(Kind::EnumEnd, tag, label, index)
+    }
+
+    fn tagged_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.tag_label_index(Kind::TaggedBegin, tag, label, index)
+    }
+
+    fn tagged_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.tag_label_index(Kind::TaggedEnd, tag, label, index)
+    }
+
+    fn tag(&mut self, tag: Option<&Tag>, label: Option<&Label>, index: Option<&Index>) -> Result {
+        self.tag_label_index(Kind::Tag, tag, label, index)
+    }
+
+    fn tag_hint(&mut self, _: &Tag) -> Result {
+        Ok(())
+    }
+
+    fn record_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+        _: Option<usize>,
+    ) -> Result {
+        self.tag_label_index(Kind::RecordBegin, tag, label, index)
+    }
+
+    fn record_value_begin(&mut self, tag: Option<&Tag>, label: &Label) -> Result {
+        self.kind(Kind::RecordValueBegin)?;
+        self.tag_arg(tag);
+        self.label_arg(label);
+
+        Ok(())
+    }
+
+    fn record_value_end(&mut self, tag: Option<&Tag>, label: &Label) -> Result {
+        self.kind(Kind::RecordValueEnd)?;
+        self.tag_arg(tag);
+        self.label_arg(label);
+
+        Ok(())
+    }
+
+    fn record_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.tag_label_index(Kind::RecordEnd, tag, label, index)
+    }
+
+    fn tuple_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+        _: Option<usize>,
+    ) -> Result {
+        self.tag_label_index(Kind::TupleBegin, tag, label, index)
+    }
+
+    fn tuple_value_begin(&mut self, tag: Option<&Tag>, index: &Index) -> Result {
+        self.kind(Kind::TupleValueBegin)?;
+        self.tag_arg(tag);
+        self.index_arg(index);
+
+        Ok(())
+    }
+
+    fn tuple_value_end(&mut self, tag: Option<&Tag>, index: &Index) -> Result {
+        self.kind(Kind::TupleValueEnd)?;
+        self.tag_arg(tag);
+        self.index_arg(index);
+
+        Ok(())
+    }
+
+    fn tuple_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.tag_label_index(Kind::TupleEnd, tag, label, index)
+    }
+
+    fn record_tuple_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+        _: Option<usize>,
+    ) -> Result {
+        self.tag_label_index(Kind::RecordTupleBegin, tag, label, index)
+    }
+
+    fn record_tuple_value_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: &Label,
+        index: &Index,
+    ) -> Result {
+        self.kind(Kind::RecordTupleValueBegin)?;
+        self.tag_arg(tag);
+        self.label_arg(label);
+        self.index_arg(index);
+
+        Ok(())
+    }
+
+    fn record_tuple_value_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: &Label,
+        index: &Index,
+    ) -> Result {
+        self.kind(Kind::RecordTupleValueEnd)?;
+        self.tag_arg(tag);
+        self.label_arg(label);
+        self.index_arg(index);
+
+        Ok(())
+    }
+
+    fn record_tuple_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.tag_label_index(Kind::RecordTupleEnd, tag, label, index)
+    }
+}
@@ -0,0 +1,638 @@
+use crate::{
+    std::{
+        boxed::Box,
+        collections::{BTreeMap, BTreeSet},
+        mem,
+        string::String,
+        vec::Vec,
+    },
+    tags, Index, Label, Result, Stream, StreamExt, Tag, Value,
+};
+
+/**
+A JSON type observed by [`InferSchema`].
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SchemaType {
+    /**
+    A `null`.
+    */
+    Null,
+    /**
+    A `true` or `false`.
+    */
+    Boolean,
+    /**
+    A whole number.
+    */
+    Integer,
+    /**
+    Any number, including those with a fractional part.
+    */
+    Number,
+    /**
+    A string of text.
+    */
+    String,
+    /**
+    An ordered sequence of values.
+    */
+    Array,
+    /**
+    A collection of named properties.
+    */
+    Object,
+}
+
+impl SchemaType {
+    /**
+    Get the name of this type in JSON Schema.
+    */
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            SchemaType::Null => "null",
+            SchemaType::Boolean => "boolean",
+            SchemaType::Integer => "integer",
+            SchemaType::Number => "number",
+            SchemaType::String => "string",
+            SchemaType::Array => "array",
+            SchemaType::Object => "object",
+        }
+    }
+}
+
+impl Value for SchemaType {
+    fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> Result {
+        stream.value(self.as_str())
+    }
+}
+
+/**
+The types observed at some path in the values streamed through [`InferSchema`].
+
+Records are treated as objects with a fixed set of properties. Maps are treated as objects
+with arbitrary keys, so the types of their values are collected into
+[`SchemaNode::additional_properties`]. Sequences and tuples are treated as arrays, with the
+types of all their elements collected into [`SchemaNode::items`].
+
+`SchemaNode` implements [`Value`] by streaming itself as a JSON Schema.
+
+This type requires the `alloc` feature.
+*/
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaNode {
+    types: BTreeSet<SchemaType>,
+    properties: BTreeMap<String, SchemaNode>,
+    required: BTreeSet<String>,
+    objects: usize,
+    additional_properties: Option<Box<SchemaNode>>,
+    items: Option<Box<SchemaNode>>,
+}
+
+impl SchemaNode {
+    /**
+    Create an empty schema node that hasn't observed any types.
+    */
+    pub fn new() -> Self {
+        SchemaNode::default()
+    }
+
+    /**
+    Get the types observed at this node.
+    */
+    pub fn types(&self) -> impl Iterator<Item = SchemaType> + '_ {
+        self.types.iter().copied()
+    }
+
+    /**
+    Whether the given type has been observed at this node.
+    */
+    pub fn has_type(&self, ty: SchemaType) -> bool {
+        self.types.contains(&ty)
+    }
+
+    /**
+    Get the properties observed on records at this node.
+    */
+    pub fn properties(&self) -> &BTreeMap<String, SchemaNode> {
+        &self.properties
+    }
+
+    /**
+    Get a property observed on records at this node.
+    */
+    pub fn property(&self, name: &str) -> Option<&SchemaNode> {
+        self.properties.get(name)
+    }
+
+    /**
+    Whether a property was present on every record observed at this node.
+    */
+    pub fn is_required(&self, name: &str) -> bool {
+        self.required.contains(name)
+    }
+
+    /**
+    Get the types observed for the values of maps at this node.
+    */
+    pub fn additional_properties(&self) -> Option<&SchemaNode> {
+        self.additional_properties.as_deref()
+    }
+
+    /**
+    Get the types observed for the elements of sequences and tuples at this node.
+    */
+    pub fn items(&self) -> Option<&SchemaNode> {
+        self.items.as_deref()
+    }
+
+    fn child(&mut self, segment: &Segment) -> &mut SchemaNode {
+        match segment {
+            Segment::Property(name) => self.properties.entry(name.clone()).or_default(),
+            Segment::AdditionalProperties => self
+                .additional_properties
+                .get_or_insert_with(Default::default),
+            Segment::Items => self.items.get_or_insert_with(Default::default),
+        }
+    }
+
+    fn object_sample(&mut self, seen: BTreeSet<String>) {
+        if self.objects == 0 {
+            self.required = seen;
+        } else {
+            self.required.retain(|name| seen.contains(name));
+        }
+
+        self.objects += 1;
+    }
+}
+
+impl Value for SchemaNode {
+    fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> Result {
+        stream.map_begin(None)?;
+
+        match self.types.len() {
+            0 => (),
+            1 => stream.map_entry("type", self.types.iter().next().unwrap())?,
+            _ => stream.map_entry_computed("type", &Set(&self.types))?,
+        }
+
+        if !self.properties.is_empty() {
+            stream.map_entry("properties", &self.properties)?;
+        }
+
+        if !self.required.is_empty() {
+            stream.map_entry_computed("required", &Set(&self.required))?;
+        }
+
+        if let Some(ref additional_properties) = self.additional_properties {
+            stream.map_entry("additionalProperties", &**additional_properties)?;
+        }
+
+        if let Some(ref items) = self.items {
+            stream.map_entry("items", &**items)?;
+        }
+
+        stream.map_end()
+    }
+}
+
+struct Set<'a, T>(&'a BTreeSet<T>);
+
+impl<'a, T: Value> Value for Set<'a, T> {
+    fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> Result {
+        stream.seq_begin(Some(self.0.len()))?;
+
+        for v in self.0 {
+            stream.seq_value(v)?;
+        }
+
+        stream.seq_end()
+    }
+}
+
+/**
+A [`Stream`] that infers a [`SchemaNode`] from the values streamed through it.
+
+Multiple values can be streamed through the same `InferSchema`, and the types observed in
+each are unioned together. A property of a record is only considered required if it's present
+on every record observed at its path. Values are typed the same way `sval_json` writes them,
+so numbers tagged with [`tags::NUMBER`] are numbers, and enum variants are objects with
+a single property for the variant, or strings for unit variants.
+
+This type requires the `alloc` feature.
+*/
+#[derive(Debug, Default)]
+pub struct InferSchema {
+    root: SchemaNode,
+    path: Vec<Segment>,
+    frames: Vec<Frame>,
+    skipping: usize,
+    is_variant: bool,
+    is_number: bool,
+}
+
+#[derive(Debug)]
+enum Segment {
+    Property(String),
+    AdditionalProperties,
+    Items,
+}
+
+#[derive(Debug)]
+enum Frame {
+    Container {
+        is_variant: bool,
+    },
+    Record {
+        is_variant: bool,
+        seen: BTreeSet<String>,
+    },
+}
+
+impl InferSchema {
+    /**
+    Create a stream that hasn't observed any values.
+    */
+    pub fn new() -> Self {
+        InferSchema::default()
+    }
+
+    /**
+    Get the schema inferred so far.
+    */
+    pub fn schema(&self) -> &SchemaNode {
+        &self.root
+    }
+
+    /**
+    Get the inferred schema.
+    */
+    pub fn into_schema(self) -> SchemaNode {
+        self.root
+    }
+
+    fn current(&mut self) -> &mut SchemaNode {
+        let mut node = &mut self.root;
+
+        for segment in &self.path {
+            node = node.child(segment);
+        }
+
+        node
+    }
+
+    fn observe(&mut self, ty: SchemaType) -> Result {
+        if self.skipping == 0 {
+            self.is_variant = false;
+            self.current().types.insert(ty);
+        }
+
+        Ok(())
+    }
+
+    /**
+    Begin a variant of an enum, which is typed as an object with a single property.
+    */
+    fn variant_begin(&mut self, label: Option<&Label>) -> bool {
+        match label {
+            Some(label) if mem::take(&mut self.is_variant) => {
+                self.current().types.insert(SchemaType::Object);
+                self.path
+                    .push(Segment::Property(String::from(label.as_str())));
+
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn variant_end(&mut self, is_variant: bool) {
+        if is_variant {
+            if let Some(Segment::Property(name)) = self.path.pop() {
+                self.current().object_sample(BTreeSet::from([name]));
+            }
+        }
+    }
+
+    fn container_begin(&mut self, label: Option<&Label>, ty: SchemaType) -> Result {
+        if self.skipping == 0 {
+            let is_variant = self.variant_begin(label);
+
+            self.observe(ty)?;
+            self.frames.push(Frame::Container { is_variant });
+        }
+
+        Ok(())
+    }
+
+    fn container_end(&mut self) -> Result {
+        if self.skipping == 0 {
+            if let Some(Frame::Container { is_variant }) = self.frames.pop() {
+                self.variant_end(is_variant);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn record_begin(&mut self, label: Option<&Label>) -> Result {
+        if self.skipping == 0 {
+            let is_variant = self.variant_begin(label);
+
+            self.observe(SchemaType::Object)?;
+            self.frames.push(Frame::Record {
+                is_variant,
+                seen: BTreeSet::new(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn record_value_begin(&mut self, label: &Label) -> Result {
+        if self.skipping == 0 {
+            let name = String::from(label.as_str());
+
+            if let Some(Frame::Record { seen, .. }) = self.frames.last_mut() {
+                seen.insert(name.clone());
+            }
+
+            self.path.push(Segment::Property(name));
+        }
+
+        Ok(())
+    }
+
+    fn record_end(&mut self) -> Result {
+        if self.skipping == 0 {
+            if let Some(Frame::Record { is_variant, seen }) = self.frames.pop() {
+                self.current().object_sample(seen);
+                self.variant_end(is_variant);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn path_push(&mut self, segment: Segment) -> Result {
+        if self.skipping == 0 {
+            self.path.push(segment);
+        }
+
+        Ok(())
+    }
+
+    fn path_pop(&mut self) -> Result {
+        if self.skipping == 0 {
+            self.path.pop();
+        }
+
+        Ok(())
+    }
+}
+
+impl<'sval> Stream<'sval> for InferSchema {
+    fn null(&mut self) -> Result {
+        self.observe(SchemaType::Null)
+    }
+
+    fn bool(&mut self, _: bool) -> Result {
+        self.observe(SchemaType::Boolean)
+    }
+
+    fn text_begin(&mut self, _: Option<usize>) -> Result {
+        if self.is_number {
+            self.observe(SchemaType::Number)
+        } else {
+            self.observe(SchemaType::String)
+        }
+    }
+
+    fn text_fragment_computed(&mut self, _: &str) -> Result {
+        Ok(())
+    }
+
+    fn text_end(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn binary_begin(&mut self, _: Option<usize>) -> Result {
+        self.observe(SchemaType::Array)?;
+
+        // Binary is written as an array of bytes
+        if self.skipping == 0 {
+            self.path.push(Segment::Items);
+            self.observe(SchemaType::Integer)?;
+            self.path.pop();
+        }
+
+        Ok(())
+    }
+
+    fn binary_fragment_computed(&mut self, _: &[u8]) -> Result {
+        Ok(())
+    }
+
+    fn binary_end(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn i64(&mut self, _: i64) -> Result {
+        self.observe(SchemaType::Integer)
+    }
+
+    fn u64(&mut self, _: u64) -> Result {
+        self.observe(SchemaType::Integer)
+    }
+
+    fn i128(&mut self, _: i128) -> Result {
+        self.observe(SchemaType::Integer)
+    }
+
+    fn u128(&mut self, _: u128) -> Result {
+        self.observe(SchemaType::Integer)
+    }
+
+    fn f64(&mut self, _: f64) -> Result {
+        self.observe(SchemaType::Number)
+    }
+
+    fn map_begin(&mut self, _: Option<usize>) -> Result {
+        self.container_begin(None, SchemaType::Object)
+    }
+
+    fn map_key_begin(&mut self) -> Result {
+        // Map keys are data rather than schema, so they're skipped
+        self.skipping += 1;
+
+        Ok(())
+    }
+
+    fn map_key_end(&mut self) -> Result {
+        self.skipping -= 1;
+
+        Ok(())
+    }
+
+    fn map_value_begin(&mut self) -> Result {
+        self.path_push(Segment::AdditionalProperties)
+    }
+
+    fn map_value_end(&mut self) -> Result {
+        self.path_pop()
+    }
+
+    fn map_end(&mut self) -> Result {
+        self.container_end()
+    }
+
+    fn seq_begin(&mut self, _: Option<usize>) -> Result {
+        self.container_begin(None, SchemaType::Array)
+    }
+
+    fn seq_value_begin(&mut self) -> Result {
+        self.path_push(Segment::Items)
+    }
+
+    fn seq_value_end(&mut self) -> Result {
+        self.path_pop()
+    }
+
+    fn seq_end(&mut self) -> Result {
+        self.container_end()
+    }
+
+    fn enum_begin(&mut self, _: Option<&Tag>, _: Option<&Label>, _: Option<&Index>) -> Result {
+        if self.skipping == 0 {
+            self.is_variant = true;
+        }
+
+        Ok(())
+    }
+
+    fn enum_end(&mut self, _: Option<&Tag>, _: Option<&Label>, _: Option<&Index>) -> Result {
+        if self.skipping == 0 {
+            self.is_variant = false;
+        }
+
+        Ok(())
+    }
+
+    fn tagged_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        _: Option<&Index>,
+    ) -> Result {
+        if self.skipping == 0 {
+            let is_variant = self.variant_begin(label);
+
+            if let Some(&tags::NUMBER) = tag {
+                self.is_number = true;
+            }
+
+            self.frames.push(Frame::Container { is_variant });
+        }
+
+        Ok(())
+    }
+
+    fn tagged_end(&mut self, tag: Option<&Tag>, _: Option<&Label>, _: Option<&Index>) -> Result {
+        if self.skipping == 0 {
+            if let Some(&tags::NUMBER) = tag {
+                self.is_number = false;
+            }
+        }
+
+        self.container_end()
+    }
+
+    fn tag(&mut self, tag: Option<&Tag>, label: Option<&Label>, index: Option<&Index>) -> Result {
+        match tag {
+            Some(&tags::RUST_OPTION_NONE) | Some(&tags::RUST_UNIT) => {
+                self.observe(SchemaType::Null)
+            }
+            _ => {
+                if label.is_some() {
+                    self.observe(SchemaType::String)
+                } else if index.and_then(|index| index.to_i64()).is_some() {
+                    self.observe(SchemaType::Integer)
+                } else {
+                    self.observe(SchemaType::Null)
+                }
+            }
+        }
+    }
+
+    fn record_begin(
+        &mut self,
+        _: Option<&Tag>,
+        label: Option<&Label>,
+        _: Option<&Index>,
+        _: Option<usize>,
+    ) -> Result {
+        self.record_begin(label)
+    }
+
+    fn record_value_begin(&mut self, _: Option<&Tag>, label: &Label) -> Result {
+        self.record_value_begin(label)
+    }
+
+    fn record_value_end(&mut self, _: Option<&Tag>, _: &Label) -> Result {
+        self.path_pop()
+    }
+
+    fn record_end(&mut self, _: Option<&Tag>, _: Option<&Label>, _: Option<&Index>) -> Result {
+        self.record_end()
+    }
+
+    fn tuple_begin(
+        &mut self,
+        _: Option<&Tag>,
+        label: Option<&Label>,
+        _: Option<&Index>,
+        _: Option<usize>,
+    ) -> Result {
+        self.container_begin(label, SchemaType::Array)
+    }
+
+    fn tuple_value_begin(&mut self, _: Option<&Tag>, _: &Index) -> Result {
+        self.path_push(Segment::Items)
+    }
+
+    fn tuple_value_end(&mut self, _: Option<&Tag>, _: &Index) -> Result {
+        self.path_pop()
+    }
+
+    fn tuple_end(&mut self, _: Option<&Tag>, _: Option<&Label>, _: Option<&Index>) -> Result {
+        self.container_end()
+    }
+
+    fn record_tuple_begin(
+        &mut self,
+        _: Option<&Tag>,
+        label: Option<&Label>,
+        _: Option<&Index>,
+        _: Option<usize>,
+    ) -> Result {
+        self.record_begin(label)
+    }
+
+    fn record_tuple_value_begin(&mut self, _: Option<&Tag>, label: &Label, _: &Index) -> Result {
+        self.record_value_begin(label)
+    }
+
+    fn record_tuple_value_end(&mut self, _: Option<&Tag>, _: &Label, _: &Index) -> Result {
+        self.path_pop()
+    }
+
+    fn record_tuple_end(
+        &mut self,
+        _: Option<&Tag>,
+        _: Option<&Label>,
+        _: Option<&Index>,
+    ) -> Result {
+        self.record_end()
+    }
+}
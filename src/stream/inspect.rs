@@ -0,0 +1,367 @@
+use crate::{std::fmt, Index, Label, Number, Result, Stream, Tag};
+
+/**
+Wrap a stream in an [`Inspect`] that calls a function with the name of each method called on it.
+*/
+pub fn inspect<'sval, S: Stream<'sval>, F: FnMut(&str)>(stream: S, f: F) -> Inspect<S, F> {
+    Inspect::new(stream, f)
+}
+
+/**
+A [`Stream`] that calls a function with the name of each method called on it.
+
+This is a diagnostic aid for seeing exactly what a [`Value`](crate::Value) streams, without
+needing to write a full stream. The function is called with the name of the method, like
+`"map_begin"` or `"i32"`, before the call is forwarded to the wrapped stream. Methods that
+recurse into nested values, like [`Stream::value`], aren't reported themselves, only the
+calls they make. All data is forwarded to the wrapped stream unchanged.
+*/
+pub struct Inspect<S, F> {
+    stream: S,
+    f: F,
+}
+
+impl<S, F> Inspect<S, F> {
+    /**
+    Wrap a stream, calling `f` with the name of each method called on it.
+    */
+    pub fn new(stream: S, f: F) -> Self {
+        Inspect { stream, f }
+    }
+
+    /**
+    Get the wrapped stream.
+    */
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+impl<S: fmt::Debug, F> fmt::Debug for Inspect<S, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Inspect")
+            .field("stream", &self.stream)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'sval, S: Stream<'sval>, F: FnMut(&str)> Stream<'sval> for Inspect<S, F> {
+    fn null(&mut self) -> Result {
+        (self.f)("null");
+        self.stream.null()
+    }
+
+    fn bool(&mut self, value: bool) -> Result {
+        (self.f)("bool");
+        self.stream.bool(value)
+    }
+
+    fn text_begin(&mut self, num_bytes: Option<usize>) -> Result {
+        (self.f)("text_begin");
+        self.stream.text_begin(num_bytes)
+    }
+
+    fn text_fragment(&mut self, fragment: &'sval str) -> Result {
+        (self.f)("text_fragment");
+        self.stream.text_fragment(fragment)
+    }
+
+    fn text_fragment_computed(&mut self, fragment: &str) -> Result {
+        (self.f)("text_fragment_computed");
+        self.stream.text_fragment_computed(fragment)
+    }
+
+    fn text_end(&mut self) -> Result {
+        (self.f)("text_end");
+        self.stream.text_end()
+    }
+
+    fn binary_begin(&mut self, num_bytes: Option<usize>) -> Result {
+        (self.f)("binary_begin");
+        self.stream.binary_begin(num_bytes)
+    }
+
+    fn binary_fragment(&mut self, fragment: &'sval [u8]) -> Result {
+        (self.f)("binary_fragment");
+        self.stream.binary_fragment(fragment)
+    }
+
+    fn binary_fragment_computed(&mut self, fragment: &[u8]) -> Result {
+        (self.f)("binary_fragment_computed");
+        self.stream.binary_fragment_computed(fragment)
+    }
+
+    fn binary_end(&mut self) -> Result {
+        (self.f)("binary_end");
+        self.stream.binary_end()
+    }
+
+    fn u8(&mut self, value: u8) -> Result {
+        (self.f)("u8");
+        self.stream.u8(value)
+    }
+
+    fn u16(&mut self, value: u16) -> Result {
+        (self.f)("u16");
+        self.stream.u16(value)
+    }
+
+    fn u32(&mut self, value: u32) -> Result {
+        (self.f)("u32");
+        self.stream.u32(value)
+    }
+
+    fn u64(&mut self, value: u64) -> Result {
+        (self.f)("u64");
+        self.stream.u64(value)
+    }
+
+    fn u128(&mut self, value: u128) -> Result {
+        (self.f)("u128");
+        self.stream.u128(value)
+    }
+
+    fn i8(&mut self, value: i8) -> Result {
+        (self.f)("i8");
+        self.stream.i8(value)
+    }
+
+    fn i16(&mut self, value: i16) -> Result {
+        (self.f)("i16");
+        self.stream.i16(value)
+    }
+
+    fn i32(&mut self, value: i32) -> Result {
+        (self.f)("i32");
+        self.stream.i32(value)
+    }
+
+    fn i64(&mut self, value: i64) -> Result {
+        (self.f)("i64");
+        self.stream.i64(value)
+    }
+
+    fn i128(&mut self, value: i128) -> Result {
+        (self.f)("i128");
+        self.stream.i128(value)
+    }
+
+    fn f32(&mut self, value: f32) -> Result {
+        (self.f)("f32");
+        self.stream.f32(value)
+    }
+
+    fn f64(&mut self, value: f64) -> Result {
+        (self.f)("f64");
+        self.stream.f64(value)
+    }
+
+    fn num(&mut self, num: &Number) -> Result {
+        (self.f)("num");
+        self.stream.num(num)
+    }
+
+    fn map_begin(&mut self, num_entries: Option<usize>) -> Result {
+        (self.f)("map_begin");
+        self.stream.map_begin(num_entries)
+    }
+
+    fn map_key_begin(&mut self) -> Result {
+        (self.f)("map_key_begin");
+        self.stream.map_key_begin()
+    }
+
+    fn map_key_end(&mut self) -> Result {
+        (self.f)("map_key_end");
+        self.stream.map_key_end()
+    }
+
+    fn map_value_begin(&mut self) -> Result {
+        (self.f)("map_value_begin");
+        self.stream.map_value_begin()
+    }
+
+    fn map_value_end(&mut self) -> Result {
+        (self.f)("map_value_end");
+        self.stream.map_value_end()
+    }
+
+    fn map_end(&mut self) -> Result {
+        (self.f)("map_end");
+        self.stream.map_end()
+    }
+
+    fn seq_begin(&mut self, num_entries: Option<usize>) -> Result {
+        (self.f)("seq_begin");
+        self.stream.seq_begin(num_entries)
+    }
+
+    fn seq_value_begin(&mut self) -> Result {
+        (self.f)("seq_value_begin");
+        self.stream.seq_value_begin()
+    }
+
+    fn seq_value_end(&mut self) -> Result {
+        (self.f)("seq_value_end");
+        self.stream.seq_value_end()
+    }
+
+    fn seq_end(&mut self) -> Result {
+        (self.f)("seq_end");
+        self.stream.seq_end()
+    }
+
+    fn enum_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        (self.f)("enum_begin");
+        self.stream.enum_begin(tag, label, index)
+    }
+
+    fn enum_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        (self.f)("enum_end");
+        self.stream.enum_end(tag, label, index)
+    }
+
+    fn tagged_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        (self.f)("tagged_begin");
+        self.stream.tagged_begin(tag, label, index)
+    }
+
+    fn tagged_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        (self.f)("tagged_end");
+        self.stream.tagged_end(tag, label, index)
+    }
+
+    fn tag(&mut self, tag: Option<&Tag>, label: Option<&Label>, index: Option<&Index>) -> Result {
+        (self.f)("tag");
+        self.stream.tag(tag, label, index)
+    }
+
+    fn tag_hint(&mut self, tag: &Tag) -> Result {
+        (self.f)("tag_hint");
+        self.stream.tag_hint(tag)
+    }
+
+    fn record_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+        num_entries: Option<usize>,
+    ) -> Result {
+        (self.f)("record_begin");
+        self.stream.record_begin(tag, label, index, num_entries)
+    }
+
+    fn record_value_begin(&mut self, tag: Option<&Tag>, label: &Label) -> Result {
+        (self.f)("record_value_begin");
+        self.stream.record_value_begin(tag, label)
+    }
+
+    fn record_value_end(&mut self, tag: Option<&Tag>, label: &Label) -> Result {
+        (self.f)("record_value_end");
+        self.stream.record_value_end(tag, label)
+    }
+
+    fn record_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        (self.f)("record_end");
+        self.stream.record_end(tag, label, index)
+    }
+
+    fn tuple_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+        num_entries: Option<usize>,
+    ) -> Result {
+        (self.f)("tuple_begin");
+        self.stream.tuple_begin(tag, label, index, num_entries)
+    }
+
+    fn tuple_value_begin(&mut self, tag: Option<&Tag>, index: &Index) -> Result {
+        (self.f)("tuple_value_begin");
+        self.stream.tuple_value_begin(tag, index)
+    }
+
+    fn tuple_value_end(&mut self, tag: Option<&Tag>, index: &Index) -> Result {
+        (self.f)("tuple_value_end");
+        self.stream.tuple_value_end(tag, index)
+    }
+
+    fn tuple_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        (self.f)("tuple_end");
+        self.stream.tuple_end(tag, label, index)
+    }
+
+    fn record_tuple_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+        num_entries: Option<usize>,
+    ) -> Result {
+        (self.f)("record_tuple_begin");
+        self.stream
+            .record_tuple_begin(tag, label, index, num_entries)
+    }
+
+    fn record_tuple_value_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: &Label,
+        index: &Index,
+    ) -> Result {
+        (self.f)("record_tuple_value_begin");
+        self.stream.record_tuple_value_begin(tag, label, index)
+    }
+
+    fn record_tuple_value_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: &Label,
+        index: &Index,
+    ) -> Result {
+        (self.f)("record_tuple_value_end");
+        self.stream.record_tuple_value_end(tag, label, index)
+    }
+
+    fn record_tuple_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        (self.f)("record_tuple_end");
+        self.stream.record_tuple_end(tag, label, index)
+    }
+}
@@ -0,0 +1,132 @@
+use crate::{Error, Result, Stream};
+
+/**
+The largest integer that can be converted to an `f64` without losing precision.
+
+This is the same as JavaScript's `Number.MAX_SAFE_INTEGER`.
+*/
+const MAX_SAFE_INTEGER: u128 = (1 << 53) - 1;
+
+/**
+Wrap a stream in an [`IntegersAsFloats`] that streams all integers as `f64`s.
+*/
+pub fn integers_as_floats<'sval, S: Stream<'sval>>(stream: S) -> IntegersAsFloats<S> {
+    IntegersAsFloats::new(stream)
+}
+
+/**
+A [`Stream`] that converts all integers into `f64`s.
+
+This is useful for targets like JavaScript, where every number is a double. Each integer,
+of any width, is forwarded to the wrapped stream through [`Stream::f64`]. All other data is
+forwarded unchanged.
+
+Integers larger in magnitude than `2^53 - 1` can't always be represented exactly as an `f64`,
+so they're rounded to the nearest one by default. Use [`IntegersAsFloats::strict`] to fail
+on these integers instead.
+*/
+#[derive(Debug)]
+pub struct IntegersAsFloats<S> {
+    stream: S,
+    is_strict: bool,
+}
+
+impl<S> IntegersAsFloats<S> {
+    /**
+    Wrap a stream, converting integers into `f64`s.
+    */
+    pub fn new(stream: S) -> Self {
+        IntegersAsFloats {
+            stream,
+            is_strict: false,
+        }
+    }
+
+    /**
+    Fail on integers larger in magnitude than `2^53 - 1` instead of rounding them.
+
+    These integers are outside of JavaScript's range of safe integers, where each
+    `f64` represents exactly one integer.
+    */
+    pub fn strict(mut self) -> Self {
+        self.is_strict = true;
+        self
+    }
+
+    /**
+    Get the wrapped stream.
+    */
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+impl<'sval, S: Stream<'sval>> IntegersAsFloats<S> {
+    fn unsigned(&mut self, value: u128) -> Result {
+        if self.is_strict && value > MAX_SAFE_INTEGER {
+            return Err(Error::unsupported());
+        }
+
+        self.stream.f64(value as f64)
+    }
+
+    fn signed(&mut self, value: i128) -> Result {
+        if self.is_strict && value.unsigned_abs() > MAX_SAFE_INTEGER {
+            return Err(Error::unsupported());
+        }
+
+        self.stream.f64(value as f64)
+    }
+}
+
+impl<'sval, S: Stream<'sval>> Stream<'sval> for IntegersAsFloats<S> {
+    forward_stream!(stream =>
+        null, bool, text_begin, text_fragment, text_fragment_computed, text_end, binary_begin,
+        binary_fragment, binary_fragment_computed, binary_end, f32, f64, num, map_begin,
+        map_key_begin, map_key_end, map_value_begin, map_value_end, map_end, seq_begin,
+        seq_value_begin, seq_value_end, seq_end, enum_begin, enum_end, tagged_begin, tagged_end,
+        tag, tag_hint, record_begin, record_value_begin, record_value_end, record_end, tuple_begin,
+        tuple_value_begin, tuple_value_end, tuple_end, record_tuple_begin, record_tuple_value_begin,
+        record_tuple_value_end, record_tuple_end,
+    );
+
+    fn u8(&mut self, value: u8) -> Result {
+        self.stream.f64(value as f64)
+    }
+
+    fn u16(&mut self, value: u16) -> Result {
+        self.stream.f64(value as f64)
+    }
+
+    fn u32(&mut self, value: u32) -> Result {
+        self.stream.f64(value as f64)
+    }
+
+    fn u64(&mut self, value: u64) -> Result {
+        self.unsigned(value as u128)
+    }
+
+    fn u128(&mut self, value: u128) -> Result {
+        self.unsigned(value)
+    }
+
+    fn i8(&mut self, value: i8) -> Result {
+        self.stream.f64(value as f64)
+    }
+
+    fn i16(&mut self, value: i16) -> Result {
+        self.stream.f64(value as f64)
+    }
+
+    fn i32(&mut self, value: i32) -> Result {
+        self.stream.f64(value as f64)
+    }
+
+    fn i64(&mut self, value: i64) -> Result {
+        self.signed(value as i128)
+    }
+
+    fn i128(&mut self, value: i128) -> Result {
+        self.signed(value)
+    }
+}
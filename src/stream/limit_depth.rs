@@ -0,0 +1,192 @@
+use crate::{Error, Index, Label, Result, Stream, Tag};
+
+/**
+Wrap a stream in a [`LimitDepth`] that fails if values are nested more than `max_depth` levels deep.
+*/
+pub fn limit_depth<'sval, S: Stream<'sval>>(stream: S, max_depth: usize) -> LimitDepth<S> {
+    LimitDepth::new(stream, max_depth)
+}
+
+/**
+A [`Stream`] that fails if values are nested more than a configured maximum depth.
+
+Streams like serializers typically recurse as they encounter nested values, so a deeply
+nested value from an untrusted source can overflow the stack. Wrapping a stream in a
+`LimitDepth` bounds that recursion. Each map, sequence, record, tuple, enum, and tagged value
+is one level of depth. All data is forwarded to the wrapped stream unchanged until the
+maximum depth is exceeded.
+*/
+#[derive(Debug)]
+pub struct LimitDepth<S> {
+    stream: S,
+    depth: usize,
+    max_depth: usize,
+}
+
+impl<S> LimitDepth<S> {
+    /**
+    Wrap a stream, failing if values are nested more than `max_depth` levels deep.
+    */
+    pub fn new(stream: S, max_depth: usize) -> Self {
+        LimitDepth {
+            stream,
+            depth: 0,
+            max_depth,
+        }
+    }
+
+    /**
+    Get the wrapped stream.
+    */
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+
+    fn depth_begin(&mut self) -> Result {
+        if self.depth >= self.max_depth {
+            return Err(Error::new());
+        }
+
+        self.depth += 1;
+
+        Ok(())
+    }
+
+    fn depth_end(&mut self) {
+        self.depth = self.depth.saturating_sub(1);
+    }
+}
+
+impl<'sval, S: Stream<'sval>> Stream<'sval> for LimitDepth<S> {
+    forward_stream!(stream =>
+        null, bool, text_begin, text_fragment, text_fragment_computed, text_end, binary_begin,
+        binary_fragment, binary_fragment_computed, binary_end, u8, u16, u32, u64, u128, i8, i16,
+        i32, i64, i128, f32, f64, num, map_key_begin, map_key_end, map_value_begin, map_value_end,
+        seq_value_begin, seq_value_end, tag, tag_hint, record_value_begin, record_value_end,
+        tuple_value_begin, tuple_value_end, record_tuple_value_begin, record_tuple_value_end,
+    );
+
+    fn map_begin(&mut self, num_entries: Option<usize>) -> Result {
+        self.depth_begin()?;
+        self.stream.map_begin(num_entries)
+    }
+
+    fn map_end(&mut self) -> Result {
+        self.depth_end();
+        self.stream.map_end()
+    }
+
+    fn seq_begin(&mut self, num_entries: Option<usize>) -> Result {
+        self.depth_begin()?;
+        self.stream.seq_begin(num_entries)
+    }
+
+    fn seq_end(&mut self) -> Result {
+        self.depth_end();
+        self.stream.seq_end()
+    }
+
+    fn enum_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.depth_begin()?;
+        self.stream.enum_begin(tag, label, index)
+    }
+
+    fn enum_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.depth_end();
+        self.stream.enum_end(tag, label, index)
+    }
+
+    fn tagged_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.depth_begin()?;
+        self.stream.tagged_begin(tag, label, index)
+    }
+
+    fn tagged_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.depth_end();
+        self.stream.tagged_end(tag, label, index)
+    }
+
+    fn record_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+        num_entries: Option<usize>,
+    ) -> Result {
+        self.depth_begin()?;
+        self.stream.record_begin(tag, label, index, num_entries)
+    }
+
+    fn record_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.depth_end();
+        self.stream.record_end(tag, label, index)
+    }
+
+    fn tuple_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+        num_entries: Option<usize>,
+    ) -> Result {
+        self.depth_begin()?;
+        self.stream.tuple_begin(tag, label, index, num_entries)
+    }
+
+    fn tuple_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.depth_end();
+        self.stream.tuple_end(tag, label, index)
+    }
+
+    fn record_tuple_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+        num_entries: Option<usize>,
+    ) -> Result {
+        self.depth_begin()?;
+        self.stream
+            .record_tuple_begin(tag, label, index, num_entries)
+    }
+
+    fn record_tuple_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.depth_end();
+        self.stream.record_tuple_end(tag, label, index)
+    }
+}
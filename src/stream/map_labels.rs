@@ -0,0 +1,114 @@
+use crate::{
+    std::{fmt, vec::Vec},
+    Error, Index, Label, Result, Stream, Tag,
+};
+
+/**
+Wrap a stream in a [`MapLabels`] that rewrites the labels of record fields.
+*/
+pub fn map_labels<'sval, S: Stream<'sval>, F: FnMut(&Label) -> Label<'static>>(
+    stream: S,
+    f: F,
+) -> MapLabels<S, F> {
+    MapLabels::new(stream, f)
+}
+
+/**
+A [`Stream`] that rewrites the labels of record fields using a function.
+
+The function is called once for each field of a record or record tuple as it begins.
+The label it returns is used for both the start and end of that field, so they're always
+consistent, even if the function would return a different label when called again.
+The labels of records themselves, and all other data, are forwarded unchanged.
+
+Labels that aren't static can be returned using [`Label::new_owned`].
+
+This type requires the `alloc` feature.
+*/
+pub struct MapLabels<S, F> {
+    stream: S,
+    f: F,
+    labels: Vec<Label<'static>>,
+}
+
+impl<S, F> MapLabels<S, F> {
+    /**
+    Wrap a stream, rewriting the labels of record fields using `f`.
+    */
+    pub fn new(stream: S, f: F) -> Self {
+        MapLabels {
+            stream,
+            f,
+            labels: Vec::new(),
+        }
+    }
+
+    /**
+    Get the wrapped stream.
+    */
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+impl<S, F: FnMut(&Label) -> Label<'static>> MapLabels<S, F> {
+    fn label_begin(&mut self, label: &Label) {
+        let label = (self.f)(label);
+        self.labels.push(label);
+    }
+
+    fn label_end(&mut self) -> Result<Label<'static>> {
+        self.labels.pop().ok_or_else(Error::new)
+    }
+}
+
+impl<S: fmt::Debug, F> fmt::Debug for MapLabels<S, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MapLabels")
+            .field("stream", &self.stream)
+            .field("labels", &self.labels)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'sval, S: Stream<'sval>, F: FnMut(&Label) -> Label<'static>> Stream<'sval>
+    for MapLabels<S, F>
+{
+    forward_stream!(stream =>
+        null, bool, text_begin, text_fragment, text_fragment_computed, text_end, binary_begin,
+        binary_fragment, binary_fragment_computed, binary_end, u8, u16, u32, u64, u128, i8, i16,
+        i32, i64, i128, f32, f64, num, map_begin, map_key_begin, map_key_end, map_value_begin,
+        map_value_end, map_end, seq_begin, seq_value_begin, seq_value_end, seq_end, enum_begin,
+        enum_end, tagged_begin, tagged_end, tag, tag_hint, record_begin, record_end, tuple_begin,
+        tuple_value_begin, tuple_value_end, tuple_end, record_tuple_begin, record_tuple_end,
+    );
+
+    fn record_value_begin(&mut self, tag: Option<&Tag>, label: &Label) -> Result {
+        self.label_begin(label);
+
+        let label = self.labels.last().ok_or_else(Error::new)?;
+        self.stream.record_value_begin(tag, label)
+    }
+
+    fn record_value_end(&mut self, tag: Option<&Tag>, _: &Label) -> Result {
+        let label = self.label_end()?;
+        self.stream.record_value_end(tag, &label)
+    }
+
+    fn record_tuple_value_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: &Label,
+        index: &Index,
+    ) -> Result {
+        self.label_begin(label);
+
+        let label = self.labels.last().ok_or_else(Error::new)?;
+        self.stream.record_tuple_value_begin(tag, label, index)
+    }
+
+    fn record_tuple_value_end(&mut self, tag: Option<&Tag>, _: &Label, index: &Index) -> Result {
+        let label = self.label_end()?;
+        self.stream.record_tuple_value_end(tag, &label, index)
+    }
+}
@@ -0,0 +1,195 @@
+use crate::{Index, Label, Result, Stream, Tag};
+
+/**
+Wrap a stream in a [`MaxDepth`] that records the deepest level of nesting it sees.
+*/
+pub fn max_depth<'sval, S: Stream<'sval>>(stream: S) -> MaxDepth<S> {
+    MaxDepth::new(stream)
+}
+
+/**
+A [`Stream`] that records the maximum depth values are nested to.
+
+Each map, sequence, record, tuple, enum, and tagged value is one level of depth, the same
+as [`LimitDepth`](crate::LimitDepth). A value with a maximum depth of `n` can be streamed
+through a `LimitDepth` with a limit of `n`. All data is forwarded to the wrapped stream
+unchanged. Unlike `LimitDepth`, this stream never fails on its own.
+*/
+#[derive(Debug)]
+pub struct MaxDepth<S> {
+    stream: S,
+    depth: usize,
+    max_depth: usize,
+}
+
+impl<S> MaxDepth<S> {
+    /**
+    Wrap a stream, recording the deepest level of nesting it sees.
+    */
+    pub fn new(stream: S) -> Self {
+        MaxDepth {
+            stream,
+            depth: 0,
+            max_depth: 0,
+        }
+    }
+
+    /**
+    Get the deepest level of nesting seen so far.
+
+    A value that isn't nested, like a number or text, has a depth of `0`.
+    */
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    /**
+    Get the wrapped stream.
+    */
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+
+    fn depth_begin(&mut self) {
+        self.depth += 1;
+        self.max_depth = self.max_depth.max(self.depth);
+    }
+
+    fn depth_end(&mut self) {
+        self.depth = self.depth.saturating_sub(1);
+    }
+}
+
+impl<'sval, S: Stream<'sval>> Stream<'sval> for MaxDepth<S> {
+    forward_stream!(stream =>
+        null, bool, text_begin, text_fragment, text_fragment_computed, text_end, binary_begin,
+        binary_fragment, binary_fragment_computed, binary_end, u8, u16, u32, u64, u128, i8, i16,
+        i32, i64, i128, f32, f64, num, map_key_begin, map_key_end, map_value_begin, map_value_end,
+        seq_value_begin, seq_value_end, tag, tag_hint, record_value_begin, record_value_end,
+        tuple_value_begin, tuple_value_end, record_tuple_value_begin, record_tuple_value_end,
+    );
+
+    fn map_begin(&mut self, num_entries: Option<usize>) -> Result {
+        self.depth_begin();
+        self.stream.map_begin(num_entries)
+    }
+
+    fn map_end(&mut self) -> Result {
+        self.depth_end();
+        self.stream.map_end()
+    }
+
+    fn seq_begin(&mut self, num_entries: Option<usize>) -> Result {
+        self.depth_begin();
+        self.stream.seq_begin(num_entries)
+    }
+
+    fn seq_end(&mut self) -> Result {
+        self.depth_end();
+        self.stream.seq_end()
+    }
+
+    fn enum_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.depth_begin();
+        self.stream.enum_begin(tag, label, index)
+    }
+
+    fn enum_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.depth_end();
+        self.stream.enum_end(tag, label, index)
+    }
+
+    fn tagged_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.depth_begin();
+        self.stream.tagged_begin(tag, label, index)
+    }
+
+    fn tagged_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.depth_end();
+        self.stream.tagged_end(tag, label, index)
+    }
+
+    fn record_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+        num_entries: Option<usize>,
+    ) -> Result {
+        self.depth_begin();
+        self.stream.record_begin(tag, label, index, num_entries)
+    }
+
+    fn record_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.depth_end();
+        self.stream.record_end(tag, label, index)
+    }
+
+    fn tuple_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+        num_entries: Option<usize>,
+    ) -> Result {
+        self.depth_begin();
+        self.stream.tuple_begin(tag, label, index, num_entries)
+    }
+
+    fn tuple_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.depth_end();
+        self.stream.tuple_end(tag, label, index)
+    }
+
+    fn record_tuple_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+        num_entries: Option<usize>,
+    ) -> Result {
+        self.depth_begin();
+        self.stream
+            .record_tuple_begin(tag, label, index, num_entries)
+    }
+
+    fn record_tuple_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.depth_end();
+        self.stream.record_tuple_end(tag, label, index)
+    }
+}
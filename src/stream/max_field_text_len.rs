@@ -0,0 +1,133 @@
+use crate::{
+    std::{collections::BTreeMap, string::String, vec::Vec},
+    Error, Index, Label, Result, Stream, Tag,
+};
+
+/**
+A [`Stream`] that fails if the text value of a record field is longer than a configured maximum.
+
+Limits are configured per field label with [`MaxFieldTextLen::with_limit`] and measured in bytes.
+When a record field with a configured limit is streamed, any text within its value is checked
+against that limit, including text in the fields of records nested within it. A nested field
+with its own configured limit is checked against that limit instead. Fields without a configured
+limit that aren't nested in a limited field aren't checked. All data is forwarded to the wrapped
+stream unchanged until a limit is exceeded.
+
+This type requires the `alloc` feature.
+*/
+#[derive(Debug)]
+pub struct MaxFieldTextLen<S> {
+    stream: S,
+    limits: BTreeMap<String, usize>,
+    fields: Vec<Option<usize>>,
+    text_len: usize,
+}
+
+impl<S> MaxFieldTextLen<S> {
+    /**
+    Wrap a stream without any configured limits.
+    */
+    pub fn new(stream: S) -> Self {
+        MaxFieldTextLen {
+            stream,
+            limits: BTreeMap::new(),
+            fields: Vec::new(),
+            text_len: 0,
+        }
+    }
+
+    /**
+    Limit text values in record fields with the given label to `max_bytes`.
+    */
+    pub fn with_limit(mut self, label: impl Into<String>, max_bytes: usize) -> Self {
+        self.limits.insert(label.into(), max_bytes);
+        self
+    }
+
+    /**
+    Get the wrapped stream.
+    */
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+
+    fn field_begin(&mut self, label: &Label) {
+        // A field without its own limit is still within the limit of its enclosing field
+        let limit = self
+            .limits
+            .get(label.as_str())
+            .copied()
+            .or_else(|| self.fields.last().copied().flatten());
+
+        self.fields.push(limit);
+    }
+
+    fn field_end(&mut self) {
+        self.fields.pop();
+    }
+
+    fn text_fragment_len(&mut self, fragment: &str) -> Result {
+        self.text_len += fragment.len();
+
+        match self.fields.last() {
+            Some(Some(max_bytes)) if self.text_len > *max_bytes => Err(Error::new()),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<'sval, S: Stream<'sval>> Stream<'sval> for MaxFieldTextLen<S> {
+    forward_stream!(stream =>
+        null, bool, text_end, binary_begin, binary_fragment, binary_fragment_computed, binary_end,
+        u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64, num, map_begin, map_key_begin,
+        map_key_end, map_value_begin, map_value_end, map_end, seq_begin, seq_value_begin,
+        seq_value_end, seq_end, enum_begin, enum_end, tagged_begin, tagged_end, tag, tag_hint,
+        record_begin, record_end, tuple_begin, tuple_value_begin, tuple_value_end, tuple_end,
+        record_tuple_begin, record_tuple_end,
+    );
+
+    fn text_begin(&mut self, num_bytes: Option<usize>) -> Result {
+        self.text_len = 0;
+        self.stream.text_begin(num_bytes)
+    }
+
+    fn text_fragment(&mut self, fragment: &'sval str) -> Result {
+        self.text_fragment_len(fragment)?;
+        self.stream.text_fragment(fragment)
+    }
+
+    fn text_fragment_computed(&mut self, fragment: &str) -> Result {
+        self.text_fragment_len(fragment)?;
+        self.stream.text_fragment_computed(fragment)
+    }
+
+    fn record_value_begin(&mut self, tag: Option<&Tag>, label: &Label) -> Result {
+        self.field_begin(label);
+        self.stream.record_value_begin(tag, label)
+    }
+
+    fn record_value_end(&mut self, tag: Option<&Tag>, label: &Label) -> Result {
+        self.field_end();
+        self.stream.record_value_end(tag, label)
+    }
+
+    fn record_tuple_value_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: &Label,
+        index: &Index,
+    ) -> Result {
+        self.field_begin(label);
+        self.stream.record_tuple_value_begin(tag, label, index)
+    }
+
+    fn record_tuple_value_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: &Label,
+        index: &Index,
+    ) -> Result {
+        self.field_end();
+        self.stream.record_tuple_value_end(tag, label, index)
+    }
+}
@@ -0,0 +1,103 @@
+use crate::{Error, Result, Stream};
+
+/**
+Wrap a stream in a [`NormalizeInts`] that streams all integers as `i64`s.
+*/
+pub fn normalize_ints<'sval, S: Stream<'sval>>(stream: S) -> NormalizeInts<S> {
+    NormalizeInts::new(stream)
+}
+
+/**
+A [`Stream`] that converts all integers into `i64`s.
+
+This is useful for targets that only support a single width of integer. Each integer,
+of any width, is forwarded to the wrapped stream through [`Stream::i64`]. Unsigned integers
+larger than `i64::MAX` are forwarded through [`Stream::u64`] instead. All other data is
+forwarded unchanged.
+
+Integers are never truncated. Streaming a 128bit integer that doesn't fit in an `i64`
+or `u64` will fail.
+*/
+#[derive(Debug)]
+pub struct NormalizeInts<S> {
+    stream: S,
+}
+
+impl<S> NormalizeInts<S> {
+    /**
+    Wrap a stream, converting integers into `i64`s.
+    */
+    pub fn new(stream: S) -> Self {
+        NormalizeInts { stream }
+    }
+
+    /**
+    Get the wrapped stream.
+    */
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+impl<'sval, S: Stream<'sval>> NormalizeInts<S> {
+    fn unsigned(&mut self, value: u128) -> Result {
+        if let Ok(value) = i64::try_from(value) {
+            self.stream.i64(value)
+        } else if let Ok(value) = u64::try_from(value) {
+            self.stream.u64(value)
+        } else {
+            Err(Error::unsupported())
+        }
+    }
+}
+
+impl<'sval, S: Stream<'sval>> Stream<'sval> for NormalizeInts<S> {
+    forward_stream!(stream =>
+        null, bool, text_begin, text_fragment, text_fragment_computed, text_end, binary_begin,
+        binary_fragment, binary_fragment_computed, binary_end, i64, f32, f64, num, map_begin,
+        map_key_begin, map_key_end, map_value_begin, map_value_end, map_end, seq_begin,
+        seq_value_begin, seq_value_end, seq_end, enum_begin, enum_end, tagged_begin, tagged_end,
+        tag, tag_hint, record_begin, record_value_begin, record_value_end, record_end, tuple_begin,
+        tuple_value_begin, tuple_value_end, tuple_end, record_tuple_begin, record_tuple_value_begin,
+        record_tuple_value_end, record_tuple_end,
+    );
+
+    fn u8(&mut self, value: u8) -> Result {
+        self.stream.i64(value as i64)
+    }
+
+    fn u16(&mut self, value: u16) -> Result {
+        self.stream.i64(value as i64)
+    }
+
+    fn u32(&mut self, value: u32) -> Result {
+        self.stream.i64(value as i64)
+    }
+
+    fn u64(&mut self, value: u64) -> Result {
+        self.unsigned(value as u128)
+    }
+
+    fn u128(&mut self, value: u128) -> Result {
+        self.unsigned(value)
+    }
+
+    fn i8(&mut self, value: i8) -> Result {
+        self.stream.i64(value as i64)
+    }
+
+    fn i16(&mut self, value: i16) -> Result {
+        self.stream.i64(value as i64)
+    }
+
+    fn i32(&mut self, value: i32) -> Result {
+        self.stream.i64(value as i64)
+    }
+
+    fn i128(&mut self, value: i128) -> Result {
+        match i64::try_from(value) {
+            Ok(value) => self.stream.i64(value),
+            Err(_) => Err(Error::unsupported()),
+        }
+    }
+}
@@ -0,0 +1,219 @@
+use crate::{std::vec::Vec, Error, Index, Label, Result, Stream, Tag};
+
+/**
+Wrap a stream in a [`RecordsAsMaps`] that forwards records, tuples, and enums as maps.
+*/
+pub fn records_as_maps<'sval, S: Stream<'sval>>(stream: S) -> RecordsAsMaps<S> {
+    RecordsAsMaps::new(stream)
+}
+
+/**
+A [`Stream`] that forwards structured values as maps.
+
+This lets a stream that only understands maps consume records and tuples:
+
+- Records and record tuples are forwarded as maps, with the label of each field as a text key.
+- Tuples are forwarded as maps, with the index of each field as a text key, like `"0"`.
+- Enums are forwarded as a map with a single entry, where the key is the label of the variant,
+  or its index if it doesn't have a label. Unit variants have a value of null.
+
+Enums nested directly within other enums are flattened into a single map.
+
+The labels, indexes, and tags of the values being converted aren't forwarded.
+All other data is forwarded to the wrapped stream unchanged.
+
+Streaming an enum variant that has neither a label nor an index will fail.
+
+This type requires the `alloc` feature.
+*/
+#[derive(Debug)]
+pub struct RecordsAsMaps<S> {
+    stream: S,
+    enums: Vec<bool>,
+    is_variant_pending: bool,
+}
+
+impl<S> RecordsAsMaps<S> {
+    /**
+    Wrap a stream, forwarding records, tuples, and enums to it as maps.
+    */
+    pub fn new(stream: S) -> Self {
+        RecordsAsMaps {
+            stream,
+            enums: Vec::new(),
+            is_variant_pending: false,
+        }
+    }
+
+    /**
+    Get the wrapped stream.
+    */
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+impl<'sval, S: Stream<'sval>> RecordsAsMaps<S> {
+    /**
+    Stream a label or index as the key of a map entry, and begin its value.
+    */
+    fn key(&mut self, label: Option<&Label>, index: Option<&Index>) -> Result {
+        self.stream.map_key_begin()?;
+
+        match (label, index) {
+            (Some(label), _) => self.stream.value_computed(label.as_str())?,
+            (None, Some(index)) => crate::stream_display(&mut self.stream, index)?,
+            (None, None) => return Err(Error::unsupported()),
+        }
+
+        self.stream.map_key_end()?;
+        self.stream.map_value_begin()
+    }
+
+    /**
+    If a value is the variant of an enum, begin the single-entry map that wraps it.
+
+    Returns whether the value was a variant.
+    */
+    fn variant_begin(&mut self, label: Option<&Label>, index: Option<&Index>) -> Result<bool> {
+        if !self.is_variant_pending {
+            return Ok(false);
+        }
+
+        self.is_variant_pending = false;
+
+        let wrapped = self.enums.last_mut().ok_or_else(Error::new)?;
+        *wrapped = true;
+
+        self.stream.map_begin(Some(1))?;
+        self.key(label, index)?;
+
+        Ok(true)
+    }
+}
+
+impl<'sval, S: Stream<'sval>> Stream<'sval> for RecordsAsMaps<S> {
+    forward_stream!(stream =>
+        null, bool, text_begin, text_fragment, text_fragment_computed, text_end, binary_begin,
+        binary_fragment, binary_fragment_computed, binary_end, u8, u16, u32, u64, u128, i8, i16,
+        i32, i64, i128, f32, f64, num, map_begin, map_key_begin, map_key_end, map_value_begin,
+        map_value_end, map_end, seq_begin, seq_value_begin, seq_value_end, seq_end, tagged_end,
+        tag_hint,
+    );
+
+    fn enum_begin(&mut self, _: Option<&Tag>, _: Option<&Label>, _: Option<&Index>) -> Result {
+        // A nested enum is flattened into the variant of the enum that contains it,
+        // so only the outermost enum will be wrapped in a map
+        self.enums.push(false);
+        self.is_variant_pending = true;
+
+        Ok(())
+    }
+
+    fn enum_end(&mut self, _: Option<&Tag>, _: Option<&Label>, _: Option<&Index>) -> Result {
+        self.is_variant_pending = false;
+
+        if self.enums.pop().ok_or_else(Error::new)? {
+            self.stream.map_value_end()?;
+            self.stream.map_end()?;
+        }
+
+        Ok(())
+    }
+
+    fn tagged_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.variant_begin(label, index)?;
+
+        self.stream.tagged_begin(tag, label, index)
+    }
+
+    fn tag(&mut self, tag: Option<&Tag>, label: Option<&Label>, index: Option<&Index>) -> Result {
+        if self.variant_begin(label, index)? {
+            return self.stream.null();
+        }
+
+        self.stream.tag(tag, label, index)
+    }
+
+    fn record_begin(
+        &mut self,
+        _: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+        num_entries: Option<usize>,
+    ) -> Result {
+        self.variant_begin(label, index)?;
+
+        self.stream.map_begin(num_entries)
+    }
+
+    fn record_value_begin(&mut self, _: Option<&Tag>, label: &Label) -> Result {
+        self.key(Some(label), None)
+    }
+
+    fn record_value_end(&mut self, _: Option<&Tag>, _: &Label) -> Result {
+        self.stream.map_value_end()
+    }
+
+    fn record_end(&mut self, _: Option<&Tag>, _: Option<&Label>, _: Option<&Index>) -> Result {
+        self.stream.map_end()
+    }
+
+    fn tuple_begin(
+        &mut self,
+        _: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+        num_entries: Option<usize>,
+    ) -> Result {
+        self.variant_begin(label, index)?;
+
+        self.stream.map_begin(num_entries)
+    }
+
+    fn tuple_value_begin(&mut self, _: Option<&Tag>, index: &Index) -> Result {
+        self.key(None, Some(index))
+    }
+
+    fn tuple_value_end(&mut self, _: Option<&Tag>, _: &Index) -> Result {
+        self.stream.map_value_end()
+    }
+
+    fn tuple_end(&mut self, _: Option<&Tag>, _: Option<&Label>, _: Option<&Index>) -> Result {
+        self.stream.map_end()
+    }
+
+    fn record_tuple_begin(
+        &mut self,
+        _: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+        num_entries: Option<usize>,
+    ) -> Result {
+        self.variant_begin(label, index)?;
+
+        self.stream.map_begin(num_entries)
+    }
+
+    fn record_tuple_value_begin(&mut self, _: Option<&Tag>, label: &Label, _: &Index) -> Result {
+        self.key(Some(label), None)
+    }
+
+    fn record_tuple_value_end(&mut self, _: Option<&Tag>, _: &Label, _: &Index) -> Result {
+        self.stream.map_value_end()
+    }
+
+    fn record_tuple_end(
+        &mut self,
+        _: Option<&Tag>,
+        _: Option<&Label>,
+        _: Option<&Index>,
+    ) -> Result {
+        self.stream.map_end()
+    }
+}
@@ -0,0 +1,259 @@
+use crate::{
+    std::{collections::BTreeSet, string::String, vec::Vec},
+    Index, Label, Result, Stream, Tag,
+};
+
+/**
+A [`Stream`] that records which expected enum variants are used by the values streamed through it.
+
+This type is a coverage aid for tests. Values are streamed into it, such as all the values in a
+serialization round-trip corpus, and then [`RequireAllVariantsHandled::unhandled`] reports which
+expected variants never appeared. Variants are matched by label, regardless of the enum they
+belong to or how deeply they're nested. The stream doesn't fail on any input.
+
+This type requires the `alloc` feature.
+*/
+#[derive(Debug, Default)]
+pub struct RequireAllVariantsHandled {
+    expected: BTreeSet<String>,
+    handled: BTreeSet<String>,
+    is_variant_next: bool,
+}
+
+impl RequireAllVariantsHandled {
+    /**
+    Expect the given variant labels to be used by the values streamed.
+    */
+    pub fn new(variants: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        RequireAllVariantsHandled {
+            expected: variants.into_iter().map(Into::into).collect(),
+            handled: BTreeSet::new(),
+            is_variant_next: false,
+        }
+    }
+
+    /**
+    Get the expected variants that haven't been used by any values streamed so far.
+
+    Variants are returned in lexicographic order.
+    */
+    pub fn unhandled(&self) -> Vec<&str> {
+        self.expected
+            .difference(&self.handled)
+            .map(|variant| variant.as_str())
+            .collect()
+    }
+
+    /**
+    Whether all expected variants have been used by values streamed so far.
+    */
+    pub fn is_all_handled(&self) -> bool {
+        self.expected.is_subset(&self.handled)
+    }
+
+    fn variant(&mut self, label: Option<&Label>) {
+        if self.is_variant_next {
+            self.is_variant_next = false;
+
+            if let Some(label) = label {
+                if !self.handled.contains(label.as_str()) {
+                    self.handled.insert(label.as_str().into());
+                }
+            }
+        }
+    }
+}
+
+impl<'sval> Stream<'sval> for RequireAllVariantsHandled {
+    fn null(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn bool(&mut self, _: bool) -> Result {
+        Ok(())
+    }
+
+    fn text_begin(&mut self, _: Option<usize>) -> Result {
+        Ok(())
+    }
+
+    fn text_fragment_computed(&mut self, _: &str) -> Result {
+        Ok(())
+    }
+
+    fn text_end(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn binary_begin(&mut self, _: Option<usize>) -> Result {
+        Ok(())
+    }
+
+    fn binary_fragment_computed(&mut self, _: &[u8]) -> Result {
+        Ok(())
+    }
+
+    fn binary_end(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn i64(&mut self, _: i64) -> Result {
+        Ok(())
+    }
+
+    fn f64(&mut self, _: f64) -> Result {
+        Ok(())
+    }
+
+    fn map_begin(&mut self, _: Option<usize>) -> Result {
+        Ok(())
+    }
+
+    fn map_key_begin(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn map_key_end(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn map_value_begin(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn map_value_end(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn map_end(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn seq_begin(&mut self, _: Option<usize>) -> Result {
+        Ok(())
+    }
+
+    fn seq_value_begin(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn seq_value_end(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn seq_end(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn enum_begin(&mut self, _: Option<&Tag>, label: Option<&Label>, _: Option<&Index>) -> Result {
+        // Nested enums are variants of their parent
+        self.variant(label);
+        self.is_variant_next = true;
+
+        Ok(())
+    }
+
+    fn enum_end(&mut self, _: Option<&Tag>, _: Option<&Label>, _: Option<&Index>) -> Result {
+        self.is_variant_next = false;
+
+        Ok(())
+    }
+
+    fn tagged_begin(
+        &mut self,
+        _: Option<&Tag>,
+        label: Option<&Label>,
+        _: Option<&Index>,
+    ) -> Result {
+        self.variant(label);
+
+        Ok(())
+    }
+
+    fn tagged_end(&mut self, _: Option<&Tag>, _: Option<&Label>, _: Option<&Index>) -> Result {
+        Ok(())
+    }
+
+    fn tag(&mut self, _: Option<&Tag>, label: Option<&Label>, _: Option<&Index>) -> Result {
+        self.variant(label);
+
+        Ok(())
+    }
+
+    fn record_begin(
+        &mut self,
+        _: Option<&Tag>,
+        label: Option<&Label>,
+        _: Option<&Index>,
+        _: Option<usize>,
+    ) -> Result {
+        self.variant(label);
+
+        Ok(())
+    }
+
+    fn record_value_begin(&mut self, _: Option<&Tag>, _: &Label) -> Result {
+        Ok(())
+    }
+
+    fn record_value_end(&mut self, _: Option<&Tag>, _: &Label) -> Result {
+        Ok(())
+    }
+
+    fn record_end(&mut self, _: Option<&Tag>, _: Option<&Label>, _: Option<&Index>) -> Result {
+        Ok(())
+    }
+
+    fn tuple_begin(
+        &mut self,
+        _: Option<&Tag>,
+        label: Option<&Label>,
+        _: Option<&Index>,
+        _: Option<usize>,
+    ) -> Result {
+        self.variant(label);
+
+        Ok(())
+    }
+
+    fn tuple_value_begin(&mut self, _: Option<&Tag>, _: &Index) -> Result {
+        Ok(())
+    }
+
+    fn tuple_value_end(&mut self, _: Option<&Tag>, _: &Index) -> Result {
+        Ok(())
+    }
+
+    fn tuple_end(&mut self, _: Option<&Tag>, _: Option<&Label>, _: Option<&Index>) -> Result {
+        Ok(())
+    }
+
+    fn record_tuple_begin(
+        &mut self,
+        _: Option<&Tag>,
+        label: Option<&Label>,
+        _: Option<&Index>,
+        _: Option<usize>,
+    ) -> Result {
+        self.variant(label);
+
+        Ok(())
+    }
+
+    fn record_tuple_value_begin(&mut self, _: Option<&Tag>, _: &Label, _: &Index) -> Result {
+        Ok(())
+    }
+
+    fn record_tuple_value_end(&mut self, _: Option<&Tag>, _: &Label, _: &Index) -> Result {
+        Ok(())
+    }
+
+    fn record_tuple_end(
+        &mut self,
+        _: Option<&Tag>,
+        _: Option<&Label>,
+        _: Option<&Index>,
+    ) -> Result {
+        Ok(())
+    }
+}
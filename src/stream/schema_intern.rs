@@ -0,0 +1,224 @@
+use crate::{
+    std::{fmt, vec::Vec},
+    Error, Index, Label, Result, Stream, Tag,
+};
+
+/**
+Wrap a stream in a [`SchemaIntern`] that assigns ids to the distinct field layouts of records.
+*/
+pub fn schema_intern<'sval, S: Stream<'sval>, F: FnMut(InternedSchema)>(
+    stream: S,
+    f: F,
+) -> SchemaIntern<S, F> {
+    SchemaIntern::new(stream, f)
+}
+
+/**
+A [`Stream`] that interns the schemas of records, calling a function as each record ends.
+
+The schema of a record is the sequence of its field labels, in the order they're streamed.
+The first time a schema is seen it's assigned the next id, starting from `0`, and the function
+is called with an [`InternedSchema`] that [`is_new`](InternedSchema::is_new). Each later record
+with the same schema calls the function with the same id instead.
+
+This is useful for formats that write many records with the same layout, like columnar
+exports, so they can write a record's labels once and refer to them by id afterwards.
+The function is called when the record ends, just before the record's end is forwarded
+to the wrapped stream. Nested records end before the records that contain them, so they're
+interned first. Record tuples are interned the same way as records.
+
+All data is forwarded to the wrapped stream unchanged.
+
+Schemas are compared by searching the ones already seen, so this type is best suited to data
+with a small number of distinct schemas. It requires the `alloc` feature.
+*/
+pub struct SchemaIntern<S, F> {
+    stream: S,
+    f: F,
+    records: Vec<Vec<Label<'static>>>,
+    schemas: Vec<Vec<Label<'static>>>,
+}
+
+/**
+The schema of a record, passed to the function of a [`SchemaIntern`].
+*/
+#[derive(Debug, Clone, Copy)]
+pub struct InternedSchema<'a> {
+    id: usize,
+    labels: &'a [Label<'static>],
+    is_new: bool,
+}
+
+impl<'a> InternedSchema<'a> {
+    /**
+    The id of the schema.
+
+    Records with the same sequence of field labels always have the same id.
+    */
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /**
+    The labels of the fields in the schema, in the order they were streamed.
+    */
+    pub fn labels(&self) -> &'a [Label<'static>] {
+        self.labels
+    }
+
+    /**
+    Whether this is the first record seen with this schema.
+    */
+    pub fn is_new(&self) -> bool {
+        self.is_new
+    }
+}
+
+impl<S, F> SchemaIntern<S, F> {
+    /**
+    Wrap a stream, interning the schemas of records and passing them to `f`.
+    */
+    pub fn new(stream: S, f: F) -> Self {
+        SchemaIntern {
+            stream,
+            f,
+            records: Vec::new(),
+            schemas: Vec::new(),
+        }
+    }
+
+    /**
+    Get the labels of the schema with the given id, if it's been seen.
+    */
+    pub fn schema(&self, id: usize) -> Option<&[Label<'static>]> {
+        self.schemas.get(id).map(|labels| &**labels)
+    }
+
+    /**
+    Get the wrapped stream.
+    */
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+impl<S, F: FnMut(InternedSchema)> SchemaIntern<S, F> {
+    fn record_begin(&mut self) {
+        self.records.push(Vec::new());
+    }
+
+    fn field(&mut self, label: &Label) -> Result {
+        let record = self.records.last_mut().ok_or_else(Error::new)?;
+        record.push(label.to_owned());
+
+        Ok(())
+    }
+
+    fn record_end(&mut self) -> Result {
+        let labels = self.records.pop().ok_or_else(Error::new)?;
+
+        match self.schemas.iter().position(|schema| *schema == labels) {
+            Some(id) => (self.f)(InternedSchema {
+                id,
+                labels: &self.schemas[id],
+                is_new: false,
+            }),
+            None => {
+                let id = self.schemas.len();
+                self.schemas.push(labels);
+
+                (self.f)(InternedSchema {
+                    id,
+                    labels: &self.schemas[id],
+                    is_new: true,
+                })
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<S: fmt::Debug, F> fmt::Debug for SchemaIntern<S, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SchemaIntern")
+            .field("stream", &self.stream)
+            .field("schemas", &self.schemas)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'sval, S: Stream<'sval>, F: FnMut(InternedSchema)> Stream<'sval> for SchemaIntern<S, F> {
+    forward_stream!(stream =>
+        null, bool, text_begin, text_fragment, text_fragment_computed, text_end, binary_begin,
+        binary_fragment, binary_fragment_computed, binary_end, u8, u16, u32, u64, u128, i8, i16,
+        i32, i64, i128, f32, f64, num, map_begin, map_key_begin, map_key_end, map_value_begin,
+        map_value_end, map_end, seq_begin, seq_value_begin, seq_value_end, seq_end, enum_begin,
+        enum_end, tagged_begin, tagged_end, tag, tag_hint, record_value_end, tuple_begin,
+        tuple_value_begin, tuple_value_end, tuple_end, record_tuple_value_end,
+    );
+
+    fn record_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+        num_entries: Option<usize>,
+    ) -> Result {
+        self.record_begin();
+
+        self.stream.record_begin(tag, label, index, num_entries)
+    }
+
+    fn record_value_begin(&mut self, tag: Option<&Tag>, label: &Label) -> Result {
+        self.field(label)?;
+
+        self.stream.record_value_begin(tag, label)
+    }
+
+    fn record_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.record_end()?;
+
+        self.stream.record_end(tag, label, index)
+    }
+
+    fn record_tuple_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+        num_entries: Option<usize>,
+    ) -> Result {
+        self.record_begin();
+
+        self.stream
+            .record_tuple_begin(tag, label, index, num_entries)
+    }
+
+    fn record_tuple_value_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: &Label,
+        index: &Index,
+    ) -> Result {
+        self.field(label)?;
+
+        self.stream.record_tuple_value_begin(tag, label, index)
+    }
+
+    fn record_tuple_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.record_end()?;
+
+        self.stream.record_tuple_end(tag, label, index)
+    }
+}
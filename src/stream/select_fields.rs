@@ -0,0 +1,440 @@
+use crate::{
+    std::{collections::BTreeMap, string::String, vec::Vec},
+    Index, Label, Number, Result, Stream, Tag,
+};
+
+/**
+A set of record fields to select with [`SelectFields`].
+
+Each selected field may have its own nested selection that's applied to records in its value.
+A field without a nested selection is streamed in full.
+*/
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FieldSelection {
+    fields: BTreeMap<String, FieldSelection>,
+}
+
+impl FieldSelection {
+    /**
+    Create an empty selection.
+    */
+    pub fn new() -> Self {
+        FieldSelection::default()
+    }
+
+    /**
+    Select a field and everything in its value.
+    */
+    pub fn field(self, label: impl Into<String>) -> Self {
+        self.field_with(label, FieldSelection::new())
+    }
+
+    /**
+    Select a field, applying a nested selection to records in its value.
+    */
+    pub fn field_with(mut self, label: impl Into<String>, selection: FieldSelection) -> Self {
+        self.fields.insert(label.into(), selection);
+        self
+    }
+
+    fn select(&self, label: &Label) -> Option<Option<&FieldSelection>> {
+        self.fields.get(label.as_str()).map(|selection| {
+            if selection.fields.is_empty() {
+                None
+            } else {
+                Some(selection)
+            }
+        })
+    }
+}
+
+/**
+A [`Stream`] that only forwards the selected fields of records.
+
+The outermost record is filtered using the given [`FieldSelection`]. Fields that aren't selected
+are skipped along with their values. Records within a selected field's value are filtered using
+that field's nested selection, if it has one. Since the number of selected fields isn't known
+upfront, filtered records are forwarded without a length hint.
+
+This type requires the `alloc` feature.
+*/
+#[derive(Debug)]
+pub struct SelectFields<'a, S> {
+    stream: S,
+    scopes: Vec<Option<&'a FieldSelection>>,
+    skipping: Option<usize>,
+}
+
+impl<'a, S> SelectFields<'a, S> {
+    /**
+    Wrap a stream, selecting fields from records streamed through it.
+    */
+    pub fn new(stream: S, selection: &'a FieldSelection) -> Self {
+        SelectFields {
+            stream,
+            scopes: Vec::from([Some(selection)]),
+            skipping: None,
+        }
+    }
+
+    /**
+    Get the wrapped stream.
+    */
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+
+    fn scope(&self) -> Option<&'a FieldSelection> {
+        self.scopes.last().copied().flatten()
+    }
+
+    fn forward(&mut self, f: impl FnOnce(&mut S) -> Result) -> Result {
+        if self.skipping.is_some() {
+            Ok(())
+        } else {
+            f(&mut self.stream)
+        }
+    }
+
+    fn record_begin_hint(&self, num_entries: Option<usize>) -> Option<usize> {
+        if self.scope().is_some() {
+            None
+        } else {
+            num_entries
+        }
+    }
+
+    fn field_begin(&mut self, label: &Label, f: impl FnOnce(&mut S) -> Result) -> Result {
+        if let Some(ref mut depth) = self.skipping {
+            *depth += 1;
+            return Ok(());
+        }
+
+        match self.scope() {
+            Some(selection) => match selection.select(label) {
+                Some(selection) => {
+                    self.scopes.push(selection);
+                    f(&mut self.stream)
+                }
+                None => {
+                    self.skipping = Some(0);
+                    Ok(())
+                }
+            },
+            None => {
+                self.scopes.push(None);
+                f(&mut self.stream)
+            }
+        }
+    }
+
+    fn field_end(&mut self, f: impl FnOnce(&mut S) -> Result) -> Result {
+        match self.skipping {
+            Some(0) => {
+                self.skipping = None;
+                Ok(())
+            }
+            Some(ref mut depth) => {
+                *depth -= 1;
+                Ok(())
+            }
+            None => {
+                self.scopes.pop();
+                f(&mut self.stream)
+            }
+        }
+    }
+
+    fn record_begin_scope(&mut self) {
+        if self.skipping.is_none() {
+            self.scopes.push(self.scope());
+        }
+    }
+
+    fn record_end_scope(&mut self) {
+        if self.skipping.is_none() {
+            self.scopes.pop();
+        }
+    }
+}
+
+impl<'a, 'sval, S: Stream<'sval>> Stream<'sval> for SelectFields<'a, S> {
+    fn null(&mut self) -> Result {
+        self.forward(|stream| stream.null())
+    }
+
+    fn bool(&mut self, value: bool) -> Result {
+        self.forward(|stream| stream.bool(value))
+    }
+
+    fn text_begin(&mut self, num_bytes: Option<usize>) -> Result {
+        self.forward(|stream| stream.text_begin(num_bytes))
+    }
+
+    fn text_fragment(&mut self, fragment: &'sval str) -> Result {
+        self.forward(|stream| stream.text_fragment(fragment))
+    }
+
+    fn text_fragment_computed(&mut self, fragment: &str) -> Result {
+        self.forward(|stream| stream.text_fragment_computed(fragment))
+    }
+
+    fn text_end(&mut self) -> Result {
+        self.forward(|stream| stream.text_end())
+    }
+
+    fn binary_begin(&mut self, num_bytes: Option<usize>) -> Result {
+        self.forward(|stream| stream.binary_begin(num_bytes))
+    }
+
+    fn binary_fragment(&mut self, fragment: &'sval [u8]) -> Result {
+        self.forward(|stream| stream.binary_fragment(fragment))
+    }
+
+    fn binary_fragment_computed(&mut self, fragment: &[u8]) -> Result {
+        self.forward(|stream| stream.binary_fragment_computed(fragment))
+    }
+
+    fn binary_end(&mut self) -> Result {
+        self.forward(|stream| stream.binary_end())
+    }
+
+    fn u8(&mut self, value: u8) -> Result {
+        self.forward(|stream| stream.u8(value))
+    }
+
+    fn u16(&mut self, value: u16) -> Result {
+        self.forward(|stream| stream.u16(value))
+    }
+
+    fn u32(&mut self, value: u32) -> Result {
+        self.forward(|stream| stream.u32(value))
+    }
+
+    fn u64(&mut self, value: u64) -> Result {
+        self.forward(|stream| stream.u64(value))
+    }
+
+    fn u128(&mut self, value: u128) -> Result {
+        self.forward(|stream| stream.u128(value))
+    }
+
+    fn i8(&mut self, value: i8) -> Result {
+        self.forward(|stream| stream.i8(value))
+    }
+
+    fn i16(&mut self, value: i16) -> Result {
+        self.forward(|stream| stream.i16(value))
+    }
+
+    fn i32(&mut self, value: i32) -> Result {
+        self.forward(|stream| stream.i32(value))
+    }
+
+    fn i64(&mut self, value: i64) -> Result {
+        self.forward(|stream| stream.i64(value))
+    }
+
+    fn i128(&mut self, value: i128) -> Result {
+        self.forward(|stream| stream.i128(value))
+    }
+
+    fn f32(&mut self, value: f32) -> Result {
+        self.forward(|stream| stream.f32(value))
+    }
+
+    fn f64(&mut self, value: f64) -> Result {
+        self.forward(|stream| stream.f64(value))
+    }
+
+    fn num(&mut self, num: &Number) -> Result {
+        self.forward(|stream| stream.num(num))
+    }
+
+    fn map_begin(&mut self, num_entries: Option<usize>) -> Result {
+        self.forward(|stream| stream.map_begin(num_entries))
+    }
+
+    fn map_key_begin(&mut self) -> Result {
+        self.forward(|stream| stream.map_key_begin())
+    }
+
+    fn map_key_end(&mut self) -> Result {
+        self.forward(|stream| stream.map_key_end())
+    }
+
+    fn map_value_begin(&mut self) -> Result {
+        self.forward(|stream| stream.map_value_begin())
+    }
+
+    fn map_value_end(&mut self) -> Result {
+        self.forward(|stream| stream.map_value_end())
+    }
+
+    fn map_end(&mut self) -> Result {
+        self.forward(|stream| stream.map_end())
+    }
+
+    fn seq_begin(&mut self, num_entries: Option<usize>) -> Result {
+        self.forward(|stream| stream.seq_begin(num_entries))
+    }
+
+    fn seq_value_begin(&mut self) -> Result {
+        self.forward(|stream| stream.seq_value_begin())
+    }
+
+    fn seq_value_end(&mut self) -> Result {
+        self.forward(|stream| stream.seq_value_end())
+    }
+
+    fn seq_end(&mut self) -> Result {
+        self.forward(|stream| stream.seq_end())
+    }
+
+    fn enum_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.forward(|stream| stream.enum_begin(tag, label, index))
+    }
+
+    fn enum_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.forward(|stream| stream.enum_end(tag, label, index))
+    }
+
+    fn tagged_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.forward(|stream| stream.tagged_begin(tag, label, index))
+    }
+
+    fn tagged_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.forward(|stream| stream.tagged_end(tag, label, index))
+    }
+
+    fn tag(&mut self, tag: Option<&Tag>, label: Option<&Label>, index: Option<&Index>) -> Result {
+        self.forward(|stream| stream.tag(tag, label, index))
+    }
+
+    fn tag_hint(&mut self, tag: &Tag) -> Result {
+        self.forward(|stream| stream.tag_hint(tag))
+    }
+
+    fn record_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+        num_entries: Option<usize>,
+    ) -> Result {
+        let num_entries = self.record_begin_hint(num_entries);
+        self.record_begin_scope();
+
+        self.forward(|stream| stream.record_begin(tag, label, index, num_entries))
+    }
+
+    fn record_value_begin(&mut self, tag: Option<&Tag>, label: &Label) -> Result {
+        self.field_begin(label, |stream| stream.record_value_begin(tag, label))
+    }
+
+    fn record_value_end(&mut self, tag: Option<&Tag>, label: &Label) -> Result {
+        self.field_end(|stream| stream.record_value_end(tag, label))
+    }
+
+    fn record_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.record_end_scope();
+
+        self.forward(|stream| stream.record_end(tag, label, index))
+    }
+
+    fn tuple_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+        num_entries: Option<usize>,
+    ) -> Result {
+        self.forward(|stream| stream.tuple_begin(tag, label, index, num_entries))
+    }
+
+    fn tuple_value_begin(&mut self, tag: Option<&Tag>, index: &Index) -> Result {
+        self.forward(|stream| stream.tuple_value_begin(tag, index))
+    }
+
+    fn tuple_value_end(&mut self, tag: Option<&Tag>, index: &Index) -> Result {
+        self.forward(|stream| stream.tuple_value_end(tag, index))
+    }
+
+    fn tuple_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.forward(|stream| stream.tuple_end(tag, label, index))
+    }
+
+    fn record_tuple_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+        num_entries: Option<usize>,
+    ) -> Result {
+        let num_entries = self.record_begin_hint(num_entries);
+        self.record_begin_scope();
+
+        self.forward(|stream| stream.record_tuple_begin(tag, label, index, num_entries))
+    }
+
+    fn record_tuple_value_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: &Label,
+        index: &Index,
+    ) -> Result {
+        self.field_begin(label, |stream| {
+            stream.record_tuple_value_begin(tag, label, index)
+        })
+    }
+
+    fn record_tuple_value_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: &Label,
+        index: &Index,
+    ) -> Result {
+        self.field_end(|stream| stream.record_tuple_value_end(tag, label, index))
+    }
+
+    fn record_tuple_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.record_end_scope();
+
+        self.forward(|stream| stream.record_tuple_end(tag, label, index))
+    }
+}
@@ -0,0 +1,364 @@
+use crate::{
+    std::{string::String, vec::Vec},
+    Error, Index, Label, Result, Stream, Tag, Value,
+};
+
+/**
+The kind of a value observed by [`Shape`].
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ShapeKind {
+    /**
+    A map.
+    */
+    Map,
+    /**
+    A sequence.
+    */
+    Seq,
+    /**
+    A record or record tuple, with labelled fields.
+    */
+    Record,
+    /**
+    A tuple.
+    */
+    Tuple,
+    /**
+    An enum.
+    */
+    Enum,
+    /**
+    A null, boolean, number, text, binary, or tag.
+    */
+    Scalar,
+}
+
+/**
+The top-level structure of a value collected by [`Shape`].
+
+This type requires the `alloc` feature.
+*/
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShapeInfo {
+    kind: Option<ShapeKind>,
+    fields: Vec<String>,
+}
+
+impl ShapeInfo {
+    /**
+    The kind of the value.
+
+    This will be `None` if no value was streamed.
+    */
+    pub fn kind(&self) -> Option<ShapeKind> {
+        self.kind
+    }
+
+    /**
+    The labels of the fields of a record, in the order they were streamed.
+
+    This will be empty for values that aren't records.
+    */
+    pub fn fields(&self) -> &[String] {
+        &self.fields
+    }
+}
+
+/**
+A [`Stream`] that collects the top-level structure of a value without buffering it.
+
+Tagged values are looked through, so an `Option` or newtype has the shape of the value
+it wraps. Records are streamed to their end to collect their field labels, but the values
+of those fields are ignored. For any other kind of value, there's nothing more to learn after
+it begins, so `Shape` stops streaming early by returning an error.
+
+That early stop looks like any other failure to the caller of [`Value::stream`], so check
+[`Shape::is_stopped`] to tell them apart, or use [`Shape::collect`], which does that for you.
+
+This type requires the `alloc` feature.
+*/
+#[derive(Debug, Default)]
+pub struct Shape {
+    info: ShapeInfo,
+    depth: usize,
+    is_stopped: bool,
+}
+
+impl Shape {
+    /**
+    Create a stream that hasn't observed a value.
+    */
+    pub fn new() -> Self {
+        Shape::default()
+    }
+
+    /**
+    Collect the top-level structure of a value.
+
+    Stopping early isn't treated as an error. This method only fails if the value itself fails.
+    */
+    pub fn collect(v: &(impl Value + ?Sized)) -> Result<ShapeInfo> {
+        let mut shape = Shape::new();
+
+        match v.stream(&mut shape) {
+            Ok(()) => Ok(shape.finish()),
+            Err(_) if shape.is_stopped() => Ok(shape.finish()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /**
+    Whether streaming was stopped early because the structure of the value was known.
+
+    If this method returns `true` then an error from streaming came from `Shape` itself,
+    rather than from the value, and can be ignored.
+    */
+    pub fn is_stopped(&self) -> bool {
+        self.is_stopped
+    }
+
+    /**
+    Get the structure collected from the value.
+    */
+    pub fn finish(self) -> ShapeInfo {
+        self.info
+    }
+
+    fn stop(&mut self) -> Result {
+        self.is_stopped = true;
+
+        Err(Error::new())
+    }
+
+    fn scalar(&mut self) -> Result {
+        if self.depth > 0 || self.info.kind.is_some() {
+            return Ok(());
+        }
+
+        self.info.kind = Some(ShapeKind::Scalar);
+        self.stop()
+    }
+
+    fn container_begin(&mut self, kind: ShapeKind) -> Result {
+        if self.depth > 0 || self.info.kind.is_some() {
+            self.depth += 1;
+
+            return Ok(());
+        }
+
+        self.info.kind = Some(kind);
+
+        if kind == ShapeKind::Record {
+            self.depth = 1;
+
+            Ok(())
+        } else {
+            self.stop()
+        }
+    }
+
+    fn container_end(&mut self) -> Result {
+        self.depth = self.depth.saturating_sub(1);
+
+        Ok(())
+    }
+
+    fn field(&mut self, label: &Label) -> Result {
+        if self.depth == 1 && self.info.kind == Some(ShapeKind::Record) {
+            self.info.fields.push(String::from(label.as_str()));
+        }
+
+        Ok(())
+    }
+}
+
+impl<'sval> Stream<'sval> for Shape {
+    fn null(&mut self) -> Result {
+        self.scalar()
+    }
+
+    fn bool(&mut self, _: bool) -> Result {
+        self.scalar()
+    }
+
+    fn text_begin(&mut self, _: Option<usize>) -> Result {
+        self.scalar()
+    }
+
+    fn text_fragment_computed(&mut self, _: &str) -> Result {
+        Ok(())
+    }
+
+    fn text_end(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn binary_begin(&mut self, _: Option<usize>) -> Result {
+        self.scalar()
+    }
+
+    fn binary_fragment_computed(&mut self, _: &[u8]) -> Result {
+        Ok(())
+    }
+
+    fn binary_end(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn i64(&mut self, _: i64) -> Result {
+        self.scalar()
+    }
+
+    fn u64(&mut self, _: u64) -> Result {
+        self.scalar()
+    }
+
+    fn i128(&mut self, _: i128) -> Result {
+        self.scalar()
+    }
+
+    fn u128(&mut self, _: u128) -> Result {
+        self.scalar()
+    }
+
+    fn f64(&mut self, _: f64) -> Result {
+        self.scalar()
+    }
+
+    fn map_begin(&mut self, _: Option<usize>) -> Result {
+        self.container_begin(ShapeKind::Map)
+    }
+
+    fn map_key_begin(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn map_key_end(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn map_value_begin(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn map_value_end(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn map_end(&mut self) -> Result {
+        self.container_end()
+    }
+
+    fn seq_begin(&mut self, _: Option<usize>) -> Result {
+        self.container_begin(ShapeKind::Seq)
+    }
+
+    fn seq_value_begin(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn seq_value_end(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn seq_end(&mut self) -> Result {
+        self.container_end()
+    }
+
+    fn enum_begin(&mut self, _: Option<&Tag>, _: Option<&Label>, _: Option<&Index>) -> Result {
+        self.container_begin(ShapeKind::Enum)
+    }
+
+    fn enum_end(&mut self, _: Option<&Tag>, _: Option<&Label>, _: Option<&Index>) -> Result {
+        self.container_end()
+    }
+
+    fn tagged_begin(&mut self, _: Option<&Tag>, _: Option<&Label>, _: Option<&Index>) -> Result {
+        // Tagged values at the top level are looked through
+        if self.depth > 0 {
+            self.depth += 1;
+        }
+
+        Ok(())
+    }
+
+    fn tagged_end(&mut self, _: Option<&Tag>, _: Option<&Label>, _: Option<&Index>) -> Result {
+        self.container_end()
+    }
+
+    fn tag(&mut self, _: Option<&Tag>, _: Option<&Label>, _: Option<&Index>) -> Result {
+        self.scalar()
+    }
+
+    fn record_begin(
+        &mut self,
+        _: Option<&Tag>,
+        _: Option<&Label>,
+        _: Option<&Index>,
+        _: Option<usize>,
+    ) -> Result {
+        self.container_begin(ShapeKind::Record)
+    }
+
+    fn record_value_begin(&mut self, _: Option<&Tag>, label: &Label) -> Result {
+        self.field(label)
+    }
+
+    fn record_value_end(&mut self, _: Option<&Tag>, _: &Label) -> Result {
+        Ok(())
+    }
+
+    fn record_end(&mut self, _: Option<&Tag>, _: Option<&Label>, _: Option<&Index>) -> Result {
+        self.container_end()
+    }
+
+    fn tuple_begin(
+        &mut self,
+        _: Option<&Tag>,
+        _: Option<&Label>,
+        _: Option<&Index>,
+        _: Option<usize>,
+    ) -> Result {
+        self.container_begin(ShapeKind::Tuple)
+    }
+
+    fn tuple_value_begin(&mut self, _: Option<&Tag>, _: &Index) -> Result {
+        Ok(())
+    }
+
+    fn tuple_value_end(&mut self, _: Option<&Tag>, _: &Index) -> Result {
+        Ok(())
+    }
+
+    fn tuple_end(&mut self, _: Option<&Tag>, _: Option<&Label>, _: Option<&Index>) -> Result {
+        self.container_end()
+    }
+
+    fn record_tuple_begin(
+        &mut self,
+        _: Option<&Tag>,
+        _: Option<&Label>,
+        _: Option<&Index>,
+        _: Option<usize>,
+    ) -> Result {
+        self.container_begin(ShapeKind::Record)
+    }
+
+    fn record_tuple_value_begin(&mut self, _: Option<&Tag>, label: &Label, _: &Index) -> Result {
+        self.field(label)
+    }
+
+    fn record_tuple_value_end(&mut self, _: Option<&Tag>, _: &Label, _: &Index) -> Result {
+        Ok(())
+    }
+
+    fn record_tuple_end(
+        &mut self,
+        _: Option<&Tag>,
+        _: Option<&Label>,
+        _: Option<&Index>,
+    ) -> Result {
+        self.container_end()
+    }
+}
@@ -0,0 +1,99 @@
+use crate::{std::string::String, Error, Result, Stream};
+
+/**
+Wrap a stream in a [`SizeText`] that forwards text values with their total length.
+*/
+pub fn size_text<'sval, S: Stream<'sval>>(stream: S) -> SizeText<S> {
+    SizeText::new(stream)
+}
+
+/**
+A [`Stream`] that buffers text values so they can be forwarded with their total length.
+
+Values that compute their text in pieces often can't give a length hint upfront, which
+makes it harder for encoders to preallocate space for them. This stream buffers all the
+fragments of each text value, then forwards it to the wrapped stream as a single fragment,
+with a length hint of the total number of bytes.
+
+The trade-off is that every text value is copied into a buffer before it's forwarded,
+even if it was already sized or borrowed. The buffer is reused between text values.
+All other data is forwarded to the wrapped stream unchanged.
+
+This type requires the `alloc` feature.
+*/
+#[derive(Debug)]
+pub struct SizeText<S> {
+    stream: S,
+    text: String,
+    is_text: bool,
+}
+
+impl<S> SizeText<S> {
+    /**
+    Wrap a stream, forwarding text values to it with their total length.
+    */
+    pub fn new(stream: S) -> Self {
+        SizeText {
+            stream,
+            text: String::new(),
+            is_text: false,
+        }
+    }
+
+    /**
+    Get the wrapped stream.
+    */
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+impl<'sval, S: Stream<'sval>> Stream<'sval> for SizeText<S> {
+    forward_stream!(stream =>
+        null, bool, binary_begin, binary_fragment, binary_fragment_computed, binary_end, u8, u16,
+        u32, u64, u128, i8, i16, i32, i64, i128, f32, f64, num, map_begin, map_key_begin,
+        map_key_end, map_value_begin, map_value_end, map_end, seq_begin, seq_value_begin,
+        seq_value_end, seq_end, enum_begin, enum_end, tagged_begin, tagged_end, tag, tag_hint,
+        record_begin, record_value_begin, record_value_end, record_end, tuple_begin,
+        tuple_value_begin, tuple_value_end, tuple_end, record_tuple_begin, record_tuple_value_begin,
+        record_tuple_value_end, record_tuple_end,
+    );
+
+    fn text_begin(&mut self, num_bytes: Option<usize>) -> Result {
+        self.text.clear();
+        self.text.reserve(num_bytes.unwrap_or(0));
+        self.is_text = true;
+
+        Ok(())
+    }
+
+    fn text_fragment(&mut self, fragment: &'sval str) -> Result {
+        self.text_fragment_computed(fragment)
+    }
+
+    fn text_fragment_computed(&mut self, fragment: &str) -> Result {
+        if !self.is_text {
+            return Err(Error::new());
+        }
+
+        self.text.push_str(fragment);
+
+        Ok(())
+    }
+
+    fn text_end(&mut self) -> Result {
+        if !self.is_text {
+            return Err(Error::new());
+        }
+
+        self.is_text = false;
+
+        self.stream.text_begin(Some(self.text.len()))?;
+
+        if !self.text.is_empty() {
+            self.stream.text_fragment_computed(&self.text)?;
+        }
+
+        self.stream.text_end()
+    }
+}
@@ -0,0 +1,360 @@
+use crate::{Index, Label, Number, Result, Stream, Tag};
+
+/**
+Wrap a pair of streams in a [`Tee`] that forwards everything it receives to both of them.
+*/
+pub fn tee<'sval, A: Stream<'sval>, B: Stream<'sval>>(a: A, b: B) -> Tee<A, B> {
+    Tee::new(a, b)
+}
+
+/**
+A [`Stream`] that forwards everything it receives to two other streams.
+
+Each call is forwarded to the first stream and then to the second, so a value can be
+streamed into both in a single pass. Borrowed text and binary fragments are passed to
+both streams as-is, without being copied.
+
+If either stream fails, streaming stops and that error is returned. The other stream
+will have received a partial value and should be considered to be in an indeterminate state.
+*/
+#[derive(Debug)]
+pub struct Tee<A, B> {
+    a: A,
+    b: B,
+}
+
+impl<A, B> Tee<A, B> {
+    /**
+    Wrap a pair of streams, forwarding everything to both of them.
+    */
+    pub fn new(a: A, b: B) -> Self {
+        Tee { a, b }
+    }
+
+    /**
+    Get the wrapped streams.
+    */
+    pub fn into_inner(self) -> (A, B) {
+        (self.a, self.b)
+    }
+}
+
+impl<'sval, A: Stream<'sval>, B: Stream<'sval>> Stream<'sval> for Tee<A, B> {
+    fn null(&mut self) -> Result {
+        self.a.null()?;
+        self.b.null()
+    }
+
+    fn bool(&mut self, value: bool) -> Result {
+        self.a.bool(value)?;
+        self.b.bool(value)
+    }
+
+    fn text_begin(&mut self, num_bytes: Option<usize>) -> Result {
+        self.a.text_begin(num_bytes)?;
+        self.b.text_begin(num_bytes)
+    }
+
+    fn text_fragment(&mut self, fragment: &'sval str) -> Result {
+        self.a.text_fragment(fragment)?;
+        self.b.text_fragment(fragment)
+    }
+
+    fn text_fragment_computed(&mut self, fragment: &str) -> Result {
+        self.a.text_fragment_computed(fragment)?;
+        self.b.text_fragment_computed(fragment)
+    }
+
+    fn text_end(&mut self) -> Result {
+        self.a.text_end()?;
+        self.b.text_end()
+    }
+
+    fn binary_begin(&mut self, num_bytes: Option<usize>) -> Result {
+        self.a.binary_begin(num_bytes)?;
+        self.b.binary_begin(num_bytes)
+    }
+
+    fn binary_fragment(&mut self, fragment: &'sval [u8]) -> Result {
+        self.a.binary_fragment(fragment)?;
+        self.b.binary_fragment(fragment)
+    }
+
+    fn binary_fragment_computed(&mut self, fragment: &[u8]) -> Result {
+        self.a.binary_fragment_computed(fragment)?;
+        self.b.binary_fragment_computed(fragment)
+    }
+
+    fn binary_end(&mut self) -> Result {
+        self.a.binary_end()?;
+        self.b.binary_end()
+    }
+
+    fn u8(&mut self, value: u8) -> Result {
+        self.a.u8(value)?;
+        self.b.u8(value)
+    }
+
+    fn u16(&mut self, value: u16) -> Result {
+        self.a.u16(value)?;
+        self.b.u16(value)
+    }
+
+    fn u32(&mut self, value: u32) -> Result {
+        self.a.u32(value)?;
+        self.b.u32(value)
+    }
+
+    fn u64(&mut self, value: u64) -> Result {
+        self.a.u64(value)?;
+        self.b.u64(value)
+    }
+
+    fn u128(&mut self, value: u128) -> Result {
+        self.a.u128(value)?;
+        self.b.u128(value)
+    }
+
+    fn i8(&mut self, value: i8) -> Result {
+        self.a.i8(value)?;
+        self.b.i8(value)
+    }
+
+    fn i16(&mut self, value: i16) -> Result {
+        self.a.i16(value)?;
+        self.b.i16(value)
+    }
+
+    fn i32(&mut self, value: i32) -> Result {
+        self.a.i32(value)?;
+        self.b.i32(value)
+    }
+
+    fn i64(&mut self, value: i64) -> Result {
+        self.a.i64(value)?;
+        self.b.i64(value)
+    }
+
+    fn i128(&mut self, value: i128) -> Result {
+        self.a.i128(value)?;
+        self.b.i128(value)
+    }
+
+    fn f32(&mut self, value: f32) -> Result {
+        self.a.f32(value)?;
+        self.b.f32(value)
+    }
+
+    fn f64(&mut self, value: f64) -> Result {
+        self.a.f64(value)?;
+        self.b.f64(value)
+    }
+
+    fn num(&mut self, num: &Number) -> Result {
+        self.a.num(num)?;
+        self.b.num(num)
+    }
+
+    fn map_begin(&mut self, num_entries: Option<usize>) -> Result {
+        self.a.map_begin(num_entries)?;
+        self.b.map_begin(num_entries)
+    }
+
+    fn map_key_begin(&mut self) -> Result {
+        self.a.map_key_begin()?;
+        self.b.map_key_begin()
+    }
+
+    fn map_key_end(&mut self) -> Result {
+        self.a.map_key_end()?;
+        self.b.map_key_end()
+    }
+
+    fn map_value_begin(&mut self) -> Result {
+        self.a.map_value_begin()?;
+        self.b.map_value_begin()
+    }
+
+    fn map_value_end(&mut self) -> Result {
+        self.a.map_value_end()?;
+        self.b.map_value_end()
+    }
+
+    fn map_end(&mut self) -> Result {
+        self.a.map_end()?;
+        self.b.map_end()
+    }
+
+    fn seq_begin(&mut self, num_entries: Option<usize>) -> Result {
+        self.a.seq_begin(num_entries)?;
+        self.b.seq_begin(num_entries)
+    }
+
+    fn seq_value_begin(&mut self) -> Result {
+        self.a.seq_value_begin()?;
+        self.b.seq_value_begin()
+    }
+
+    fn seq_value_end(&mut self) -> Result {
+        self.a.seq_value_end()?;
+        self.b.seq_value_end()
+    }
+
+    fn seq_end(&mut self) -> Result {
+        self.a.seq_end()?;
+        self.b.seq_end()
+    }
+
+    fn enum_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.a.enum_begin(tag, label, index)?;
+        self.b.enum_begin(tag, label, index)
+    }
+
+    fn enum_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.a.enum_end(tag, label, index)?;
+        self.b.enum_end(tag, label, index)
+    }
+
+    fn tagged_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.a.tagged_begin(tag, label, index)?;
+        self.b.tagged_begin(tag, label, index)
+    }
+
+    fn tagged_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.a.tagged_end(tag, label, index)?;
+        self.b.tagged_end(tag, label, index)
+    }
+
+    fn tag(&mut self, tag: Option<&Tag>, label: Option<&Label>, index: Option<&Index>) -> Result {
+        self.a.tag(tag, label, index)?;
+        self.b.tag(tag, label, index)
+    }
+
+    fn tag_hint(&mut self, tag: &Tag) -> Result {
+        self.a.tag_hint(tag)?;
+        self.b.tag_hint(tag)
+    }
+
+    fn record_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+        num_entries: Option<usize>,
+    ) -> Result {
+        self.a.record_begin(tag, label, index, num_entries)?;
+        self.b.record_begin(tag, label, index, num_entries)
+    }
+
+    fn record_value_begin(&mut self, tag: Option<&Tag>, label: &Label) -> Result {
+        self.a.record_value_begin(tag, label)?;
+        self.b.record_value_begin(tag, label)
+    }
+
+    fn record_value_end(&mut self, tag: Option<&Tag>, label: &Label) -> Result {
+        self.a.record_value_end(tag, label)?;
+        self.b.record_value_end(tag, label)
+    }
+
+    fn record_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.a.record_end(tag, label, index)?;
+        self.b.record_end(tag, label, index)
+    }
+
+    fn tuple_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+        num_entries: Option<usize>,
+    ) -> Result {
+        self.a.tuple_begin(tag, label, index, num_entries)?;
+        self.b.tuple_begin(tag, label, index, num_entries)
+    }
+
+    fn tuple_value_begin(&mut self, tag: Option<&Tag>, index: &Index) -> Result {
+        self.a.tuple_value_begin(tag, index)?;
+        self.b.tuple_value_begin(tag, index)
+    }
+
+    fn tuple_value_end(&mut self, tag: Option<&Tag>, index: &Index) -> Result {
+        self.a.tuple_value_end(tag, index)?;
+        self.b.tuple_value_end(tag, index)
+    }
+
+    fn tuple_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.a.tuple_end(tag, label, index)?;
+        self.b.tuple_end(tag, label, index)
+    }
+
+    fn record_tuple_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+        num_entries: Option<usize>,
+    ) -> Result {
+        self.a.record_tuple_begin(tag, label, index, num_entries)?;
+        self.b.record_tuple_begin(tag, label, index, num_entries)
+    }
+
+    fn record_tuple_value_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: &Label,
+        index: &Index,
+    ) -> Result {
+        self.a.record_tuple_value_begin(tag, label, index)?;
+        self.b.record_tuple_value_begin(tag, label, index)
+    }
+
+    fn record_tuple_value_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: &Label,
+        index: &Index,
+    ) -> Result {
+        self.a.record_tuple_value_end(tag, label, index)?;
+        self.b.record_tuple_value_end(tag, label, index)
+    }
+
+    fn record_tuple_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.a.record_tuple_end(tag, label, index)?;
+        self.b.record_tuple_end(tag, label, index)
+    }
+}
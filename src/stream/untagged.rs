@@ -0,0 +1,176 @@
+use crate::{tags, Index, Label, Result, Stream, Tag};
+
+/**
+Wrap a stream in an [`Untagged`] that strips tags from everything it receives.
+*/
+pub fn untagged<'sval, S: Stream<'sval>>(stream: S) -> Untagged<S> {
+    Untagged::new(stream)
+}
+
+/**
+A [`Stream`] that strips tags from values before forwarding them to another stream.
+
+This is useful for formats that have no way to represent tags, like plain key-value text.
+Tag hints are dropped, and tagged values and enums are unwrapped so their inner value is
+forwarded directly. So `Some(1)` is streamed as just `1`. Maps, sequences, records, and
+tuples are forwarded with any tags removed, but keep their labels and indexes.
+
+A standalone [`Stream::tag`], like `None` or a unit enum variant, is converted into a plain
+value. `None` and `()` become `null`. Other tags become their label as text, or their index
+as an integer if they don't have a label, or `null` if they have neither.
+This matches how `sval_json` writes standalone tags.
+
+Stripping tags is lossy. Values that use tags to carry meaning, like numbers stored as
+text, will be forwarded without it.
+*/
+#[derive(Debug)]
+pub struct Untagged<S> {
+    stream: S,
+}
+
+impl<S> Untagged<S> {
+    /**
+    Wrap a stream, stripping tags from everything forwarded to it.
+    */
+    pub fn new(stream: S) -> Self {
+        Untagged { stream }
+    }
+
+    /**
+    Get the wrapped stream.
+    */
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+impl<'sval, S: Stream<'sval>> Stream<'sval> for Untagged<S> {
+    forward_stream!(stream =>
+        null, bool, text_begin, text_fragment, text_fragment_computed, text_end, binary_begin,
+        binary_fragment, binary_fragment_computed, binary_end, u8, u16, u32, u64, u128, i8, i16,
+        i32, i64, i128, f32, f64, num, map_begin, map_key_begin, map_key_end, map_value_begin,
+        map_value_end, map_end, seq_begin, seq_value_begin, seq_value_end, seq_end,
+    );
+
+    fn enum_begin(&mut self, _: Option<&Tag>, _: Option<&Label>, _: Option<&Index>) -> Result {
+        Ok(())
+    }
+
+    fn enum_end(&mut self, _: Option<&Tag>, _: Option<&Label>, _: Option<&Index>) -> Result {
+        Ok(())
+    }
+
+    fn tagged_begin(&mut self, _: Option<&Tag>, _: Option<&Label>, _: Option<&Index>) -> Result {
+        Ok(())
+    }
+
+    fn tagged_end(&mut self, _: Option<&Tag>, _: Option<&Label>, _: Option<&Index>) -> Result {
+        Ok(())
+    }
+
+    fn tag(&mut self, tag: Option<&Tag>, label: Option<&Label>, index: Option<&Index>) -> Result {
+        match tag {
+            Some(&tags::RUST_OPTION_NONE) | Some(&tags::RUST_UNIT) => self.stream.null(),
+            _ => {
+                if let Some(label) = label {
+                    self.stream.value_computed(label.as_str())
+                } else if let Some(index) = index.and_then(|index| index.to_i64()) {
+                    self.stream.i64(index)
+                } else {
+                    self.stream.null()
+                }
+            }
+        }
+    }
+
+    fn tag_hint(&mut self, _: &Tag) -> Result {
+        Ok(())
+    }
+
+    fn record_begin(
+        &mut self,
+        _: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+        num_entries: Option<usize>,
+    ) -> Result {
+        self.stream.record_begin(None, label, index, num_entries)
+    }
+
+    fn record_value_begin(&mut self, _: Option<&Tag>, label: &Label) -> Result {
+        self.stream.record_value_begin(None, label)
+    }
+
+    fn record_value_end(&mut self, _: Option<&Tag>, label: &Label) -> Result {
+        self.stream.record_value_end(None, label)
+    }
+
+    fn record_end(
+        &mut self,
+        _: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.stream.record_end(None, label, index)
+    }
+
+    fn tuple_begin(
+        &mut self,
+        _: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+        num_entries: Option<usize>,
+    ) -> Result {
+        self.stream.tuple_begin(None, label, index, num_entries)
+    }
+
+    fn tuple_value_begin(&mut self, _: Option<&Tag>, index: &Index) -> Result {
+        self.stream.tuple_value_begin(None, index)
+    }
+
+    fn tuple_value_end(&mut self, _: Option<&Tag>, index: &Index) -> Result {
+        self.stream.tuple_value_end(None, index)
+    }
+
+    fn tuple_end(
+        &mut self,
+        _: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.stream.tuple_end(None, label, index)
+    }
+
+    fn record_tuple_begin(
+        &mut self,
+        _: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+        num_entries: Option<usize>,
+    ) -> Result {
+        self.stream
+            .record_tuple_begin(None, label, index, num_entries)
+    }
+
+    fn record_tuple_value_begin(
+        &mut self,
+        _: Option<&Tag>,
+        label: &Label,
+        index: &Index,
+    ) -> Result {
+        self.stream.record_tuple_value_begin(None, label, index)
+    }
+
+    fn record_tuple_value_end(&mut self, _: Option<&Tag>, label: &Label, index: &Index) -> Result {
+        self.stream.record_tuple_value_end(None, label, index)
+    }
+
+    fn record_tuple_end(
+        &mut self,
+        _: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.stream.record_tuple_end(None, label, index)
+    }
+}
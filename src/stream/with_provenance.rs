@@ -0,0 +1,610 @@
+use core::cell::Cell;
+
+use crate::{default_stream, tags, Error, Index, Label, Result, Stream, Tag, Value};
+
+/**
+A [`Stream`] that injects metadata fields into every top-level record.
+
+The metadata is given as a [`Value`] that streams a record, such as a struct with fields like
+`_source` and `_ingested_at`. Its fields are streamed into each top-level record before that
+record's own fields. Nested records are forwarded unchanged. Since the number of injected fields
+isn't known upfront, top-level records are forwarded without a length hint. The indexes of fields
+in record tuples are shifted to follow the injected fields.
+
+This type requires the `alloc` feature.
+*/
+#[derive(Debug)]
+pub struct WithProvenance<'a, S, P: ?Sized> {
+    stream: S,
+    provenance: &'a P,
+    depth: usize,
+    injected: usize,
+}
+
+impl<'a, S, P: Value + ?Sized> WithProvenance<'a, S, P> {
+    /**
+    Wrap a stream, injecting the fields of `provenance` into its top-level records.
+    */
+    pub fn new(stream: S, provenance: &'a P) -> Self {
+        WithProvenance {
+            stream,
+            provenance,
+            depth: 0,
+            injected: 0,
+        }
+    }
+
+    /**
+    Get the wrapped stream.
+    */
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+
+    fn begin(&mut self, f: impl FnOnce(&mut S) -> Result) -> Result {
+        self.depth += 1;
+        f(&mut self.stream)
+    }
+
+    fn end(&mut self, f: impl FnOnce(&mut S) -> Result) -> Result {
+        self.depth -= 1;
+        f(&mut self.stream)
+    }
+
+    fn inject<'sval>(&mut self, is_record_tuple: bool) -> Result
+    where
+        S: Stream<'sval>,
+    {
+        let injected = Injected {
+            provenance: self.provenance,
+            is_record_tuple,
+            count: Cell::new(0),
+        };
+
+        default_stream::value_computed(&mut self.stream, &injected)?;
+
+        self.injected = injected.count.get();
+
+        Ok(())
+    }
+
+    fn shift(&self, index: &Index) -> Index {
+        if self.depth != 1 {
+            return index.clone();
+        }
+
+        match index.to_u64() {
+            Some(i) => {
+                let shifted = Index::new_u64(i + self.injected as u64);
+
+                match index.tag() {
+                    Some(tag) => shifted.with_tag(tag),
+                    None => shifted,
+                }
+            }
+            None => index.clone(),
+        }
+    }
+}
+
+impl<'a, 'sval, S: Stream<'sval>, P: Value + ?Sized> Stream<'sval> for WithProvenance<'a, S, P> {
+    forward_stream!(stream =>
+        null, bool, text_begin, text_fragment, text_fragment_computed, text_end, binary_begin,
+        binary_fragment, binary_fragment_computed, binary_end, u8, u16, u32, u64, u128, i8, i16,
+        i32, i64, i128, f32, f64, num, map_key_begin, map_key_end, map_value_begin, map_value_end,
+        seq_value_begin, seq_value_end, tag, tag_hint, record_value_begin, record_value_end,
+        tuple_value_begin, tuple_value_end,
+    );
+
+    fn map_begin(&mut self, num_entries: Option<usize>) -> Result {
+        self.begin(|stream| stream.map_begin(num_entries))
+    }
+
+    fn map_end(&mut self) -> Result {
+        self.end(|stream| stream.map_end())
+    }
+
+    fn seq_begin(&mut self, num_entries: Option<usize>) -> Result {
+        self.begin(|stream| stream.seq_begin(num_entries))
+    }
+
+    fn seq_end(&mut self) -> Result {
+        self.end(|stream| stream.seq_end())
+    }
+
+    fn enum_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.begin(|stream| stream.enum_begin(tag, label, index))
+    }
+
+    fn enum_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.end(|stream| stream.enum_end(tag, label, index))
+    }
+
+    fn tagged_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.begin(|stream| stream.tagged_begin(tag, label, index))
+    }
+
+    fn tagged_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.end(|stream| stream.tagged_end(tag, label, index))
+    }
+
+    fn record_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+        num_entries: Option<usize>,
+    ) -> Result {
+        if self.depth == 0 {
+            self.begin(|stream| stream.record_begin(tag, label, index, None))?;
+            self.inject(false)
+        } else {
+            self.begin(|stream| stream.record_begin(tag, label, index, num_entries))
+        }
+    }
+
+    fn record_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.end(|stream| stream.record_end(tag, label, index))
+    }
+
+    fn tuple_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+        num_entries: Option<usize>,
+    ) -> Result {
+        self.begin(|stream| stream.tuple_begin(tag, label, index, num_entries))
+    }
+
+    fn tuple_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.end(|stream| stream.tuple_end(tag, label, index))
+    }
+
+    fn record_tuple_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+        num_entries: Option<usize>,
+    ) -> Result {
+        if self.depth == 0 {
+            self.begin(|stream| stream.record_tuple_begin(tag, label, index, None))?;
+            self.inject(true)
+        } else {
+            self.begin(|stream| stream.record_tuple_begin(tag, label, index, num_entries))
+        }
+    }
+
+    fn record_tuple_value_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: &Label,
+        index: &Index,
+    ) -> Result {
+        let index = self.shift(index);
+
+        self.stream.record_tuple_value_begin(tag, label, &index)
+    }
+
+    fn record_tuple_value_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: &Label,
+        index: &Index,
+    ) -> Result {
+        let index = self.shift(index);
+
+        self.stream.record_tuple_value_end(tag, label, &index)
+    }
+
+    fn record_tuple_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.end(|stream| stream.record_tuple_end(tag, label, index))
+    }
+}
+
+/**
+The fields of a provenance record, without the record itself.
+*/
+struct Injected<'a, P: ?Sized> {
+    provenance: &'a P,
+    is_record_tuple: bool,
+    count: Cell<usize>,
+}
+
+impl<'a, P: Value + ?Sized> Value for Injected<'a, P> {
+    fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> Result {
+        let mut fields = Fields {
+            stream,
+            is_record_tuple: self.is_record_tuple,
+            depth: 0,
+            count: 0,
+        };
+
+        self.provenance.stream(&mut fields)?;
+
+        self.count.set(fields.count);
+
+        Ok(())
+    }
+}
+
+/**
+A [`Stream`] that forwards the fields of a record into a record that's already in progress.
+*/
+struct Fields<'a, S: ?Sized> {
+    stream: &'a mut S,
+    is_record_tuple: bool,
+    depth: usize,
+    count: usize,
+}
+
+impl<'a, S: ?Sized> Fields<'a, S> {
+    fn value(&mut self, f: impl FnOnce(&mut S) -> Result) -> Result {
+        if self.depth == 0 {
+            // The provenance must be a record
+            return Err(Error::unsupported());
+        }
+
+        f(self.stream)
+    }
+
+    fn begin(&mut self, f: impl FnOnce(&mut S) -> Result) -> Result {
+        self.value(f)?;
+        self.depth += 1;
+
+        Ok(())
+    }
+
+    fn end(&mut self, f: impl FnOnce(&mut S) -> Result) -> Result {
+        self.depth -= 1;
+        f(self.stream)
+    }
+
+    fn fields_begin(&mut self, f: impl FnOnce(&mut S) -> Result) -> Result {
+        if self.depth == 0 {
+            self.depth = 1;
+
+            Ok(())
+        } else {
+            self.begin(f)
+        }
+    }
+
+    fn fields_end(&mut self, f: impl FnOnce(&mut S) -> Result) -> Result {
+        self.depth -= 1;
+
+        if self.depth == 0 {
+            Ok(())
+        } else {
+            f(self.stream)
+        }
+    }
+
+    fn field_index(&self) -> Index {
+        Index::new(self.count).with_tag(&tags::VALUE_OFFSET)
+    }
+}
+
+impl<'a, 'sval, S: Stream<'sval> + ?Sized> Stream<'sval> for Fields<'a, S> {
+    fn null(&mut self) -> Result {
+        self.value(|stream| stream.null())
+    }
+
+    fn bool(&mut self, value: bool) -> Result {
+        self.value(|stream| stream.bool(value))
+    }
+
+    fn text_begin(&mut self, num_bytes: Option<usize>) -> Result {
+        self.value(|stream| stream.text_begin(num_bytes))
+    }
+
+    fn text_fragment(&mut self, fragment: &'sval str) -> Result {
+        self.stream.text_fragment(fragment)
+    }
+
+    fn text_fragment_computed(&mut self, fragment: &str) -> Result {
+        self.stream.text_fragment_computed(fragment)
+    }
+
+    fn text_end(&mut self) -> Result {
+        self.stream.text_end()
+    }
+
+    fn binary_begin(&mut self, num_bytes: Option<usize>) -> Result {
+        self.value(|stream| stream.binary_begin(num_bytes))
+    }
+
+    fn binary_fragment(&mut self, fragment: &'sval [u8]) -> Result {
+        self.stream.binary_fragment(fragment)
+    }
+
+    fn binary_fragment_computed(&mut self, fragment: &[u8]) -> Result {
+        self.stream.binary_fragment_computed(fragment)
+    }
+
+    fn binary_end(&mut self) -> Result {
+        self.stream.binary_end()
+    }
+
+    fn u8(&mut self, value: u8) -> Result {
+        self.value(|stream| stream.u8(value))
+    }
+
+    fn u16(&mut self, value: u16) -> Result {
+        self.value(|stream| stream.u16(value))
+    }
+
+    fn u32(&mut self, value: u32) -> Result {
+        self.value(|stream| stream.u32(value))
+    }
+
+    fn u64(&mut self, value: u64) -> Result {
+        self.value(|stream| stream.u64(value))
+    }
+
+    fn u128(&mut self, value: u128) -> Result {
+        self.value(|stream| stream.u128(value))
+    }
+
+    fn i8(&mut self, value: i8) -> Result {
+        self.value(|stream| stream.i8(value))
+    }
+
+    fn i16(&mut self, value: i16) -> Result {
+        self.value(|stream| stream.i16(value))
+    }
+
+    fn i32(&mut self, value: i32) -> Result {
+        self.value(|stream| stream.i32(value))
+    }
+
+    fn i64(&mut self, value: i64) -> Result {
+        self.value(|stream| stream.i64(value))
+    }
+
+    fn i128(&mut self, value: i128) -> Result {
+        self.value(|stream| stream.i128(value))
+    }
+
+    fn f32(&mut self, value: f32) -> Result {
+        self.value(|stream| stream.f32(value))
+    }
+
+    fn f64(&mut self, value: f64) -> Result {
+        self.value(|stream| stream.f64(value))
+    }
+
+    fn map_begin(&mut self, num_entries: Option<usize>) -> Result {
+        self.begin(|stream| stream.map_begin(num_entries))
+    }
+
+    fn map_key_begin(&mut self) -> Result {
+        self.stream.map_key_begin()
+    }
+
+    fn map_key_end(&mut self) -> Result {
+        self.stream.map_key_end()
+    }
+
+    fn map_value_begin(&mut self) -> Result {
+        self.stream.map_value_begin()
+    }
+
+    fn map_value_end(&mut self) -> Result {
+        self.stream.map_value_end()
+    }
+
+    fn map_end(&mut self) -> Result {
+        self.end(|stream| stream.map_end())
+    }
+
+    fn seq_begin(&mut self, num_entries: Option<usize>) -> Result {
+        self.begin(|stream| stream.seq_begin(num_entries))
+    }
+
+    fn seq_value_begin(&mut self) -> Result {
+        self.stream.seq_value_begin()
+    }
+
+    fn seq_value_end(&mut self) -> Result {
+        self.stream.seq_value_end()
+    }
+
+    fn seq_end(&mut self) -> Result {
+        self.end(|stream| stream.seq_end())
+    }
+
+    fn enum_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.begin(|stream| stream.enum_begin(tag, label, index))
+    }
+
+    fn enum_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.end(|stream| stream.enum_end(tag, label, index))
+    }
+
+    fn tagged_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.begin(|stream| stream.tagged_begin(tag, label, index))
+    }
+
+    fn tagged_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.end(|stream| stream.tagged_end(tag, label, index))
+    }
+
+    fn tag(&mut self, tag: Option<&Tag>, label: Option<&Label>, index: Option<&Index>) -> Result {
+        self.value(|stream| stream.tag(tag, label, index))
+    }
+
+    fn tag_hint(&mut self, tag: &Tag) -> Result {
+        self.stream.tag_hint(tag)
+    }
+
+    fn record_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+        num_entries: Option<usize>,
+    ) -> Result {
+        self.fields_begin(|stream| stream.record_begin(tag, label, index, num_entries))
+    }
+
+    fn record_value_begin(&mut self, tag: Option<&Tag>, label: &Label) -> Result {
+        if self.depth == 1 && self.is_record_tuple {
+            let index = self.field_index();
+
+            self.stream.record_tuple_value_begin(tag, label, &index)
+        } else {
+            self.stream.record_value_begin(tag, label)
+        }
+    }
+
+    fn record_value_end(&mut self, tag: Option<&Tag>, label: &Label) -> Result {
+        if self.depth == 1 {
+            let index = self.field_index();
+            self.count += 1;
+
+            if self.is_record_tuple {
+                return self.stream.record_tuple_value_end(tag, label, &index);
+            }
+        }
+
+        self.stream.record_value_end(tag, label)
+    }
+
+    fn record_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.fields_end(|stream| stream.record_end(tag, label, index))
+    }
+
+    fn tuple_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+        num_entries: Option<usize>,
+    ) -> Result {
+        self.begin(|stream| stream.tuple_begin(tag, label, index, num_entries))
+    }
+
+    fn tuple_value_begin(&mut self, tag: Option<&Tag>, index: &Index) -> Result {
+        self.stream.tuple_value_begin(tag, index)
+    }
+
+    fn tuple_value_end(&mut self, tag: Option<&Tag>, index: &Index) -> Result {
+        self.stream.tuple_value_end(tag, index)
+    }
+
+    fn tuple_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.end(|stream| stream.tuple_end(tag, label, index))
+    }
+
+    fn record_tuple_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+        num_entries: Option<usize>,
+    ) -> Result {
+        self.fields_begin(|stream| stream.record_tuple_begin(tag, label, index, num_entries))
+    }
+
+    fn record_tuple_value_begin(
+        &mut self,
+        tag: Option<&Tag>,
+        label: &Label,
+        index: &Index,
+    ) -> Result {
+        if self.depth == 1 {
+            self.record_value_begin(tag, label)
+        } else {
+            self.stream.record_tuple_value_begin(tag, label, index)
+        }
+    }
+
+    fn record_tuple_value_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: &Label,
+        index: &Index,
+    ) -> Result {
+        if self.depth == 1 {
+            self.record_value_end(tag, label)
+        } else {
+            self.stream.record_tuple_value_end(tag, label, index)
+        }
+    }
+
+    fn record_tuple_end(
+        &mut self,
+        tag: Option<&Tag>,
+        label: Option<&Label>,
+        index: Option<&Index>,
+    ) -> Result {
+        self.fields_end(|stream| stream.record_tuple_end(tag, label, index))
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{Index, Label, Result, Stream, Tag};
+use crate::{
+    std::num::{Saturating, Wrapping},
+    Index, Label, Result, Stream, Tag,
+};
 
 /**
 A producer of structured data.
@@ -247,13 +250,25 @@ macro_rules! impl_value_forward {
 
 impl_value_forward!({impl<'a, T: Value + ?Sized> Value for &'a T} => x => { **x });
 
+// Wrapping integers only change how arithmetic behaves, so they're streamed as their inner value
+impl_value_forward!({impl<T: Value> Value for Wrapping<T>} => x => { x.0 });
+impl_value_forward!({impl<T: Value> Value for Saturating<T>} => x => { x.0 });
+
 #[cfg(feature = "alloc")]
 mod alloc_support {
     use super::*;
 
-    use crate::std::boxed::Box;
+    use crate::std::{boxed::Box, rc::Rc};
+
+    #[cfg(target_has_atomic = "ptr")]
+    use crate::std::sync::Arc;
 
     impl_value_forward!({impl<T: Value + ?Sized> Value for Box<T>} => x => { **x });
+    impl_value_forward!({impl<T: Value + ?Sized> Value for Rc<T>} => x => { **x });
+
+    // `Arc` is only available on targets that support pointer-sized atomics
+    #[cfg(target_has_atomic = "ptr")]
+    impl_value_forward!({impl<T: Value + ?Sized> Value for Arc<T>} => x => { **x });
 }
 
 pub mod default_value {
@@ -2,7 +2,11 @@
 Test utilities for `sval`.
 */
 
-use std::any::type_name;
+use std::{any::type_name, cell::RefCell, rc::Rc};
+
+mod random;
+
+pub use self::random::*;
 
 /**
 Assert that a value streams to exactly the sequence of tokens provided.
@@ -16,9 +20,11 @@ pub fn assert_tokens<'sval, V: sval::Value + ?Sized>(value: &'sval V, tokens: &[
             assert_eq!(
                 tokens,
                 stream.as_tokens(),
-                "{} != {}",
+                "{} != {}\nexpected:\n{}\nactual:\n{}",
                 sval_fmt::stream_to_string(AsValue(tokens)),
-                sval_fmt::stream_to_string(AsValue(stream.as_tokens()))
+                sval_fmt::stream_to_string(AsValue(stream.as_tokens())),
+                debug_tokens(tokens),
+                debug_tokens(stream.as_tokens())
             );
 
             #[cfg(test)]
@@ -30,9 +36,11 @@ pub fn assert_tokens<'sval, V: sval::Value + ?Sized>(value: &'sval V, tokens: &[
                 assert_eq!(
                     tokens,
                     dyn_stream.as_tokens(),
-                    "(dyn) {} != {}",
+                    "(dyn) {} != {}\nexpected:\n{}\nactual:\n{}",
                     sval_fmt::stream_to_string(AsValue(tokens)),
-                    sval_fmt::stream_to_string(AsValue(dyn_stream.as_tokens()))
+                    sval_fmt::stream_to_string(AsValue(dyn_stream.as_tokens())),
+                    debug_tokens(tokens),
+                    debug_tokens(dyn_stream.as_tokens())
                 );
             }
         },
@@ -40,6 +48,201 @@ pub fn assert_tokens<'sval, V: sval::Value + ?Sized>(value: &'sval V, tokens: &[
     }
 }
 
+/**
+Assert that a value streams to exactly the sequence of tokens provided, both directly
+and after being buffered.
+
+The value is first checked using [`assert_tokens`]. It's then collected into a
+[`sval_buffer::ValueBuf`], and the tokens produced by streaming that buffer are compared
+with the ones provided.
+
+Buffering doesn't preserve the way text and binary values are split into fragments,
+or whether those fragments are borrowed or computed. When comparing buffered tokens,
+each text or binary value is normalized into its complete contents, so `TextFragment`
+and `TextFragmentComputed` (along with `BinaryFragment` and `BinaryFragmentComputed`)
+are treated as equivalent. Length hints on text and binary are also ignored.
+*/
+#[track_caller]
+pub fn assert_tokens_roundtrip<'sval, V: sval::Value + ?Sized>(
+    value: &'sval V,
+    tokens: &[Token<'sval>],
+) {
+    assert_tokens(value, tokens);
+
+    let buf = match sval_buffer::ValueBuf::collect(value) {
+        Ok(buf) => buf,
+        Err(err) => panic!("failed to buffer `{}`: {}", type_name::<V>(), err),
+    };
+
+    let mut stream = TokenBuf::new();
+
+    if let Err(_) = sval::Value::stream(&buf, &mut stream) {
+        stream.fail::<V>();
+    }
+
+    assert_eq!(
+        normalize_fragments(tokens),
+        normalize_fragments(stream.as_tokens()),
+        "(buffered) {} != {}\nexpected:\n{}\nactual:\n{}",
+        sval_fmt::stream_to_string(AsValue(tokens)),
+        sval_fmt::stream_to_string(AsValue(stream.as_tokens())),
+        debug_tokens(tokens),
+        debug_tokens(stream.as_tokens())
+    );
+}
+
+/**
+Assert that a value streams to the sequence of tokens provided, ignoring length hints.
+
+Length hints on maps, sequences, records, tuples, and record tuples are ignored, so
+`MapBegin(Some(2))` and `MapBegin(None)` are treated as equivalent. This is useful for
+values where the hint is an implementation detail that doesn't affect the data streamed.
+All other tokens are compared exactly, the same as [`assert_tokens`].
+*/
+#[track_caller]
+pub fn assert_tokens_ignore_hints<'sval, V: sval::Value + ?Sized>(
+    value: &'sval V,
+    tokens: &[Token<'sval>],
+) {
+    let mut stream = TokenBuf::new();
+
+    if let Err(_) = value.stream(&mut stream) {
+        stream.fail::<V>();
+    }
+
+    assert_eq!(
+        normalize_hints(tokens),
+        normalize_hints(stream.as_tokens()),
+        "(ignoring hints) {} != {}\nexpected:\n{}\nactual:\n{}",
+        sval_fmt::stream_to_string(AsValue(tokens)),
+        sval_fmt::stream_to_string(AsValue(stream.as_tokens())),
+        debug_tokens(tokens),
+        debug_tokens(stream.as_tokens())
+    );
+}
+
+/**
+Format a sequence of tokens for diagnostics.
+
+Each token is written on its own line using its `Debug` representation. Tokens are indented
+by the depth they appear at, so the values within a map, sequence, record, or other
+structure are indented one level deeper than its begin and end tokens.
+*/
+pub fn debug_tokens(tokens: &[Token]) -> String {
+    let mut out = String::new();
+    let mut depth: usize = 0;
+
+    for token in tokens {
+        if token.is_end() {
+            depth = depth.saturating_sub(1);
+        }
+
+        for _ in 0..depth {
+            out.push_str("    ");
+        }
+
+        out.push_str(&format!("{:?}\n", token));
+
+        if token.is_begin() {
+            depth += 1;
+        }
+    }
+
+    out
+}
+
+/**
+A token where text and binary values are collapsed into their complete contents.
+*/
+#[derive(Debug, PartialEq)]
+enum Normalized<'a, 'b> {
+    Text(String),
+    Binary(Vec<u8>),
+    Token(&'a Token<'b>),
+}
+
+fn normalize_fragments<'a, 'b>(tokens: &'a [Token<'b>]) -> Vec<Normalized<'a, 'b>> {
+    let mut normalized = Vec::new();
+    let mut tokens = tokens.iter();
+
+    while let Some(token) = tokens.next() {
+        match token {
+            Token::TextBegin(_) => {
+                let mut text = String::new();
+
+                for token in tokens.by_ref() {
+                    match token {
+                        Token::TextFragment(fragment) => text.push_str(fragment),
+                        Token::TextFragmentComputed(fragment) => text.push_str(fragment),
+                        _ => break,
+                    }
+                }
+
+                normalized.push(Normalized::Text(text));
+            }
+            Token::BinaryBegin(_) => {
+                let mut binary = Vec::new();
+
+                for token in tokens.by_ref() {
+                    match token {
+                        Token::BinaryFragment(fragment) => binary.extend_from_slice(fragment),
+                        Token::BinaryFragmentComputed(fragment) => {
+                            binary.extend_from_slice(fragment)
+                        }
+                        _ => break,
+                    }
+                }
+
+                normalized.push(Normalized::Binary(binary));
+            }
+            token => normalized.push(Normalized::Token(token)),
+        }
+    }
+
+    normalized
+}
+
+/**
+A token where the length hints of maps, sequences, records, and tuples are removed.
+*/
+#[derive(Debug, PartialEq)]
+enum Unhinted<'a, 'b> {
+    MapBegin,
+    SeqBegin,
+    RecordBegin(
+        &'a Option<sval::Tag>,
+        &'a Option<sval::Label<'static>>,
+        &'a Option<sval::Index>,
+    ),
+    TupleBegin(
+        &'a Option<sval::Tag>,
+        &'a Option<sval::Label<'static>>,
+        &'a Option<sval::Index>,
+    ),
+    RecordTupleBegin(
+        &'a Option<sval::Tag>,
+        &'a Option<sval::Label<'static>>,
+        &'a Option<sval::Index>,
+    ),
+    Token(&'a Token<'b>),
+}
+
+fn normalize_hints<'a, 'b>(tokens: &'a [Token<'b>]) -> Vec<Unhinted<'a, 'b>> {
+    tokens
+        .iter()
+        .map(|token| match token {
+            Token::MapBegin(_) => Unhinted::MapBegin,
+            Token::SeqBegin(_) => Unhinted::SeqBegin,
+            Token::RecordBegin(tag, label, index, _) => Unhinted::RecordBegin(tag, label, index),
+            Token::TupleBegin(tag, label, index, _) => Unhinted::TupleBegin(tag, label, index),
+            Token::RecordTupleBegin(tag, label, index, _) => {
+                Unhinted::RecordTupleBegin(tag, label, index)
+            }
+            token => Unhinted::Token(token),
+        })
+        .collect()
+}
+
 /**
 Assert that a value streams without failing.
 */
@@ -52,6 +255,46 @@ pub fn assert_valid<V: sval::Value>(value: V) {
     }
 }
 
+/**
+Assert that a value streams as binary using only borrowed fragments.
+
+The fragments must point into `binary` itself, so the value can't have copied its bytes.
+*/
+#[track_caller]
+pub fn assert_borrowed_binary<'sval, V: sval::Value + ?Sized>(
+    value: &'sval V,
+    binary: &'sval [u8],
+) {
+    let mut stream = TokenBuf::new();
+
+    if let Err(_) = value.stream(&mut stream) {
+        stream.fail::<V>();
+    }
+
+    let mut offset = 0;
+
+    match stream.as_tokens() {
+        [Token::BinaryBegin(_), fragments @ .., Token::BinaryEnd] => {
+            for fragment in fragments {
+                match fragment {
+                    Token::BinaryFragment(fragment)
+                        if offset + fragment.len() <= binary.len()
+                            && fragment.as_ptr() == binary[offset..].as_ptr() =>
+                    {
+                        offset += fragment.len();
+                    }
+                    _ => stream.fail::<V>(),
+                }
+            }
+        }
+        _ => stream.fail::<V>(),
+    }
+
+    if offset != binary.len() {
+        stream.fail::<V>();
+    }
+}
+
 /**
 Assert that a value fails to stream.
 */
@@ -323,6 +566,50 @@ pub enum Token<'a> {
     ),
 }
 
+impl<'a> Token<'a> {
+    fn is_begin(&self) -> bool {
+        matches!(
+            self,
+            Token::TextBegin(..)
+                | Token::BinaryBegin(..)
+                | Token::MapBegin(..)
+                | Token::MapKeyBegin
+                | Token::MapValueBegin
+                | Token::SeqBegin(..)
+                | Token::SeqValueBegin
+                | Token::EnumBegin(..)
+                | Token::TaggedBegin(..)
+                | Token::RecordBegin(..)
+                | Token::RecordValueBegin(..)
+                | Token::TupleBegin(..)
+                | Token::TupleValueBegin(..)
+                | Token::RecordTupleBegin(..)
+                | Token::RecordTupleValueBegin(..)
+        )
+    }
+
+    fn is_end(&self) -> bool {
+        matches!(
+            self,
+            Token::TextEnd
+                | Token::BinaryEnd
+                | Token::MapEnd
+                | Token::MapKeyEnd
+                | Token::MapValueEnd
+                | Token::SeqEnd
+                | Token::SeqValueEnd
+                | Token::EnumEnd(..)
+                | Token::TaggedEnd(..)
+                | Token::RecordEnd(..)
+                | Token::RecordValueEnd(..)
+                | Token::TupleEnd(..)
+                | Token::TupleValueEnd(..)
+                | Token::RecordTupleEnd(..)
+                | Token::RecordTupleValueEnd(..)
+        )
+    }
+}
+
 // Avoid exposing `sval_buffer`-like functionality here
 // Use `sval_buffer` instead
 struct AsValue<'a, 'b>(&'a [Token<'b>]);
@@ -465,10 +752,10 @@ impl<'a> TokenBuf<'a> {
     #[track_caller]
     fn fail<T: ?Sized>(&self) {
         panic!(
-            "the `impl sval::Value for {}` is invalid\nstreamed to:\n  `{}`\nraw:\n  `{:?}`",
+            "the `impl sval::Value for {}` is invalid\nstreamed to:\n  `{}`\nraw:\n{}",
             type_name::<T>(),
             sval_fmt::stream_to_string(AsValue(&self.tokens)),
-            self.tokens
+            debug_tokens(&self.tokens)
         );
     }
 }
@@ -849,12 +1136,116 @@ impl<'sval> sval::Stream<'sval> for TokenBuf<'sval> {
     }
 }
 
+/**
+Assert that a stream adapter passes calls to [`sval::Stream::num`] through to the stream it wraps unchanged.
+
+The adapter is given a [`NumBuf`] to wrap. A record with a single field `a` holding a number
+is streamed through it, and the number must reach the `NumBuf` as a single call to `num`.
+*/
+#[track_caller]
+pub fn assert_forwards_num<'sval, S: sval::Stream<'sval>>(adapter: impl FnOnce(NumBuf) -> S) {
+    let nums = NumBuf::default();
+    let mut stream = adapter(nums.clone());
+
+    let label = sval::Label::new("a");
+    let num = sval::Number::new("1.5").unwrap();
+
+    let streamed = (|| {
+        stream.record_begin(None, None, None, Some(1))?;
+        stream.record_value_begin(None, &label)?;
+        stream.num(&num)?;
+        stream.record_value_end(None, &label)?;
+        stream.record_end(None, None, None)
+    })();
+
+    assert!(
+        streamed.is_ok(),
+        "failed to stream a number through `{}`",
+        type_name::<S>()
+    );
+    assert_eq!(
+        vec!["1.5".to_owned()],
+        nums.as_nums(),
+        "`{}` didn't forward `num` unchanged",
+        type_name::<S>()
+    );
+}
+
+/**
+A stream that records the numbers passed to [`sval::Stream::num`], and ignores everything else.
+
+Clones of a `NumBuf` share the same numbers, so one can be given to a stream adapter
+while another is used to inspect what reached it.
+*/
+#[derive(Default, Clone, Debug)]
+pub struct NumBuf(Rc<RefCell<Vec<String>>>);
+
+impl NumBuf {
+    /**
+    Get the numbers recorded so far.
+    */
+    pub fn as_nums(&self) -> Vec<String> {
+        self.0.borrow().clone()
+    }
+}
+
+impl<'sval> sval::Stream<'sval> for NumBuf {
+    fn null(&mut self) -> sval::Result {
+        Ok(())
+    }
+
+    fn bool(&mut self, _: bool) -> sval::Result {
+        Ok(())
+    }
+
+    fn text_begin(&mut self, _: Option<usize>) -> sval::Result {
+        Ok(())
+    }
+
+    fn text_fragment_computed(&mut self, _: &str) -> sval::Result {
+        Ok(())
+    }
+
+    fn text_end(&mut self) -> sval::Result {
+        Ok(())
+    }
+
+    fn i64(&mut self, _: i64) -> sval::Result {
+        Ok(())
+    }
+
+    fn f64(&mut self, _: f64) -> sval::Result {
+        Ok(())
+    }
+
+    fn num(&mut self, num: &sval::Number) -> sval::Result {
+        self.0.borrow_mut().push(num.as_str().to_owned());
+        Ok(())
+    }
+
+    fn seq_begin(&mut self, _: Option<usize>) -> sval::Result {
+        Ok(())
+    }
+
+    fn seq_value_begin(&mut self) -> sval::Result {
+        Ok(())
+    }
+
+    fn seq_value_end(&mut self) -> sval::Result {
+        Ok(())
+    }
+
+    fn seq_end(&mut self) -> sval::Result {
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     use std::{
-        collections::{BTreeMap, HashMap},
+        collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque},
         fmt,
     };
 
@@ -883,6 +1274,43 @@ mod tests {
         assert_tokens(&true, &[Token::Bool(true)]);
     }
 
+    #[test]
+    fn stream_wrapping() {
+        use std::num::{Saturating, Wrapping};
+
+        assert_tokens(&Wrapping(5u8), &[Token::U8(5)]);
+        assert_tokens(&Wrapping(-5i64), &[Token::I64(-5)]);
+        assert_tokens(&Saturating(5u32), &[Token::U32(5)]);
+        assert_tokens(&Saturating(-5i128), &[Token::I128(-5)]);
+
+        assert_eq!(Some(5), sval::Value::to_u8(&Wrapping(5u8)));
+        assert_eq!(Some(sval::tags::NUMBER), sval::Value::tag(&Saturating(5u8)));
+    }
+
+    #[test]
+    fn stream_uuid() {
+        let bytes = [
+            0x67, 0xe5, 0x50, 0x44, 0x10, 0xb1, 0x42, 0x6f, 0x92, 0x47, 0xbb, 0x68, 0x0e, 0x5f,
+            0xe0, 0xc8,
+        ];
+
+        assert_tokens(
+            &sval::Uuid(bytes),
+            &[
+                Token::TaggedBegin(Some(sval::tags::UUID), None, None),
+                Token::BinaryBegin(Some(16)),
+                Token::BinaryFragment(&bytes),
+                Token::BinaryEnd,
+                Token::TaggedEnd(Some(sval::tags::UUID), None, None),
+            ],
+        );
+
+        assert_eq!(
+            "67e55044-10b1-426f-9247-bb680e5fe0c8",
+            sval::Uuid(bytes).to_string()
+        );
+    }
+
     #[test]
     fn stream_option() {
         assert_tokens(
@@ -913,275 +1341,3038 @@ mod tests {
     }
 
     #[test]
-    fn stream_unit() {
-        assert_tokens(&(), &[Token::Tag(Some(sval::tags::RUST_UNIT), None, None)]);
-    }
-
-    #[test]
-    fn stream_binary() {
+    fn stream_result() {
         assert_tokens(
-            &sval::BinarySlice::new(&[1, 2, 3]),
+            &Ok::<i32, &str>(1),
             &[
-                Token::BinaryBegin(Some(3)),
-                Token::BinaryFragment(&[1, 2, 3]),
-                Token::BinaryEnd,
+                Token::EnumBegin(None, Some(sval::Label::new("Result")), None),
+                Token::TaggedBegin(
+                    Some(sval::tags::RUST_RESULT_OK),
+                    Some(sval::Label::new("Ok")),
+                    Some(sval::Index::new(0)),
+                ),
+                Token::I32(1),
+                Token::TaggedEnd(
+                    Some(sval::tags::RUST_RESULT_OK),
+                    Some(sval::Label::new("Ok")),
+                    Some(sval::Index::new(0)),
+                ),
+                Token::EnumEnd(None, Some(sval::Label::new("Result")), None),
             ],
         );
 
         assert_tokens(
-            sval::BinaryArray::new(&[1, 2, 3]),
+            &Err::<i32, &str>("boom"),
             &[
-                Token::TaggedBegin(Some(sval::tags::CONSTANT_SIZE), None, None),
-                Token::BinaryBegin(Some(3)),
-                Token::BinaryFragment(&[1, 2, 3]),
-                Token::BinaryEnd,
-                Token::TaggedEnd(Some(sval::tags::CONSTANT_SIZE), None, None),
+                Token::EnumBegin(None, Some(sval::Label::new("Result")), None),
+                Token::TaggedBegin(
+                    Some(sval::tags::RUST_RESULT_ERR),
+                    Some(sval::Label::new("Err")),
+                    Some(sval::Index::new(1)),
+                ),
+                Token::TextBegin(Some(4)),
+                Token::TextFragment("boom"),
+                Token::TextEnd,
+                Token::TaggedEnd(
+                    Some(sval::tags::RUST_RESULT_ERR),
+                    Some(sval::Label::new("Err")),
+                    Some(sval::Index::new(1)),
+                ),
+                Token::EnumEnd(None, Some(sval::Label::new("Result")), None),
             ],
         );
     }
 
     #[test]
-    fn stream_map_empty() {
-        assert_tokens(
-            &BTreeMap::<u8, u8>::new(),
-            &[Token::MapBegin(Some(0)), Token::MapEnd],
-        );
-        assert_tokens(
-            &HashMap::<u8, u8>::new(),
-            &[Token::MapBegin(Some(0)), Token::MapEnd],
-        );
-    }
+    fn stream_result_err_fails() {
+        struct Fails;
 
-    #[test]
-    fn stream_map() {
-        let map = {
-            let mut map = BTreeMap::new();
+        impl sval::Value for Fails {
+            fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(
+                &'sval self,
+                _: &mut S,
+            ) -> sval::Result {
+                sval::error()
+            }
+        }
 
-            map.insert(1, 2);
+        assert!(sval::stream(&mut TokenBuf::new(), &Err::<i32, Fails>(Fails)).is_err());
+    }
 
-            map
-        };
+    #[test]
+    fn stream_range() {
         assert_tokens(
-            &map,
+            &(0..10),
             &[
-                Token::MapBegin(Some(1)),
-                Token::MapKeyBegin,
-                Token::I32(1),
-                Token::MapKeyEnd,
-                Token::MapValueBegin,
-                Token::I32(2),
-                Token::MapValueEnd,
-                Token::MapEnd,
+                Token::RecordBegin(
+                    Some(sval::tags::RANGE),
+                    Some(sval::Label::new("Range")),
+                    None,
+                    Some(2),
+                ),
+                Token::RecordValueBegin(None, sval::Label::new("start")),
+                Token::I32(0),
+                Token::RecordValueEnd(None, sval::Label::new("start")),
+                Token::RecordValueBegin(None, sval::Label::new("end")),
+                Token::I32(10),
+                Token::RecordValueEnd(None, sval::Label::new("end")),
+                Token::RecordEnd(
+                    Some(sval::tags::RANGE),
+                    Some(sval::Label::new("Range")),
+                    None,
+                ),
             ],
         );
 
-        let map = {
-            let mut map = HashMap::new();
+        assert_tokens(
+            &(0..=10),
+            &[
+                Token::RecordBegin(
+                    Some(sval::tags::RANGE),
+                    Some(sval::Label::new("RangeInclusive")),
+                    None,
+                    Some(2),
+                ),
+                Token::RecordValueBegin(None, sval::Label::new("start")),
+                Token::I32(0),
+                Token::RecordValueEnd(None, sval::Label::new("start")),
+                Token::RecordValueBegin(None, sval::Label::new("end")),
+                Token::I32(10),
+                Token::RecordValueEnd(None, sval::Label::new("end")),
+                Token::RecordEnd(
+                    Some(sval::tags::RANGE),
+                    Some(sval::Label::new("RangeInclusive")),
+                    None,
+                ),
+            ],
+        );
 
-            map.insert(1, 2);
+        #[allow(clippy::reversed_empty_ranges)]
+        let empty = 10..0;
 
-            map
-        };
         assert_tokens(
-            &map,
+            &empty,
             &[
-                Token::MapBegin(Some(1)),
-                Token::MapKeyBegin,
-                Token::I32(1),
-                Token::MapKeyEnd,
-                Token::MapValueBegin,
-                Token::I32(2),
-                Token::MapValueEnd,
-                Token::MapEnd,
+                Token::RecordBegin(
+                    Some(sval::tags::RANGE),
+                    Some(sval::Label::new("Range")),
+                    None,
+                    Some(2),
+                ),
+                Token::RecordValueBegin(None, sval::Label::new("start")),
+                Token::I32(10),
+                Token::RecordValueEnd(None, sval::Label::new("start")),
+                Token::RecordValueBegin(None, sval::Label::new("end")),
+                Token::I32(0),
+                Token::RecordValueEnd(None, sval::Label::new("end")),
+                Token::RecordEnd(
+                    Some(sval::tags::RANGE),
+                    Some(sval::Label::new("Range")),
+                    None,
+                ),
             ],
         );
     }
 
     #[test]
-    fn stream_seq_empty() {
-        assert_tokens(&(&[] as &[u8]), &[Token::SeqBegin(Some(0)), Token::SeqEnd]);
+    fn stream_duration() {
+        fn duration_tokens(secs: u64, nanos: u32) -> Vec<Token<'static>> {
+            vec![
+                Token::RecordTupleBegin(
+                    Some(sval::tags::DURATION),
+                    Some(sval::Label::new("Duration")),
+                    None,
+                    Some(2),
+                ),
+                Token::RecordTupleValueBegin(None, sval::Label::new("secs"), sval::Index::new(0)),
+                Token::U64(secs),
+                Token::RecordTupleValueEnd(None, sval::Label::new("secs"), sval::Index::new(0)),
+                Token::RecordTupleValueBegin(None, sval::Label::new("nanos"), sval::Index::new(1)),
+                Token::U32(nanos),
+                Token::RecordTupleValueEnd(None, sval::Label::new("nanos"), sval::Index::new(1)),
+                Token::RecordTupleEnd(
+                    Some(sval::tags::DURATION),
+                    Some(sval::Label::new("Duration")),
+                    None,
+                ),
+            ]
+        }
+
+        assert_tokens(&std::time::Duration::new(3, 500), &duration_tokens(3, 500));
+
+        let base = std::time::Instant::now();
+        let at = base + std::time::Duration::from_millis(1500);
+
+        assert_tokens(&sval::elapsed(base, at), &duration_tokens(1, 500_000_000));
 
+        // If the instants are out of order then the elapsed time is zero
+        assert_tokens(&sval::elapsed(at, base), &duration_tokens(0, 0));
+    }
+
+    #[test]
+    fn stream_duration_seconds() {
         assert_tokens(
-            &[] as &[u8; 0],
-            &[
-                Token::TaggedBegin(Some(sval::tags::CONSTANT_SIZE), None, None),
-                Token::SeqBegin(Some(0)),
-                Token::SeqEnd,
-                Token::TaggedEnd(Some(sval::tags::CONSTANT_SIZE), None, None),
-            ],
+            &sval::DurationSeconds(std::time::Duration::from_millis(1500)),
+            &[Token::F64(1.5)],
         );
 
         assert_tokens(
-            &Vec::<u8>::new(),
-            &[Token::SeqBegin(Some(0)), Token::SeqEnd],
+            &sval::DurationSeconds(std::time::Duration::new(3, 500)),
+            &[Token::F64(3.0000005)],
+        );
+
+        // Nanoseconds are lost from durations that are too long to represent exactly
+        let long = std::time::Duration::new(1 << 40, 1);
+        assert_eq!(
+            long.as_secs() as f64,
+            sval::DurationSeconds(long).as_secs_f64()
         );
     }
 
     #[test]
-    fn stream_seq() {
-        assert_tokens(
-            &(&[1] as &[i32]),
+    fn stream_unit() {
+        assert_tokens(&(), &[Token::Tag(Some(sval::tags::RUST_UNIT), None, None)]);
+    }
+
+    #[test]
+    fn stream_once() {
+        let cell = std::cell::OnceCell::new();
+        assert_tokens(&cell, &[Token::Null]);
+
+        cell.set(1).unwrap();
+        assert_tokens(&cell, &[Token::I32(1)]);
+
+        let lock = std::sync::OnceLock::new();
+        assert_tokens(&lock, &[Token::Null]);
+
+        lock.set(1).unwrap();
+        assert_tokens(&lock, &[Token::I32(1)]);
+    }
+
+    #[test]
+    fn stream_interior_mutability() {
+        let cell = std::cell::RefCell::new(1);
+        assert_tokens(&cell, &[Token::I32(1)]);
+
+        // A value that's already mutably borrowed fails instead of panicking
+        {
+            let _borrowed = cell.borrow_mut();
+
+            assert!(sval::stream_computed(&mut TokenBuf::new(), &cell).is_err());
+        }
+
+        assert_tokens(&std::cell::Cell::new(1), &[Token::I32(1)]);
+
+        let mutex = std::sync::Mutex::new(1);
+        assert_tokens(&mutex, &[Token::I32(1)]);
+
+        // A poisoned lock fails instead of panicking
+        let _ = std::panic::catch_unwind(|| {
+            let _guard = mutex.lock().unwrap();
+
+            panic!("poison the lock");
+        });
+
+        assert!(mutex.is_poisoned());
+        assert!(sval::stream_computed(&mut TokenBuf::new(), &mutex).is_err());
+    }
+
+    #[test]
+    fn stream_shared_pointer() {
+        assert_tokens(&std::sync::Arc::new(42), &[Token::I32(42)]);
+        assert_tokens(&std::rc::Rc::new(42), &[Token::I32(42)]);
+    }
+
+    #[test]
+    fn stream_cycle_guard() {
+        use std::{cell::RefCell, rc::Rc};
+
+        struct Node {
+            next: RefCell<Option<Rc<Node>>>,
+        }
+
+        impl sval::Value for Node {
+            fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(
+                &'sval self,
+                stream: &mut S,
+            ) -> sval::Result {
+                stream.value(&self.next)
+            }
+        }
+
+        let node = Rc::new(Node {
+            next: RefCell::new(None),
+        });
+
+        // A value that isn't nested within itself is forwarded unchanged
+        let shared = Rc::new(1);
+        let value = vec![shared.clone(), shared];
+
+        let mut stream = sval::cycle_guard(TokenBuf::new());
+        sval::stream(&mut stream, &value).unwrap();
+
+        assert_eq!(
             &[
-                Token::SeqBegin(Some(1)),
+                Token::SeqBegin(Some(2)),
+                Token::SeqValueBegin,
+                Token::I32(1),
+                Token::SeqValueEnd,
                 Token::SeqValueBegin,
                 Token::I32(1),
                 Token::SeqValueEnd,
                 Token::SeqEnd,
             ],
+            stream.into_inner().as_tokens()
+        );
+
+        // A value that's nested within itself fails instead of overflowing the stack
+        *node.next.borrow_mut() = Some(node.clone());
+
+        assert!(sval::stream(&mut sval::cycle_guard(TokenBuf::new()), &*node).is_err());
+
+        // Break the cycle so the node can be dropped
+        node.next.borrow_mut().take();
+
+        assert_forwards_num(sval::cycle_guard);
+    }
+
+    #[test]
+    fn stream_ffi_strings() {
+        use std::ffi::{CString, OsString};
+
+        let c_string = CString::new("abc").unwrap();
+        assert_tokens(
+            &c_string,
+            &[
+                Token::TaggedBegin(Some(sval::tags::C_STRING), None, None),
+                Token::BinaryBegin(Some(3)),
+                Token::BinaryFragment(b"abc"),
+                Token::BinaryEnd,
+                Token::TaggedEnd(Some(sval::tags::C_STRING), None, None),
+            ],
         );
 
+        // The bytes of a `CStr` don't need to be valid UTF8
         assert_tokens(
-            &[1],
+            &c"\xff",
             &[
-                Token::TaggedBegin(Some(sval::tags::CONSTANT_SIZE), None, None),
-                Token::SeqBegin(Some(1)),
+                Token::TaggedBegin(Some(sval::tags::C_STRING), None, None),
+                Token::BinaryBegin(Some(1)),
+                Token::BinaryFragment(b"\xff"),
+                Token::BinaryEnd,
+                Token::TaggedEnd(Some(sval::tags::C_STRING), None, None),
+            ],
+        );
+
+        let os_string = OsString::from("a\0b");
+        assert_tokens(
+            &os_string,
+            &[
+                Token::TaggedBegin(Some(sval::tags::OS_STRING), None, None),
+                Token::TextBegin(Some(3)),
+                Token::TextFragment("a\0b"),
+                Token::TextEnd,
+                Token::TaggedEnd(Some(sval::tags::OS_STRING), None, None),
+            ],
+        );
+
+        // Platform strings that aren't valid UTF8 are converted lossily
+        #[cfg(unix)]
+        {
+            use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+            assert_tokens(
+                &OsStr::from_bytes(b"a\xffb"),
+                &[
+                    Token::TaggedBegin(Some(sval::tags::OS_STRING), None, None),
+                    Token::TextBegin(Some(5)),
+                    Token::TextFragmentComputed("a\u{fffd}b".to_owned()),
+                    Token::TextEnd,
+                    Token::TaggedEnd(Some(sval::tags::OS_STRING), None, None),
+                ],
+            );
+        }
+    }
+
+    #[test]
+    fn stream_iter() {
+        let value = sval::iter((0..3).map(|i| i * 2));
+
+        // The iterator is cloned, so the value can be streamed more than once
+        for _ in 0..2 {
+            assert_tokens(
+                &value,
+                &[
+                    Token::SeqBegin(Some(3)),
+                    Token::SeqValueBegin,
+                    Token::I32(0),
+                    Token::SeqValueEnd,
+                    Token::SeqValueBegin,
+                    Token::I32(2),
+                    Token::SeqValueEnd,
+                    Token::SeqValueBegin,
+                    Token::I32(4),
+                    Token::SeqValueEnd,
+                    Token::SeqEnd,
+                ],
+            );
+        }
+
+        // Iterators without an exact length don't have a length hint
+        assert_tokens(
+            &sval::iter((0..4).filter(|i| i % 2 == 0)),
+            &[
+                Token::SeqBegin(None),
                 Token::SeqValueBegin,
-                Token::I32(1),
+                Token::I32(0),
+                Token::SeqValueEnd,
+                Token::SeqValueBegin,
+                Token::I32(2),
                 Token::SeqValueEnd,
                 Token::SeqEnd,
-                Token::TaggedEnd(Some(sval::tags::CONSTANT_SIZE), None, None),
             ],
         );
 
-        assert_tokens(
-            &vec![1],
+        // An item that fails to stream stops the sequence
+        let cell = std::cell::RefCell::new(1);
+        let _borrowed = cell.borrow_mut();
+
+        assert!(sval::stream(&mut TokenBuf::new(), &sval::iter([&cell])).is_err());
+    }
+
+    #[test]
+    fn tokens_ignore_hints() {
+        let map = BTreeMap::from([(1, vec![true])]);
+
+        super::assert_tokens_ignore_hints(
+            &map,
             &[
-                Token::SeqBegin(Some(1)),
-                Token::SeqValueBegin,
+                Token::MapBegin(None),
+                Token::MapKeyBegin,
                 Token::I32(1),
+                Token::MapKeyEnd,
+                Token::MapValueBegin,
+                Token::SeqBegin(Some(42)),
+                Token::SeqValueBegin,
+                Token::Bool(true),
                 Token::SeqValueEnd,
                 Token::SeqEnd,
+                Token::MapValueEnd,
+                Token::MapEnd,
             ],
         );
 
-        assert_tokens(
-            &(1, 2, 3),
+        super::assert_tokens_ignore_hints(
+            &(1, 2),
             &[
-                Token::TupleBegin(None, None, None, Some(3)),
+                Token::TupleBegin(None, None, None, None),
                 Token::TupleValueBegin(None, sval::Index::new(0)),
                 Token::I32(1),
                 Token::TupleValueEnd(None, sval::Index::new(0)),
                 Token::TupleValueBegin(None, sval::Index::new(1)),
                 Token::I32(2),
                 Token::TupleValueEnd(None, sval::Index::new(1)),
-                Token::TupleValueBegin(None, sval::Index::new(2)),
-                Token::I32(3),
-                Token::TupleValueEnd(None, sval::Index::new(2)),
                 Token::TupleEnd(None, None, None),
             ],
         );
     }
 
     #[test]
-    fn stream_tagged() {
-        struct BigInt {
-            is_negative: bool,
-            digits: &'static str,
-        }
-
-        impl fmt::Display for BigInt {
-            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-                if self.is_negative {
-                    f.write_str("-")?;
-                }
-
-                f.write_str(self.digits)
-            }
-        }
+    #[should_panic]
+    fn tokens_ignore_hints_compares_data() {
+        super::assert_tokens_ignore_hints(
+            &[1],
+            &[
+                Token::TaggedBegin(Some(sval::tags::CONSTANT_SIZE), None, None),
+                Token::SeqBegin(None),
+                Token::SeqValueBegin,
+                Token::I32(2),
+                Token::SeqValueEnd,
+                Token::SeqEnd,
+                Token::TaggedEnd(Some(sval::tags::CONSTANT_SIZE), None, None),
+            ],
+        );
+    }
 
-        impl sval::Value for BigInt {
-            fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(
-                &'sval self,
-                stream: &mut S,
-            ) -> sval::Result {
-                stream.tagged_begin(Some(&sval::tags::NUMBER), None, None)?;
-                sval::stream_display(stream, self)?;
-                stream.tagged_end(Some(&sval::tags::NUMBER), None, None)
-            }
-        }
+    #[test]
+    fn stream_binary() {
+        assert_tokens(
+            &sval::BinarySlice::new(&[1, 2, 3]),
+            &[
+                Token::BinaryBegin(Some(3)),
+                Token::BinaryFragment(&[1, 2, 3]),
+                Token::BinaryEnd,
+            ],
+        );
 
         assert_tokens(
-            &BigInt {
-                is_negative: true,
-                digits: "123456",
-            },
+            sval::BinaryArray::new(&[1, 2, 3]),
             &[
-                Token::TaggedBegin(Some(sval::tags::NUMBER), None, None),
-                Token::TextBegin(None),
-                Token::TextFragmentComputed("-".to_owned()),
-                Token::TextFragmentComputed("123456".to_owned()),
-                Token::TextEnd,
-                Token::TaggedEnd(Some(sval::tags::NUMBER), None, None),
+                Token::TaggedBegin(Some(sval::tags::CONSTANT_SIZE), None, None),
+                Token::BinaryBegin(Some(3)),
+                Token::BinaryFragment(&[1, 2, 3]),
+                Token::BinaryEnd,
+                Token::TaggedEnd(Some(sval::tags::CONSTANT_SIZE), None, None),
             ],
         );
     }
 
     #[test]
-    fn stream_tag_hints() {
-        struct WithHints;
+    fn stream_binary_array_sizes() {
+        fn assert_binary_array<const N: usize>(binary: &[u8; N]) {
+            assert_tokens(
+                sval::BinaryArray::new(binary),
+                &[
+                    Token::TaggedBegin(Some(sval::tags::CONSTANT_SIZE), None, None),
+                    Token::BinaryBegin(Some(N)),
+                    Token::BinaryFragment(binary),
+                    Token::BinaryEnd,
+                    Token::TaggedEnd(Some(sval::tags::CONSTANT_SIZE), None, None),
+                ],
+            );
 
-        impl sval::Value for WithHints {
-            fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(
-                &'sval self,
-                stream: &mut S,
-            ) -> sval::Result {
-                stream.tag_hint(&sval::Tag::new("test"))?;
+            assert_eq!(
+                Some(&binary[..]),
+                sval::Value::to_binary(sval::BinaryArray::new(binary))
+            );
+        }
 
-                stream.value(&42)?;
+        assert_binary_array(&[]);
+        assert_binary_array(&[1]);
+        assert_binary_array(&[1, 2, 3, 4]);
+        assert_binary_array(&[u8::MAX; 32]);
+        assert_binary_array(&[0; 1024]);
+    }
 
-                stream.tag_hint(&sval::Tag::new("test"))
-            }
-        }
+    #[test]
+    fn stream_cow() {
+        use std::borrow::Cow;
+
+        let binary = vec![1, 2, 3];
+
+        let borrowed = Cow::Borrowed(&*binary);
+        super::assert_borrowed_binary(&borrowed, &binary);
+
+        let owned = Cow::<[u8]>::Owned(binary.clone());
+        super::assert_borrowed_binary(&owned, &owned);
 
         assert_tokens(
-            &WithHints,
+            &borrowed,
             &[
-                Token::TagHint(sval::Tag::new("test")),
-                Token::I32(42),
-                Token::TagHint(sval::Tag::new("test")),
+                Token::BinaryBegin(Some(3)),
+                Token::BinaryFragment(&[1, 2, 3]),
+                Token::BinaryEnd,
+            ],
+        );
+
+        assert_tokens(
+            &Cow::Borrowed("abc"),
+            &[
+                Token::TextBegin(Some(3)),
+                Token::TextFragment("abc"),
+                Token::TextEnd,
+            ],
+        );
+
+        assert_tokens(
+            &Cow::<str>::Owned("abc".into()),
+            &[
+                Token::TextBegin(Some(3)),
+                Token::TextFragment("abc"),
+                Token::TextEnd,
             ],
         );
     }
 
     #[test]
-    fn stream_invalid() {
-        #[derive(Debug)]
-        enum KaboomMaybe {
-            Nah,
-            Kaboom,
-        }
+    fn stream_ext() {
+        use sval::StreamExt as _;
 
-        impl sval::Value for KaboomMaybe {
+        struct Entries;
+
+        impl sval::Value for Entries {
             fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(
                 &'sval self,
                 stream: &mut S,
             ) -> sval::Result {
-                match self {
-                    KaboomMaybe::Nah => stream.bool(true),
-                    KaboomMaybe::Kaboom => sval::error(),
-                }
+                stream.seq_begin(Some(2))?;
+                stream.seq_value(&1)?;
+                stream.seq_value_computed(&2)?;
+                stream.seq_end()?;
+
+                stream.map_begin(Some(2))?;
+                stream.map_entry("a", &1)?;
+                stream.map_entry_computed("b", &2)?;
+                stream.map_end()
             }
         }
 
+        assert_tokens(
+            &Entries,
+            &[
+                Token::SeqBegin(Some(2)),
+                Token::SeqValueBegin,
+                Token::I32(1),
+                Token::SeqValueEnd,
+                Token::SeqValueBegin,
+                Token::I32(2),
+                Token::SeqValueEnd,
+                Token::SeqEnd,
+                Token::MapBegin(Some(2)),
+                Token::MapKeyBegin,
+                Token::TextBegin(Some(1)),
+                Token::TextFragment("a"),
+                Token::TextEnd,
+                Token::MapKeyEnd,
+                Token::MapValueBegin,
+                Token::I32(1),
+                Token::MapValueEnd,
+                Token::MapKeyBegin,
+                Token::TextBegin(Some(1)),
+                Token::TextFragmentComputed("b".to_owned()),
+                Token::TextEnd,
+                Token::MapKeyEnd,
+                Token::MapValueBegin,
+                Token::I32(2),
+                Token::MapValueEnd,
+                Token::MapEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn stream_char() {
+        for (v, text) in [('a', "a"), ('é', "é"), ('😀', "😀")] {
+            assert_tokens(
+                &v,
+                &[
+                    Token::TaggedBegin(Some(sval::tags::CHAR), None, None),
+                    Token::TextBegin(Some(text.len())),
+                    Token::TextFragmentComputed(text.to_owned()),
+                    Token::TextEnd,
+                    Token::TaggedEnd(Some(sval::tags::CHAR), None, None),
+                ],
+            );
+        }
+    }
+
+    #[test]
+    fn stream_net() {
+        use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV6};
+
+        fn tagged_text(tag: sval::Tag, text: &str) -> Vec<Token<'static>> {
+            vec![
+                Token::TaggedBegin(Some(tag.clone()), None, None),
+                Token::TextBegin(Some(text.len())),
+                Token::TextFragmentComputed(text.to_owned()),
+                Token::TextEnd,
+                Token::TaggedEnd(Some(tag), None, None),
+            ]
+        }
+
+        assert_tokens(
+            &Ipv4Addr::new(127, 0, 0, 1),
+            &tagged_text(sval::tags::IP_ADDR, "127.0.0.1"),
+        );
+
+        assert_tokens(
+            &Ipv6Addr::LOCALHOST,
+            &tagged_text(sval::tags::IP_ADDR, "::1"),
+        );
+
+        assert_tokens(
+            &IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)),
+            &tagged_text(sval::tags::IP_ADDR, "10.0.0.1"),
+        );
+
+        assert_tokens(
+            &IpAddr::V6(Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1)),
+            &tagged_text(sval::tags::IP_ADDR, "fe80::1"),
+        );
+
+        assert_tokens(
+            &SocketAddr::from(([127, 0, 0, 1], 8080)),
+            &tagged_text(sval::tags::SOCKET_ADDR, "127.0.0.1:8080"),
+        );
+
+        assert_tokens(
+            &SocketAddr::from((Ipv6Addr::LOCALHOST, 8080)),
+            &tagged_text(sval::tags::SOCKET_ADDR, "[::1]:8080"),
+        );
+
+        assert_tokens(
+            &SocketAddrV6::new(Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1), 8080, 0, 2),
+            &tagged_text(sval::tags::SOCKET_ADDR, "[fe80::1%2]:8080"),
+        );
+    }
+
+    #[test]
+    fn stream_roundtrip() {
+        struct Fragments;
+
+        impl sval::Value for Fragments {
+            fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(
+                &'sval self,
+                stream: &mut S,
+            ) -> sval::Result {
+                stream.record_begin(None, Some(&sval::Label::new("Fragments")), None, Some(2))?;
+
+                stream.record_value_begin(None, &sval::Label::new("text"))?;
+                stream.text_begin(None)?;
+                stream.text_fragment("a")?;
+                stream.text_fragment_computed("b")?;
+                stream.text_end()?;
+                stream.record_value_end(None, &sval::Label::new("text"))?;
+
+                stream.record_value_begin(None, &sval::Label::new("binary"))?;
+                stream.binary_begin(Some(2))?;
+                stream.binary_fragment(&[1])?;
+                stream.binary_fragment(&[2])?;
+                stream.binary_end()?;
+                stream.record_value_end(None, &sval::Label::new("binary"))?;
+
+                stream.record_end(None, Some(&sval::Label::new("Fragments")), None)
+            }
+        }
+
+        super::assert_tokens_roundtrip(
+            &Fragments,
+            &[
+                Token::RecordBegin(None, Some(sval::Label::new("Fragments")), None, Some(2)),
+                Token::RecordValueBegin(None, sval::Label::new("text")),
+                Token::TextBegin(None),
+                Token::TextFragment("a"),
+                Token::TextFragmentComputed("b".into()),
+                Token::TextEnd,
+                Token::RecordValueEnd(None, sval::Label::new("text")),
+                Token::RecordValueBegin(None, sval::Label::new("binary")),
+                Token::BinaryBegin(Some(2)),
+                Token::BinaryFragment(&[1]),
+                Token::BinaryFragment(&[2]),
+                Token::BinaryEnd,
+                Token::RecordValueEnd(None, sval::Label::new("binary")),
+                Token::RecordEnd(None, Some(sval::Label::new("Fragments")), None),
+            ],
+        );
+
+        super::assert_tokens_roundtrip(
+            &Some(vec![1, 2]),
+            &[
+                Token::TaggedBegin(
+                    Some(sval::tags::RUST_OPTION_SOME),
+                    Some(sval::Label::new("Some")),
+                    Some(sval::Index::new(1)),
+                ),
+                Token::SeqBegin(Some(2)),
+                Token::SeqValueBegin,
+                Token::I32(1),
+                Token::SeqValueEnd,
+                Token::SeqValueBegin,
+                Token::I32(2),
+                Token::SeqValueEnd,
+                Token::SeqEnd,
+                Token::TaggedEnd(
+                    Some(sval::tags::RUST_OPTION_SOME),
+                    Some(sval::Label::new("Some")),
+                    Some(sval::Index::new(1)),
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn debug_tokens_indented() {
+        assert_eq!(
+            "\
+MapBegin(Some(1))
+    MapKeyBegin
+        TextBegin(Some(1))
+            TextFragment(\"a\")
+        TextEnd
+    MapKeyEnd
+    MapValueBegin
+        SeqBegin(Some(1))
+            SeqValueBegin
+                I32(1)
+            SeqValueEnd
+        SeqEnd
+    MapValueEnd
+MapEnd
+",
+            super::debug_tokens(&[
+                Token::MapBegin(Some(1)),
+                Token::MapKeyBegin,
+                Token::TextBegin(Some(1)),
+                Token::TextFragment("a"),
+                Token::TextEnd,
+                Token::MapKeyEnd,
+                Token::MapValueBegin,
+                Token::SeqBegin(Some(1)),
+                Token::SeqValueBegin,
+                Token::I32(1),
+                Token::SeqValueEnd,
+                Token::SeqEnd,
+                Token::MapValueEnd,
+                Token::MapEnd,
+            ])
+        );
+
+        // Unbalanced tokens don't underflow
+        assert_eq!(
+            "SeqEnd\nNull\n",
+            super::debug_tokens(&[Token::SeqEnd, Token::Null])
+        );
+    }
+
+    #[test]
+    fn stream_map_empty() {
+        assert_tokens(
+            &BTreeMap::<u8, u8>::new(),
+            &[Token::MapBegin(Some(0)), Token::MapEnd],
+        );
+        assert_tokens(
+            &HashMap::<u8, u8>::new(),
+            &[Token::MapBegin(Some(0)), Token::MapEnd],
+        );
+    }
+
+    #[test]
+    fn stream_map() {
         let map = {
             let mut map = BTreeMap::new();
 
-            map.insert(1, KaboomMaybe::Nah);
-            map.insert(2, KaboomMaybe::Nah);
-            map.insert(3, KaboomMaybe::Kaboom);
-            map.insert(4, KaboomMaybe::Nah);
+            map.insert(1, 2);
 
             map
         };
+        assert_tokens(
+            &map,
+            &[
+                Token::MapBegin(Some(1)),
+                Token::MapKeyBegin,
+                Token::I32(1),
+                Token::MapKeyEnd,
+                Token::MapValueBegin,
+                Token::I32(2),
+                Token::MapValueEnd,
+                Token::MapEnd,
+            ],
+        );
 
-        let e = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-            assert_valid(&map);
-        }))
-        .unwrap_err();
+        let map = {
+            let mut map = HashMap::new();
 
-        assert!(e
-            .downcast_ref::<String>()
-            .unwrap()
-            .contains("{ 1: true, 2: true, 3: "));
+            map.insert(1, 2);
+
+            map
+        };
+        assert_tokens(
+            &map,
+            &[
+                Token::MapBegin(Some(1)),
+                Token::MapKeyBegin,
+                Token::I32(1),
+                Token::MapKeyEnd,
+                Token::MapValueBegin,
+                Token::I32(2),
+                Token::MapValueEnd,
+                Token::MapEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn stream_set() {
+        assert_tokens(
+            &BTreeSet::from([3, 1, 2]),
+            &[
+                Token::SeqBegin(Some(3)),
+                Token::SeqValueBegin,
+                Token::I32(1),
+                Token::SeqValueEnd,
+                Token::SeqValueBegin,
+                Token::I32(2),
+                Token::SeqValueEnd,
+                Token::SeqValueBegin,
+                Token::I32(3),
+                Token::SeqValueEnd,
+                Token::SeqEnd,
+            ],
+        );
+
+        assert_tokens(
+            &HashSet::from([1]),
+            &[
+                Token::SeqBegin(Some(1)),
+                Token::SeqValueBegin,
+                Token::I32(1),
+                Token::SeqValueEnd,
+                Token::SeqEnd,
+            ],
+        );
+
+        assert_tokens(
+            &BinaryHeap::from([1]),
+            &[
+                Token::SeqBegin(Some(1)),
+                Token::SeqValueBegin,
+                Token::I32(1),
+                Token::SeqValueEnd,
+                Token::SeqEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn stream_deque() {
+        let mut deque = VecDeque::from([2, 3]);
+        deque.push_front(1);
+
+        assert_tokens(
+            &deque,
+            &[
+                Token::SeqBegin(Some(3)),
+                Token::SeqValueBegin,
+                Token::I32(1),
+                Token::SeqValueEnd,
+                Token::SeqValueBegin,
+                Token::I32(2),
+                Token::SeqValueEnd,
+                Token::SeqValueBegin,
+                Token::I32(3),
+                Token::SeqValueEnd,
+                Token::SeqEnd,
+            ],
+        );
+
+        assert_tokens(
+            &LinkedList::from([1]),
+            &[
+                Token::SeqBegin(Some(1)),
+                Token::SeqValueBegin,
+                Token::I32(1),
+                Token::SeqValueEnd,
+                Token::SeqEnd,
+            ],
+        );
+
+        assert_tokens(
+            &VecDeque::<i32>::new(),
+            &[Token::SeqBegin(Some(0)), Token::SeqEnd],
+        );
+        assert_tokens(
+            &LinkedList::<i32>::new(),
+            &[Token::SeqBegin(Some(0)), Token::SeqEnd],
+        );
+    }
+
+    #[test]
+    fn stream_filtered_map() {
+        let map = {
+            let mut map = BTreeMap::new();
+
+            map.insert(1, 2);
+            map.insert(2, 3);
+            map.insert(3, 4);
+
+            map
+        };
+
+        assert_tokens(
+            &sval::FilteredMap::new(&map, |k: &i32, _: &i32| *k != 2),
+            &[
+                Token::MapBegin(Some(2)),
+                Token::MapKeyBegin,
+                Token::I32(1),
+                Token::MapKeyEnd,
+                Token::MapValueBegin,
+                Token::I32(2),
+                Token::MapValueEnd,
+                Token::MapKeyBegin,
+                Token::I32(3),
+                Token::MapKeyEnd,
+                Token::MapValueBegin,
+                Token::I32(4),
+                Token::MapValueEnd,
+                Token::MapEnd,
+            ],
+        );
+
+        let map = {
+            let mut map = HashMap::new();
+
+            map.insert(1, 2);
+            map.insert(2, 3);
+
+            map
+        };
+
+        assert_tokens(
+            &sval::FilteredMap::new(&map, |_: &i32, v: &i32| *v > 2),
+            &[
+                Token::MapBegin(Some(1)),
+                Token::MapKeyBegin,
+                Token::I32(2),
+                Token::MapKeyEnd,
+                Token::MapValueBegin,
+                Token::I32(3),
+                Token::MapValueEnd,
+                Token::MapEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn stream_mapped_map() {
+        let map = {
+            let mut map = BTreeMap::new();
+
+            map.insert(1, 2);
+            map.insert(2, 3);
+
+            map
+        };
+
+        assert_tokens(
+            &sval::MappedMap::new(&map, |v: &i32| *v * 10),
+            &[
+                Token::MapBegin(Some(2)),
+                Token::MapKeyBegin,
+                Token::I32(1),
+                Token::MapKeyEnd,
+                Token::MapValueBegin,
+                Token::I32(20),
+                Token::MapValueEnd,
+                Token::MapKeyBegin,
+                Token::I32(2),
+                Token::MapKeyEnd,
+                Token::MapValueBegin,
+                Token::I32(30),
+                Token::MapValueEnd,
+                Token::MapEnd,
+            ],
+        );
+
+        let map = {
+            let mut map = HashMap::new();
+
+            map.insert("password", String::from("hunter2"));
+
+            map
+        };
+
+        // Values can be mapped to a different type
+        assert_tokens(
+            &sval::MappedMap::new(&map, |_: &String| "[redacted]"),
+            &[
+                Token::MapBegin(Some(1)),
+                Token::MapKeyBegin,
+                Token::TextBegin(Some(8)),
+                Token::TextFragment("password"),
+                Token::TextEnd,
+                Token::MapKeyEnd,
+                Token::MapValueBegin,
+                Token::TextBegin(Some(10)),
+                Token::TextFragmentComputed("[redacted]".to_owned()),
+                Token::TextEnd,
+                Token::MapValueEnd,
+                Token::MapEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn stream_sorted_map() {
+        let map = (0..100).map(|k| (k, k * 2)).collect::<HashMap<_, _>>();
+
+        let mut tokens = vec![Token::MapBegin(Some(100))];
+
+        for k in 0..100 {
+            tokens.extend([
+                Token::MapKeyBegin,
+                Token::I32(k),
+                Token::MapKeyEnd,
+                Token::MapValueBegin,
+                Token::I32(k * 2),
+                Token::MapValueEnd,
+            ]);
+        }
+
+        tokens.push(Token::MapEnd);
+
+        assert_tokens(&sval::sorted(&map), &tokens);
+    }
+
+    #[test]
+    fn stream_split_map() {
+        let map = {
+            let mut map = BTreeMap::new();
+
+            map.insert(1, 2);
+            map.insert(2, 3);
+
+            map
+        };
+
+        assert_tokens(
+            &sval::SplitMap::new(&map),
+            &[
+                Token::RecordBegin(None, None, None, Some(2)),
+                Token::RecordValueBegin(None, sval::Label::new("keys")),
+                Token::SeqBegin(Some(2)),
+                Token::SeqValueBegin,
+                Token::I32(1),
+                Token::SeqValueEnd,
+                Token::SeqValueBegin,
+                Token::I32(2),
+                Token::SeqValueEnd,
+                Token::SeqEnd,
+                Token::RecordValueEnd(None, sval::Label::new("keys")),
+                Token::RecordValueBegin(None, sval::Label::new("values")),
+                Token::SeqBegin(Some(2)),
+                Token::SeqValueBegin,
+                Token::I32(2),
+                Token::SeqValueEnd,
+                Token::SeqValueBegin,
+                Token::I32(3),
+                Token::SeqValueEnd,
+                Token::SeqEnd,
+                Token::RecordValueEnd(None, sval::Label::new("values")),
+                Token::RecordEnd(None, None, None),
+            ],
+        );
+
+        let map = (0..100).map(|k| (k, k * 2)).collect::<HashMap<i32, i32>>();
+
+        let split = sval::SplitMap::new(&map);
+
+        let mut tokens = TokenBuf::new();
+        sval::stream(&mut tokens, &split).unwrap();
+
+        let mut keys = Vec::new();
+        let mut values = Vec::new();
+        let mut field = None;
+
+        for token in tokens.as_tokens() {
+            match token {
+                Token::RecordValueBegin(_, label) => field = Some(label.as_str()),
+                Token::I32(v) if field == Some("keys") => keys.push(*v),
+                Token::I32(v) if field == Some("values") => values.push(*v),
+                _ => (),
+            }
+        }
+
+        assert_eq!(100, keys.len());
+        assert_eq!(keys.iter().map(|k| k * 2).collect::<Vec<_>>(), values);
+    }
+
+    #[test]
+    fn stream_seq_empty() {
+        assert_tokens(&(&[] as &[u8]), &[Token::SeqBegin(Some(0)), Token::SeqEnd]);
+
+        assert_tokens(
+            &[] as &[u8; 0],
+            &[
+                Token::TaggedBegin(Some(sval::tags::CONSTANT_SIZE), None, None),
+                Token::SeqBegin(Some(0)),
+                Token::SeqEnd,
+                Token::TaggedEnd(Some(sval::tags::CONSTANT_SIZE), None, None),
+            ],
+        );
+
+        assert_tokens(
+            &Vec::<u8>::new(),
+            &[Token::SeqBegin(Some(0)), Token::SeqEnd],
+        );
+    }
+
+    #[test]
+    fn stream_seq() {
+        assert_tokens(
+            &(&[1] as &[i32]),
+            &[
+                Token::SeqBegin(Some(1)),
+                Token::SeqValueBegin,
+                Token::I32(1),
+                Token::SeqValueEnd,
+                Token::SeqEnd,
+            ],
+        );
+
+        assert_tokens(
+            &[1],
+            &[
+                Token::TaggedBegin(Some(sval::tags::CONSTANT_SIZE), None, None),
+                Token::SeqBegin(Some(1)),
+                Token::SeqValueBegin,
+                Token::I32(1),
+                Token::SeqValueEnd,
+                Token::SeqEnd,
+                Token::TaggedEnd(Some(sval::tags::CONSTANT_SIZE), None, None),
+            ],
+        );
+
+        assert_tokens(
+            &vec![1],
+            &[
+                Token::SeqBegin(Some(1)),
+                Token::SeqValueBegin,
+                Token::I32(1),
+                Token::SeqValueEnd,
+                Token::SeqEnd,
+            ],
+        );
+
+        assert_tokens(
+            &(1, 2, 3),
+            &[
+                Token::TupleBegin(None, None, None, Some(3)),
+                Token::TupleValueBegin(None, sval::Index::new(0)),
+                Token::I32(1),
+                Token::TupleValueEnd(None, sval::Index::new(0)),
+                Token::TupleValueBegin(None, sval::Index::new(1)),
+                Token::I32(2),
+                Token::TupleValueEnd(None, sval::Index::new(1)),
+                Token::TupleValueBegin(None, sval::Index::new(2)),
+                Token::I32(3),
+                Token::TupleValueEnd(None, sval::Index::new(2)),
+                Token::TupleEnd(None, None, None),
+            ],
+        );
+    }
+
+    #[test]
+    fn stream_array() {
+        assert_tokens(
+            &[1i32, 2, 3],
+            &[
+                Token::TaggedBegin(Some(sval::tags::CONSTANT_SIZE), None, None),
+                Token::SeqBegin(Some(3)),
+                Token::SeqValueBegin,
+                Token::I32(1),
+                Token::SeqValueEnd,
+                Token::SeqValueBegin,
+                Token::I32(2),
+                Token::SeqValueEnd,
+                Token::SeqValueBegin,
+                Token::I32(3),
+                Token::SeqValueEnd,
+                Token::SeqEnd,
+                Token::TaggedEnd(Some(sval::tags::CONSTANT_SIZE), None, None),
+            ],
+        );
+
+        let large = {
+            let mut large = [0u16; 1000];
+
+            for (i, v) in large.iter_mut().enumerate() {
+                *v = i as u16;
+            }
+
+            large
+        };
+
+        let mut tokens = vec![
+            Token::TaggedBegin(Some(sval::tags::CONSTANT_SIZE), None, None),
+            Token::SeqBegin(Some(1000)),
+        ];
+
+        for v in large {
+            tokens.extend([Token::SeqValueBegin, Token::U16(v), Token::SeqValueEnd]);
+        }
+
+        tokens.extend([
+            Token::SeqEnd,
+            Token::TaggedEnd(Some(sval::tags::CONSTANT_SIZE), None, None),
+        ]);
+
+        assert_tokens(&large, &tokens);
+    }
+
+    #[test]
+    fn stream_tagged() {
+        struct BigInt {
+            is_negative: bool,
+            digits: &'static str,
+        }
+
+        impl fmt::Display for BigInt {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                if self.is_negative {
+                    f.write_str("-")?;
+                }
+
+                f.write_str(self.digits)
+            }
+        }
+
+        impl sval::Value for BigInt {
+            fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(
+                &'sval self,
+                stream: &mut S,
+            ) -> sval::Result {
+                stream.tagged_begin(Some(&sval::tags::NUMBER), None, None)?;
+                sval::stream_display(stream, self)?;
+                stream.tagged_end(Some(&sval::tags::NUMBER), None, None)
+            }
+        }
+
+        assert_tokens(
+            &BigInt {
+                is_negative: true,
+                digits: "123456",
+            },
+            &[
+                Token::TaggedBegin(Some(sval::tags::NUMBER), None, None),
+                Token::TextBegin(None),
+                Token::TextFragmentComputed("-".to_owned()),
+                Token::TextFragmentComputed("123456".to_owned()),
+                Token::TextEnd,
+                Token::TaggedEnd(Some(sval::tags::NUMBER), None, None),
+            ],
+        );
+    }
+
+    #[test]
+    fn stream_num() {
+        assert_tokens(
+            &sval::Number::new("-123456789012345678901234567890.5e-3").unwrap(),
+            &[
+                Token::TaggedBegin(Some(sval::tags::NUMBER), None, None),
+                Token::TextBegin(Some(36)),
+                Token::TextFragmentComputed("-123456789012345678901234567890.5e-3".to_owned()),
+                Token::TextEnd,
+                Token::TaggedEnd(Some(sval::tags::NUMBER), None, None),
+            ],
+        );
+    }
+
+    #[test]
+    fn stream_tagged_value() {
+        assert_tokens(
+            &sval::tagged(sval::tags::NUMBER, "abc"),
+            &[
+                Token::TaggedBegin(Some(sval::tags::NUMBER), None, None),
+                Token::TextBegin(Some(3)),
+                Token::TextFragment("abc"),
+                Token::TextEnd,
+                Token::TaggedEnd(Some(sval::tags::NUMBER), None, None),
+            ],
+        );
+
+        assert_tokens(
+            &sval::tagged_computed(sval::tags::NUMBER, "abc"),
+            &[
+                Token::TaggedBegin(Some(sval::tags::NUMBER), None, None),
+                Token::TextBegin(Some(3)),
+                Token::TextFragmentComputed("abc".to_owned()),
+                Token::TextEnd,
+                Token::TaggedEnd(Some(sval::tags::NUMBER), None, None),
+            ],
+        );
+
+        // Values made up of multiple tokens are wrapped as a whole
+        assert_tokens(
+            &sval::tagged(sval::Tag::new("pair"), &(1, 2))
+                .with_label(sval::Label::new("Pair"))
+                .with_index(sval::Index::new(0)),
+            &[
+                Token::TaggedBegin(
+                    Some(sval::Tag::new("pair")),
+                    Some(sval::Label::new("Pair")),
+                    Some(sval::Index::new(0)),
+                ),
+                Token::TupleBegin(None, None, None, Some(2)),
+                Token::TupleValueBegin(None, sval::Index::new(0)),
+                Token::I32(1),
+                Token::TupleValueEnd(None, sval::Index::new(0)),
+                Token::TupleValueBegin(None, sval::Index::new(1)),
+                Token::I32(2),
+                Token::TupleValueEnd(None, sval::Index::new(1)),
+                Token::TupleEnd(None, None, None),
+                Token::TaggedEnd(
+                    Some(sval::Tag::new("pair")),
+                    Some(sval::Label::new("Pair")),
+                    Some(sval::Index::new(0)),
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn stream_tag_hints() {
+        struct WithHints;
+
+        impl sval::Value for WithHints {
+            fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(
+                &'sval self,
+                stream: &mut S,
+            ) -> sval::Result {
+                stream.tag_hint(&sval::Tag::new("test"))?;
+
+                stream.value(&42)?;
+
+                stream.tag_hint(&sval::Tag::new("test"))
+            }
+        }
+
+        assert_tokens(
+            &WithHints,
+            &[
+                Token::TagHint(sval::Tag::new("test")),
+                Token::I32(42),
+                Token::TagHint(sval::Tag::new("test")),
+            ],
+        );
+    }
+
+    #[test]
+    fn stream_invalid() {
+        #[derive(Debug)]
+        enum KaboomMaybe {
+            Nah,
+            Kaboom,
+        }
+
+        impl sval::Value for KaboomMaybe {
+            fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(
+                &'sval self,
+                stream: &mut S,
+            ) -> sval::Result {
+                match self {
+                    KaboomMaybe::Nah => stream.bool(true),
+                    KaboomMaybe::Kaboom => sval::error(),
+                }
+            }
+        }
+
+        let map = {
+            let mut map = BTreeMap::new();
+
+            map.insert(1, KaboomMaybe::Nah);
+            map.insert(2, KaboomMaybe::Nah);
+            map.insert(3, KaboomMaybe::Kaboom);
+            map.insert(4, KaboomMaybe::Nah);
+
+            map
+        };
+
+        let e = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            assert_valid(&map);
+        }))
+        .unwrap_err();
+
+        assert!(e
+            .downcast_ref::<String>()
+            .unwrap()
+            .contains("{ 1: true, 2: true, 3: "));
+    }
+
+    #[test]
+    fn stream_max_field_text_len() {
+        struct Record<'a> {
+            name: &'a str,
+            description: &'a str,
+            tags: &'a [&'a str],
+        }
+
+        impl<'a> sval::Value for Record<'a> {
+            fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(
+                &'sval self,
+                stream: &mut S,
+            ) -> sval::Result {
+                stream.record_begin(None, Some(&sval::Label::new("Record")), None, Some(3))?;
+
+                stream.record_value_begin(None, &sval::Label::new("name"))?;
+                stream.value(self.name)?;
+                stream.record_value_end(None, &sval::Label::new("name"))?;
+
+                stream.record_value_begin(None, &sval::Label::new("description"))?;
+                stream.value(self.description)?;
+                stream.record_value_end(None, &sval::Label::new("description"))?;
+
+                stream.record_value_begin(None, &sval::Label::new("tags"))?;
+                stream.value(self.tags)?;
+                stream.record_value_end(None, &sval::Label::new("tags"))?;
+
+                stream.record_end(None, Some(&sval::Label::new("Record")), None)
+            }
+        }
+
+        fn stream_limited(record: &Record) -> sval::Result {
+            let mut stream = sval::MaxFieldTextLen::new(TokenBuf::new())
+                .with_limit("name", 4)
+                .with_limit("tags", 3);
+
+            sval::stream(&mut stream, record)
+        }
+
+        assert!(stream_limited(&Record {
+            name: "abcd",
+            description: "not limited at all",
+            tags: &["a", "bcd"],
+        })
+        .is_ok());
+
+        assert!(stream_limited(&Record {
+            name: "abcde",
+            description: "",
+            tags: &[],
+        })
+        .is_err());
+
+        assert!(stream_limited(&Record {
+            name: "",
+            description: "",
+            tags: &["a", "bcde"],
+        })
+        .is_err());
+
+        let mut stream = sval::MaxFieldTextLen::new(TokenBuf::new()).with_limit("name", 4);
+        sval::stream(
+            &mut stream,
+            &Record {
+                name: "abcd",
+                description: "",
+                tags: &[],
+            },
+        )
+        .unwrap();
+
+        let buf = stream.into_inner();
+        let mut expected = TokenBuf::new();
+        sval::stream(
+            &mut expected,
+            &Record {
+                name: "abcd",
+                description: "",
+                tags: &[],
+            },
+        )
+        .unwrap();
+
+        assert_eq!(expected.as_tokens(), buf.as_tokens());
+
+        assert_forwards_num(|nums| sval::MaxFieldTextLen::new(nums).with_limit("a", 1));
+    }
+
+    #[test]
+    fn stream_max_field_text_len_nested() {
+        struct Field<V>(&'static str, V);
+
+        impl<V: sval::Value> sval::Value for Field<V> {
+            fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(
+                &'sval self,
+                stream: &mut S,
+            ) -> sval::Result {
+                let label = sval::Label::new(self.0);
+
+                stream.record_begin(None, None, None, Some(1))?;
+
+                stream.record_value_begin(None, &label)?;
+                stream.value(&self.1)?;
+                stream.record_value_end(None, &label)?;
+
+                stream.record_end(None, None, None)
+            }
+        }
+
+        fn stream_limited(value: impl sval::Value, limits: &[(&str, usize)]) -> sval::Result {
+            let mut stream = sval::MaxFieldTextLen::new(TokenBuf::new());
+
+            for (label, max_bytes) in limits {
+                stream = stream.with_limit(*label, *max_bytes);
+            }
+
+            sval::stream(&mut stream, &value)
+        }
+
+        // Text in unlimited fields nested within a limited field is checked
+        assert!(stream_limited(Field("outer", Field("inner", "abc")), &[("outer", 3)]).is_ok());
+        assert!(stream_limited(Field("outer", Field("inner", "abcd")), &[("outer", 3)]).is_err());
+
+        // A nested field's own limit is used instead of its enclosing field's
+        assert!(stream_limited(
+            Field("outer", Field("inner", "abcd")),
+            &[("outer", 3), ("inner", 4)]
+        )
+        .is_ok());
+
+        // Limits apply through more than one level of nesting
+        assert!(stream_limited(
+            Field("outer", Field("inner", Field("other", "abcd"))),
+            &[("inner", 3)]
+        )
+        .is_err());
+
+        // Fields that aren't nested within a limited field aren't checked
+        assert!(stream_limited(Field("outer", Field("inner", "abcd")), &[("other", 3)]).is_ok());
+    }
+
+    #[test]
+    fn stream_select_fields() {
+        fn select<'a>(selection: &sval::FieldSelection, tokens: &[Token<'a>]) -> TokenBuf<'a> {
+            let mut stream = sval::SelectFields::new(TokenBuf::new(), selection);
+            sval::stream_computed(&mut stream, AsValue(tokens)).unwrap();
+
+            stream.into_inner()
+        }
+
+        let user = {
+            use Token::*;
+
+            [
+                RecordBegin(None, Some(sval::Label::new("User")), None, Some(3)),
+                RecordValueBegin(None, sval::Label::new("id")),
+                I32(1),
+                RecordValueEnd(None, sval::Label::new("id")),
+                RecordValueBegin(None, sval::Label::new("address")),
+                RecordBegin(None, Some(sval::Label::new("Address")), None, Some(2)),
+                RecordValueBegin(None, sval::Label::new("city")),
+                I32(2),
+                RecordValueEnd(None, sval::Label::new("city")),
+                RecordValueBegin(None, sval::Label::new("zip")),
+                I32(3),
+                RecordValueEnd(None, sval::Label::new("zip")),
+                RecordEnd(None, Some(sval::Label::new("Address")), None),
+                RecordValueEnd(None, sval::Label::new("address")),
+                RecordValueBegin(None, sval::Label::new("posts")),
+                SeqBegin(Some(1)),
+                SeqValueBegin,
+                RecordBegin(None, Some(sval::Label::new("Post")), None, Some(2)),
+                RecordValueBegin(None, sval::Label::new("id")),
+                I32(4),
+                RecordValueEnd(None, sval::Label::new("id")),
+                RecordValueBegin(None, sval::Label::new("title")),
+                I32(5),
+                RecordValueEnd(None, sval::Label::new("title")),
+                RecordEnd(None, Some(sval::Label::new("Post")), None),
+                SeqValueEnd,
+                SeqEnd,
+                RecordValueEnd(None, sval::Label::new("posts")),
+                RecordEnd(None, Some(sval::Label::new("User")), None),
+            ]
+        };
+
+        // Select a field along with its whole value
+        assert_eq!(
+            {
+                use Token::*;
+
+                vec![
+                    RecordBegin(None, Some(sval::Label::new("User")), None, None),
+                    RecordValueBegin(None, sval::Label::new("address")),
+                    RecordBegin(None, Some(sval::Label::new("Address")), None, Some(2)),
+                    RecordValueBegin(None, sval::Label::new("city")),
+                    I32(2),
+                    RecordValueEnd(None, sval::Label::new("city")),
+                    RecordValueBegin(None, sval::Label::new("zip")),
+                    I32(3),
+                    RecordValueEnd(None, sval::Label::new("zip")),
+                    RecordEnd(None, Some(sval::Label::new("Address")), None),
+                    RecordValueEnd(None, sval::Label::new("address")),
+                    RecordEnd(None, Some(sval::Label::new("User")), None),
+                ]
+            },
+            select(&sval::FieldSelection::new().field("address"), &user).as_tokens(),
+        );
+
+        // Select fields within nested records, including through sequences
+        assert_eq!(
+            {
+                use Token::*;
+
+                vec![
+                    RecordBegin(None, Some(sval::Label::new("User")), None, None),
+                    RecordValueBegin(None, sval::Label::new("id")),
+                    I32(1),
+                    RecordValueEnd(None, sval::Label::new("id")),
+                    RecordValueBegin(None, sval::Label::new("address")),
+                    RecordBegin(None, Some(sval::Label::new("Address")), None, None),
+                    RecordValueBegin(None, sval::Label::new("zip")),
+                    I32(3),
+                    RecordValueEnd(None, sval::Label::new("zip")),
+                    RecordEnd(None, Some(sval::Label::new("Address")), None),
+                    RecordValueEnd(None, sval::Label::new("address")),
+                    RecordValueBegin(None, sval::Label::new("posts")),
+                    SeqBegin(Some(1)),
+                    SeqValueBegin,
+                    RecordBegin(None, Some(sval::Label::new("Post")), None, None),
+                    RecordValueBegin(None, sval::Label::new("title")),
+                    I32(5),
+                    RecordValueEnd(None, sval::Label::new("title")),
+                    RecordEnd(None, Some(sval::Label::new("Post")), None),
+                    SeqValueEnd,
+                    SeqEnd,
+                    RecordValueEnd(None, sval::Label::new("posts")),
+                    RecordEnd(None, Some(sval::Label::new("User")), None),
+                ]
+            },
+            select(
+                &sval::FieldSelection::new()
+                    .field("id")
+                    .field_with("address", sval::FieldSelection::new().field("zip"))
+                    .field_with("posts", sval::FieldSelection::new().field("title")),
+                &user
+            )
+            .as_tokens(),
+        );
+
+        let selection = sval::FieldSelection::new().field("a");
+        assert_forwards_num(|nums| sval::SelectFields::new(nums, &selection));
+    }
+
+    #[test]
+    fn stream_assert_primitive() {
+        fn is_primitive(v: impl sval::Value) -> bool {
+            sval::stream_computed(&mut sval::assert_primitive(), v).is_ok()
+        }
+
+        assert!(is_primitive("key"));
+        assert!(is_primitive(String::from("key")));
+        assert!(is_primitive(1));
+        assert!(is_primitive(u128::MAX));
+        assert!(is_primitive(1.5));
+        assert!(is_primitive(true));
+        assert!(is_primitive(()));
+        assert!(is_primitive(Some(1)));
+        assert!(is_primitive(None::<i32>));
+
+        assert!(!is_primitive(["key"]));
+        assert!(!is_primitive(vec![1, 2]));
+        assert!(!is_primitive(BTreeMap::from([("a", 1)])));
+        assert!(!is_primitive((1, 2)));
+        assert!(!is_primitive(sval::BinarySlice::new(b"key")));
+        assert!(!is_primitive(AsValue(&[
+            Token::RecordBegin(None, None, None, Some(0)),
+            Token::RecordEnd(None, None, None),
+        ])));
+        assert!(!is_primitive(AsValue(&[
+            Token::EnumBegin(None, Some(sval::Label::new("Enum")), None),
+            Token::Tag(None, Some(sval::Label::new("Variant")), None),
+            Token::EnumEnd(None, Some(sval::Label::new("Enum")), None),
+        ])));
+    }
+
+    #[test]
+    fn stream_error_kind() {
+        let err = sval::stream_computed(&mut sval::assert_primitive(), vec![1, 2]).unwrap_err();
+
+        assert_eq!(sval::ErrorKind::Unsupported, err.kind());
+        assert!(err.is_unsupported());
+
+        let err = sval::Error::new();
+
+        assert_eq!(sval::ErrorKind::Other, err.kind());
+        assert!(!err.is_unsupported());
+
+        assert_eq!(
+            sval::ErrorKind::InvalidUtf8,
+            sval::Error::invalid_utf8().kind()
+        );
+        assert_eq!(sval::ErrorKind::Buffer, sval::Error::buffer().kind());
+    }
+
+    #[test]
+    fn stream_require_all_variants_handled() {
+        let mut variants =
+            sval::RequireAllVariantsHandled::new(["Constant", "Tagged", "Record", "Inner"]);
+
+        assert_eq!(
+            vec!["Constant", "Inner", "Record", "Tagged"],
+            variants.unhandled()
+        );
+
+        sval::stream_computed(
+            &mut variants,
+            AsValue(&[
+                Token::EnumBegin(None, Some(sval::Label::new("Enum")), None),
+                Token::Tag(None, Some(sval::Label::new("Constant")), None),
+                Token::EnumEnd(None, Some(sval::Label::new("Enum")), None),
+            ]),
+        )
+        .unwrap();
+
+        // Labels of values that aren't variants aren't recorded
+        sval::stream_computed(
+            &mut variants,
+            AsValue(&[
+                Token::RecordBegin(None, Some(sval::Label::new("Record")), None, Some(1)),
+                Token::RecordValueBegin(None, sval::Label::new("a")),
+                Token::TaggedBegin(None, Some(sval::Label::new("Tagged")), None),
+                Token::I32(1),
+                Token::TaggedEnd(None, Some(sval::Label::new("Tagged")), None),
+                Token::RecordValueEnd(None, sval::Label::new("a")),
+                Token::RecordEnd(None, Some(sval::Label::new("Record")), None),
+            ]),
+        )
+        .unwrap();
+
+        assert_eq!(vec!["Inner", "Record", "Tagged"], variants.unhandled());
+
+        // Variants within other variants and nested enums are recorded
+        sval::stream_computed(
+            &mut variants,
+            AsValue(&[
+                Token::EnumBegin(None, Some(sval::Label::new("Enum")), None),
+                Token::RecordBegin(None, Some(sval::Label::new("Record")), None, Some(1)),
+                Token::RecordValueBegin(None, sval::Label::new("a")),
+                Token::EnumBegin(None, Some(sval::Label::new("Enum")), None),
+                Token::EnumBegin(None, Some(sval::Label::new("Inner")), None),
+                Token::TaggedBegin(None, Some(sval::Label::new("Tagged")), None),
+                Token::I32(1),
+                Token::TaggedEnd(None, Some(sval::Label::new("Tagged")), None),
+                Token::EnumEnd(None, Some(sval::Label::new("Inner")), None),
+                Token::EnumEnd(None, Some(sval::Label::new("Enum")), None),
+                Token::RecordValueEnd(None, sval::Label::new("a")),
+                Token::RecordEnd(None, Some(sval::Label::new("Record")), None),
+                Token::EnumEnd(None, Some(sval::Label::new("Enum")), None),
+            ]),
+        )
+        .unwrap();
+
+        assert!(variants.unhandled().is_empty());
+        assert!(variants.is_all_handled());
+    }
+
+    #[test]
+    fn stream_with_provenance() {
+        fn with_provenance<'a>(provenance: &[Token], tokens: &[Token<'a>]) -> TokenBuf<'a> {
+            let provenance = AsValue(provenance);
+
+            let mut stream = sval::WithProvenance::new(TokenBuf::new(), &provenance);
+            sval::stream_computed(&mut stream, AsValue(tokens)).unwrap();
+
+            stream.into_inner()
+        }
+
+        let provenance = {
+            use Token::*;
+
+            [
+                RecordBegin(None, Some(sval::Label::new("Provenance")), None, Some(2)),
+                RecordValueBegin(None, sval::Label::new("_source")),
+                TextBegin(Some(3)),
+                TextFragment("app"),
+                TextEnd,
+                RecordValueEnd(None, sval::Label::new("_source")),
+                RecordValueBegin(None, sval::Label::new("_ingested_at")),
+                U64(42),
+                RecordValueEnd(None, sval::Label::new("_ingested_at")),
+                RecordEnd(None, Some(sval::Label::new("Provenance")), None),
+            ]
+        };
+
+        // Fields are injected into top-level records, but not nested ones
+        assert_eq!(
+            {
+                use Token::*;
+
+                vec![
+                    RecordBegin(None, Some(sval::Label::new("Event")), None, None),
+                    RecordValueBegin(None, sval::Label::new("_source")),
+                    TextBegin(Some(3)),
+                    TextFragmentComputed("app".into()),
+                    TextEnd,
+                    RecordValueEnd(None, sval::Label::new("_source")),
+                    RecordValueBegin(None, sval::Label::new("_ingested_at")),
+                    U64(42),
+                    RecordValueEnd(None, sval::Label::new("_ingested_at")),
+                    RecordValueBegin(None, sval::Label::new("inner")),
+                    RecordBegin(None, None, None, Some(1)),
+                    RecordValueBegin(None, sval::Label::new("a")),
+                    I32(1),
+                    RecordValueEnd(None, sval::Label::new("a")),
+                    RecordEnd(None, None, None),
+                    RecordValueEnd(None, sval::Label::new("inner")),
+                    RecordEnd(None, Some(sval::Label::new("Event")), None),
+                ]
+            },
+            with_provenance(&provenance, &{
+                use Token::*;
+
+                [
+                    RecordBegin(None, Some(sval::Label::new("Event")), None, Some(1)),
+                    RecordValueBegin(None, sval::Label::new("inner")),
+                    RecordBegin(None, None, None, Some(1)),
+                    RecordValueBegin(None, sval::Label::new("a")),
+                    I32(1),
+                    RecordValueEnd(None, sval::Label::new("a")),
+                    RecordEnd(None, None, None),
+                    RecordValueEnd(None, sval::Label::new("inner")),
+                    RecordEnd(None, Some(sval::Label::new("Event")), None),
+                ]
+            })
+            .as_tokens()
+        );
+
+        // Fields of record tuples are indexed after the injected fields
+        assert_eq!(
+            {
+                use Token::*;
+
+                vec![
+                    RecordTupleBegin(None, None, None, None),
+                    RecordTupleValueBegin(
+                        None,
+                        sval::Label::new("_source"),
+                        sval::Index::new(0).with_tag(&sval::tags::VALUE_OFFSET),
+                    ),
+                    TextBegin(Some(3)),
+                    TextFragmentComputed("app".into()),
+                    TextEnd,
+                    RecordTupleValueEnd(
+                        None,
+                        sval::Label::new("_source"),
+                        sval::Index::new(0).with_tag(&sval::tags::VALUE_OFFSET),
+                    ),
+                    RecordTupleValueBegin(
+                        None,
+                        sval::Label::new("_ingested_at"),
+                        sval::Index::new(1).with_tag(&sval::tags::VALUE_OFFSET),
+                    ),
+                    U64(42),
+                    RecordTupleValueEnd(
+                        None,
+                        sval::Label::new("_ingested_at"),
+                        sval::Index::new(1).with_tag(&sval::tags::VALUE_OFFSET),
+                    ),
+                    RecordTupleValueBegin(None, sval::Label::new("a"), sval::Index::new(2)),
+                    I32(1),
+                    RecordTupleValueEnd(None, sval::Label::new("a"), sval::Index::new(2)),
+                    RecordTupleEnd(None, None, None),
+                ]
+            },
+            with_provenance(&provenance, &{
+                use Token::*;
+
+                [
+                    RecordTupleBegin(None, None, None, Some(1)),
+                    RecordTupleValueBegin(None, sval::Label::new("a"), sval::Index::new(0)),
+                    I32(1),
+                    RecordTupleValueEnd(None, sval::Label::new("a"), sval::Index::new(0)),
+                    RecordTupleEnd(None, None, None),
+                ]
+            })
+            .as_tokens()
+        );
+
+        // Other values are forwarded unchanged
+        assert_eq!(
+            vec![Token::I32(1)],
+            with_provenance(&provenance, &[Token::I32(1)]).as_tokens()
+        );
+
+        // The provenance must be a record
+        let provenance = AsValue(&[Token::I32(1)]);
+        let mut stream = sval::WithProvenance::new(TokenBuf::new(), &provenance);
+        assert!(sval::stream_computed(
+            &mut stream,
+            AsValue(&[
+                Token::RecordBegin(None, None, None, None),
+                Token::RecordEnd(None, None, None),
+            ])
+        )
+        .unwrap_err()
+        .is_unsupported());
+
+        let provenance = AsValue(&[
+            Token::RecordBegin(None, None, None, Some(0)),
+            Token::RecordEnd(None, None, None),
+        ]);
+        assert_forwards_num(|nums| sval::WithProvenance::new(nums, &provenance));
+    }
+
+    #[test]
+    fn stream_infer_schema() {
+        use sval::SchemaType::*;
+
+        fn user<'a>(id: Token<'a>, name: Option<&'a str>) -> Vec<Token<'a>> {
+            let mut tokens = vec![
+                Token::RecordBegin(None, Some(sval::Label::new("User")), None, None),
+                Token::RecordValueBegin(None, sval::Label::new("id")),
+                id,
+                Token::RecordValueEnd(None, sval::Label::new("id")),
+            ];
+
+            if let Some(name) = name {
+                tokens.extend([
+                    Token::RecordValueBegin(None, sval::Label::new("name")),
+                    Token::TextBegin(Some(name.len())),
+                    Token::TextFragment(name),
+                    Token::TextEnd,
+                    Token::RecordValueEnd(None, sval::Label::new("name")),
+                ]);
+            }
+
+            tokens.push(Token::RecordEnd(None, Some(sval::Label::new("User")), None));
+            tokens
+        }
+
+        let mut stream = sval::InferSchema::new();
+
+        // Types observed across samples are unioned
+        sval::stream_computed(&mut stream, AsValue(&user(Token::I32(1), Some("a")))).unwrap();
+        sval::stream_computed(&mut stream, AsValue(&user(Token::F64(1.5), None))).unwrap();
+
+        let schema = stream.into_schema();
+
+        assert_eq!(vec![Object], schema.types().collect::<Vec<_>>());
+        assert_eq!(
+            vec![Integer, Number],
+            schema.property("id").unwrap().types().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![String],
+            schema.property("name").unwrap().types().collect::<Vec<_>>()
+        );
+
+        // Only properties present on every sample are required
+        assert!(schema.is_required("id"));
+        assert!(!schema.is_required("name"));
+
+        assert_eq!(
+            "{ \"type\": \"object\", \"properties\": { \"id\": { \"type\": [\"integer\", \"number\"] }, \"name\": { \"type\": \"string\" } }, \"required\": [\"id\"] }",
+            sval_fmt::stream_to_string(&schema)
+        );
+
+        // Sequences, maps, and optional values
+        let mut stream = sval::InferSchema::new();
+
+        sval::stream_computed(&mut stream, &[Some(1), None][..]).unwrap();
+        sval::stream_computed(&mut stream, std::collections::BTreeMap::from([("a", true)]))
+            .unwrap();
+
+        let schema = stream.into_schema();
+
+        assert_eq!(vec![Array, Object], schema.types().collect::<Vec<_>>());
+        assert_eq!(
+            vec![Null, Integer],
+            schema.items().unwrap().types().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![Boolean],
+            schema
+                .additional_properties()
+                .unwrap()
+                .types()
+                .collect::<Vec<_>>()
+        );
+
+        // Enum variants are objects with a single property, or strings for unit variants
+        let mut stream = sval::InferSchema::new();
+
+        sval::stream_computed(
+            &mut stream,
+            AsValue(&[
+                Token::EnumBegin(None, Some(sval::Label::new("Shape")), None),
+                Token::TaggedBegin(None, Some(sval::Label::new("Circle")), None),
+                Token::F64(1.0),
+                Token::TaggedEnd(None, Some(sval::Label::new("Circle")), None),
+                Token::EnumEnd(None, Some(sval::Label::new("Shape")), None),
+            ]),
+        )
+        .unwrap();
+        sval::stream_computed(
+            &mut stream,
+            AsValue(&[
+                Token::EnumBegin(None, Some(sval::Label::new("Shape")), None),
+                Token::Tag(None, Some(sval::Label::new("Empty")), None),
+                Token::EnumEnd(None, Some(sval::Label::new("Shape")), None),
+            ]),
+        )
+        .unwrap();
+
+        let schema = stream.into_schema();
+
+        assert_eq!(vec![String, Object], schema.types().collect::<Vec<_>>());
+        assert_eq!(
+            vec![Number],
+            schema
+                .property("Circle")
+                .unwrap()
+                .types()
+                .collect::<Vec<_>>()
+        );
+        assert!(schema.is_required("Circle"));
+    }
+
+    #[test]
+    fn stream_field_profiler() {
+        fn row<'a>(name: Option<&'a str>, age: Token<'a>) -> Vec<Token<'a>> {
+            let mut tokens = vec![Token::RecordBegin(None, None, None, None)];
+
+            if let Some(name) = name {
+                tokens.extend([
+                    Token::RecordValueBegin(None, sval::Label::new("name")),
+                    Token::TextBegin(Some(name.len())),
+                    Token::TextFragment(name),
+                    Token::TextEnd,
+                    Token::RecordValueEnd(None, sval::Label::new("name")),
+                ]);
+            }
+
+            tokens.extend([
+                Token::RecordValueBegin(None, sval::Label::new("age")),
+                age,
+                Token::RecordValueEnd(None, sval::Label::new("age")),
+                Token::RecordValueBegin(None, sval::Label::new("tags")),
+                Token::SeqBegin(None),
+                Token::SeqValueBegin,
+                Token::I32(1),
+                Token::SeqValueEnd,
+                Token::SeqEnd,
+                Token::RecordValueEnd(None, sval::Label::new("tags")),
+                Token::RecordEnd(None, None, None),
+            ]);
+
+            tokens
+        }
+
+        let mut rows = vec![Token::SeqBegin(None)];
+        for (name, age) in [
+            (Some("a"), Token::I32(30)),
+            (Some("b"), Token::Null),
+            (None, Token::F64(2.5)),
+            (Some("c"), Token::U64(41)),
+            (
+                Some("a"),
+                Token::Tag(Some(sval::tags::RUST_OPTION_NONE), None, None),
+            ),
+        ] {
+            rows.push(Token::SeqValueBegin);
+            rows.extend(row(name, age));
+            rows.push(Token::SeqValueEnd);
+        }
+        rows.push(Token::SeqEnd);
+
+        let mut stream = sval::FieldProfiler::new().with_max_distinct(2);
+        sval::stream_computed(&mut stream, AsValue(&rows)).unwrap();
+
+        let fields = stream.into_fields();
+
+        assert_eq!(
+            vec!["age", "name", "tags"],
+            fields.keys().map(|k| k.as_str()).collect::<Vec<_>>()
+        );
+
+        let age = &fields["age"];
+        assert_eq!(5, age.count());
+        assert_eq!(2, age.null_count());
+        assert_eq!(Some(2.5), age.min());
+        assert_eq!(Some(41.0), age.max());
+
+        let name = &fields["name"];
+        assert_eq!(4, name.count());
+        assert_eq!(0, name.null_count());
+        assert_eq!(None, name.min());
+        assert_eq!(2, name.distinct_count());
+        assert!(name.is_distinct_capped());
+
+        // Values nested within fields only count towards the field itself
+        let tags = &fields["tags"];
+        assert_eq!(5, tags.count());
+        assert_eq!(None, tags.min());
+        assert_eq!(0, tags.distinct_count());
+        assert!(!tags.is_distinct_capped());
+    }
+
+    #[test]
+    fn stream_limit_depth() {
+        fn nested_maps(depth: usize) -> Vec<Token<'static>> {
+            let mut tokens = Vec::new();
+
+            for _ in 0..depth {
+                tokens.extend([
+                    Token::MapBegin(Some(1)),
+                    Token::MapKeyBegin,
+                    Token::I32(1),
+                    Token::MapKeyEnd,
+                    Token::MapValueBegin,
+                ]);
+            }
+
+            tokens.push(Token::Null);
+
+            for _ in 0..depth {
+                tokens.extend([Token::MapValueEnd, Token::MapEnd]);
+            }
+
+            tokens
+        }
+
+        // Values nested beyond the limit fail
+        let mut stream = sval::limit_depth(TokenBuf::new(), 50);
+        assert!(sval::stream_computed(&mut stream, AsValue(&nested_maps(100))).is_err());
+
+        // Values within the limit are forwarded unchanged
+        let tokens = nested_maps(50);
+
+        let mut stream = sval::limit_depth(TokenBuf::new(), 50);
+        sval::stream_computed(&mut stream, AsValue(&tokens)).unwrap();
+
+        assert_eq!(tokens, stream.into_inner().as_tokens());
+
+        // Depth is released as values end, so siblings don't accumulate
+        let mut stream = sval::limit_depth(TokenBuf::new(), 2);
+        sval::stream_computed(&mut stream, vec![vec![1, 2], vec![3]]).unwrap();
+
+        let mut stream = sval::limit_depth(TokenBuf::new(), 2);
+        assert!(sval::stream_computed(&mut stream, vec![vec![vec![1]]]).is_err());
+
+        // Tagged values, like `Some`, are also a level of depth
+        let mut stream = sval::limit_depth(TokenBuf::new(), 2);
+        assert!(sval::stream_computed(&mut stream, vec![vec![Some(1)]]).is_err());
+
+        assert_forwards_num(|nums| sval::limit_depth(nums, 1));
+    }
+
+    #[test]
+    fn stream_binary_as_text() {
+        // "a€😀", with each multi-byte character split across fragments
+        let binary = [
+            Token::BinaryBegin(Some(8)),
+            Token::BinaryFragment(&[b'a', 0xe2]),
+            Token::BinaryFragment(&[0x82]),
+            Token::BinaryFragment(&[0xac, 0xf0, 0x9f]),
+            Token::BinaryFragmentComputed(vec![0x98, 0x80]),
+            Token::BinaryEnd,
+        ];
+
+        let binary = AsValue(&binary);
+
+        let mut stream = sval::binary_as_text(TokenBuf::new());
+        sval::stream(&mut stream, &binary).unwrap();
+
+        assert_eq!(
+            &[
+                Token::TextBegin(Some(8)),
+                Token::TextFragment("a"),
+                Token::TextFragmentComputed("€".into()),
+                Token::TextFragmentComputed("😀".into()),
+                Token::TextEnd,
+            ],
+            stream.into_inner().as_tokens()
+        );
+
+        // Complete fragments are forwarded as they are
+        let mut stream = sval::binary_as_text(TokenBuf::new());
+        sval::stream(&mut stream, sval::BinarySlice::new("a€".as_bytes())).unwrap();
+
+        assert_eq!(
+            &[
+                Token::TextBegin(Some(4)),
+                Token::TextFragment("a€"),
+                Token::TextEnd,
+            ],
+            stream.into_inner().as_tokens()
+        );
+
+        // Invalid UTF8 fails
+        for invalid in [
+            &[
+                Token::BinaryBegin(None),
+                Token::BinaryFragment(&[0xff]),
+                Token::BinaryEnd,
+            ][..],
+            &[
+                Token::BinaryBegin(None),
+                Token::BinaryFragment(&[0xe2]),
+                Token::BinaryFragment(b"a"),
+                Token::BinaryEnd,
+            ],
+            // A character that's never completed
+            &[
+                Token::BinaryBegin(None),
+                Token::BinaryFragment(&[0xe2, 0x82]),
+                Token::BinaryEnd,
+            ],
+        ] {
+            let mut stream = sval::binary_as_text(TokenBuf::new());
+            assert_eq!(
+                sval::ErrorKind::InvalidUtf8,
+                sval::stream(&mut stream, &AsValue(invalid))
+                    .unwrap_err()
+                    .kind()
+            );
+        }
+
+        assert_forwards_num(sval::binary_as_text);
+    }
+
+    #[test]
+    fn stream_map_labels() {
+        // Labels are mapped once per field, so the start and end of a field always match
+        let mut calls = 0;
+        let mut stream = sval::map_labels(TokenBuf::new(), |label| {
+            calls += 1;
+            sval::Label::new_owned(format!("{}{}", label.as_str(), calls))
+        });
+
+        let record = [
+            Token::RecordBegin(None, Some(sval::Label::new("Record")), None, Some(1)),
+            Token::RecordValueBegin(None, sval::Label::new("a")),
+            Token::I32(1),
+            Token::RecordValueEnd(None, sval::Label::new("a")),
+            Token::RecordEnd(None, Some(sval::Label::new("Record")), None),
+        ];
+        let record = AsValue(&record);
+
+        sval::stream(&mut stream, &record).unwrap();
+
+        assert_eq!(
+            &[
+                Token::RecordBegin(None, Some(sval::Label::new("Record")), None, Some(1)),
+                Token::RecordValueBegin(None, sval::Label::new("a1")),
+                Token::I32(1),
+                Token::RecordValueEnd(None, sval::Label::new("a1")),
+                Token::RecordEnd(None, Some(sval::Label::new("Record")), None),
+            ],
+            stream.into_inner().as_tokens()
+        );
+
+        assert_forwards_num(|nums| sval::map_labels(nums, |label| label.to_owned()));
+    }
+
+    #[test]
+    fn stream_size_text() {
+        let tokens = [
+            Token::SeqBegin(None),
+            Token::SeqValueBegin,
+            Token::TextBegin(None),
+            Token::TextFragment("a"),
+            Token::TextFragmentComputed("bc".to_owned()),
+            Token::TextEnd,
+            Token::SeqValueEnd,
+            Token::SeqValueBegin,
+            Token::TextBegin(None),
+            Token::TextEnd,
+            Token::SeqValueEnd,
+            Token::SeqEnd,
+        ];
+        let value = AsValue(&tokens);
+
+        let mut stream = sval::size_text(TokenBuf::new());
+        sval::stream(&mut stream, &value).unwrap();
+
+        // Fragmented text is forwarded as a single fragment with its total length
+        assert_eq!(
+            &[
+                Token::SeqBegin(None),
+                Token::SeqValueBegin,
+                Token::TextBegin(Some(3)),
+                Token::TextFragmentComputed("abc".to_owned()),
+                Token::TextEnd,
+                Token::SeqValueEnd,
+                Token::SeqValueBegin,
+                Token::TextBegin(Some(0)),
+                Token::TextEnd,
+                Token::SeqValueEnd,
+                Token::SeqEnd,
+            ],
+            stream.into_inner().as_tokens()
+        );
+
+        assert_forwards_num(sval::size_text);
+    }
+
+    #[test]
+    fn stream_inspect() {
+        let value = vec![Some(1), None];
+        let mut calls = Vec::new();
+
+        let mut stream = sval::inspect(TokenBuf::new(), |method| calls.push(method.to_owned()));
+        sval::stream(&mut stream, &value).unwrap();
+
+        let tokens = stream.into_inner();
+
+        assert_eq!(
+            &[
+                "seq_begin",
+                "seq_value_begin",
+                "tagged_begin",
+                "i32",
+                "tagged_end",
+                "seq_value_end",
+                "seq_value_begin",
+                "tag",
+                "seq_value_end",
+                "seq_end",
+            ][..],
+            &*calls
+        );
+
+        // All data is forwarded unchanged
+        let mut expected = TokenBuf::new();
+        sval::stream(&mut expected, &value).unwrap();
+
+        assert_eq!(expected.as_tokens(), tokens.as_tokens());
+
+        let mut calls = Vec::new();
+        assert_forwards_num(|nums| sval::inspect(nums, |method| calls.push(method.to_owned())));
+
+        assert_eq!(
+            &[
+                "record_begin",
+                "record_value_begin",
+                "num",
+                "record_value_end",
+                "record_end",
+            ][..],
+            &*calls
+        );
+    }
+
+    #[test]
+    fn stream_schema_intern() {
+        fn record(labels: &[&'static str]) -> Vec<Token<'static>> {
+            let mut tokens = vec![Token::RecordBegin(None, None, None, None)];
+
+            for (i, label) in labels.iter().enumerate() {
+                tokens.push(Token::RecordValueBegin(None, sval::Label::new(label)));
+                tokens.push(Token::I32(i as i32));
+                tokens.push(Token::RecordValueEnd(None, sval::Label::new(label)));
+            }
+
+            tokens.push(Token::RecordEnd(None, None, None));
+            tokens
+        }
+
+        let mut tokens = vec![Token::SeqBegin(None)];
+        for labels in [&["a", "b"], &["a", "b"], &["a", "b"], &["b", "a"]] {
+            tokens.push(Token::SeqValueBegin);
+            tokens.extend(record(labels));
+            tokens.push(Token::SeqValueEnd);
+        }
+        tokens.push(Token::SeqEnd);
+
+        let value = AsValue(&tokens);
+
+        let mut interned = Vec::new();
+        let mut stream = sval::schema_intern(TokenBuf::new(), |schema| {
+            interned.push((schema.id(), schema.is_new(), schema.labels().len()));
+        });
+
+        sval::stream(&mut stream, &value).unwrap();
+
+        // Identical records are interned once, and a different order of labels is a new schema
+        assert_eq!(
+            Some(&[sval::Label::new("a"), sval::Label::new("b")][..]),
+            stream.schema(0)
+        );
+        assert_eq!(
+            Some(&[sval::Label::new("b"), sval::Label::new("a")][..]),
+            stream.schema(1)
+        );
+        assert_eq!(None, stream.schema(2));
+
+        // All data is forwarded unchanged
+        assert_eq!(&*tokens, stream.into_inner().as_tokens());
+
+        assert_eq!(
+            vec![(0, true, 2), (0, false, 2), (0, false, 2), (1, true, 2)],
+            interned
+        );
+
+        assert_forwards_num(|nums| sval::schema_intern(nums, |_| {}));
+    }
+
+    #[test]
+    fn stream_max_depth() {
+        let value = vec![vec![vec![1]], vec![]];
+
+        let mut stream = sval::max_depth(TokenBuf::new());
+        sval::stream_computed(&mut stream, &value).unwrap();
+
+        assert_eq!(3, stream.max_depth());
+
+        // All data is forwarded unchanged
+        let mut expected = TokenBuf::new();
+        sval::stream_computed(&mut expected, &value).unwrap();
+
+        assert_eq!(expected.as_tokens(), stream.into_inner().as_tokens());
+
+        // Every kind of container, including tagged values, is a level of depth
+        let mut stream = sval::max_depth(TokenBuf::new());
+        sval::stream_computed(&mut stream, (1, vec![Some(Some(1))])).unwrap();
+
+        assert_eq!(4, stream.max_depth());
+
+        // Values that aren't nested have no depth
+        let mut stream = sval::max_depth(TokenBuf::new());
+        sval::stream_computed(&mut stream, 42).unwrap();
+
+        assert_eq!(0, stream.max_depth());
+
+        assert_forwards_num(sval::max_depth);
+    }
+
+    #[test]
+    fn stream_hash() {
+        use std::collections::hash_map::DefaultHasher;
+
+        struct Fragments<'a>(&'a [&'a str], bool);
+
+        impl<'a> sval::Value for Fragments<'a> {
+            fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(
+                &'sval self,
+                stream: &mut S,
+            ) -> sval::Result {
+                stream.record_begin(None, None, None, None)?;
+                stream.record_value_begin(None, &sval::Label::new("text"))?;
+
+                stream.text_begin(None)?;
+                for fragment in self.0 {
+                    if self.1 {
+                        stream.text_fragment_computed(fragment)?;
+                    } else {
+                        stream.text_fragment(fragment)?;
+                    }
+                }
+                stream.text_end()?;
+
+                stream.record_value_end(None, &sval::Label::new("text"))?;
+                stream.record_end(None, None, None)
+            }
+        }
+
+        fn hash(v: impl sval::Value) -> u64 {
+            let mut stream = sval::hash_stream(DefaultHasher::new());
+            sval::stream_computed(&mut stream, v).unwrap();
+
+            stream.finish()
+        }
+
+        let long = "a longer piece of text that spans more than one chunk of the hasher";
+
+        let expected = hash(Fragments(&["hello world"], false));
+
+        // Text hashes the same regardless of how it's split into fragments
+        assert_eq!(expected, hash(Fragments(&["hello", " ", "world"], false)));
+        assert_eq!(expected, hash(Fragments(&["hel", "lo world"], true)));
+        assert_eq!(expected, hash(Fragments(&["", "hello world", ""], true)));
+        assert_eq!(
+            hash(Fragments(&[long], false)),
+            hash(Fragments(&[&long[..5], &long[5..40], &long[40..]], true))
+        );
+
+        // The boundaries between values still matter
+        assert_ne!(hash(("ab", "c")), hash(("a", "bc")));
+        assert_ne!(hash(vec!["ab"]), hash(vec!["a", "b"]));
+
+        // Different content produces different hashes
+        assert_ne!(expected, hash(Fragments(&["hello", "world"], false)));
+        assert_ne!(hash(1i32), hash(2i32));
+        assert_eq!(hash(vec![Some(1), None]), hash(vec![Some(1), None]));
+    }
+
+    #[test]
+    fn stream_normalize_ints() {
+        let mut stream = sval::normalize_ints(TokenBuf::new());
+        sval::stream_computed(
+            &mut stream,
+            (5u8, -2i16, 3u32, u64::MAX, 5u128, -6i128, 7.5f32, "8"),
+        )
+        .unwrap();
+
+        assert_eq!(
+            &[
+                Token::TupleBegin(None, None, None, Some(8)),
+                Token::TupleValueBegin(None, sval::Index::new(0)),
+                Token::I64(5),
+                Token::TupleValueEnd(None, sval::Index::new(0)),
+                Token::TupleValueBegin(None, sval::Index::new(1)),
+                Token::I64(-2),
+                Token::TupleValueEnd(None, sval::Index::new(1)),
+                Token::TupleValueBegin(None, sval::Index::new(2)),
+                Token::I64(3),
+                Token::TupleValueEnd(None, sval::Index::new(2)),
+                Token::TupleValueBegin(None, sval::Index::new(3)),
+                Token::U64(u64::MAX),
+                Token::TupleValueEnd(None, sval::Index::new(3)),
+                Token::TupleValueBegin(None, sval::Index::new(4)),
+                Token::I64(5),
+                Token::TupleValueEnd(None, sval::Index::new(4)),
+                Token::TupleValueBegin(None, sval::Index::new(5)),
+                Token::I64(-6),
+                Token::TupleValueEnd(None, sval::Index::new(5)),
+                Token::TupleValueBegin(None, sval::Index::new(6)),
+                Token::F32(7.5),
+                Token::TupleValueEnd(None, sval::Index::new(6)),
+                Token::TupleValueBegin(None, sval::Index::new(7)),
+                Token::TextBegin(Some(1)),
+                Token::TextFragmentComputed("8".to_owned()),
+                Token::TextEnd,
+                Token::TupleValueEnd(None, sval::Index::new(7)),
+                Token::TupleEnd(None, None, None),
+            ],
+            stream.into_inner().as_tokens()
+        );
+
+        // 128bit integers that don't fit in 64bits fail
+        for v in [u128::MAX, u64::MAX as u128 + 1] {
+            let mut stream = sval::normalize_ints(TokenBuf::new());
+            assert!(sval::stream_computed(&mut stream, v)
+                .unwrap_err()
+                .is_unsupported());
+        }
+
+        for v in [i128::MIN, i64::MIN as i128 - 1] {
+            let mut stream = sval::normalize_ints(TokenBuf::new());
+            assert!(sval::stream_computed(&mut stream, v)
+                .unwrap_err()
+                .is_unsupported());
+        }
+
+        assert_forwards_num(sval::normalize_ints);
+    }
+
+    #[test]
+    fn stream_integers_as_floats() {
+        let mut stream = sval::integers_as_floats(TokenBuf::new());
+        sval::stream_computed(
+            &mut stream,
+            (1u8, -2i16, 3u32, -4i64, 5u128, -6i128, 7.5f32, "8"),
+        )
+        .unwrap();
+
+        assert_eq!(
+            &[
+                Token::TupleBegin(None, None, None, Some(8)),
+                Token::TupleValueBegin(None, sval::Index::new(0)),
+                Token::F64(1.0),
+                Token::TupleValueEnd(None, sval::Index::new(0)),
+                Token::TupleValueBegin(None, sval::Index::new(1)),
+                Token::F64(-2.0),
+                Token::TupleValueEnd(None, sval::Index::new(1)),
+                Token::TupleValueBegin(None, sval::Index::new(2)),
+                Token::F64(3.0),
+                Token::TupleValueEnd(None, sval::Index::new(2)),
+                Token::TupleValueBegin(None, sval::Index::new(3)),
+                Token::F64(-4.0),
+                Token::TupleValueEnd(None, sval::Index::new(3)),
+                Token::TupleValueBegin(None, sval::Index::new(4)),
+                Token::F64(5.0),
+                Token::TupleValueEnd(None, sval::Index::new(4)),
+                Token::TupleValueBegin(None, sval::Index::new(5)),
+                Token::F64(-6.0),
+                Token::TupleValueEnd(None, sval::Index::new(5)),
+                Token::TupleValueBegin(None, sval::Index::new(6)),
+                Token::F32(7.5),
+                Token::TupleValueEnd(None, sval::Index::new(6)),
+                Token::TupleValueBegin(None, sval::Index::new(7)),
+                Token::TextBegin(Some(1)),
+                Token::TextFragmentComputed("8".to_owned()),
+                Token::TextEnd,
+                Token::TupleValueEnd(None, sval::Index::new(7)),
+                Token::TupleEnd(None, None, None),
+            ],
+            stream.into_inner().as_tokens()
+        );
+
+        const MAX_SAFE_INTEGER: i64 = (1 << 53) - 1;
+
+        // Integers beyond 2^53 are rounded by default
+        let mut stream = sval::integers_as_floats(TokenBuf::new());
+        sval::stream_computed(&mut stream, MAX_SAFE_INTEGER + 2).unwrap();
+        assert_eq!(
+            &[Token::F64(9007199254740992.0)],
+            stream.into_inner().as_tokens()
+        );
+
+        // Strict mode accepts safe integers
+        for v in [MAX_SAFE_INTEGER, -MAX_SAFE_INTEGER] {
+            let mut stream = sval::integers_as_floats(TokenBuf::new()).strict();
+            sval::stream_computed(&mut stream, v).unwrap();
+            assert_eq!(&[Token::F64(v as f64)], stream.into_inner().as_tokens());
+        }
+
+        // Strict mode fails on integers beyond 2^53
+        let mut stream = sval::integers_as_floats(TokenBuf::new()).strict();
+        assert!(sval::stream_computed(&mut stream, MAX_SAFE_INTEGER + 1)
+            .unwrap_err()
+            .is_unsupported());
+
+        let mut stream = sval::integers_as_floats(TokenBuf::new()).strict();
+        assert!(sval::stream_computed(&mut stream, -MAX_SAFE_INTEGER - 1)
+            .unwrap_err()
+            .is_unsupported());
+
+        let mut stream = sval::integers_as_floats(TokenBuf::new()).strict();
+        assert!(sval::stream_computed(&mut stream, u64::MAX)
+            .unwrap_err()
+            .is_unsupported());
+
+        let mut stream = sval::integers_as_floats(TokenBuf::new()).strict();
+        assert!(sval::stream_computed(&mut stream, i128::MIN)
+            .unwrap_err()
+            .is_unsupported());
+
+        assert_forwards_num(sval::integers_as_floats);
+    }
+
+    #[test]
+    fn stream_canonical_encoding() {
+        let mut stream = sval::canonical_encoding(TokenBuf::new());
+        sval::stream_computed(&mut stream, HashMap::from([("b", 300u64), ("a", 1)])).unwrap();
+
+        assert_eq!(
+            &[
+                Token::MapBegin(None),
+                Token::MapKeyBegin,
+                Token::TextBegin(Some(1)),
+                Token::TextFragmentComputed("a".to_owned()),
+                Token::TextEnd,
+                Token::MapKeyEnd,
+                Token::MapValueBegin,
+                Token::U8(1),
+                Token::MapValueEnd,
+                Token::MapKeyBegin,
+                Token::TextBegin(Some(1)),
+                Token::TextFragmentComputed("b".to_owned()),
+                Token::TextEnd,
+                Token::MapKeyEnd,
+                Token::MapValueBegin,
+                Token::U16(300),
+                Token::MapValueEnd,
+                Token::MapEnd,
+            ],
+            stream.into_inner().as_tokens()
+        );
+
+        // Integer keys are sorted numerically
+        let mut stream = sval::canonical_encoding(TokenBuf::new());
+        sval::stream_computed(
+            &mut stream,
+            HashMap::from([(1000i64, ()), (-300, ()), (2, ())]),
+        )
+        .unwrap();
+
+        let keys = stream
+            .into_inner()
+            .as_tokens()
+            .windows(2)
+            .filter(|tokens| tokens[0] == Token::MapKeyBegin)
+            .map(|tokens| format!("{:?}", tokens[1]))
+            .collect::<Vec<_>>();
+
+        assert_eq!(vec!["I16(-300)", "U8(2)", "U16(1000)"], keys);
+
+        // Equivalent values produce the same tokens
+        let mut a = sval::canonical_encoding(TokenBuf::new());
+        sval::stream(&mut a, &(-1i64, 2.5f32, 7u128)).unwrap();
+
+        let mut b = sval::canonical_encoding(TokenBuf::new());
+        sval::stream(&mut b, &(-1i8, 2.5f64, 7u8)).unwrap();
+
+        assert_eq!(a.into_inner().as_tokens(), b.into_inner().as_tokens());
+    }
+
+    #[test]
+    fn stream_diff() {
+        let old = (1, [true, false], "a");
+        let new = (1, [true, true, false], "b");
+
+        let patch = sval::diff(&old, &new).unwrap();
+
+        assert_eq!(
+            vec![
+                (sval::PatchOp::Replace, "/1/1"),
+                (sval::PatchOp::Add, "/1/2"),
+                (sval::PatchOp::Replace, "/2"),
+            ],
+            patch
+                .iter()
+                .map(|op| (op.op(), op.path()))
+                .collect::<Vec<_>>()
+        );
+
+        assert!(sval::diff(&new, &new).unwrap().is_empty());
+
+        // Maps with keys that can't be written as strings can't be diffed
+        let map = BTreeMap::from([((1, 2), 3)]);
+
+        assert!(sval::diff(&map, &map).unwrap_err().is_unsupported());
+    }
+
+    #[test]
+    fn stream_tee() {
+        let value = (1, "borrowed", [true, false]);
+
+        let mut a = TokenBuf::new();
+        let mut b = TokenBuf::new();
+        sval::stream(&mut sval::tee(&mut a, &mut b), &value).unwrap();
+
+        assert!(a.as_tokens().contains(&Token::TextFragment("borrowed")));
+        assert_eq!(a.as_tokens(), b.as_tokens());
+
+        // Streaming stops at the first error
+        let mut a = TokenBuf::new();
+        let mut b = TokenBuf::new();
+        assert!(
+            sval::stream(&mut sval::tee(&mut a, sval::limit_depth(&mut b, 0)), &value).is_err()
+        );
+
+        assert_eq!(
+            &[Token::TupleBegin(None, None, None, Some(3))],
+            a.as_tokens()
+        );
+        assert!(b.as_tokens().is_empty());
+
+        assert_forwards_num(|nums| sval::tee(nums, TokenBuf::new()));
+        assert_forwards_num(|nums| sval::tee(TokenBuf::new(), nums));
+    }
+
+    #[test]
+    fn stream_from_fn() {
+        use sval::Stream as _;
+
+        let value = sval_dynamic::from_fn(|stream| {
+            let id = sval::Label::new("id");
+            let name = sval::Label::new("name");
+
+            stream.record_begin(None, Some(&sval::Label::new("Row")), None, Some(2))?;
+
+            stream.record_value_begin(None, &id)?;
+            stream.i32(1)?;
+            stream.record_value_end(None, &id)?;
+
+            stream.record_value_begin(None, &name)?;
+            stream.text_begin(Some(4))?;
+            stream.text_fragment("sval")?;
+            stream.text_end()?;
+            stream.record_value_end(None, &name)?;
+
+            stream.record_end(None, Some(&sval::Label::new("Row")), None)
+        });
+
+        assert_tokens(
+            &value,
+            &[
+                Token::RecordBegin(None, Some(sval::Label::new("Row")), None, Some(2)),
+                Token::RecordValueBegin(None, sval::Label::new("id")),
+                Token::I32(1),
+                Token::RecordValueEnd(None, sval::Label::new("id")),
+                Token::RecordValueBegin(None, sval::Label::new("name")),
+                Token::TextBegin(Some(4)),
+                Token::TextFragment("sval"),
+                Token::TextEnd,
+                Token::RecordValueEnd(None, sval::Label::new("name")),
+                Token::RecordEnd(None, Some(sval::Label::new("Row")), None),
+            ],
+        );
+    }
+
+    #[test]
+    fn stream_untagged() {
+        fn assert_untagged(expected: &[Token], v: &(impl sval::Value + ?Sized)) {
+            let mut buf = TokenBuf::new();
+            sval::stream_computed(&mut sval::untagged(&mut buf), v).unwrap();
+
+            assert_eq!(expected, buf.as_tokens());
+        }
+
+        struct UnitVariant;
+
+        impl sval::Value for UnitVariant {
+            fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(
+                &'sval self,
+                stream: &mut S,
+            ) -> sval::Result {
+                stream.enum_begin(None, Some(&sval::Label::new("Enum")), None)?;
+                stream.tag(
+                    None,
+                    Some(&sval::Label::new("A")),
+                    Some(&sval::Index::new(0)),
+                )?;
+                stream.enum_end(None, Some(&sval::Label::new("Enum")), None)
+            }
+        }
+
+        assert_untagged(&[Token::I32(1)], &Some(1));
+        assert_untagged(&[Token::I32(1)], &Some(Some(1)));
+        assert_untagged(&[Token::Null], &None::<i32>);
+        assert_untagged(&[Token::Null], &());
+
+        assert_untagged(
+            &[
+                Token::TextBegin(Some(1)),
+                Token::TextFragmentComputed("A".into()),
+                Token::TextEnd,
+            ],
+            &UnitVariant,
+        );
+
+        assert_untagged(
+            &[
+                Token::TextBegin(Some(3)),
+                Token::TextFragmentComputed("1.5".into()),
+                Token::TextEnd,
+            ],
+            &sval::tagged(sval::tags::NUMBER, "1.5"),
+        );
+
+        assert_untagged(
+            &[
+                Token::SeqBegin(Some(2)),
+                Token::SeqValueBegin,
+                Token::I32(1),
+                Token::SeqValueEnd,
+                Token::SeqValueBegin,
+                Token::Null,
+                Token::SeqValueEnd,
+                Token::SeqEnd,
+            ],
+            &[Some(1), None],
+        );
+
+        assert_forwards_num(sval::untagged);
+    }
+
+    #[test]
+    fn stream_shape() {
+        use sval::ShapeKind;
+
+        let record = [
+            Token::RecordBegin(None, Some(sval::Label::new("Row")), None, Some(2)),
+            Token::RecordValueBegin(None, sval::Label::new("id")),
+            Token::I32(1),
+            Token::RecordValueEnd(None, sval::Label::new("id")),
+            Token::RecordValueBegin(None, sval::Label::new("nested")),
+            Token::RecordBegin(None, None, None, Some(1)),
+            Token::RecordValueBegin(None, sval::Label::new("inner")),
+            Token::Bool(true),
+            Token::RecordValueEnd(None, sval::Label::new("inner")),
+            Token::RecordEnd(None, None, None),
+            Token::RecordValueEnd(None, sval::Label::new("nested")),
+            Token::RecordEnd(None, Some(sval::Label::new("Row")), None),
+        ];
+
+        // Records collect the labels of their own fields, but not nested ones
+        let shape = sval::Shape::collect(&AsValue(&record)).unwrap();
+        assert_eq!(Some(ShapeKind::Record), shape.kind());
+        assert_eq!(&["id", "nested"], shape.fields());
+
+        let shape = sval::Shape::collect(&Some(AsValue(&record))).unwrap();
+        assert_eq!(Some(ShapeKind::Record), shape.kind());
+        assert_eq!(&["id", "nested"], shape.fields());
+
+        // Other values stop as soon as they begin
+        let mut shape = sval::Shape::new();
+        assert!(sval::stream(&mut shape, &vec![1, 2, 3]).is_err());
+        assert!(shape.is_stopped());
+        assert_eq!(Some(ShapeKind::Seq), shape.finish().kind());
+
+        assert_eq!(
+            Some(ShapeKind::Seq),
+            sval::Shape::collect(&[1, 2, 3]).unwrap().kind()
+        );
+        assert_eq!(
+            Some(ShapeKind::Map),
+            sval::Shape::collect(&BTreeMap::from([(1, 2)]))
+                .unwrap()
+                .kind()
+        );
+        assert_eq!(
+            Some(ShapeKind::Tuple),
+            sval::Shape::collect(&(1, true)).unwrap().kind()
+        );
+        assert_eq!(
+            Some(ShapeKind::Scalar),
+            sval::Shape::collect(&"text").unwrap().kind()
+        );
+        assert_eq!(
+            Some(ShapeKind::Scalar),
+            sval::Shape::collect(&None::<i32>).unwrap().kind()
+        );
+        assert!(sval::Shape::collect(&1).unwrap().fields().is_empty());
+
+        // Stopping early means the rest of the value is never streamed
+        struct FailsAfterBegin;
+
+        impl sval::Value for FailsAfterBegin {
+            fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(
+                &'sval self,
+                stream: &mut S,
+            ) -> sval::Result {
+                stream.enum_begin(None, None, None)?;
+
+                sval::error()
+            }
+        }
+
+        assert_eq!(
+            Some(ShapeKind::Enum),
+            sval::Shape::collect(&FailsAfterBegin).unwrap().kind()
+        );
+
+        // Failures from the value itself are still reported
+        struct Fails;
+
+        impl sval::Value for Fails {
+            fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(
+                &'sval self,
+                _: &mut S,
+            ) -> sval::Result {
+                sval::error()
+            }
+        }
+
+        let mut shape = sval::Shape::new();
+        assert!(sval::stream(&mut shape, &Fails).is_err());
+        assert!(!shape.is_stopped());
+
+        assert!(sval::Shape::collect(&Fails).is_err());
+    }
+
+    #[test]
+    fn random_value() {
+        for seed in 0..100 {
+            let value = super::random_value(seed, GenConfig::new());
+
+            // The same seed always produces the same value
+            assert_eq!(value, super::random_value(seed, GenConfig::new()));
+
+            let mut tokens = TokenBuf::new();
+            sval::stream(&mut tokens, &value).unwrap();
+
+            assert_tokens_roundtrip(&value, tokens.as_tokens());
+        }
+
+        assert_ne!(
+            super::random_value(1, GenConfig::new()),
+            super::random_value(2, GenConfig::new())
+        );
+    }
+
+    #[test]
+    fn random_value_config() {
+        let config = GenConfig::new()
+            .with_max_depth(0)
+            .with_floats(false)
+            .with_large_integers(false)
+            .with_binary(false);
+
+        for seed in 0..100 {
+            let value = super::random_value(seed, config.clone());
+
+            let mut tokens = TokenBuf::new();
+            sval::stream(&mut tokens, &value).unwrap();
+
+            assert!(
+                tokens.as_tokens().iter().all(|token| matches!(
+                    token,
+                    Token::Null
+                        | Token::Bool(_)
+                        | Token::I64(_)
+                        | Token::U64(_)
+                        | Token::TextBegin(_)
+                        | Token::TextFragment(_)
+                        | Token::TextEnd
+                )),
+                "{}",
+                debug_tokens(tokens.as_tokens())
+            );
+        }
     }
 }
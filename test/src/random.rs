@@ -0,0 +1,402 @@
+use std::fmt;
+
+/**
+Generate a pseudo-random value.
+
+The same `seed` and `config` always produce the same value, so failures found by streaming
+many random values can be reproduced from the seed that caused them. The value is always valid,
+so any stream that fails on it has found a bug.
+
+```
+for seed in 0..100 {
+    sval_test::assert_valid(sval_test::random_value(seed, sval_test::GenConfig::new()));
+}
+```
+*/
+pub fn random_value(seed: u64, config: GenConfig) -> RandomValue {
+    let mut rng = Rng(seed);
+
+    RandomValue(Node::generate(&mut rng, &config, 0))
+}
+
+/**
+Configuration for the values produced by [`random_value`].
+
+By default, values are nested up to 3 levels deep, containers have up to 4 elements, and
+all kinds of containers and scalars are generated.
+*/
+#[derive(Debug, Clone)]
+pub struct GenConfig {
+    max_depth: usize,
+    max_len: usize,
+    maps: bool,
+    seqs: bool,
+    records: bool,
+    tuples: bool,
+    enums: bool,
+    floats: bool,
+    large_integers: bool,
+    binary: bool,
+}
+
+impl Default for GenConfig {
+    fn default() -> Self {
+        GenConfig::new()
+    }
+}
+
+impl GenConfig {
+    /**
+    Create a config that generates all kinds of values.
+    */
+    pub fn new() -> Self {
+        GenConfig {
+            max_depth: 3,
+            max_len: 4,
+            maps: true,
+            seqs: true,
+            records: true,
+            tuples: true,
+            enums: true,
+            floats: true,
+            large_integers: true,
+            binary: true,
+        }
+    }
+
+    /**
+    Nest containers at most `max_depth` levels deep.
+
+    A depth of `0` only generates scalars.
+    */
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /**
+    Generate at most `max_len` elements in containers, and `max_len` characters in text.
+    */
+    pub fn with_max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self
+    }
+
+    /**
+    Whether to generate maps.
+    */
+    pub fn with_maps(mut self, maps: bool) -> Self {
+        self.maps = maps;
+        self
+    }
+
+    /**
+    Whether to generate sequences.
+    */
+    pub fn with_seqs(mut self, seqs: bool) -> Self {
+        self.seqs = seqs;
+        self
+    }
+
+    /**
+    Whether to generate records.
+    */
+    pub fn with_records(mut self, records: bool) -> Self {
+        self.records = records;
+        self
+    }
+
+    /**
+    Whether to generate tuples.
+    */
+    pub fn with_tuples(mut self, tuples: bool) -> Self {
+        self.tuples = tuples;
+        self
+    }
+
+    /**
+    Whether to generate enums.
+    */
+    pub fn with_enums(mut self, enums: bool) -> Self {
+        self.enums = enums;
+        self
+    }
+
+    /**
+    Whether to generate floating point numbers.
+
+    Only finite numbers are generated.
+    */
+    pub fn with_floats(mut self, floats: bool) -> Self {
+        self.floats = floats;
+        self
+    }
+
+    /**
+    Whether to generate 128-bit integers.
+    */
+    pub fn with_large_integers(mut self, large_integers: bool) -> Self {
+        self.large_integers = large_integers;
+        self
+    }
+
+    /**
+    Whether to generate binary values.
+    */
+    pub fn with_binary(mut self, binary: bool) -> Self {
+        self.binary = binary;
+        self
+    }
+}
+
+/**
+A value produced by [`random_value`].
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct RandomValue(Node);
+
+impl fmt::Display for RandomValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        sval_fmt::stream_to_fmt(f, self)
+    }
+}
+
+impl sval::Value for RandomValue {
+    fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> sval::Result {
+        self.0.stream(stream)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Null,
+    Bool(bool),
+    I64(i64),
+    U64(u64),
+    I128(i128),
+    U128(u128),
+    F64(f64),
+    Text(String),
+    Binary(Vec<u8>),
+    Map(Vec<(Node, Node)>),
+    Seq(Vec<Node>),
+    Record(Vec<(String, Node)>),
+    Tuple(Vec<Node>),
+    Tag(String, usize),
+    Variant(String, usize, Box<Node>),
+}
+
+// Characters that are likely to need escaping or multi-byte encoding
+const CHARS: &[char] = &[
+    'a', 'b', 'Z', '0', ' ', '"', '\'', '\\', '/', '\n', '\t', '\u{0}', '\u{7f}', 'é', '€', '😀',
+];
+
+impl Node {
+    fn generate(rng: &mut Rng, config: &GenConfig, depth: usize) -> Node {
+        let mut kinds: Vec<fn(&mut Rng, &GenConfig, usize) -> Node> = vec![
+            |_, _, _| Node::Null,
+            |rng, _, _| Node::Bool(rng.next() % 2 == 0),
+            |rng, _, _| Node::I64(rng.next() as i64),
+            |rng, _, _| Node::U64(rng.next()),
+            |rng, config, _| Node::Text(Node::text(rng, config)),
+        ];
+
+        if config.large_integers {
+            kinds.push(|rng, _, _| Node::I128(((rng.next() as i128) << 64) | rng.next() as i128));
+            kinds.push(|rng, _, _| Node::U128(((rng.next() as u128) << 64) | rng.next() as u128));
+        }
+
+        if config.floats {
+            kinds.push(|rng, _, _| {
+                Node::F64((rng.next() as i64) as f64 / (1u64 << rng.below(64)) as f64)
+            });
+        }
+
+        if config.binary {
+            kinds.push(|rng, config, _| {
+                Node::Binary(
+                    (0..rng.below(config.max_len + 1))
+                        .map(|_| rng.next() as u8)
+                        .collect(),
+                )
+            });
+        }
+
+        if depth < config.max_depth {
+            if config.maps {
+                kinds.push(|rng, config, depth| {
+                    Node::Map(
+                        (0..rng.below(config.max_len + 1))
+                            .map(|i| {
+                                // Keys are always text so maps are valid in formats like JSON
+                                let key = Node::Text(format!("{}{}", Node::text(rng, config), i));
+                                let value = Node::generate(rng, config, depth + 1);
+
+                                (key, value)
+                            })
+                            .collect(),
+                    )
+                });
+            }
+
+            if config.seqs {
+                kinds.push(|rng, config, depth| Node::Seq(Node::elements(rng, config, depth)));
+            }
+
+            if config.records {
+                kinds.push(|rng, config, depth| {
+                    Node::Record(
+                        Node::elements(rng, config, depth)
+                            .into_iter()
+                            .enumerate()
+                            .map(|(i, value)| (format!("field_{}", i), value))
+                            .collect(),
+                    )
+                });
+            }
+
+            if config.tuples {
+                kinds.push(|rng, config, depth| Node::Tuple(Node::elements(rng, config, depth)));
+            }
+
+            if config.enums {
+                kinds.push(|rng, config, depth| {
+                    let index = rng.below(config.max_len + 1);
+                    let label = format!("Variant{}", index);
+
+                    if rng.next() % 2 == 0 {
+                        Node::Tag(label, index)
+                    } else {
+                        Node::Variant(
+                            label,
+                            index,
+                            Box::new(Node::generate(rng, config, depth + 1)),
+                        )
+                    }
+                });
+            }
+        }
+
+        let kind = kinds[rng.below(kinds.len())];
+
+        kind(rng, config, depth)
+    }
+
+    fn elements(rng: &mut Rng, config: &GenConfig, depth: usize) -> Vec<Node> {
+        (0..rng.below(config.max_len + 1))
+            .map(|_| Node::generate(rng, config, depth + 1))
+            .collect()
+    }
+
+    fn text(rng: &mut Rng, config: &GenConfig) -> String {
+        (0..rng.below(config.max_len + 1))
+            .map(|_| CHARS[rng.below(CHARS.len())])
+            .collect()
+    }
+}
+
+impl sval::Value for Node {
+    fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> sval::Result {
+        match self {
+            Node::Null => stream.null(),
+            Node::Bool(v) => stream.bool(*v),
+            Node::I64(v) => stream.i64(*v),
+            Node::U64(v) => stream.u64(*v),
+            Node::I128(v) => stream.i128(*v),
+            Node::U128(v) => stream.u128(*v),
+            Node::F64(v) => stream.f64(*v),
+            Node::Text(v) => stream.value(&**v),
+            Node::Binary(v) => stream.value(sval::BinarySlice::new(v)),
+            Node::Map(entries) => {
+                stream.map_begin(Some(entries.len()))?;
+
+                for (k, v) in entries {
+                    stream.map_key_begin()?;
+                    stream.value(k)?;
+                    stream.map_key_end()?;
+
+                    stream.map_value_begin()?;
+                    stream.value(v)?;
+                    stream.map_value_end()?;
+                }
+
+                stream.map_end()
+            }
+            Node::Seq(values) => {
+                stream.seq_begin(Some(values.len()))?;
+
+                for v in values {
+                    stream.seq_value_begin()?;
+                    stream.value(v)?;
+                    stream.seq_value_end()?;
+                }
+
+                stream.seq_end()
+            }
+            Node::Record(fields) => {
+                stream.record_begin(None, None, None, Some(fields.len()))?;
+
+                for (label, v) in fields {
+                    let label = sval::Label::new_computed(label);
+
+                    stream.record_value_begin(None, &label)?;
+                    stream.value(v)?;
+                    stream.record_value_end(None, &label)?;
+                }
+
+                stream.record_end(None, None, None)
+            }
+            Node::Tuple(values) => {
+                stream.tuple_begin(None, None, None, Some(values.len()))?;
+
+                for (i, v) in values.iter().enumerate() {
+                    let index = sval::Index::new(i);
+
+                    stream.tuple_value_begin(None, &index)?;
+                    stream.value(v)?;
+                    stream.tuple_value_end(None, &index)?;
+                }
+
+                stream.tuple_end(None, None, None)
+            }
+            Node::Tag(label, index) => {
+                let label = sval::Label::new_computed(label);
+                let index = sval::Index::new(*index);
+
+                stream.enum_begin(None, None, None)?;
+                stream.tag(None, Some(&label), Some(&index))?;
+                stream.enum_end(None, None, None)
+            }
+            Node::Variant(label, index, v) => {
+                let label = sval::Label::new_computed(label);
+                let index = sval::Index::new(*index);
+
+                stream.enum_begin(None, None, None)?;
+                stream.tagged_begin(None, Some(&label), Some(&index))?;
+                stream.value(&**v)?;
+                stream.tagged_end(None, Some(&label), Some(&index))?;
+                stream.enum_end(None, None, None)
+            }
+        }
+    }
+}
+
+/**
+A small deterministic PRNG (SplitMix64).
+*/
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}